@@ -193,6 +193,10 @@ struct DnaDefJson {
     pub name: String,
     pub uuid: String,
     pub properties: serde_json::Value,
+    #[serde(default)]
+    pub redundancy_target: Option<u32>,
+    #[serde(default)]
+    pub rate_limits: Vec<RateLimit>,
     pub zomes: BTreeMap<ZomeName, ZomeJson>,
 }
 
@@ -213,6 +217,8 @@ impl DnaDefJson {
             name: dna.name,
             uuid: dna.uuid,
             properties: properties.0,
+            redundancy_target: dna.redundancy_target,
+            rate_limits: dna.rate_limits,
             zomes,
         })
     }
@@ -245,7 +251,9 @@ impl DnaDefJson {
             name: self.name.clone(),
             uuid: self.uuid.clone(),
             properties,
+            rate_limits: self.rate_limits.clone(),
             zomes,
+            redundancy_target: self.redundancy_target,
         };
 
         Ok(DnaFile::new(dna, wasm_list).await?)
@@ -66,6 +66,7 @@ pub enum AdminRequestCli {
     ListActiveApps,
     ActivateApp(ActivateApp),
     DeactivateApp(DeactivateApp),
+    UninstallApp(UninstallApp),
     DumpState(DumpState),
     /// Calls AdminRequest::AddAgentInfo.
     /// [Unimplemented].
@@ -88,6 +89,10 @@ pub struct AddAppWs {
     /// Optional port number.
     /// Defaults to assigned by OS.
     pub port: Option<u16>,
+    #[structopt(short, long)]
+    /// Optional InstalledAppId to bind the interface to.
+    /// Only a bound interface can make crypto requests.
+    pub app_id: Option<String>,
 }
 
 #[derive(Debug, StructOpt, Clone)]
@@ -127,6 +132,17 @@ pub struct DeactivateApp {
     pub app_id: String,
 }
 
+#[derive(Debug, StructOpt, Clone)]
+/// Calls AdminRequest::UninstallApp
+/// and uninstalls the app.
+pub struct UninstallApp {
+    /// The InstalledAppId to uninstall.
+    pub app_id: String,
+    #[structopt(short, long)]
+    /// Also delete the app's cell environments.
+    pub delete_cell_data: bool,
+}
+
 #[derive(Debug, StructOpt, Clone)]
 /// Calls AdminRequest::DumpState
 /// and dumps the current cell's state.
@@ -240,6 +256,11 @@ async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Resul
             deactivate_app(cmd, args).await?;
             msg!("Deactivated app: {:?}", app_id);
         }
+        AdminRequestCli::UninstallApp(args) => {
+            let app_id = args.app_id.clone();
+            uninstall_app(cmd, args).await?;
+            msg!("Uninstalled app: {:?}", app_id);
+        }
         AdminRequestCli::DumpState(args) => {
             let state = dump_state(cmd, args).await?;
             msg!("DUMP STATE \n{}", state);
@@ -425,13 +446,32 @@ pub async fn deactivate_app(cmd: &mut CmdRunner, args: DeactivateApp) -> anyhow:
     Ok(())
 }
 
+/// Calls [`AdminRequest::UninstallApp`] and uninstalls the app.
+pub async fn uninstall_app(cmd: &mut CmdRunner, args: UninstallApp) -> anyhow::Result<()> {
+    let resp = cmd
+        .command(AdminRequest::UninstallApp {
+            installed_app_id: args.app_id,
+            delete_cell_data: args.delete_cell_data,
+        })
+        .await?;
+    ensure!(
+        matches!(resp, AdminResponse::AppUninstalled),
+        "Failed to uninstall app, got: {:?}",
+        resp
+    );
+    Ok(())
+}
+
 /// Calls [`AdminRequest::AttachAppInterface`] and adds another app interface.
 pub async fn attach_app_interface(cmd: &mut CmdRunner, args: AddAppWs) -> anyhow::Result<u16> {
     if let Some(port) = args.port {
         ensure!(is_free(port), "port {} is not free", port);
     }
     let resp = cmd
-        .command(AdminRequest::AttachAppInterface { port: args.port })
+        .command(AdminRequest::AttachAppInterface {
+            port: args.port,
+            installed_app_id: args.app_id,
+        })
         .await?;
     match resp {
         AdminResponse::AppInterfaceAttached { port } => Ok(port),
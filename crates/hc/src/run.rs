@@ -38,6 +38,7 @@ pub async fn run(
             &mut cmd,
             AddAppWs {
                 port: Some(app_port),
+                app_id: None,
             },
         )
         .await?;
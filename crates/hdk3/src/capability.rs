@@ -1,3 +1,6 @@
+pub mod capability_claims;
+pub mod capability_grants;
+pub mod capability_info;
 pub mod create_cap_claim;
 pub mod create_cap_grant;
 pub mod delete_cap_grant;
@@ -0,0 +1,13 @@
+use crate::prelude::*;
+
+/// Lists the CapClaims that are live on the local source chain.
+///
+/// A claim is live if it has been created or updated and not since updated or deleted.
+/// Pass a tag to only list the claims with that tag.
+///
+/// A live claim is no guarantee that the grantor will still accept the secret.
+///
+/// @see create_cap_claim
+pub fn capability_claims(tag: Option<String>) -> ExternResult<Vec<CapClaimInfo>> {
+    host_call::<Option<String>, Vec<CapClaimInfo>>(__capability_claims, tag)
+}
@@ -0,0 +1,13 @@
+use crate::prelude::*;
+
+/// Lists the CapGrants that are live on the local source chain.
+///
+/// A grant is live if it has been created or updated and not since updated or deleted.
+/// Pass a tag to only list the grants with that tag.
+///
+/// Each grant comes with the HeaderHash that is needed to update or delete it.
+///
+/// @see create_cap_grant
+pub fn capability_grants(tag: Option<String>) -> ExternResult<Vec<CapGrantInfo>> {
+    host_call::<Option<String>, Vec<CapGrantInfo>>(__capability_grants, tag)
+}
@@ -0,0 +1,11 @@
+use crate::prelude::*;
+
+/// The capability that authorized the current zome call.
+///
+/// This is the calling agent and the grant that matched their call.
+/// The grant is `CapGrant::ChainAuthor` when the agent is calling their own chain.
+///
+/// Only available to zome calls, it is an error to call this from a callback.
+pub fn capability_info() -> ExternResult<CapabilityInfo> {
+    host_call::<(), CapabilityInfo>(__capability_info, ())
+}
@@ -1,6 +1,7 @@
 pub mod agent_info;
 pub mod call;
 pub mod call_remote;
+pub mod count_links;
 pub mod create;
 pub mod create_link;
 pub mod delete;
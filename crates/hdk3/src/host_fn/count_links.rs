@@ -0,0 +1,18 @@
+use crate::prelude::*;
+
+/// Count the live links on a base that match a tag prefix and a [LinkQuery],
+/// without returning the links themselves.
+///
+/// A query limit caps the count.
+///
+/// @see get_links_query
+pub fn count_links(
+    base: EntryHash,
+    link_tag: Option<LinkTag>,
+    query: LinkQuery,
+) -> ExternResult<u32> {
+    host_call::<GetLinksInput, u32>(
+        __count_links,
+        GetLinksInput::new(base, link_tag).query(query),
+    )
+}
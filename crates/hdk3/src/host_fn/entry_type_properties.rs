@@ -1 +1,25 @@
-//! @todo
+use crate::prelude::*;
+
+/// Get the properties declared on one of this zome's entry defs, deserialized into the type the
+/// zome expects.
+///
+/// Properties are declared with `EntryDef::with_properties` in the `entry_defs` callback.
+/// Returns `None` if the entry def has no properties and an error if there is no entry def with
+/// this id in the zome.
+///
+/// ```ignore
+/// entry_defs![Post::entry_def()
+///     .with_properties(SerializedBytes::try_from(PostProperties { max_length: 140 }).unwrap())];
+///
+/// let post_properties: Option<PostProperties> = entry_type_properties("post")?;
+/// ```
+pub fn entry_type_properties<I, P>(entry_def_id: I) -> ExternResult<Option<P>>
+where
+    I: Into<EntryDefId>,
+    P: TryFrom<SerializedBytes, Error = SerializedBytesError>,
+{
+    host_call::<EntryDefId, Option<SerializedBytes>>(__entry_type_properties, entry_def_id.into())?
+        .map(P::try_from)
+        .transpose()
+        .map_err(WasmError::Serialize)
+}
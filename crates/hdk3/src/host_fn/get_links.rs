@@ -22,3 +22,25 @@ use crate::prelude::*;
 pub fn get_links(base: EntryHash, link_tag: Option<LinkTag>) -> ExternResult<Links> {
     host_call::<GetLinksInput, Links>(__get_links, GetLinksInput::new(base, link_tag))
 }
+
+/// Get the live links on a base that match a tag prefix and a [LinkQuery].
+///
+/// The query is sent to the authorities so only the links asked for come back.
+/// A query with a cursor or limit returns links ordered by their tag bytes then
+/// their create link hash, so a page can be continued from its last link:
+///
+/// ```ignore
+/// let page = get_links_query(base.clone(), None, LinkQuery::new().limit(10))?.into_inner();
+/// if let Some(last) = page.last() {
+///     let next = get_links_query(base, None, LinkQuery::new().after(last).limit(10))?;
+/// }
+/// ```
+///
+/// @see get_links
+pub fn get_links_query(
+    base: EntryHash,
+    link_tag: Option<LinkTag>,
+    query: LinkQuery,
+) -> ExternResult<Links> {
+    host_call::<GetLinksInput, Links>(__get_links, GetLinksInput::new(base, link_tag).query(query))
+}
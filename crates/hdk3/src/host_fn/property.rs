@@ -1 +1,22 @@
-//! @todo
+use crate::prelude::*;
+
+/// Get the properties of the dna, deserialized into the type the zome expects.
+///
+/// Properties are set on the dna when it is installed, e.g. with `DnaFile::with_properties`, so
+/// the same wasm can be configured differently in each dna it is compiled into.
+///
+/// ```ignore
+/// #[derive(serde::Serialize, serde::Deserialize, SerializedBytes, Debug)]
+/// struct MyProperties {
+///     max_length: u32,
+/// }
+///
+/// let my_properties: MyProperties = property()?;
+/// ```
+pub fn property<P>() -> ExternResult<P>
+where
+    P: TryFrom<SerializedBytes, Error = SerializedBytesError>,
+{
+    let properties = host_call::<(), SerializedBytes>(__property, ())?;
+    P::try_from(properties).map_err(WasmError::Serialize)
+}
@@ -1,8 +1,9 @@
 use crate::prelude::*;
 
-/// Walks the source chain in reverse (latest to oldest) filtering by header and/or entry type
+/// Walks the source chain filtering by header and/or entry types, sequence and timestamp
+/// ranges and entry hashes.
 ///
-/// Given a header and entry type, returns an ElementVec
+/// Elements are returned newest first unless the filter is `ascending`, up to its `limit`.
 ///
 /// @todo document this better with examples
 /// @todo do we want to return elements rather than hashes?
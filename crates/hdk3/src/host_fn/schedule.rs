@@ -1 +1,41 @@
-//! @todo
+use crate::prelude::*;
+
+/// Schedule a function in the current zome to be called by the host later.
+///
+/// The function is called with the given payload after `delay` has elapsed, and if `interval` is
+/// `Some` it will keep being called with that interval until it is scheduled again.
+///
+/// ```ignore
+/// // sweep expired entries once an hour starting in a minute
+/// schedule(
+///     "sweep",
+///     (),
+///     core::time::Duration::from_secs(60),
+///     Some(core::time::Duration::from_secs(60 * 60)),
+/// )?;
+/// ```
+///
+/// The scheduled function must be an extern of the current zome that accepts the payload.
+/// It is called as the author of the chain so no capability grant is needed.
+///
+/// Schedules are persisted by the host so they survive the conductor restarting.
+/// A schedule is identified by the zome and function name, so scheduling the same function again
+/// replaces any existing schedule for it.
+///
+/// Calls are made on a best effort basis, if the conductor is not running when the function is
+/// due it will be called as soon as possible after the conductor starts again.
+pub fn schedule<I>(
+    fn_name: impl Into<FunctionName>,
+    payload: I,
+    delay: core::time::Duration,
+    interval: Option<core::time::Duration>,
+) -> ExternResult<()>
+where
+    I: serde::Serialize + std::fmt::Debug,
+{
+    #[allow(clippy::unit_arg)]
+    host_call::<ScheduleInput, ()>(
+        __schedule,
+        ScheduleInput::new(fn_name.into(), ExternIO::encode(payload)?, delay, interval),
+    )
+}
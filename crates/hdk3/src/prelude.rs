@@ -1,4 +1,7 @@
 pub use crate::app_entry;
+pub use crate::capability::capability_claims::capability_claims;
+pub use crate::capability::capability_grants::capability_grants;
+pub use crate::capability::capability_info::capability_info;
 pub use crate::capability::create_cap_claim::create_cap_claim;
 pub use crate::capability::create_cap_grant::create_cap_grant;
 pub use crate::capability::delete_cap_grant::delete_cap_grant;
@@ -23,20 +26,25 @@ pub use crate::hash_path::path::Path;
 pub use crate::host_fn::agent_info::agent_info;
 pub use crate::host_fn::call::call;
 pub use crate::host_fn::call_remote::call_remote;
+pub use crate::host_fn::count_links::count_links;
 pub use crate::host_fn::create::create;
 pub use crate::host_fn::create_link::create_link;
 pub use crate::host_fn::delete::delete;
 pub use crate::host_fn::delete_link::delete_link;
 pub use crate::host_fn::emit_signal::emit_signal;
+pub use crate::host_fn::entry_type_properties::entry_type_properties;
 pub use crate::host_fn::get::get;
 pub use crate::host_fn::get_agent_activity::get_agent_activity;
 pub use crate::host_fn::get_details::get_details;
 pub use crate::host_fn::get_link_details::get_link_details;
 pub use crate::host_fn::get_links::get_links;
+pub use crate::host_fn::get_links::get_links_query;
+pub use crate::host_fn::property::property;
 pub use crate::host_fn::query::query;
 pub use crate::host_fn::random_bytes::random_bytes;
 pub use crate::host_fn::random_bytes::*;
 pub use crate::host_fn::remote_signal::remote_signal;
+pub use crate::host_fn::schedule::schedule;
 pub use crate::host_fn::sign::sign;
 pub use crate::host_fn::sign::sign_raw;
 pub use crate::host_fn::sys_time::sys_time;
@@ -84,6 +92,7 @@ macro_rules! holochain_externs {
             __encrypt,
             __zome_info,
             __property,
+            __entry_type_properties,
             __random_bytes,
             __show_env,
             __sys_time,
@@ -95,6 +104,7 @@ macro_rules! holochain_externs {
             __get_details,
             __get_links,
             __get_link_details,
+            __count_links,
             __get_agent_activity,
             __query,
             __call_remote,
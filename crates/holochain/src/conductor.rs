@@ -13,6 +13,7 @@
 // TODO: clean up allows once parent is fully documented
 
 pub mod api;
+mod autonomic;
 mod cell;
 #[allow(clippy::module_inception)]
 #[allow(missing_docs)]
@@ -21,6 +22,7 @@ mod conductor;
 pub mod config;
 #[allow(missing_docs)]
 pub mod dna_store;
+pub mod dpki;
 pub mod entry_def_store;
 #[allow(missing_docs)]
 pub mod error;
@@ -29,6 +31,7 @@ pub mod interactive;
 pub mod interface;
 pub mod manager;
 pub mod p2p_store;
+pub mod passphrase_service;
 pub mod paths;
 pub mod state;
 
@@ -14,7 +14,7 @@ use holo_hash::DnaHash;
 use holochain_conductor_api::ZomeCall;
 use holochain_keystore::KeystoreSender;
 use holochain_types::prelude::*;
-use tracing::*;
+use kitsune_p2p::agent_store::AgentInfoSigned;
 
 /// The concrete implementation of [CellConductorApiT], which is used to give
 /// Cells an API for calling back to their [Conductor].
@@ -63,9 +63,8 @@ impl CellConductorApiT for CellConductorApi {
         }
     }
 
-    async fn dpki_request(&self, _method: String, _args: String) -> ConductorApiResult<String> {
-        warn!("Using placeholder dpki");
-        Ok("TODO".to_string())
+    async fn dpki_key_state(&self, key: &AgentPubKey) -> ConductorApiResult<KeyState> {
+        self.conductor_handle.dpki_key_state(key).await
     }
 
     async fn autonomic_cue(&self, cue: AutonomicCue) -> ConductorApiResult<()> {
@@ -107,6 +106,18 @@ impl CellConductorApiT for CellConductorApi {
         self.conductor_handle.get_entry_def(key).await
     }
 
+    async fn get_agent_info(
+        &self,
+        agent: &AgentPubKey,
+    ) -> ConductorApiResult<Option<AgentInfoSigned>> {
+        let cell_id = CellId::new(self.cell_id.dna_hash().clone(), agent.clone());
+        Ok(self
+            .conductor_handle
+            .get_agent_infos(Some(cell_id))
+            .await?
+            .pop())
+    }
+
     fn into_call_zome_handle(self) -> CellConductorReadHandle {
         Arc::new(self)
     }
@@ -126,9 +137,8 @@ pub trait CellConductorApiT: Clone + Send + Sync + Sized {
         call: ZomeCall,
     ) -> ConductorApiResult<ZomeCallResult>;
 
-    /// Make a request to the DPKI service running for this Conductor.
-    /// TODO: decide on actual signature
-    async fn dpki_request(&self, method: String, args: String) -> ConductorApiResult<String>;
+    /// Ask the DPKI service running for this Conductor for the state of an agent key.
+    async fn dpki_key_state(&self, key: &AgentPubKey) -> ConductorApiResult<KeyState>;
 
     /// Cue the autonomic system to run an [AutonomicProcess] earlier than its scheduled time.
     /// This is basically a heuristic designed to help things run more smoothly.
@@ -153,6 +163,12 @@ pub trait CellConductorApiT: Clone + Send + Sync + Sized {
     /// Get a [EntryDef] from the [EntryDefBuf]
     async fn get_entry_def(&self, key: &EntryDefBufferKey) -> Option<EntryDef>;
 
+    /// Get the agent info this conductor holds for an agent in this cell's space
+    async fn get_agent_info(
+        &self,
+        agent: &AgentPubKey,
+    ) -> ConductorApiResult<Option<AgentInfoSigned>>;
+
     /// Turn this into a call zome handle
     fn into_call_zome_handle(self) -> CellConductorReadHandle;
 }
@@ -226,12 +226,37 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                     .await?;
                 Ok(AdminResponse::AppDeactivated)
             }
-            AttachAppInterface { port } => {
+            UninstallApp {
+                installed_app_id,
+                delete_cell_data,
+            } => {
+                self.conductor_handle
+                    .uninstall_app(installed_app_id, delete_cell_data)
+                    .await?;
+                Ok(AdminResponse::AppUninstalled)
+            }
+            MigrateAgent {
+                installed_app_id,
+                cell_nick,
+                new_dna_hash,
+                membrane_proof,
+            } => {
+                let app = self
+                    .conductor_handle
+                    .clone()
+                    .migrate_agent(installed_app_id, cell_nick, new_dna_hash, membrane_proof)
+                    .await?;
+                Ok(AdminResponse::AgentMigrated(app))
+            }
+            AttachAppInterface {
+                port,
+                installed_app_id,
+            } => {
                 let port = port.unwrap_or(0);
                 let port = self
                     .conductor_handle
                     .clone()
-                    .add_app_interface(port)
+                    .add_app_interface(port, installed_app_id)
                     .await?;
                 Ok(AdminResponse::AppInterfaceAttached { port })
             }
@@ -239,6 +264,13 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                 let state = self.conductor_handle.dump_cell_state(&cell_id).await?;
                 Ok(AdminResponse::StateDumped(state))
             }
+            GetCellHealth { cell_id } => {
+                let report = self
+                    .conductor_handle
+                    .get_cell_health_report(&cell_id)
+                    .await?;
+                Ok(AdminResponse::CellHealthReported(report))
+            }
             AddAgentInfo { agent_infos } => {
                 self.conductor_handle.add_agent_infos(agent_infos).await?;
                 Ok(AdminResponse::AgentInfoAdded)
@@ -297,6 +329,7 @@ mod test {
     use crate::conductor::Conductor;
     use anyhow::Result;
     use holochain_lmdb::test_utils::test_environments;
+    use holochain_state::source_chain::SourceChain;
     use holochain_types::app::InstallAppDnaPayload;
     use holochain_types::test_utils::fake_agent_pubkey_1;
     use holochain_types::test_utils::fake_dna_file;
@@ -516,6 +549,249 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test(threaded_scheduler)]
+    async fn migrate_agent_between_dnas() -> Result<()> {
+        observability::test_run().ok();
+        let envs = test_environments();
+        let handle = Conductor::builder().test(&envs).await?;
+        let shutdown = handle.take_shutdown_handle().await.unwrap();
+        let admin_api = RealAdminInterfaceApi::new(handle.clone());
+        let zomes = vec![(
+            TestWasm::MigrateAgentPass.into(),
+            TestWasm::MigrateAgentPass.into(),
+        )];
+        let old_dna = fake_dna_zomes("old", zomes.clone());
+        let new_dna = fake_dna_zomes("new", zomes);
+        let old_dna_hash = old_dna.dna_hash().clone();
+        let new_dna_hash = new_dna.dna_hash().clone();
+        let agent_key = fake_agent_pubkey_2();
+        let old_cell_id = CellId::new(old_dna_hash.clone(), agent_key.clone());
+        let new_cell_id = CellId::new(new_dna_hash.clone(), agent_key);
+        let nick = "cell".to_string();
+        let migrate = || AdminRequest::MigrateAgent {
+            installed_app_id: "app".to_string(),
+            cell_nick: nick.clone(),
+            new_dna_hash: new_dna_hash.clone(),
+            membrane_proof: None,
+        };
+
+        handle.install_dna(old_dna).await?;
+        handle
+            .clone()
+            .install_app(
+                "app".to_string(),
+                vec![(InstalledCell::new(old_cell_id.clone(), nick.clone()), None)],
+            )
+            .await?;
+        handle.activate_app("app".to_string()).await?;
+        handle.clone().setup_cells().await?;
+        let old_env = handle.get_cell_env(&old_cell_id).await?;
+
+        // The new Dna must be registered first
+        let response = admin_api.handle_admin_request(migrate()).await;
+        assert_matches!(response, AdminResponse::Error(_));
+
+        handle.install_dna(new_dna).await?;
+        let response = admin_api.handle_admin_request(migrate()).await;
+        let expected = InstalledApp {
+            installed_app_id: "app".to_string(),
+            cell_data: vec![InstalledCell::new(new_cell_id.clone(), nick.clone())],
+        };
+        assert_matches!(response, AdminResponse::AgentMigrated(app) if app == expected);
+        assert_eq!(handle.list_cell_ids().await?, vec![new_cell_id.clone()]);
+
+        // The old chain is closed and the new one opened after genesis
+        let old_chain = SourceChain::new(old_env.into())?;
+        assert_matches!(
+            old_chain.get_at_index(3)?.unwrap().header(),
+            Header::CloseChain(h) if h.new_dna_hash == new_dna_hash
+        );
+        let new_env = handle.get_cell_env(&new_cell_id).await?;
+        let new_chain = SourceChain::new(new_env.into())?;
+        assert_matches!(
+            new_chain.get_at_index(3)?.unwrap().header(),
+            Header::OpenChain(h) if h.prev_dna_hash == old_dna_hash
+        );
+
+        // The agent already has a cell on the new Dna
+        let response = admin_api.handle_admin_request(migrate()).await;
+        assert_matches!(response, AdminResponse::Error(_));
+
+        handle.shutdown().await;
+        tokio::time::timeout(std::time::Duration::from_secs(1), shutdown)
+            .await
+            .ok();
+        Ok(())
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn failed_migration_keeps_the_old_cell() -> Result<()> {
+        observability::test_run().ok();
+        let envs = test_environments();
+        let handle = Conductor::builder().test(&envs).await?;
+        let shutdown = handle.take_shutdown_handle().await.unwrap();
+        let admin_api = RealAdminInterfaceApi::new(handle.clone());
+        let old_dna = fake_dna_zomes(
+            "old",
+            vec![(
+                TestWasm::MigrateAgentPass.into(),
+                TestWasm::MigrateAgentPass.into(),
+            )],
+        );
+        // Opening the chain on the new Dna fails after its cell is created
+        let new_dna = fake_dna_zomes(
+            "new",
+            vec![(
+                TestWasm::MigrateAgentFail.into(),
+                TestWasm::MigrateAgentFail.into(),
+            )],
+        );
+        let old_dna_hash = old_dna.dna_hash().clone();
+        let new_dna_hash = new_dna.dna_hash().clone();
+        let agent_key = fake_agent_pubkey_2();
+        let old_cell_id = CellId::new(old_dna_hash, agent_key.clone());
+        let new_cell_id = CellId::new(new_dna_hash.clone(), agent_key);
+        let nick = "cell".to_string();
+
+        handle.install_dna(old_dna).await?;
+        handle.install_dna(new_dna).await?;
+        handle
+            .clone()
+            .install_app(
+                "app".to_string(),
+                vec![(InstalledCell::new(old_cell_id.clone(), nick.clone()), None)],
+            )
+            .await?;
+        handle.activate_app("app".to_string()).await?;
+        handle.clone().setup_cells().await?;
+        let old_env = handle.get_cell_env(&old_cell_id).await?;
+
+        let response = admin_api
+            .handle_admin_request(AdminRequest::MigrateAgent {
+                installed_app_id: "app".to_string(),
+                cell_nick: nick.clone(),
+                new_dna_hash,
+                membrane_proof: None,
+            })
+            .await;
+        assert_matches!(response, AdminResponse::Error(_));
+
+        // The app is still on the old cell and the new cell is gone
+        let expected = InstalledApp {
+            installed_app_id: "app".to_string(),
+            cell_data: vec![InstalledCell::new(old_cell_id.clone(), nick)],
+        };
+        assert_eq!(
+            handle.get_app_info(&"app".to_string()).await?,
+            Some(expected)
+        );
+        assert_eq!(handle.list_cell_ids().await?, vec![old_cell_id]);
+        assert_matches!(handle.get_cell_env(&new_cell_id).await, Err(_));
+
+        // The old chain was never closed
+        let old_chain = SourceChain::new(old_env.into())?;
+        assert_matches!(old_chain.get_at_index(3)?, None);
+
+        handle.shutdown().await;
+        tokio::time::timeout(std::time::Duration::from_secs(1), shutdown)
+            .await
+            .ok();
+        Ok(())
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn uninstall_app_removes_cells_and_unused_dnas() -> Result<()> {
+        observability::test_run().ok();
+        let envs = test_environments();
+        let handle = Conductor::builder().test(&envs).await?;
+        let shutdown = handle.take_shutdown_handle().await.unwrap();
+        let admin_api = RealAdminInterfaceApi::new(handle.clone());
+        let uuid = Uuid::new_v4();
+        let dna = fake_dna_zomes(
+            &uuid.to_string(),
+            vec![(TestWasm::Foo.into(), TestWasm::Foo.into())],
+        );
+        let dna_hash = dna.dna_hash().clone();
+        let (dna_path, _tempdir) = write_fake_dna_file(dna.clone()).await.unwrap();
+        let cell_id1 = CellId::new(dna_hash.clone(), fake_agent_pubkey_1());
+        let cell_id2 = CellId::new(dna_hash.clone(), fake_agent_pubkey_2());
+
+        // Two apps with cells on the same dna, only the first one active
+        let res = admin_api
+            .handle_admin_request(AdminRequest::InstallApp(Box::new(InstallAppPayload {
+                dnas: vec![InstallAppDnaPayload::path_only(dna_path, "".to_string())],
+                installed_app_id: "first".to_string(),
+                agent_key: fake_agent_pubkey_1(),
+            })))
+            .await;
+        assert_matches!(res, AdminResponse::AppInstalled(_));
+        let res = admin_api
+            .handle_admin_request(AdminRequest::InstallApp(Box::new(InstallAppPayload {
+                dnas: vec![InstallAppDnaPayload::hash_only(
+                    dna_hash.clone(),
+                    "".to_string(),
+                )],
+                installed_app_id: "second".to_string(),
+                agent_key: fake_agent_pubkey_2(),
+            })))
+            .await;
+        assert_matches!(res, AdminResponse::AppInstalled(_));
+        let res = admin_api
+            .handle_admin_request(AdminRequest::ActivateApp {
+                installed_app_id: "first".to_string(),
+            })
+            .await;
+        assert_matches!(res, AdminResponse::AppActivated);
+        let env_path = handle.get_cell_env(&cell_id1).await?.path().clone();
+        assert!(env_path.exists());
+
+        // Uninstalling the first app removes its cell but not the dna
+        // the second app still uses
+        let res = admin_api
+            .handle_admin_request(AdminRequest::UninstallApp {
+                installed_app_id: "first".to_string(),
+                delete_cell_data: true,
+            })
+            .await;
+        assert_matches!(res, AdminResponse::AppUninstalled);
+        let res = admin_api
+            .handle_admin_request(AdminRequest::ListCellIds)
+            .await;
+        assert_matches!(res, AdminResponse::CellIdsListed(v) if !v.contains(&cell_id1));
+        assert!(!env_path.exists());
+        let res = admin_api.handle_admin_request(AdminRequest::ListDnas).await;
+        assert_matches!(res, AdminResponse::DnasListed(v) if v == vec![dna_hash.clone()]);
+        assert!(handle.get_app_info(&"first".to_string()).await?.is_none());
+
+        // Uninstalling the inactive second app removes the dna
+        let res = admin_api
+            .handle_admin_request(AdminRequest::UninstallApp {
+                installed_app_id: "second".to_string(),
+                delete_cell_data: false,
+            })
+            .await;
+        assert_matches!(res, AdminResponse::AppUninstalled);
+        let res = admin_api.handle_admin_request(AdminRequest::ListDnas).await;
+        assert_matches!(res, AdminResponse::DnasListed(v) if v.is_empty());
+        assert!(handle.get_app_info(&"second".to_string()).await?.is_none());
+        assert!(!handle.list_cell_ids().await?.contains(&cell_id2));
+
+        // It's an error to uninstall an app that isn't installed
+        let res = admin_api
+            .handle_admin_request(AdminRequest::UninstallApp {
+                installed_app_id: "second".to_string(),
+                delete_cell_data: false,
+            })
+            .await;
+        assert_matches!(res, AdminResponse::Error(_));
+
+        handle.shutdown().await;
+        tokio::time::timeout(std::time::Duration::from_secs(1), shutdown)
+            .await
+            .ok();
+        Ok(())
+    }
+
     #[tokio::test(threaded_scheduler)]
     async fn dna_read_parses() -> Result<()> {
         let uuid = Uuid::new_v4();
@@ -2,10 +2,14 @@ use super::InterfaceApi;
 use crate::conductor::api::error::ConductorApiResult;
 use crate::conductor::api::error::ExternalApiWireError;
 use crate::conductor::api::error::SerializationError;
+use crate::conductor::error::ConductorError;
 use crate::conductor::interface::error::InterfaceError;
 use crate::conductor::interface::error::InterfaceResult;
+use crate::conductor::interface::SignalSubscriptions;
 use crate::conductor::state::AppInterfaceId;
 use crate::conductor::ConductorHandle;
+use holochain_conductor_api::signal_subscription::SignalSubscription;
+use holochain_keystore::KeystoreSenderExt;
 
 use holochain_serialized_bytes::prelude::*;
 
@@ -41,6 +45,8 @@ pub trait AppInterfaceApi: 'static + Send + Sync + Clone {
 pub struct RealAppInterfaceApi {
     conductor_handle: ConductorHandle,
     interface_id: AppInterfaceId,
+    installed_app_id: Option<InstalledAppId>,
+    signal_subscriptions: SignalSubscriptions,
 }
 
 impl RealAppInterfaceApi {
@@ -49,8 +55,23 @@ impl RealAppInterfaceApi {
         Self {
             conductor_handle,
             interface_id,
+            installed_app_id: None,
+            signal_subscriptions: SignalSubscriptions::default(),
         }
     }
+
+    /// Bind this interface to an app, which crypto requests are made on behalf of
+    pub fn bound_to(mut self, installed_app_id: Option<InstalledAppId>) -> Self {
+        self.installed_app_id = installed_app_id;
+        self
+    }
+
+    /// Use these signal subscriptions, which belong to a single connection,
+    /// for `SignalSubscription` requests
+    pub fn with_signal_subscriptions(mut self, signal_subscriptions: SignalSubscriptions) -> Self {
+        self.signal_subscriptions = signal_subscriptions;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -97,9 +118,149 @@ impl AppInterfaceApi for RealAppInterfaceApi {
                     Err(e) => Ok(AppResponse::Error(e.into())),
                 }
             }
-            AppRequest::SignalSubscription(_) => Ok(AppResponse::Unimplemented(request)),
-            AppRequest::Crypto(_) => Ok(AppResponse::Unimplemented(request)),
+            AppRequest::SignalSubscription(SignalSubscription {
+                installed_app_id,
+                filters,
+            }) => {
+                let app = self
+                    .conductor_handle
+                    .get_app_info(&installed_app_id)
+                    .await?
+                    .ok_or_else(|| ConductorError::AppNotInstalled(installed_app_id.clone()))?;
+                let cell_ids = app
+                    .cell_data
+                    .into_iter()
+                    .map(|cell| cell.into_id())
+                    .collect();
+                self.signal_subscriptions
+                    .set(installed_app_id, cell_ids, filters);
+                Ok(AppResponse::SignalSubscriptionUpdated)
+            }
+            AppRequest::SubscribeData(subscription) => {
+                let installed_app_id = match &self.installed_app_id {
+                    Some(installed_app_id) => installed_app_id.clone(),
+                    None => {
+                        return Ok(AppResponse::Error(ExternalApiWireError::InterfaceNotBound(
+                            "Data can only be subscribed to on an interface bound to an app"
+                                .to_string(),
+                        )))
+                    }
+                };
+                let app = self
+                    .conductor_handle
+                    .get_app_info(&installed_app_id)
+                    .await?
+                    .ok_or_else(|| ConductorError::AppNotInstalled(installed_app_id.clone()))?;
+                let cell_ids = app
+                    .cell_data
+                    .into_iter()
+                    .map(|cell| cell.into_id())
+                    .collect();
+                self.signal_subscriptions
+                    .subscribe_data(installed_app_id, cell_ids, subscription);
+                Ok(AppResponse::DataSubscribed)
+            }
+            AppRequest::UnsubscribeData(subscription) => {
+                // Nothing can have been subscribed to on an unbound interface
+                if let Some(installed_app_id) = &self.installed_app_id {
+                    self.signal_subscriptions
+                        .unsubscribe_data(installed_app_id, &subscription);
+                }
+                Ok(AppResponse::DataUnsubscribed)
+            }
+            AppRequest::Crypto(request) => {
+                let installed_app_id = match &self.installed_app_id {
+                    Some(installed_app_id) => installed_app_id.clone(),
+                    None => {
+                        return Ok(crypto_unauthorized(
+                            "This interface is not bound to an app".to_string(),
+                        ))
+                    }
+                };
+                let app = match self.active_app(&installed_app_id).await? {
+                    Some(app) => app,
+                    None => {
+                        return Ok(crypto_unauthorized(format!(
+                            "The app {} is not active on this conductor",
+                            installed_app_id
+                        )))
+                    }
+                };
+                let keystore = self.conductor_handle.keystore();
+                match *request {
+                    CryptoRequest::Sign(input) => {
+                        if !app
+                            .cell_data
+                            .iter()
+                            .any(|cell| cell.as_id().agent_pubkey() == &input.key)
+                        {
+                            return Ok(crypto_unauthorized(format!(
+                                "The agent {} is not an agent of the app {}",
+                                input.key, installed_app_id
+                            )));
+                        }
+                        Ok(AppResponse::Signature(keystore.sign(input).await?))
+                    }
+                    CryptoRequest::CreateX25519Keypair => Ok(AppResponse::X25519KeypairCreated(
+                        self.conductor_handle
+                            .create_app_x25519_keypair(&installed_app_id)
+                            .await?,
+                    )),
+                    CryptoRequest::Encrypt(input) => {
+                        let keys = self
+                            .conductor_handle
+                            .get_app_x25519_keys(&installed_app_id)
+                            .await?;
+                        if !keys.contains(input.as_sender_ref()) {
+                            return Ok(crypto_unauthorized(format!(
+                                "The sender key was not created for the app {}",
+                                installed_app_id
+                            )));
+                        }
+                        Ok(AppResponse::Encrypted(
+                            keystore.x_25519_x_salsa20_poly1305_encrypt(input).await?,
+                        ))
+                    }
+                    CryptoRequest::Decrypt(input) => {
+                        let keys = self
+                            .conductor_handle
+                            .get_app_x25519_keys(&installed_app_id)
+                            .await?;
+                        if !keys.contains(input.as_recipient_ref()) {
+                            return Ok(crypto_unauthorized(format!(
+                                "The recipient key was not created for the app {}",
+                                installed_app_id
+                            )));
+                        }
+                        Ok(AppResponse::Decrypted(
+                            keystore.x_25519_x_salsa20_poly1305_decrypt(input).await?,
+                        ))
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn crypto_unauthorized(reason: String) -> AppResponse {
+    AppResponse::Error(ExternalApiWireError::CryptoUnauthorized(reason))
+}
+
+impl RealAppInterfaceApi {
+    /// Get the app with this id if it is installed and active
+    async fn active_app(
+        &self,
+        installed_app_id: &InstalledAppId,
+    ) -> ConductorApiResult<Option<InstalledApp>> {
+        if !self
+            .conductor_handle
+            .list_active_apps()
+            .await?
+            .contains(installed_app_id)
+        {
+            return Ok(None);
         }
+        Ok(self.conductor_handle.get_app_info(installed_app_id).await?)
     }
 }
 
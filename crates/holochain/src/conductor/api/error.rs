@@ -84,6 +84,10 @@ pub enum ConductorApiError {
 
     #[error(transparent)]
     SourceChainError(#[from] SourceChainError),
+
+    /// The DPKI service failed to answer
+    #[error("DpkiError: {0}")]
+    DpkiError(String),
 }
 
 /// All the serialization errors that can occur
@@ -107,6 +111,9 @@ impl From<ConductorApiError> for ExternalApiWireError {
     fn from(err: ConductorApiError) -> Self {
         match err {
             ConductorApiError::DnaReadError(e) => ExternalApiWireError::DnaReadError(e),
+            ConductorApiError::ConductorError(e @ ConductorError::MigrateAgentFailed(_, _)) => {
+                ExternalApiWireError::MigrateAgent(e.to_string())
+            }
             e => ExternalApiWireError::internal(e),
         }
     }
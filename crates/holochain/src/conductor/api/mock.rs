@@ -11,6 +11,7 @@ use async_trait::async_trait;
 use holo_hash::DnaHash;
 use holochain_keystore::KeystoreSender;
 use holochain_types::prelude::*;
+use kitsune_p2p::agent_store::AgentInfoSigned;
 use mockall::mock;
 
 // Unfortunate workaround to get mockall to work with async_trait, due to the complexity of each.
@@ -30,7 +31,7 @@ mock! {
 
         fn sync_autonomic_cue(&self, cue: AutonomicCue) -> ConductorApiResult<()>;
 
-        fn sync_dpki_request(&self, method: String, args: String) -> ConductorApiResult<String>;
+        fn sync_dpki_key_state(&self, key: &AgentPubKey) -> ConductorApiResult<KeyState>;
 
         fn mock_keystore(&self) -> &KeystoreSender;
         fn mock_signal_broadcaster(&self) -> SignalBroadcaster;
@@ -38,6 +39,7 @@ mock! {
         fn sync_get_this_dna(&self) -> ConductorApiResult<DnaFile>;
         fn sync_get_zome(&self, dna_hash: &DnaHash, zome_name: &ZomeName) -> ConductorApiResult<Zome>;
         fn sync_get_entry_def(&self, key: &EntryDefBufferKey) -> Option<EntryDef>;
+        fn sync_get_agent_info(&self, agent: &AgentPubKey) -> ConductorApiResult<Option<AgentInfoSigned>>;
         fn into_call_zome_handle(self) -> super::CellConductorReadHandle;
     }
 
@@ -60,8 +62,8 @@ impl CellConductorApiT for MockCellConductorApi {
         self.sync_call_zome(cell_id, call)
     }
 
-    async fn dpki_request(&self, method: String, args: String) -> ConductorApiResult<String> {
-        self.sync_dpki_request(method, args)
+    async fn dpki_key_state(&self, key: &AgentPubKey) -> ConductorApiResult<KeyState> {
+        self.sync_dpki_key_state(key)
     }
 
     async fn autonomic_cue(&self, cue: AutonomicCue) -> ConductorApiResult<()> {
@@ -92,6 +94,13 @@ impl CellConductorApiT for MockCellConductorApi {
         self.sync_get_entry_def(key)
     }
 
+    async fn get_agent_info(
+        &self,
+        agent: &AgentPubKey,
+    ) -> ConductorApiResult<Option<AgentInfoSigned>> {
+        self.sync_get_agent_info(agent)
+    }
+
     fn into_call_zome_handle(self) -> super::CellConductorReadHandle {
         self.into_call_zome_handle()
     }
@@ -0,0 +1,53 @@
+//! The conductor runs each [AutonomicProcess] on every cell periodically,
+//! in a single task managed by the task manager.
+
+use super::handle::ConductorHandle;
+use super::manager::ManagedTaskResult;
+use holochain_types::prelude::*;
+use tokio::sync::broadcast;
+use tracing::*;
+
+/// How often each cell checks the health of the ops it has authored.
+pub const HEALTH_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+/// How often each cell retries validating the ops it has abandoned.
+pub const SLOW_HEAL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30 * 60);
+
+/// Run the autonomic processes until the conductor shuts down.
+pub(crate) async fn autonomic_task(
+    handle: ConductorHandle,
+    mut stop: broadcast::Receiver<()>,
+) -> ManagedTaskResult {
+    let mut health_check = tokio::time::interval(HEALTH_CHECK_INTERVAL);
+    let mut slow_heal = tokio::time::interval(SLOW_HEAL_INTERVAL);
+    // Intervals tick immediately so skip the first tick
+    health_check.tick().await;
+    slow_heal.tick().await;
+    loop {
+        let process = tokio::select! {
+            _ = stop.recv() => {
+                tracing::warn!("Conductor is shutting down: stopping autonomic processes.");
+                break;
+            }
+            _ = health_check.tick() => AutonomicProcess::HealthCheck,
+            _ = slow_heal.tick() => AutonomicProcess::SlowHeal,
+        };
+        run_on_all_cells(&handle, process).await;
+    }
+    Ok(())
+}
+
+async fn run_on_all_cells(handle: &ConductorHandle, process: AutonomicProcess) {
+    let cell_ids = match handle.list_cell_ids().await {
+        Ok(cell_ids) => cell_ids,
+        Err(error) => {
+            error!(?error, "Failed to list cells for autonomic process");
+            return;
+        }
+    };
+    for cell_id in cell_ids {
+        if let Err(error) = handle.run_autonomic_process(process, &cell_id).await {
+            warn!(?cell_id, ?process, ?error, "Autonomic process failed");
+        }
+    }
+}
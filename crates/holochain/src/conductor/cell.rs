@@ -15,16 +15,26 @@ use crate::conductor::entry_def_store::get_entry_def_from_ids;
 use crate::conductor::handle::ConductorHandle;
 use crate::core::queue_consumer::spawn_queue_consumer_tasks;
 use crate::core::queue_consumer::InitialQueueTriggers;
+use crate::core::queue_consumer::QueueConsumerTasks;
 use crate::core::queue_consumer::QueueTriggers;
 use crate::core::ribosome::guest_callback::init::InitResult;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
 use crate::core::ribosome::real_ribosome::RealRibosome;
 use crate::core::ribosome::ZomeCallInvocation;
 use crate::core::workflow::call_zome_workflow;
 use crate::core::workflow::error::WorkflowError;
 use crate::core::workflow::genesis_workflow::genesis_workflow;
+use crate::core::workflow::health_check_workflow::health_check_workflow;
+use crate::core::workflow::health_check_workflow::HealthCheckWorkspace;
 use crate::core::workflow::incoming_dht_ops_workflow::incoming_dht_ops_workflow;
+use crate::core::workflow::incoming_validation_receipt_workflow::incoming_validation_receipt_workflow;
+use crate::core::workflow::incoming_warrant_workflow::incoming_warrant_workflow;
 use crate::core::workflow::initialize_zomes_workflow;
+use crate::core::workflow::migrate_agent_workflow::migrate_agent_workflow;
+use crate::core::workflow::migrate_agent_workflow::MigrateAgentWorkflowArgs;
 use crate::core::workflow::produce_dht_ops_workflow::dht_op_light::light_to_op;
+use crate::core::workflow::slow_heal_workflow::slow_heal_workflow;
+use crate::core::workflow::slow_heal_workflow::SlowHealWorkspace;
 use crate::core::workflow::CallZomeWorkflowArgs;
 use crate::core::workflow::CallZomeWorkspace;
 use crate::core::workflow::GenesisWorkflowArgs;
@@ -38,6 +48,7 @@ use futures::future::FutureExt;
 use hash_type::AnyDht;
 use holo_hash::*;
 use holochain_cascade::authority;
+use holochain_keystore::AgentPubKeyExt;
 use holochain_lmdb::db::GetDb;
 use holochain_lmdb::env::EnvironmentRead;
 use holochain_lmdb::env::EnvironmentWrite;
@@ -102,6 +113,9 @@ where
     env: EnvironmentWrite,
     holochain_p2p_cell: P2pCell,
     queue_triggers: QueueTriggers,
+    queue_consumer_tasks: QueueConsumerTasks,
+    redundancy_target: u32,
+    health_report: parking_lot::RwLock<Option<CellHealthReport>>,
 }
 
 impl Cell {
@@ -115,6 +129,8 @@ impl Cell {
         mut holochain_p2p_cell: holochain_p2p::HolochainP2pCell,
         managed_task_add_sender: sync::mpsc::Sender<ManagedTaskAdd>,
         managed_task_stop_broadcaster: sync::broadcast::Sender<()>,
+        receipt_bundle_size: u32,
+        redundancy_target: u32,
     ) -> CellResult<(Self, InitialQueueTriggers)> {
         let conductor_api = CellConductorApi::new(conductor_handle.clone(), id.clone());
 
@@ -125,15 +141,25 @@ impl Cell {
         };
 
         if has_genesis {
+            // chains written before the capability index existed
+            // need it filled in before any calls are authorized
+            let mut chain = SourceChainBuf::new(env.clone().into())?;
+            if chain.backfill_capabilities()? {
+                env.guard()
+                    .with_commit::<SourceChainError, _, _>(|writer| chain.flush_to_txn(writer))?;
+            }
+
             holochain_p2p_cell.join().await?;
-            let (queue_triggers, initial_queue_triggers) = spawn_queue_consumer_tasks(
-                &env,
-                holochain_p2p_cell.clone(),
-                conductor_api.clone(),
-                managed_task_add_sender,
-                managed_task_stop_broadcaster,
-            )
-            .await;
+            let (queue_triggers, initial_queue_triggers, queue_consumer_tasks) =
+                spawn_queue_consumer_tasks(
+                    &env,
+                    holochain_p2p_cell.clone(),
+                    conductor_api.clone(),
+                    managed_task_add_sender,
+                    managed_task_stop_broadcaster,
+                    receipt_bundle_size,
+                )
+                .await;
 
             Ok((
                 Self {
@@ -142,6 +168,9 @@ impl Cell {
                     env,
                     holochain_p2p_cell,
                     queue_triggers,
+                    queue_consumer_tasks,
+                    redundancy_target,
+                    health_report: parking_lot::RwLock::new(None),
                 },
                 initial_queue_triggers,
             ))
@@ -159,6 +188,12 @@ impl Cell {
         cell_env: EnvironmentWrite,
         membrane_proof: Option<SerializedBytes>,
     ) -> CellResult<()> {
+        // The chain may already exist, e.g. when a migration is retried,
+        // and running genesis again would fork it
+        if SourceChainBuf::new(cell_env.clone().into())?.has_genesis() {
+            return Ok(());
+        }
+
         // get the dna
         let dna_file = conductor_handle
             .get_dna(id.dna_hash())
@@ -258,6 +293,22 @@ impl Cell {
                 .instrument(debug_span!("cell_handle_publish"))
                 .await;
             }
+            PublishWarrant {
+                span_context: _,
+                respond,
+                warrant,
+                ..
+            } => {
+                async {
+                    let res = self
+                        .handle_publish_warrant(warrant)
+                        .await
+                        .map_err(holochain_p2p::HolochainP2pError::other);
+                    respond.respond(Ok(async move { res }.boxed().into()));
+                }
+                .instrument(debug_span!("cell_handle_publish_warrant"))
+                .await;
+            }
             GetValidationPackage {
                 span_context: _,
                 respond,
@@ -324,6 +375,22 @@ impl Cell {
                 .instrument(debug_span!("cell_handle_get_links"))
                 .await;
             }
+            CountLinks {
+                span_context: _,
+                respond,
+                link_key,
+                options,
+                ..
+            } => {
+                async {
+                    let res = self
+                        .handle_count_links(link_key, options)
+                        .map_err(holochain_p2p::HolochainP2pError::other);
+                    respond.respond(Ok(async move { res }.boxed().into()));
+                }
+                .instrument(debug_span!("cell_handle_count_links"))
+                .await;
+            }
             GetAgentActivity {
                 span_context: _,
                 respond,
@@ -393,11 +460,12 @@ impl Cell {
             SignNetworkData {
                 span_context: _,
                 respond,
+                data,
                 ..
             } => {
                 async {
                     let res = self
-                        .handle_sign_network_data()
+                        .handle_sign_network_data(data)
                         .await
                         .map_err(holochain_p2p::HolochainP2pError::other);
                     respond.respond(Ok(async move { res }.boxed().into()));
@@ -409,12 +477,12 @@ impl Cell {
         Ok(())
     }
 
-    #[instrument(skip(self, _request_validation_receipt, _dht_hash, ops))]
+    #[instrument(skip(self, request_validation_receipt, _dht_hash, ops))]
     /// we are receiving a "publish" event from the network
     async fn handle_publish(
         &self,
         from_agent: AgentPubKey,
-        _request_validation_receipt: bool,
+        request_validation_receipt: bool,
         _dht_hash: holo_hash::AnyDhtHash,
         ops: Vec<(holo_hash::DhtOpHash, holochain_types::dht_op::DhtOp)>,
     ) -> CellResult<()> {
@@ -423,6 +491,7 @@ impl Cell {
             self.queue_triggers.sys_validation.clone(),
             ops,
             Some(from_agent),
+            request_validation_receipt,
         )
         .await
         .map_err(Box::new)
@@ -431,6 +500,15 @@ impl Cell {
         Ok(())
     }
 
+    #[instrument(skip(self, warrant))]
+    /// we are receiving a warrant from another agent activity authority
+    async fn handle_publish_warrant(&self, warrant: Warrant) -> CellResult<()> {
+        incoming_warrant_workflow(&self.env, warrant, &self.conductor_api)
+            .await
+            .map_err(Box::new)?;
+        Ok(())
+    }
+
     #[instrument(skip(self))]
     /// a remote node is attempting to retrieve a validation package
     #[tracing::instrument(skip(self), level = "trace")]
@@ -512,14 +590,15 @@ impl Cell {
         authority::handle_get_element(env, hash).map_err(Into::into)
     }
 
-    #[instrument(skip(self, _dht_hash, _options))]
+    #[instrument(skip(self, options))]
     /// a remote node is asking us for metadata
     async fn handle_get_meta(
         &self,
-        _dht_hash: holo_hash::AnyDhtHash,
-        _options: holochain_p2p::event::GetMetaOptions,
+        dht_hash: holo_hash::AnyDhtHash,
+        options: holochain_p2p::event::GetMetaOptions,
     ) -> CellResult<MetadataSet> {
-        unimplemented!()
+        let env = self.env.clone();
+        authority::handle_get_meta(env.into(), dht_hash, options).map_err(Into::into)
     }
 
     #[instrument(skip(self, options))]
@@ -537,6 +616,18 @@ impl Cell {
         authority::handle_get_links(env.into(), link_key, options).map_err(Into::into)
     }
 
+    #[instrument(skip(self, options))]
+    /// a remote node is asking us to count links
+    fn handle_count_links(
+        &self,
+        link_key: WireLinkMetaKey,
+        options: holochain_p2p::event::GetLinksOptions,
+    ) -> CellResult<CountLinksResponse> {
+        debug!(id = ?self.id());
+        let env = self.env.clone();
+        authority::handle_count_links(env.into(), link_key, options).map_err(Into::into)
+    }
+
     #[instrument(skip(self, options))]
     fn handle_get_agent_activity(
         &self,
@@ -550,8 +641,12 @@ impl Cell {
 
     /// a remote agent is sending us a validation receipt.
     #[tracing::instrument(skip(self))]
-    async fn handle_validation_receipt(&self, _receipt: SerializedBytes) -> CellResult<()> {
-        unimplemented!()
+    async fn handle_validation_receipt(&self, receipt: SerializedBytes) -> CellResult<()> {
+        let receipt: SignedValidationReceipt = receipt.try_into()?;
+        incoming_validation_receipt_workflow(&self.env, receipt, &self.conductor_api)
+            .await
+            .map_err(Box::new)?;
+        Ok(())
     }
 
     #[instrument(skip(self, dht_arc, since, until))]
@@ -598,7 +693,10 @@ impl Cell {
                         let cas = ElementBuf::rejected(self.env.clone().into())?;
                         light_to_op(val.op, &cas)?
                     }
-                    ValidationStatus::Abandoned => todo!("Add when abandoned store is added"),
+                    ValidationStatus::Abandoned => {
+                        let cas = ElementBuf::abandoned(self.env.clone().into())?;
+                        light_to_op(val.op, &cas)?
+                    }
                 };
                 let basis = full_op.dht_basis();
                 out.push((basis, op_hash, full_op));
@@ -608,9 +706,13 @@ impl Cell {
     }
 
     /// the network module would like this cell/agent to sign some data
-    #[tracing::instrument(skip(self))]
-    async fn handle_sign_network_data(&self) -> CellResult<Signature> {
-        Ok(vec![0; 64].into())
+    #[tracing::instrument(skip(self, data))]
+    async fn handle_sign_network_data(&self, data: Vec<u8>) -> CellResult<Signature> {
+        Ok(self
+            .id
+            .agent_pubkey()
+            .sign_raw(self.env.keystore(), &data)
+            .await?)
     }
 
     /// When the Conductor determines that it's time to execute some [AutonomicProcess],
@@ -618,9 +720,47 @@ impl Cell {
     #[tracing::instrument(skip(self, process))]
     pub async fn handle_autonomic_process(&self, process: AutonomicProcess) -> CellResult<()> {
         match process {
-            AutonomicProcess::SlowHeal => unimplemented!(),
-            AutonomicProcess::HealthCheck => unimplemented!(),
+            AutonomicProcess::SlowHeal => {
+                let workspace = SlowHealWorkspace::new(self.env.clone().into())?;
+                let num_ops = slow_heal_workflow(
+                    workspace,
+                    self.env.clone().into(),
+                    &mut self.queue_triggers.sys_validation.clone(),
+                )
+                .await
+                .map_err(Box::new)?;
+                debug!(num_ops, "Retrying abandoned ops");
+            }
+            AutonomicProcess::HealthCheck => {
+                let redundancy_target = self
+                    .conductor_api
+                    .get_this_dna()
+                    .await
+                    .map_err(Box::new)?
+                    .dna_def()
+                    .redundancy_target
+                    .unwrap_or(self.redundancy_target);
+                let workspace = HealthCheckWorkspace::new(self.env.clone().into())?;
+                let report = health_check_workflow(
+                    workspace,
+                    self.env.clone().into(),
+                    &mut self.holochain_p2p_cell.clone(),
+                    &mut self.queue_triggers.publish_dht_ops.clone(),
+                    redundancy_target,
+                )
+                .await
+                .map_err(Box::new)?;
+                debug!(?report);
+                *self.health_report.write() = Some(report);
+            }
         }
+        Ok(())
+    }
+
+    /// The report from the last health check on this cell,
+    /// or None if the health check hasn't run yet.
+    pub fn health_report(&self) -> Option<CellHealthReport> {
+        self.health_report.read().clone()
     }
 
     #[instrument(skip(self, from_agent, fn_name, cap, payload))]
@@ -633,6 +773,17 @@ impl Cell {
         cap: Option<CapSecret>,
         payload: ExternIO,
     ) -> CellResult<SerializedBytes> {
+        // Don't serve agents we hold a warrant against
+        if WarrantStore::new(self.env.clone().into())?.is_warranted(&from_agent)? {
+            warn!(agent = ?from_agent, "Refusing remote call from warranted agent");
+            return Ok(ZomeCallResponse::Unauthorized(
+                self.id.clone(),
+                zome_name,
+                fn_name,
+                from_agent,
+            )
+            .try_into()?);
+        }
         let invocation = ZomeCall {
             cell_id: self.id.clone(),
             zome_name,
@@ -683,7 +834,7 @@ impl Cell {
             workspace_lock,
             self.holochain_p2p_cell.clone(),
             keystore,
-            arc.clone().into(),
+            arc.clone(),
             args,
             self.queue_triggers.produce_dht_ops.clone(),
         )
@@ -739,6 +890,41 @@ impl Cell {
         Ok(())
     }
 
+    /// Run the migrate_agent callback in every zome and, if they all pass,
+    /// close or open this cell's chain to record the migration.
+    /// The other Dna is the one being migrated to when closing
+    /// or from when opening.
+    #[tracing::instrument(skip(self))]
+    pub(super) async fn migrate_agent(
+        &self,
+        migrate_agent: MigrateAgent,
+        other_dna_hash: DnaHash,
+    ) -> CellResult<MigrateAgentResult> {
+        let workspace = CallZomeWorkspace::new(self.env().clone().into())?;
+        let ribosome = self.get_ribosome().await?;
+        let dna_def = ribosome.dna_file().dna_def().clone();
+        let args = MigrateAgentWorkflowArgs {
+            dna_def,
+            ribosome,
+            migrate_agent,
+            other_dna_hash,
+        };
+        Ok(migrate_agent_workflow(
+            workspace,
+            self.env.clone().into(),
+            self.queue_triggers.produce_dht_ops.clone(),
+            args,
+        )
+        .await
+        .map_err(Box::new)?)
+    }
+
+    /// Stop this Cell's queue consumers, waiting for any workflow they
+    /// are running to finish, so nothing writes to its environment afterwards.
+    pub(super) async fn stop_workflows(&self) {
+        self.queue_consumer_tasks.stop().await
+    }
+
     /// Delete all data associated with this Cell by deleting the associated
     /// LMDB environment. Completely reverses Cell creation.
     #[tracing::instrument(skip(self))]
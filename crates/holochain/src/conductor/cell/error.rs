@@ -51,6 +51,8 @@ pub enum CellError {
     #[error(transparent)]
     HolochainP2pError(#[from] HolochainP2pError),
     #[error(transparent)]
+    KeystoreError(#[from] holochain_keystore::KeystoreError),
+    #[error(transparent)]
     ConductorApiError(#[from] Box<ConductorApiError>),
     #[error(transparent)]
     SerializedBytesError(#[from] holochain_serialized_bytes::SerializedBytesError),
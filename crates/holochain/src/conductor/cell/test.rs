@@ -1,7 +1,13 @@
 use crate::conductor::manager::spawn_task_manager;
+use crate::core::workflow::health_check_workflow::DEFAULT_REDUNDANCY_TARGET;
 use crate::core::workflow::incoming_dht_ops_workflow::IncomingDhtOpsWorkspace;
+use crate::core::workflow::publish_dht_ops_workflow::DEFAULT_RECEIPT_BUNDLE_SIZE;
+use crate::fixt::CreateFixturator;
+use crate::fixt::DeleteFixturator;
 use crate::fixt::DnaFileFixturator;
+use crate::fixt::EntryHashFixturator;
 use crate::fixt::SignatureFixturator;
+use crate::fixt::UpdateFixturator;
 use crate::test_utils::test_network;
 use ::fixt::prelude::*;
 use holo_hash::HasHash;
@@ -45,6 +51,8 @@ async fn test_cell_handle_publish() {
         holochain_p2p_cell,
         add_task_sender,
         stop_tx.clone(),
+        DEFAULT_RECEIPT_BUNDLE_SIZE,
+        DEFAULT_REDUNDANCY_TARGET,
     )
     .await
     .unwrap();
@@ -76,3 +84,192 @@ async fn test_cell_handle_publish() {
     stop_tx.send(()).unwrap();
     shutdown.await.unwrap();
 }
+
+#[tokio::test(threaded_scheduler)]
+async fn test_cell_handle_get_meta_from_vault() {
+    use holo_hash::AnyDhtHash;
+    use holochain_cascade::authority::handle_get_meta;
+    use holochain_lmdb::buffer::BufferedStore;
+    use holochain_lmdb::env::WriteManager;
+    use holochain_p2p::event::GetMetaOptions;
+    use holochain_state::metadata::MetadataBuf;
+    use holochain_state::metadata::MetadataBufT;
+
+    let cell_env = test_cell_env();
+    let env = cell_env.env();
+
+    let entry_hash = fixt!(EntryHash);
+    let mut live = fixt!(Create);
+    let mut deleted = fixt!(Create);
+    let mut rejected = fixt!(Create);
+    for h in [&mut live, &mut deleted, &mut rejected].iter_mut() {
+        h.entry_hash = entry_hash.clone();
+    }
+    let deleted_hash = HeaderHashed::from_content_sync(deleted.clone().into());
+    let deleted_hash = deleted_hash.as_hash().clone();
+
+    let mut update = fixt!(Update);
+    update.original_header_address = deleted_hash.clone();
+    update.original_entry_address = entry_hash.clone();
+    let mut delete = fixt!(Delete);
+    delete.deletes_address = deleted_hash.clone();
+    delete.deletes_entry_address = entry_hash.clone();
+
+    let timed = |h: Header| TimedHeaderHash::from(HeaderHashed::from_content_sync(h));
+    let live_th = timed(live.clone().into());
+    let deleted_th = timed(deleted.clone().into());
+    let rejected_th = timed(rejected.clone().into());
+    let update_th = timed(update.clone().into());
+    let delete_th = timed(delete.clone().into());
+
+    // Populate the vault the way integration does
+    let mut meta_vault = MetadataBuf::vault(env.clone().into()).unwrap();
+    meta_vault
+        .register_header(NewEntryHeader::Create(live))
+        .unwrap();
+    meta_vault
+        .register_header(NewEntryHeader::Create(deleted))
+        .unwrap();
+    meta_vault
+        .register_rejected_header(NewEntryHeader::Create(rejected))
+        .unwrap();
+    meta_vault.register_update(update).unwrap();
+    meta_vault.register_delete(delete).unwrap();
+    env.with_commit(|writer| meta_vault.flush_to_txn_ref(writer))
+        .unwrap();
+
+    let none = MetadataRequest {
+        all_valid_headers: false,
+        all_invalid_headers: false,
+        all_deletes: false,
+        all_updates: false,
+        follow_redirects: false,
+        entry_dht_status: false,
+    };
+    let get_meta = |hash: AnyDhtHash, metadata_request: MetadataRequest| {
+        handle_get_meta(
+            env.clone().into(),
+            hash,
+            GetMetaOptions { metadata_request },
+        )
+        .unwrap()
+    };
+    let empty = || MetadataSet {
+        headers: Default::default(),
+        invalid_headers: Default::default(),
+        deletes: Default::default(),
+        updates: Default::default(),
+        entry_dht_status: None,
+    };
+    let set = |hashes: Vec<&TimedHeaderHash>| hashes.into_iter().cloned().collect();
+    let entry_basis: AnyDhtHash = entry_hash.clone().into();
+    let header_basis: AnyDhtHash = deleted_hash.clone().into();
+
+    // Nothing requested, nothing returned
+    assert_eq!(get_meta(entry_basis.clone(), none.clone()), empty());
+
+    let meta = get_meta(
+        entry_basis.clone(),
+        MetadataRequest {
+            all_valid_headers: true,
+            ..none.clone()
+        },
+    );
+    assert_eq!(
+        meta,
+        MetadataSet {
+            headers: set(vec![&live_th, &deleted_th]),
+            ..empty()
+        }
+    );
+
+    let meta = get_meta(
+        entry_basis.clone(),
+        MetadataRequest {
+            all_invalid_headers: true,
+            ..none.clone()
+        },
+    );
+    assert_eq!(
+        meta,
+        MetadataSet {
+            invalid_headers: set(vec![&rejected_th]),
+            ..empty()
+        }
+    );
+
+    let meta = get_meta(
+        entry_basis.clone(),
+        MetadataRequest {
+            all_deletes: true,
+            ..none.clone()
+        },
+    );
+    assert_eq!(
+        meta,
+        MetadataSet {
+            deletes: set(vec![&delete_th]),
+            ..empty()
+        }
+    );
+
+    let meta = get_meta(
+        entry_basis.clone(),
+        MetadataRequest {
+            all_updates: true,
+            ..none.clone()
+        },
+    );
+    assert_eq!(
+        meta,
+        MetadataSet {
+            updates: set(vec![&update_th]),
+            ..empty()
+        }
+    );
+
+    // One of the two headers is still live
+    let meta = get_meta(
+        entry_basis.clone(),
+        MetadataRequest {
+            entry_dht_status: true,
+            ..none.clone()
+        },
+    );
+    assert_eq!(
+        meta,
+        MetadataSet {
+            entry_dht_status: Some(EntryDhtStatus::Live),
+            ..empty()
+        }
+    );
+
+    // A header basis only carries deletes and updates
+    let meta = get_meta(
+        header_basis.clone(),
+        MetadataRequest {
+            all_valid_headers: true,
+            all_invalid_headers: true,
+            entry_dht_status: true,
+            ..none.clone()
+        },
+    );
+    assert_eq!(meta, empty());
+
+    let meta = get_meta(
+        header_basis,
+        MetadataRequest {
+            all_deletes: true,
+            all_updates: true,
+            ..none
+        },
+    );
+    assert_eq!(
+        meta,
+        MetadataSet {
+            deletes: set(vec![&delete_th]),
+            updates: set(vec![&update_th]),
+            ..empty()
+        }
+    );
+}
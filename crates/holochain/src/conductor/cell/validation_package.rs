@@ -91,14 +91,24 @@ pub(super) async fn get_as_author(
             // TODO: I'm not sure if we should handle this case, it seems like they should already have the element
             Ok(None.into())
         }
-        RequiredValidationType::SubChain => Ok(Some(get_as_author_sub_chain(
-            header_seq,
-            app_entry_type,
-            &source_chain,
-        )?)
-        .into()),
+        RequiredValidationType::SubChain => {
+            let meta_authored = MetadataBuf::authored(env)?;
+            Ok(Some(get_as_author_sub_chain(
+                header_seq,
+                app_entry_type,
+                &source_chain,
+                &meta_authored,
+            )?)
+            .into())
+        }
         RequiredValidationType::Full => {
-            Ok(Some(get_as_author_full(header_seq, &source_chain)?).into())
+            let meta_authored = MetadataBuf::authored(env)?;
+            Ok(Some(get_as_author_full(
+                header_seq,
+                &source_chain,
+                &meta_authored,
+            )?)
+            .into())
         }
         RequiredValidationType::Custom => {
             let element_authored = ElementBuf::authored(env.clone(), false)?;
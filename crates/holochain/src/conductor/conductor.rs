@@ -10,11 +10,14 @@
 //! users in a testing environment.
 use super::api::RealAdminInterfaceApi;
 use super::api::RealAppInterfaceApi;
+use super::autonomic::autonomic_task;
 use super::config::AdminInterfaceConfig;
 use super::config::InterfaceDriver;
 use super::dna_store::DnaDefBuf;
 use super::dna_store::DnaStore;
 use super::dna_store::RealDnaStore;
+use super::dpki::DpkiApp;
+use super::dpki::DpkiImpl;
 use super::entry_def_store::get_entry_defs;
 use super::entry_def_store::EntryDefBuf;
 use super::entry_def_store::EntryDefBufferKey;
@@ -26,6 +29,7 @@ use super::interface::websocket::spawn_admin_interface_task;
 use super::interface::websocket::spawn_app_interface_task;
 use super::interface::websocket::spawn_websocket_listener;
 use super::interface::websocket::SIGNAL_BUFFER_SIZE;
+use super::interface::DataSubscribers;
 use super::interface::SignalBroadcaster;
 use super::manager::keep_alive_task;
 use super::manager::spawn_task_manager;
@@ -35,6 +39,7 @@ use super::manager::TaskManagerRunHandle;
 use super::p2p_store::all_agent_infos;
 use super::p2p_store::get_single_agent_info;
 use super::p2p_store::inject_agent_infos;
+use super::passphrase_service::passphrase_provider;
 use super::paths::EnvironmentRootPath;
 use super::state::AppInterfaceId;
 use super::state::ConductorState;
@@ -48,12 +53,15 @@ use crate::conductor::dna_store::MockDnaStore;
 use crate::conductor::error::ConductorResult;
 use crate::conductor::handle::ConductorHandle;
 use crate::core::queue_consumer::InitialQueueTriggers;
+use crate::core::workflow::health_check_workflow::DEFAULT_REDUNDANCY_TARGET;
+use crate::core::workflow::publish_dht_ops_workflow::DEFAULT_RECEIPT_BUNDLE_SIZE;
 pub use builder::*;
 use fallible_iterator::FallibleIterator;
 use futures::future;
 use futures::future::TryFutureExt;
 use futures::stream::StreamExt;
 use holo_hash::DnaHash;
+use holo_hash::WasmHash;
 use holochain_keystore::lair_keystore::spawn_lair_keystore;
 use holochain_keystore::test_keystore::spawn_test_keystore;
 use holochain_keystore::KeystoreSender;
@@ -62,6 +70,7 @@ use holochain_lmdb::buffer::BufferedStore;
 use holochain_lmdb::buffer::KvStore;
 use holochain_lmdb::buffer::KvStoreT;
 use holochain_lmdb::db;
+use holochain_lmdb::encryption::EncryptionKey;
 use holochain_lmdb::env::EnvironmentKind;
 use holochain_lmdb::env::EnvironmentWrite;
 use holochain_lmdb::env::ReadManager;
@@ -73,6 +82,7 @@ use holochain_state::wasm::WasmBuf;
 use holochain_types::prelude::*;
 use kitsune_p2p::agent_store::AgentInfoSigned;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio::sync::RwLock;
@@ -135,6 +145,9 @@ where
     /// Collection app interface data, keyed by id
     app_interfaces: HashMap<AppInterfaceId, AppInterfaceRuntime>,
 
+    /// The data subscriptions of the connections to all app interfaces
+    data_subscribers: DataSubscribers,
+
     /// Channel on which to send info about tasks we want to manage
     managed_task_add_sender: mpsc::Sender<ManagedTaskAdd>,
 
@@ -156,6 +169,20 @@ where
 
     /// Handle to the network actor.
     holochain_p2p: holochain_p2p::HolochainP2pRef,
+
+    /// How many validation receipts an op needs before the cells
+    /// of this conductor stop publishing it.
+    receipt_bundle_size: u32,
+
+    /// How many authorities the health check of the cells of this conductor
+    /// expects to hold each authored op, unless their Dna sets its own.
+    redundancy_target: u32,
+
+    /// Whether the private entries of each cell are encrypted at rest.
+    encrypt_private_entries: bool,
+
+    /// The DPKI service used to check the state of agent keys, if any.
+    dpki: Option<DpkiImpl>,
 }
 
 impl Conductor {
@@ -308,20 +335,51 @@ where
         Ok(())
     }
 
+    /// Spawn the task which runs the autonomic processes on every cell
+    /// and register it with the TaskManager
+    pub(super) async fn add_autonomic_task_via_handle(
+        &mut self,
+        handle: ConductorHandle,
+    ) -> ConductorResult<()> {
+        let stop_rx = self.managed_task_stop_broadcaster.subscribe();
+        let task = tokio::spawn(autonomic_task(handle, stop_rx));
+        self.manage_task(ManagedTaskAdd::new(
+            task,
+            Box::new(|result| {
+                result.unwrap_or_else(|e| {
+                    error!(
+                        error = &e as &dyn std::error::Error,
+                        "Autonomic processes died"
+                    )
+                });
+                None
+            }),
+        ))
+        .await
+    }
+
     pub(super) async fn add_app_interface_via_handle(
         &mut self,
         port: u16,
+        installed_app_id: Option<InstalledAppId>,
         handle: ConductorHandle,
     ) -> ConductorResult<u16> {
         let interface_id: AppInterfaceId = format!("interface-{}", port).into();
-        let app_api = RealAppInterfaceApi::new(handle, interface_id.clone());
+        let app_api = RealAppInterfaceApi::new(handle, interface_id.clone())
+            .bound_to(installed_app_id.clone());
         // This receiver is thrown away because we can produce infinite new
         // receivers from the Sender
         let (signal_tx, _r) = tokio::sync::broadcast::channel(SIGNAL_BUFFER_SIZE);
         let stop_rx = self.managed_task_stop_broadcaster.subscribe();
-        let (port, task) = spawn_app_interface_task(port, app_api, signal_tx.clone(), stop_rx)
-            .await
-            .map_err(Box::new)?;
+        let (port, task) = spawn_app_interface_task(
+            port,
+            app_api,
+            signal_tx.clone(),
+            self.data_subscribers.clone(),
+            stop_rx,
+        )
+        .await
+        .map_err(Box::new)?;
         // TODO: RELIABILITY: Handle this task by restarting it if it fails and log the error
         self.manage_task(ManagedTaskAdd::dont_handle(task)).await?;
         let interface = AppInterfaceRuntime::Websocket { signal_tx };
@@ -331,7 +389,7 @@ where
         }
 
         self.app_interfaces.insert(interface_id.clone(), interface);
-        let config = AppInterfaceConfig::websocket(port);
+        let config = AppInterfaceConfig::websocket(port).bound_to(installed_app_id);
         self.update_state(|mut state| {
             state.app_interfaces.insert(interface_id, config);
             Ok(state)
@@ -355,7 +413,7 @@ where
                 unreachable!()
             };
             let _ = self
-                .add_app_interface_via_handle(port, handle.clone())
+                .add_app_interface_via_handle(port, i.installed_app_id.clone(), handle.clone())
                 .await?;
         }
         Ok(())
@@ -368,32 +426,56 @@ where
                 .map(|i| i.signal_tx())
                 .cloned()
                 .collect(),
+            self.data_subscribers.clone(),
         )
     }
 
+    pub(super) fn dpki(&self) -> Option<DpkiImpl> {
+        self.dpki.clone()
+    }
+
     /// Perform Genesis on the source chains for each of the specified CellIds.
     ///
     /// If genesis fails for any cell, this entire function fails, and all other
     /// partial or complete successes are rolled back.
-    pub(super) async fn genesis_cells(
+    ///
+    /// The returned future doesn't borrow the conductor so it can be awaited
+    /// after the conductor lock is dropped. Genesis asks DPKI about the agent
+    /// keys and a DPKI app needs to call back into the conductor.
+    pub(super) fn genesis_cells(
         &self,
         cell_ids_with_proofs: Vec<(CellId, Option<MembraneProof>)>,
         conductor_handle: ConductorHandle,
-    ) -> ConductorResult<()> {
-        let root_env_dir = std::path::PathBuf::from(self.root_env_dir.clone());
-        let keystore = self.keystore.clone();
+    ) -> impl std::future::Future<Output = ConductorResult<()>> + Send + 'static {
+        Self::genesis_cells_inner(
+            cell_ids_with_proofs,
+            conductor_handle,
+            std::path::PathBuf::from(self.root_env_dir.clone()),
+            self.keystore.clone(),
+            self.encrypt_private_entries,
+        )
+    }
 
+    async fn genesis_cells_inner(
+        cell_ids_with_proofs: Vec<(CellId, Option<MembraneProof>)>,
+        conductor_handle: ConductorHandle,
+        root_env_dir: std::path::PathBuf,
+        keystore: KeystoreSender,
+        encrypt_private_entries: bool,
+    ) -> ConductorResult<()> {
         let cells_tasks = cell_ids_with_proofs.into_iter().map(|(cell_id, proof)| {
             let root_env_dir = root_env_dir.clone();
-            let keystore = self.keystore.clone();
+            let keystore = keystore.clone();
             let conductor_handle = conductor_handle.clone();
             let cell_id_inner = cell_id.clone();
             tokio::spawn(async move {
-                let env = EnvironmentWrite::new(
+                let env = open_cell_env(
                     &root_env_dir,
-                    EnvironmentKind::Cell(cell_id_inner.clone()),
-                    keystore.clone(),
-                )?;
+                    cell_id_inner.clone(),
+                    keystore,
+                    encrypt_private_entries,
+                )
+                .await?;
                 Cell::genesis(cell_id_inner, conductor_handle, env, proof).await
             })
             .map_err(CellError::from)
@@ -480,11 +562,13 @@ where
                                 cell_id.agent_pubkey().clone(),
                             );
 
-                            let env = EnvironmentWrite::new_cell(
+                            let env = open_cell_env(
                                 &dir,
                                 cell_id.clone(),
                                 keystore.clone(),
-                            )?;
+                                self.encrypt_private_entries,
+                            )
+                            .await?;
                             Cell::create(
                                 cell_id.clone(),
                                 conductor_handle.clone(),
@@ -492,6 +576,8 @@ where
                                 holochain_p2p_cell,
                                 self.managed_task_add_sender.clone(),
                                 self.managed_task_stop_broadcaster.clone(),
+                                self.receipt_bundle_size,
+                                self.redundancy_target,
                             )
                             .await
                         },
@@ -582,6 +668,33 @@ where
         Ok(())
     }
 
+    /// Replace the cell with the same nick in an active app in the database
+    pub(super) async fn replace_app_cell_in_db(
+        &mut self,
+        installed_app_id: InstalledAppId,
+        new_cell: InstalledCell,
+    ) -> ConductorResult<()> {
+        self.update_state(move |mut state| {
+            let cells = state
+                .active_apps
+                .get_mut(&installed_app_id)
+                .ok_or_else(|| ConductorError::AppNotActive(installed_app_id.clone()))?;
+            let cell = cells
+                .iter_mut()
+                .find(|cell| cell.as_nick() == new_cell.as_nick())
+                .ok_or_else(|| {
+                    ConductorError::CellNickMissing(
+                        installed_app_id.clone(),
+                        new_cell.as_nick().clone(),
+                    )
+                })?;
+            *cell = new_cell;
+            Ok(state)
+        })
+        .await?;
+        Ok(())
+    }
+
     /// Deactivate an app in the database
     pub(super) async fn deactivate_app_in_db(
         &mut self,
@@ -610,6 +723,98 @@ where
             .collect())
     }
 
+    /// Record an x25519 key created for an app
+    pub(super) async fn add_app_x25519_key_in_db(
+        &mut self,
+        installed_app_id: InstalledAppId,
+        key: X25519PubKey,
+    ) -> ConductorResult<()> {
+        self.update_state(move |mut state| {
+            state
+                .app_x25519_keys
+                .entry(installed_app_id)
+                .or_default()
+                .push(key);
+            Ok(state)
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Remove an app from the database, whether it's active or not,
+    /// along with the signal subscriptions for it.
+    /// Returns the ids of its cells.
+    pub(super) async fn remove_app_from_db(
+        &mut self,
+        installed_app_id: InstalledAppId,
+    ) -> ConductorResult<Vec<CellId>> {
+        let app = self
+            .get_state()
+            .await?
+            .get_app_info(&installed_app_id)
+            .ok_or_else(|| ConductorError::AppNotInstalled(installed_app_id.clone()))?;
+        self.update_state(move |mut state| {
+            state.active_apps.remove(&installed_app_id);
+            state.inactive_apps.remove(&installed_app_id);
+            for interface in state.app_interfaces.values_mut() {
+                interface.signal_subscriptions.remove(&installed_app_id);
+            }
+            state.app_x25519_keys.remove(&installed_app_id);
+            Ok(state)
+        })
+        .await?;
+        Ok(app.cell_data.into_iter().map(|c| c.into_id()).collect())
+    }
+
+    /// Remove the Dnas which no installed app has a cell for from the
+    /// DnaStore and the wasm environment, along with any wasm only they use
+    pub(super) async fn remove_unused_dnas(
+        &mut self,
+        dna_hashes: Vec<DnaHash>,
+    ) -> ConductorResult<()> {
+        let state = self.get_state().await?;
+        let in_use: HashSet<&DnaHash> = state
+            .installed_cell_ids()
+            .map(|cell_id| cell_id.dna_hash())
+            .collect();
+        let unused: HashSet<DnaHash> = dna_hashes
+            .into_iter()
+            .filter(|dna_hash| !in_use.contains(dna_hash))
+            .collect();
+        if unused.is_empty() {
+            return Ok(());
+        }
+
+        let wasm_hashes = |dna_hashes: Vec<DnaHash>, dna_store: &DS| -> HashSet<WasmHash> {
+            dna_hashes
+                .iter()
+                .filter_map(|dna_hash| dna_store.get(dna_hash))
+                .flat_map(|dna| dna.code().keys().cloned().collect::<Vec<_>>())
+                .collect()
+        };
+        let unused_wasm = wasm_hashes(unused.iter().cloned().collect(), &self.dna_store);
+        self.dna_store.remove_dnas(unused.clone());
+        let used_wasm = wasm_hashes(self.dna_store.list(), &self.dna_store);
+
+        let environ = self.wasm_env.clone();
+        let wasm = environ.get_db(&*holochain_lmdb::db::WASM)?;
+        let dna_def_db = environ.get_db(&*holochain_lmdb::db::DNA_DEF)?;
+        let mut wasm_buf = WasmBuf::new(environ.clone().into(), wasm)?;
+        let mut dna_def_buf = DnaDefBuf::new(environ.clone().into(), dna_def_db)?;
+        for dna_hash in unused {
+            dna_def_buf.delete(dna_hash);
+        }
+        for wasm_hash in unused_wasm.difference(&used_wasm) {
+            wasm_buf.delete(wasm_hash.clone());
+        }
+        {
+            let env = environ.guard();
+            env.with_commit(|writer| wasm_buf.flush_to_txn(writer))?;
+            env.with_commit(|writer| dna_def_buf.flush_to_txn(writer))?;
+        }
+        Ok(())
+    }
+
     /// Add fully constructed cells to the cell map in the Conductor
     pub(super) fn add_cells(&mut self, cells: Vec<(Cell, InitialQueueTriggers)>) {
         for (cell, trigger) in cells {
@@ -671,11 +876,29 @@ where
         Ok((dnas, defs))
     }
 
-    /// Remove cells from the cell map in the Conductor
-    pub(super) fn remove_cells(&mut self, cell_ids: Vec<CellId>) {
+    /// Remove cells from the cell map in the Conductor,
+    /// returning the cells that were running
+    pub(super) fn remove_cells(&mut self, cell_ids: Vec<CellId>) -> Vec<Arc<Cell>> {
+        cell_ids
+            .into_iter()
+            .filter_map(|cell_id| self.cells.remove(&cell_id))
+            .map(|item| item.cell)
+            .collect()
+    }
+
+    /// Delete the environments of these cells from disk
+    pub(super) async fn remove_cell_envs(&self, cell_ids: Vec<CellId>) -> ConductorResult<()> {
+        let root_env_dir = std::path::PathBuf::from(self.root_env_dir.clone());
         for cell_id in cell_ids {
-            self.cells.remove(&cell_id);
+            EnvironmentWrite::new(
+                &root_env_dir,
+                EnvironmentKind::Cell(cell_id),
+                self.keystore.clone(),
+            )?
+            .remove()
+            .await?;
         }
+        Ok(())
     }
 
     pub(super) fn add_agent_infos(
@@ -822,6 +1045,7 @@ where
             cells: HashMap::new(),
             shutting_down: false,
             app_interfaces: HashMap::new(),
+            data_subscribers: DataSubscribers::default(),
             managed_task_add_sender: task_tx,
             managed_task_stop_broadcaster: stop_tx,
             task_manager_run_handle,
@@ -830,6 +1054,10 @@ where
             keystore,
             root_env_dir,
             holochain_p2p,
+            receipt_bundle_size: DEFAULT_RECEIPT_BUNDLE_SIZE,
+            redundancy_target: DEFAULT_REDUNDANCY_TARGET,
+            encrypt_private_entries: false,
+            dpki: None,
         })
     }
 
@@ -873,10 +1101,12 @@ pub type ConductorStateDb = KvStore<UnitDbKey, ConductorState>;
 mod builder {
     use super::*;
     use crate::conductor::dna_store::RealDnaStore;
+    use crate::conductor::handle::ConductorHandleT;
     use crate::conductor::ConductorHandle;
     use holochain_lmdb::env::EnvironmentKind;
     #[cfg(any(test, feature = "test_utils"))]
     use holochain_lmdb::test_utils::TestEnvironments;
+    use std::sync::Weak;
 
     /// A configurable Builder for Conductor and sometimes ConductorHandle
     #[derive(Default)]
@@ -887,6 +1117,8 @@ mod builder {
         pub dna_store: DS,
         /// Optional keystore override
         pub keystore: Option<KeystoreSender>,
+        /// Optional DPKI service override
+        pub dpki: Option<DpkiImpl>,
         #[cfg(any(test, feature = "test_utils"))]
         /// Optional state override (for testing)
         pub state: Option<ConductorState>,
@@ -951,7 +1183,17 @@ mod builder {
                     .unwrap();
                 keystore
             } else {
-                spawn_lair_keystore(self.config.keystore_path.as_deref()).await?
+                // The keypairs that seal the private entry keys are in the
+                // keystore, so encrypting them needs a passphrase even if
+                // no passphrase service is configured
+                let passphrase_service = match &self.config.passphrase_service {
+                    Some(service) => Some(service.clone()),
+                    None if self.config.encrypt_private_entries => Some(Default::default()),
+                    None => None,
+                };
+                let passphrase_provider = passphrase_service.map(passphrase_provider);
+                spawn_lair_keystore(self.config.keystore_path.as_deref(), passphrase_provider)
+                    .await?
             };
             let env_path = self.config.environment_path.clone();
 
@@ -971,7 +1213,10 @@ mod builder {
             let state = self.state;
 
             let Self {
-                dna_store, config, ..
+                dna_store,
+                config,
+                dpki,
+                ..
             } = self;
 
             let network_config = match &config.network {
@@ -1003,31 +1248,56 @@ mod builder {
             #[cfg(any(test, feature = "test_utils"))]
             let conductor = Self::update_fake_state(state, conductor).await?;
 
-            Self::finish(conductor, config, p2p_evt).await
+            Self::finish(conductor, config, dpki, p2p_evt).await
         }
 
         async fn finish(
-            conductor: Conductor<DS>,
+            mut conductor: Conductor<DS>,
             conductor_config: ConductorConfig,
+            dpki: Option<DpkiImpl>,
             p2p_evt: holochain_p2p::event::HolochainP2pEventReceiver,
         ) -> ConductorResult<ConductorHandle> {
+            if let Some(receipt_bundle_size) = conductor_config.receipt_bundle_size {
+                conductor.receipt_bundle_size = receipt_bundle_size;
+            }
+            if let Some(redundancy_target) = conductor_config.redundancy_target {
+                conductor.redundancy_target = redundancy_target;
+            }
+            conductor.encrypt_private_entries = conductor_config.encrypt_private_entries;
+
             // Get data before handle
             let keystore = conductor.keystore.clone();
             let holochain_p2p = conductor.holochain_p2p.clone();
 
             // Create handle
-            let handle: ConductorHandle = Arc::new(ConductorHandleImpl {
+            let handle = Arc::new(ConductorHandleImpl {
                 conductor: RwLock::new(conductor),
                 keystore,
                 holochain_p2p,
             });
 
+            // The DPKI app needs the handle to call into its cell
+            let dpki = dpki.or_else(|| {
+                conductor_config.dpki.as_ref().map(|config| {
+                    let weak: Weak<dyn ConductorHandleT> = Arc::downgrade(&handle);
+                    Arc::new(DpkiApp::new(
+                        weak,
+                        config.installed_app_id.clone(),
+                        config.zome_name.clone().into(),
+                    )) as DpkiImpl
+                })
+            });
+            handle.conductor.write().await.dpki = dpki;
+            let handle: ConductorHandle = handle;
+
             handle.add_dnas().await?;
 
             tokio::task::spawn(p2p_event_task(p2p_evt, handle.clone()));
 
             let cell_startup_errors = handle.clone().setup_cells().await?;
 
+            handle.clone().start_autonomic_processes().await?;
+
             // TODO: This should probably be emitted over the admin interface
             if !cell_startup_errors.is_empty() {
                 error!(
@@ -1049,6 +1319,12 @@ mod builder {
             Ok(handle)
         }
 
+        /// Use this DPKI service instead of the app in the [ConductorConfig]
+        pub fn with_dpki(mut self, dpki: DpkiImpl) -> Self {
+            self.dpki = Some(dpki);
+            self
+        }
+
         /// Pass a test keystore in, to ensure that generated test agents
         /// are actually available for signing (especially for tryorama compat)
         pub fn with_keystore(mut self, keystore: KeystoreSender) -> Self {
@@ -1103,11 +1379,32 @@ mod builder {
             #[cfg(any(test, feature = "test_utils"))]
             let conductor = Self::update_fake_state(self.state, conductor).await?;
 
-            Self::finish(conductor, self.config, p2p_evt).await
+            Self::finish(conductor, self.config, self.dpki, p2p_evt).await
         }
     }
 }
 
+/// Open the environment for a cell, with its private entry key if
+/// private entries are encrypted at rest
+async fn open_cell_env(
+    root_env_dir: &std::path::Path,
+    cell_id: CellId,
+    keystore: KeystoreSender,
+    encrypt_private_entries: bool,
+) -> DatabaseResult<EnvironmentWrite> {
+    let private_entry_key = if encrypt_private_entries {
+        Some(EncryptionKey::for_private_entries(&keystore, root_env_dir, &cell_id).await?)
+    } else {
+        None
+    };
+    EnvironmentWrite::new_with_private_entry_key(
+        root_env_dir,
+        EnvironmentKind::Cell(cell_id),
+        keystore,
+        private_entry_key,
+    )
+}
+
 #[instrument(skip(p2p_evt, handle))]
 async fn p2p_event_task(
     p2p_evt: holochain_p2p::event::HolochainP2pEventReceiver,
@@ -188,3 +188,249 @@ async fn proxy_tls_inner(
 
     Ok(())
 }
+
+/// The DPKI app's own cells run genesis before the app is active,
+/// so installing it must not wait on DPKI, but other apps must.
+#[tokio::test(threaded_scheduler)]
+async fn can_install_the_dpki_app() {
+    use crate::conductor::api::error::ConductorApiError;
+    use crate::conductor::handle::ConductorHandleT;
+    use holochain_conductor_api::config::conductor::DpkiConfig;
+    use holochain_types::test_utils::fake_agent_pubkey_1;
+    use holochain_types::test_utils::fake_agent_pubkey_2;
+    use holochain_wasm_test_utils::TestWasm;
+
+    let envs = test_environments();
+    let handle = ConductorBuilder::new()
+        .config(ConductorConfig {
+            dpki: Some(DpkiConfig {
+                installed_app_id: "dpki".to_string(),
+                zome_name: "foo".to_string(),
+            }),
+            ..Default::default()
+        })
+        .test(&envs)
+        .await
+        .unwrap();
+
+    let agent = fake_agent_pubkey_1();
+    // Not installed yet
+    assert_matches!(
+        handle.dpki_key_state(&agent).await,
+        Err(ConductorApiError::DpkiError(_))
+    );
+
+    let dna_file = DnaFile::new(
+        DnaDef {
+            name: "dpki".to_string(),
+            uuid: "5b1e4cbb-5e4f-4aa3-a5e5-8b83b5d4a3c1".to_string(),
+            properties: SerializedBytes::try_from(()).unwrap(),
+            rate_limits: Vec::new(),
+            zomes: vec![TestWasm::Foo.into()],
+            redundancy_target: None,
+        },
+        vec![TestWasm::Foo.into()],
+    )
+    .await
+    .unwrap();
+    // Other apps can't run genesis until DPKI can check their agents
+    let other_cell_id = CellId::new(dna_file.dna_hash().clone(), fake_agent_pubkey_2());
+    handle.install_dna(dna_file.clone()).await.unwrap();
+    assert!(handle
+        .clone()
+        .install_app(
+            "other".to_string(),
+            vec![(InstalledCell::new(other_cell_id, "other".into()), None)],
+        )
+        .await
+        .is_err());
+
+    let cell_id = CellId::new(dna_file.dna_hash().clone(), agent.clone());
+    crate::test_utils::install_app(
+        "dpki",
+        vec![(InstalledCell::new(cell_id, "dpki".into()), None)],
+        vec![dna_file],
+        handle.clone(),
+    )
+    .await;
+
+    // Once it's running DPKI's own agents are only known to the app,
+    // and this test zome has no key state function
+    assert_matches!(
+        handle.dpki_key_state(&agent).await,
+        Err(ConductorApiError::DpkiError(_))
+    );
+
+    handle.shutdown().await;
+}
+
+/// A migration that fails after the new chain is opened keeps that chain,
+/// and a retry reuses it instead of forking it with a second genesis.
+#[tokio::test(threaded_scheduler)]
+async fn retried_migration_does_not_fork_the_new_chain() {
+    use crate::conductor::api::error::ConductorApiError;
+    use crate::conductor::handle::ConductorHandleT;
+    use holochain_state::prelude::WarrantStore;
+    use holochain_types::test_utils::fake_agent_pubkey_1;
+    use holochain_wasm_test_utils::TestWasm;
+
+    let envs = test_environments();
+    let handle = ConductorBuilder::new().test(&envs).await.unwrap();
+
+    // The old Dna refuses to close its chain
+    let old_dna = DnaFile::new(
+        DnaDef {
+            name: "old".to_string(),
+            uuid: "8c2f0e6a-1d4b-4c7e-9a3f-5b6d7e8f9a01".to_string(),
+            properties: SerializedBytes::try_from(()).unwrap(),
+            rate_limits: Vec::new(),
+            zomes: vec![TestWasm::MigrateAgentFail.into()],
+            redundancy_target: None,
+        },
+        vec![TestWasm::MigrateAgentFail.into()],
+    )
+    .await
+    .unwrap();
+    let new_dna = DnaFile::new(
+        DnaDef {
+            name: "new".to_string(),
+            uuid: "8c2f0e6a-1d4b-4c7e-9a3f-5b6d7e8f9a02".to_string(),
+            properties: SerializedBytes::try_from(()).unwrap(),
+            rate_limits: Vec::new(),
+            zomes: vec![TestWasm::MigrateAgentPass.into()],
+            redundancy_target: None,
+        },
+        vec![TestWasm::MigrateAgentPass.into()],
+    )
+    .await
+    .unwrap();
+    let agent = fake_agent_pubkey_1();
+    let old_cell_id = CellId::new(old_dna.dna_hash().clone(), agent.clone());
+    let new_cell_id = CellId::new(new_dna.dna_hash().clone(), agent.clone());
+    handle.install_dna(new_dna).await.unwrap();
+    crate::test_utils::install_app(
+        "app",
+        vec![(InstalledCell::new(old_cell_id.clone(), "nick".into()), None)],
+        vec![old_dna],
+        handle.clone(),
+    )
+    .await;
+
+    let new_env = || {
+        EnvironmentWrite::new(
+            envs.tempdir().path(),
+            EnvironmentKind::Cell(new_cell_id.clone()),
+            envs.keystore(),
+        )
+        .unwrap()
+    };
+    let new_chain = || {
+        let chain = SourceChainBuf::new(new_env().into()).unwrap();
+        (0..chain.len() as u32)
+            .map(|i| chain.get_at_index(i).unwrap().unwrap().header().clone())
+            .collect::<Vec<_>>()
+    };
+
+    let mut chains = Vec::new();
+    for _ in 0..2 {
+        assert_matches!(
+            handle
+                .clone()
+                .migrate_agent(
+                    "app".to_string(),
+                    "nick".into(),
+                    new_cell_id.dna_hash().clone(),
+                    None,
+                )
+                .await,
+            Err(ConductorApiError::ConductorError(
+                ConductorError::MigrateAgentFailed(_, _)
+            ))
+        );
+        // The app is left on the old cell
+        let app = handle
+            .get_app_info(&"app".to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            app.cell_data
+                .into_iter()
+                .map(InstalledCell::into_id)
+                .collect::<Vec<_>>(),
+            vec![old_cell_id.clone()]
+        );
+        chains.push(new_chain());
+    }
+
+    // The retry didn't run genesis or open the chain again
+    assert_eq!(chains[0], chains[1]);
+    assert_eq!(chains[1].len(), 4);
+    assert_matches!(chains[1][3], Header::OpenChain(_));
+    assert!(!WarrantStore::new(new_env().into())
+        .unwrap()
+        .is_warranted(&agent)
+        .unwrap());
+
+    handle.shutdown().await;
+}
+
+/// Uninstalling an app stops its cells' workflows before their data is
+/// deleted, and forgets the x25519 keys it created.
+#[tokio::test(threaded_scheduler)]
+async fn uninstall_app_stops_workflows_and_removes_its_data() {
+    use crate::conductor::handle::ConductorHandleT;
+    use holochain_types::test_utils::fake_agent_pubkey_1;
+    use holochain_wasm_test_utils::TestWasm;
+
+    let envs = test_environments();
+    let handle = ConductorBuilder::new().test(&envs).await.unwrap();
+
+    let dna = DnaFile::new(
+        DnaDef {
+            name: "uninstall".to_string(),
+            uuid: "3e7a9c1b-5d2f-4a8e-b6c0-1f9d8e7a6b51".to_string(),
+            properties: SerializedBytes::try_from(()).unwrap(),
+            rate_limits: Vec::new(),
+            zomes: vec![TestWasm::Foo.into()],
+            redundancy_target: None,
+        },
+        vec![TestWasm::Foo.into()],
+    )
+    .await
+    .unwrap();
+    let cell_id = CellId::new(dna.dna_hash().clone(), fake_agent_pubkey_1());
+    crate::test_utils::install_app(
+        "app",
+        vec![(InstalledCell::new(cell_id.clone(), "nick".into()), None)],
+        vec![dna],
+        handle.clone(),
+    )
+    .await;
+    let app_id = "app".to_string();
+    handle.create_app_x25519_keypair(&app_id).await.unwrap();
+    assert_eq!(handle.get_app_x25519_keys(&app_id).await.unwrap().len(), 1);
+
+    // Have the workflows busy while the app is uninstalled
+    let mut triggers = handle.get_cell_triggers(&cell_id).await.unwrap();
+    triggers.produce_dht_ops.trigger();
+    triggers.publish_dht_ops.trigger();
+    triggers.sys_validation.trigger();
+
+    handle.uninstall_app(app_id.clone(), true).await.unwrap();
+
+    let cell_dir = envs.tempdir().path().join(cell_id.to_string());
+    assert!(!cell_dir.exists());
+    assert!(!handle.list_cell_ids().await.unwrap().contains(&cell_id));
+    assert!(handle
+        .get_app_x25519_keys(&app_id)
+        .await
+        .unwrap()
+        .is_empty());
+
+    // Nothing is left running that could write the environment back
+    tokio::time::delay_for(std::time::Duration::from_millis(100)).await;
+    assert!(!cell_dir.exists());
+
+    handle.shutdown().await;
+}
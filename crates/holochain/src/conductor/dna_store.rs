@@ -28,6 +28,7 @@ pub struct DnaDefBuf {
 pub trait DnaStore: Default + Send + Sync {
     fn add_dna(&mut self, dna: DnaFile);
     fn add_dnas<T: IntoIterator<Item = (DnaHash, DnaFile)> + 'static>(&mut self, dnas: T);
+    fn remove_dnas<T: IntoIterator<Item = DnaHash> + 'static>(&mut self, dnas: T);
     fn add_entry_def(&mut self, k: EntryDefBufferKey, entry_def: EntryDef);
     fn add_entry_defs<T: IntoIterator<Item = (EntryDefBufferKey, EntryDef)> + 'static>(
         &mut self,
@@ -47,6 +48,11 @@ impl DnaStore for RealDnaStore {
     fn add_dnas<T: IntoIterator<Item = (DnaHash, DnaFile)> + 'static>(&mut self, dnas: T) {
         self.dnas.extend(dnas);
     }
+    fn remove_dnas<T: IntoIterator<Item = DnaHash> + 'static>(&mut self, dnas: T) {
+        for dna_hash in dnas {
+            self.dnas.remove(&dna_hash);
+        }
+    }
     #[instrument]
     fn list(&self) -> Vec<DnaHash> {
         self.dnas.keys().cloned().collect()
@@ -94,6 +100,10 @@ impl DnaDefBuf {
         Ok(())
     }
 
+    pub fn delete(&mut self, dna_hash: DnaHash) {
+        self.dna_defs.delete(dna_hash);
+    }
+
     pub fn get_all(&self) -> DatabaseResult<Vec<DnaDefHashed>> {
         fresh_reader!(self.dna_defs.env(), |r| self
             .dna_defs
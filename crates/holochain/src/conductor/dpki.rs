@@ -0,0 +1,177 @@
+//! The DPKI service the conductor uses to check the state of agent keys.
+//!
+//! The conductor can be backed by a DPKI app installed in the conductor,
+//! named in the [DpkiConfig], or any other [DpkiService] passed to the
+//! [ConductorBuilder]. Without a DPKI service every key is [KeyState::NotFound],
+//! which is treated as valid. A DPKI app that isn't installed and active
+//! can't answer, so checking a key fails until it is.
+//!
+//! [DpkiConfig]: holochain_conductor_api::config::conductor::DpkiConfig
+//! [ConductorBuilder]: super::ConductorBuilder
+
+use super::api::error::ConductorApiError;
+use super::api::error::ConductorApiResult;
+use super::api::ZomeCall;
+use super::handle::ConductorHandleT;
+use holochain_types::prelude::*;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::Weak;
+use std::time::Duration;
+use std::time::Instant;
+
+/// How long a key state from the DPKI app is reused before asking again.
+pub const KEY_STATE_TTL: Duration = Duration::from_secs(10);
+
+/// A shared reference to the DPKI service of a conductor.
+pub type DpkiImpl = Arc<dyn DpkiService>;
+
+/// The interface to a DPKI service.
+#[async_trait::async_trait]
+pub trait DpkiService: Send + Sync {
+    /// Get the current state of this agent key.
+    async fn key_state(&self, key: &AgentPubKey) -> ConductorApiResult<KeyState>;
+
+    /// The cells of an app are about to run genesis with these agents.
+    fn begin_app_genesis(&self, _installed_app_id: &InstalledAppId, _agents: Vec<AgentPubKey>) {}
+
+    /// The cells of an app have finished genesis, whether or not it succeeded.
+    fn end_app_genesis(&self, _installed_app_id: &InstalledAppId) {}
+}
+
+/// DPKI backed by an app installed in this conductor.
+///
+/// The app's first cell must have a zome with a [DPKI_KEY_STATE_FN]
+/// function that takes an [AgentPubKey] and returns a [KeyState].
+/// Answers are cached for [KEY_STATE_TTL].
+pub struct DpkiApp {
+    handle: Weak<dyn ConductorHandleT>,
+    installed_app_id: InstalledAppId,
+    zome_name: ZomeName,
+    /// The DPKI app's own agents while its cells run genesis
+    genesis_agents: parking_lot::Mutex<HashSet<AgentPubKey>>,
+    cache: parking_lot::Mutex<HashMap<AgentPubKey, (KeyState, Instant)>>,
+}
+
+impl DpkiApp {
+    /// Create a DPKI service that calls into an installed app.
+    /// The handle is weak because the conductor holds the DPKI service.
+    pub fn new(
+        handle: Weak<dyn ConductorHandleT>,
+        installed_app_id: InstalledAppId,
+        zome_name: ZomeName,
+    ) -> Self {
+        Self {
+            handle,
+            installed_app_id,
+            zome_name,
+            genesis_agents: Default::default(),
+            cache: Default::default(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DpkiService for DpkiApp {
+    async fn key_state(&self, key: &AgentPubKey) -> ConductorApiResult<KeyState> {
+        // The DPKI app's own cells run genesis before the app is installed,
+        // when it can't be called, so DPKI doesn't know their agents.
+        if self.genesis_agents.lock().contains(key) {
+            return Ok(KeyState::NotFound);
+        }
+        if let Some((state, at)) = self.cache.lock().get(key) {
+            if at.elapsed() < KEY_STATE_TTL {
+                return Ok(state.clone());
+            }
+        }
+        let handle = self
+            .handle
+            .upgrade()
+            .ok_or_else(|| ConductorApiError::DpkiError("Conductor has shut down".into()))?;
+        let app = handle
+            .get_app_info(&self.installed_app_id)
+            .await?
+            .ok_or_else(|| ConductorApiError::DpkiError("DPKI app is not installed".into()))?;
+        if !handle
+            .list_active_apps()
+            .await?
+            .contains(&self.installed_app_id)
+        {
+            return Err(ConductorApiError::DpkiError(
+                "DPKI app is not active".into(),
+            ));
+        }
+        let cell_id = app
+            .cell_data
+            .into_iter()
+            .next()
+            .ok_or_else(|| ConductorApiError::DpkiError("DPKI app has no cells".into()))?
+            .into_id();
+        let call = ZomeCall {
+            provenance: cell_id.agent_pubkey().clone(),
+            cell_id,
+            zome_name: self.zome_name.clone(),
+            fn_name: DPKI_KEY_STATE_FN.into(),
+            payload: ExternIO::encode(key.clone())
+                .map_err(|e| ConductorApiError::DpkiError(e.to_string()))?,
+            cap: None,
+        };
+        let state: KeyState = match handle.call_zome(call).await? {
+            Ok(ZomeCallResponse::Ok(output)) => output
+                .decode()
+                .map_err(|e| ConductorApiError::DpkiError(e.to_string()))?,
+            Ok(response) => {
+                return Err(ConductorApiError::DpkiError(format!(
+                    "DPKI app returned {:?}",
+                    response
+                )))
+            }
+            Err(e) => return Err(ConductorApiError::DpkiError(e.to_string())),
+        };
+        self.cache
+            .lock()
+            .insert(key.clone(), (state.clone(), Instant::now()));
+        Ok(state)
+    }
+
+    fn begin_app_genesis(&self, installed_app_id: &InstalledAppId, agents: Vec<AgentPubKey>) {
+        if *installed_app_id == self.installed_app_id {
+            self.genesis_agents.lock().extend(agents);
+        }
+    }
+
+    fn end_app_genesis(&self, installed_app_id: &InstalledAppId) {
+        if *installed_app_id == self.installed_app_id {
+            self.genesis_agents.lock().clear();
+        }
+    }
+}
+
+/// A DPKI service held in memory, for testing.
+#[derive(Clone, Default)]
+pub struct MemoryDpki(Arc<parking_lot::Mutex<HashMap<AgentPubKey, KeyState>>>);
+
+impl MemoryDpki {
+    /// Set the state of a key.
+    pub fn set(&self, key: AgentPubKey, state: KeyState) {
+        self.0.lock().insert(key, state);
+    }
+
+    /// Revoke a key from this time on.
+    pub fn revoke(&self, key: AgentPubKey, at: Timestamp) {
+        self.set(key, KeyState::Revoked(at));
+    }
+}
+
+#[async_trait::async_trait]
+impl DpkiService for MemoryDpki {
+    async fn key_state(&self, key: &AgentPubKey) -> ConductorApiResult<KeyState> {
+        Ok(self
+            .0
+            .lock()
+            .get(key)
+            .cloned()
+            .unwrap_or(KeyState::NotFound))
+    }
+}
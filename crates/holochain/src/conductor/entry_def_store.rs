@@ -249,6 +249,7 @@ mod tests {
             crdt_type: CrdtType,
             required_validations: 5.into(),
             required_validation_type: Default::default(),
+            properties: None,
         };
         let comment_def = EntryDef {
             id: "comment".into(),
@@ -256,6 +257,7 @@ mod tests {
             crdt_type: CrdtType,
             required_validations: 5.into(),
             required_validation_type: Default::default(),
+            properties: None,
         };
         let dna_wasm = DnaWasmHashed::from_content(TestWasm::EntryDefs.into())
             .await
@@ -4,8 +4,10 @@ use crate::conductor::cell::error::CellError;
 use crate::core::workflow::error::WorkflowError;
 use holochain_conductor_api::conductor::ConductorConfigError;
 use holochain_lmdb::error::DatabaseError;
+use holochain_types::app::CellNick;
 use holochain_types::app::InstalledAppId;
 use holochain_zome_types::cell::CellId;
+use holochain_zome_types::zome::ZomeName;
 use thiserror::Error;
 
 pub type ConductorResult<T> = Result<T, ConductorError>;
@@ -96,6 +98,18 @@ pub enum ConductorError {
 
     #[error(transparent)]
     KitsuneP2pError(#[from] kitsune_p2p::KitsuneP2pError),
+
+    #[error("Agent info was not signed by the agent it is for: {0:?}")]
+    InvalidAgentInfoSignature(kitsune_p2p::KitsuneAgent),
+
+    #[error("The app {0} has no cell with the nick {1}")]
+    CellNickMissing(InstalledAppId, CellNick),
+
+    #[error("Tried to migrate an agent to a cell that already exists: {0:?}")]
+    CellAlreadyExists(CellId),
+
+    #[error("The migrate_agent callback in zome {0} failed: {1}")]
+    MigrateAgentFailed(ZomeName, String),
 }
 
 #[derive(Error, Debug)]
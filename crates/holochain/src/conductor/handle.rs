@@ -41,11 +41,13 @@
 //! types for testing. If we did not have a way of hiding this type genericity,
 //! code which interacted with the Conductor would also have to be highly generic.
 
+use super::api::error::ConductorApiError;
 use super::api::error::ConductorApiResult;
 use super::api::ZomeCall;
 use super::config::AdminInterfaceConfig;
 use super::dna_store::DnaStore;
 use super::entry_def_store::EntryDefBufferKey;
+use super::error::ConductorError;
 use super::error::ConductorResult;
 use super::error::CreateAppError;
 use super::interface::SignalBroadcaster;
@@ -55,13 +57,17 @@ use super::p2p_store::put_agent_info_signed;
 use super::p2p_store::query_agent_info_signed;
 use super::Cell;
 use super::Conductor;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
 use crate::core::workflow::CallZomeWorkspaceLock;
 use crate::core::workflow::ZomeCallResult;
 use derive_more::From;
 use futures::future::FutureExt;
+use holochain_keystore::KeystoreSenderExt;
 use holochain_p2p::event::HolochainP2pEvent::*;
+use holochain_p2p::HolochainP2pSender;
 use holochain_types::prelude::*;
 use kitsune_p2p::agent_store::AgentInfoSigned;
+use std::collections::HashSet;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::*;
@@ -100,8 +106,12 @@ pub trait ConductorHandleT: Send + Sync {
     /// Should only be run once at Conductor initialization.
     async fn startup_app_interfaces(self: Arc<Self>) -> ConductorResult<()>;
 
-    /// Add an app interface
-    async fn add_app_interface(self: Arc<Self>, port: u16) -> ConductorResult<u16>;
+    /// Add an app interface, optionally bound to an app
+    async fn add_app_interface(
+        self: Arc<Self>,
+        port: u16,
+        installed_app_id: Option<InstalledAppId>,
+    ) -> ConductorResult<u16>;
 
     /// Install a [Dna] in this Conductor
     async fn install_dna(&self, dna: DnaFile) -> ConductorResult<()>;
@@ -138,6 +148,17 @@ pub trait ConductorHandleT: Send + Sync {
     /// Cue the autonomic system to perform some action early (experimental)
     async fn autonomic_cue(&self, cue: AutonomicCue, cell_id: &CellId) -> ConductorApiResult<()>;
 
+    /// Run an [AutonomicProcess] on a cell now
+    async fn run_autonomic_process(
+        &self,
+        process: AutonomicProcess,
+        cell_id: &CellId,
+    ) -> ConductorApiResult<()>;
+
+    /// Start the task which periodically runs the [AutonomicProcess]es on every cell.
+    /// Should only be run once at Conductor initialization.
+    async fn start_autonomic_processes(self: Arc<Self>) -> ConductorResult<()>;
+
     /// Get a Websocket port which will
     async fn get_arbitrary_admin_websocket_port(&self) -> Option<u16>;
 
@@ -178,6 +199,29 @@ pub trait ConductorHandleT: Send + Sync {
     #[allow(clippy::ptr_arg)]
     async fn deactivate_app(&self, installed_app_id: InstalledAppId) -> ConductorResult<()>;
 
+    /// Uninstall an app, whether it's active or not.
+    /// Its cells, unless another app has them too, leave the network and are
+    /// removed, along with their environments if `delete_cell_data` is set.
+    /// Dnas and wasm which no other app uses are removed.
+    async fn uninstall_app(
+        &self,
+        installed_app_id: InstalledAppId,
+        delete_cell_data: bool,
+    ) -> ConductorResult<()>;
+
+    /// Migrate the agent of one of an active app's cells to a new Dna.
+    /// Creates and opens a chain for the same agent on the new Dna, replaces
+    /// the old cell in the app under the same nick and then closes the old chain.
+    /// If any step fails the app is left on the old cell and the new one is stopped.
+    /// Its chain is kept and reused if the migration is retried.
+    async fn migrate_agent(
+        self: Arc<Self>,
+        installed_app_id: InstalledAppId,
+        cell_nick: CellNick,
+        new_dna_hash: DnaHash,
+        membrane_proof: Option<MembraneProof>,
+    ) -> ConductorApiResult<InstalledApp>;
+
     /// List Cell Ids
     async fn list_cell_ids(&self) -> ConductorResult<Vec<CellId>>;
 
@@ -188,6 +232,13 @@ pub trait ConductorHandleT: Send + Sync {
     #[allow(clippy::ptr_arg)]
     async fn dump_cell_state(&self, cell_id: &CellId) -> ConductorApiResult<String>;
 
+    /// Get the report from the last health check on a cell
+    #[allow(clippy::ptr_arg)]
+    async fn get_cell_health_report(
+        &self,
+        cell_id: &CellId,
+    ) -> ConductorApiResult<Option<CellHealthReport>>;
+
     /// Access the broadcast Sender which will send a Signal across every
     /// attached app interface
     async fn signal_broadcaster(&self) -> SignalBroadcaster;
@@ -199,6 +250,24 @@ pub trait ConductorHandleT: Send + Sync {
         installed_app_id: &InstalledAppId,
     ) -> ConductorResult<Option<InstalledApp>>;
 
+    /// Ask the DPKI service for the state of an agent key.
+    /// Returns [KeyState::NotFound] if this conductor has no DPKI service.
+    async fn dpki_key_state(&self, key: &AgentPubKey) -> ConductorApiResult<KeyState>;
+
+    /// Create a new x25519 keypair in the keystore for an app
+    #[allow(clippy::ptr_arg)]
+    async fn create_app_x25519_keypair(
+        &self,
+        installed_app_id: &InstalledAppId,
+    ) -> ConductorApiResult<X25519PubKey>;
+
+    /// Get the x25519 keys an app has created
+    #[allow(clippy::ptr_arg)]
+    async fn get_app_x25519_keys(
+        &self,
+        installed_app_id: &InstalledAppId,
+    ) -> ConductorResult<Vec<X25519PubKey>>;
+
     /// Add signed agent info to the conductor
     async fn add_agent_infos(&self, agent_infos: Vec<AgentInfoSigned>) -> ConductorApiResult<()>;
 
@@ -271,9 +340,14 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
             .await
     }
 
-    async fn add_app_interface(self: Arc<Self>, port: u16) -> ConductorResult<u16> {
+    async fn add_app_interface(
+        self: Arc<Self>,
+        port: u16,
+        installed_app_id: Option<InstalledAppId>,
+    ) -> ConductorResult<u16> {
         let mut lock = self.conductor.write().await;
-        lock.add_app_interface_via_handle(port, self.clone()).await
+        lock.add_app_interface_via_handle(port, installed_app_id, self.clone())
+            .await
     }
 
     async fn install_dna(&self, dna: DnaFile) -> ConductorResult<()> {
@@ -337,6 +411,7 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
             } => {
                 let env = { self.conductor.read().await.p2p_env() };
                 let res = put_agent_info_signed(env, agent_info_signed)
+                    .await
                     .map_err(holochain_p2p::HolochainP2pError::other);
                 respond.respond(Ok(async move { res }.boxed().into()));
             }
@@ -397,6 +472,23 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         Ok(())
     }
 
+    async fn run_autonomic_process(
+        &self,
+        process: AutonomicProcess,
+        cell_id: &CellId,
+    ) -> ConductorApiResult<()> {
+        let cell = self.cell_by_id(cell_id).await?;
+        Ok(cell.handle_autonomic_process(process).await?)
+    }
+
+    async fn start_autonomic_processes(self: Arc<Self>) -> ConductorResult<()> {
+        self.conductor
+            .write()
+            .await
+            .add_autonomic_task_via_handle(self.clone())
+            .await
+    }
+
     async fn take_shutdown_handle(&self) -> Option<TaskManagerRunHandle> {
         self.conductor.write().await.take_shutdown_handle()
     }
@@ -425,17 +517,30 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         installed_app_id: InstalledAppId,
         cell_data: Vec<(InstalledCell, Option<MembraneProof>)>,
     ) -> ConductorResult<()> {
-        self.conductor
-            .read()
-            .await
-            .genesis_cells(
+        // The lock is dropped before genesis runs so DPKI can call into the conductor
+        let (genesis, dpki) = {
+            let lock = self.conductor.read().await;
+            let genesis = lock.genesis_cells(
                 cell_data
                     .iter()
                     .map(|(c, p)| (c.as_id().clone(), p.clone()))
                     .collect(),
                 self.clone(),
-            )
-            .await?;
+            );
+            (genesis, lock.dpki())
+        };
+        if let Some(dpki) = &dpki {
+            let agents = cell_data
+                .iter()
+                .map(|(c, _)| c.as_id().agent_pubkey().clone())
+                .collect();
+            dpki.begin_app_genesis(&installed_app_id, agents);
+        }
+        let result = genesis.await;
+        if let Some(dpki) = &dpki {
+            dpki.end_app_genesis(&installed_app_id);
+        }
+        result?;
 
         let cell_data = cell_data.into_iter().map(|(c, _)| c).collect();
         let app = InstalledApp {
@@ -501,6 +606,151 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         Ok(())
     }
 
+    async fn uninstall_app(
+        &self,
+        installed_app_id: InstalledAppId,
+        delete_cell_data: bool,
+    ) -> ConductorResult<()> {
+        let (cell_ids_to_remove, removed_cells) = {
+            let mut lock = self.conductor.write().await;
+            let cell_ids = lock.remove_app_from_db(installed_app_id).await?;
+            // Another app may have some of the same cells
+            let state = lock.get_state().await?;
+            let still_installed: HashSet<&CellId> = state.installed_cell_ids().collect();
+            let cell_ids: Vec<CellId> = cell_ids
+                .into_iter()
+                .filter(|cell_id| !still_installed.contains(cell_id))
+                .collect();
+            let cells = lock.remove_cells(cell_ids.clone());
+            (cell_ids, cells)
+        };
+
+        // Stop the workflows before their environments can be deleted
+        for cell in removed_cells {
+            cell.stop_workflows().await;
+        }
+
+        // Leave the network without holding the lock
+        for cell_id in &cell_ids_to_remove {
+            if let Err(e) = self
+                .holochain_p2p
+                .leave(cell_id.dna_hash().clone(), cell_id.agent_pubkey().clone())
+                .await
+            {
+                warn!(?cell_id, error = ?e, "Failed to leave the network");
+            }
+        }
+
+        let mut lock = self.conductor.write().await;
+        if delete_cell_data {
+            lock.remove_cell_envs(cell_ids_to_remove.clone()).await?;
+        }
+        lock.remove_unused_dnas(
+            cell_ids_to_remove
+                .into_iter()
+                .map(|cell_id| cell_id.dna_hash().clone())
+                .collect(),
+        )
+        .await
+    }
+
+    async fn migrate_agent(
+        self: Arc<Self>,
+        installed_app_id: InstalledAppId,
+        cell_nick: CellNick,
+        new_dna_hash: DnaHash,
+        membrane_proof: Option<MembraneProof>,
+    ) -> ConductorApiResult<InstalledApp> {
+        let old_cell_id = self
+            .get_app_info(&installed_app_id)
+            .await?
+            .ok_or_else(|| ConductorError::AppNotInstalled(installed_app_id.clone()))?
+            .cell_data
+            .into_iter()
+            .find(|cell| *cell.as_nick() == cell_nick)
+            .map(InstalledCell::into_id)
+            .ok_or_else(|| {
+                ConductorError::CellNickMissing(installed_app_id.clone(), cell_nick.clone())
+            })?;
+        let old_dna_hash = old_cell_id.dna_hash().clone();
+        let new_cell_id = CellId::new(new_dna_hash.clone(), old_cell_id.agent_pubkey().clone());
+        if self.get_dna(&new_dna_hash).await.is_none() {
+            return Err(ConductorApiError::DnaMissing(new_dna_hash));
+        }
+        if self.list_cell_ids().await?.contains(&new_cell_id) {
+            return Err(ConductorError::CellAlreadyExists(new_cell_id).into());
+        }
+
+        // The old cell is only running if the app is active
+        let old_cell = self.cell_by_id(&old_cell_id).await?;
+
+        // Create and open the new cell before closing the old chain
+        // so a failure at any step leaves the agent on the old cell.
+        let genesis = self
+            .conductor
+            .read()
+            .await
+            .genesis_cells(vec![(new_cell_id.clone(), membrane_proof)], self.clone());
+        genesis.await?;
+        let result =
+            async {
+                self.conductor
+                    .write()
+                    .await
+                    .replace_app_cell_in_db(
+                        installed_app_id.clone(),
+                        InstalledCell::new(new_cell_id.clone(), cell_nick.clone()),
+                    )
+                    .await?;
+                // We only care about this app's cells
+                let this_app_error = self.clone().setup_cells().await?.into_iter().find(
+                    |cell_error| match cell_error {
+                        CreateAppError::Failed {
+                            installed_app_id: error_app_id,
+                            ..
+                        } => *error_app_id == installed_app_id,
+                    },
+                );
+                if let Some(error) = this_app_error {
+                    return Err(ConductorError::from(error).into());
+                }
+
+                // Open the new chain
+                let new_cell = self.cell_by_id(&new_cell_id).await?;
+                if let MigrateAgentResult::Fail(zome_name, reason) = new_cell
+                    .migrate_agent(MigrateAgent::Open, old_dna_hash)
+                    .await?
+                {
+                    return Err(ConductorError::MigrateAgentFailed(zome_name, reason).into());
+                }
+
+                // Close the old chain last
+                if let MigrateAgentResult::Fail(zome_name, reason) = old_cell
+                    .migrate_agent(MigrateAgent::Close, new_dna_hash)
+                    .await?
+                {
+                    return Err(ConductorError::MigrateAgentFailed(zome_name, reason).into());
+                }
+                Ok::<_, ConductorApiError>(())
+            }
+            .await;
+        if let Err(e) = result {
+            self.roll_back_migration(
+                installed_app_id,
+                InstalledCell::new(old_cell_id, cell_nick),
+                new_cell_id,
+            )
+            .await;
+            return Err(e);
+        }
+        self.conductor.write().await.remove_cells(vec![old_cell_id]);
+
+        Ok(self
+            .get_app_info(&installed_app_id)
+            .await?
+            .ok_or(ConductorError::AppNotInstalled(installed_app_id))?)
+    }
+
     async fn list_cell_ids(&self) -> ConductorResult<Vec<CellId>> {
         self.conductor.read().await.list_cell_ids().await
     }
@@ -513,6 +763,14 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         self.conductor.read().await.dump_cell_state(cell_id).await
     }
 
+    async fn get_cell_health_report(
+        &self,
+        cell_id: &CellId,
+    ) -> ConductorApiResult<Option<CellHealthReport>> {
+        let cell = self.cell_by_id(cell_id).await?;
+        Ok(cell.health_report())
+    }
+
     async fn signal_broadcaster(&self) -> SignalBroadcaster {
         self.conductor.read().await.signal_broadcaster()
     }
@@ -530,6 +788,43 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
             .get_app_info(installed_app_id))
     }
 
+    async fn dpki_key_state(&self, key: &AgentPubKey) -> ConductorApiResult<KeyState> {
+        // Don't hold the lock while DPKI runs, it may call back into the conductor
+        let dpki = self.conductor.read().await.dpki();
+        match dpki {
+            Some(dpki) => dpki.key_state(key).await,
+            None => Ok(KeyState::NotFound),
+        }
+    }
+
+    async fn create_app_x25519_keypair(
+        &self,
+        installed_app_id: &InstalledAppId,
+    ) -> ConductorApiResult<X25519PubKey> {
+        let key = self.keystore.create_x25519_keypair().await?;
+        self.conductor
+            .write()
+            .await
+            .add_app_x25519_key_in_db(installed_app_id.clone(), key)
+            .await?;
+        Ok(key)
+    }
+
+    async fn get_app_x25519_keys(
+        &self,
+        installed_app_id: &InstalledAppId,
+    ) -> ConductorResult<Vec<X25519PubKey>> {
+        Ok(self
+            .conductor
+            .read()
+            .await
+            .get_state()
+            .await?
+            .app_x25519_keys
+            .remove(installed_app_id)
+            .unwrap_or_default())
+    }
+
     async fn add_agent_infos(&self, agent_infos: Vec<AgentInfoSigned>) -> ConductorApiResult<()> {
         self.conductor.read().await.add_agent_infos(agent_infos)
     }
@@ -584,4 +879,37 @@ impl<DS: DnaStore + 'static> ConductorHandleImpl<DS> {
         let lock = self.conductor.read().await;
         Ok(lock.cell_by_id(cell_id)?)
     }
+
+    /// Put the old cell back in the app after a failed migration and
+    /// stop the new cell.
+    /// The new cell's chain may already be published so its environment
+    /// is kept for a retry to reuse instead of forking it with a new genesis.
+    async fn roll_back_migration(
+        &self,
+        installed_app_id: InstalledAppId,
+        old_cell: InstalledCell,
+        new_cell_id: CellId,
+    ) {
+        {
+            let mut lock = self.conductor.write().await;
+            if let Err(e) = lock
+                .replace_app_cell_in_db(installed_app_id, old_cell)
+                .await
+            {
+                error!(error = ?e, "Failed to put the old cell back after a failed migration");
+            }
+            lock.remove_cells(vec![new_cell_id.clone()]);
+        }
+        // Leave the network without holding the lock
+        if let Err(e) = self
+            .holochain_p2p
+            .leave(
+                new_cell_id.dna_hash().clone(),
+                new_cell_id.agent_pubkey().clone(),
+            )
+            .await
+        {
+            warn!(?new_cell_id, error = ?e, "Failed to leave the network");
+        }
+    }
 }
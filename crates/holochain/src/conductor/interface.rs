@@ -8,8 +8,18 @@
 use crate::conductor::api::*;
 use error::InterfaceError;
 use error::InterfaceResult;
+use holochain_conductor_api::signal_subscription::DataSubscription;
+use holochain_conductor_api::signal_subscription::SignalFilterSet;
+use holochain_types::app::InstalledAppId;
+use holochain_types::signal::DataChange;
 use holochain_types::signal::Signal;
+use holochain_types::signal::SystemSignal;
+use holochain_zome_types::cell::CellId;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::convert::TryInto;
+use std::sync::Arc;
+use std::sync::Weak;
 use tokio::sync::broadcast;
 
 #[allow(missing_docs)]
@@ -45,11 +55,124 @@ impl AppInterfaceRuntime {
     }
 }
 
+/// The data subscriptions of a connection, along with the Cells of each app
+type DataSubscriptionMap = HashMap<InstalledAppId, (Vec<CellId>, HashSet<DataSubscription>)>;
+
+/// The data subscriptions of every App interface connection of a Conductor,
+/// so that Cells only emit the data changes somebody is watching
+#[derive(Clone, Debug, Default)]
+pub struct DataSubscribers(
+    Arc<parking_lot::RwLock<Vec<Weak<parking_lot::RwLock<DataSubscriptionMap>>>>>,
+);
+
+impl DataSubscribers {
+    /// Add a connection's data subscriptions,
+    /// forgetting those of connections which have closed
+    fn register(&self, data: &Arc<parking_lot::RwLock<DataSubscriptionMap>>) {
+        let mut subscribers = self.0.write();
+        subscribers.retain(|subscriber| subscriber.strong_count() > 0);
+        subscribers.push(Arc::downgrade(data));
+    }
+
+    /// Whether any connection is subscribed to this change in a Cell's data
+    pub fn watching(&self, cell_id: &CellId, change: &DataChange) -> bool {
+        self.0
+            .read()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .any(|data| data_subscribed(&data.read(), cell_id, change))
+    }
+}
+
+fn data_subscribed(data: &DataSubscriptionMap, cell_id: &CellId, change: &DataChange) -> bool {
+    data.values().any(|(cell_ids, subscriptions)| {
+        cell_ids.contains(cell_id) && subscriptions.iter().any(|s| s.matches(change))
+    })
+}
+
+/// The signal subscriptions made over a single App interface connection,
+/// along with the Cells of each subscribed app
+#[derive(Clone, Debug, Default)]
+pub struct SignalSubscriptions {
+    filters: Arc<parking_lot::RwLock<HashMap<InstalledAppId, (Vec<CellId>, SignalFilterSet)>>>,
+    data: Arc<parking_lot::RwLock<DataSubscriptionMap>>,
+}
+
+impl SignalSubscriptions {
+    /// Make the data subscriptions of this connection known to the Cells,
+    /// for as long as the connection is open
+    pub fn registered(self, data_subscribers: &DataSubscribers) -> Self {
+        data_subscribers.register(&self.data);
+        self
+    }
+
+    /// Replace the subscription for an app which has these Cells
+    pub fn set(
+        &self,
+        installed_app_id: InstalledAppId,
+        cell_ids: Vec<CellId>,
+        filters: SignalFilterSet,
+    ) {
+        self.filters
+            .write()
+            .insert(installed_app_id, (cell_ids, filters));
+    }
+
+    /// Watch some data held by the Cells of an app
+    pub fn subscribe_data(
+        &self,
+        installed_app_id: InstalledAppId,
+        cell_ids: Vec<CellId>,
+        subscription: DataSubscription,
+    ) {
+        let mut data = self.data.write();
+        let (app_cell_ids, subscriptions) = data.entry(installed_app_id).or_default();
+        *app_cell_ids = cell_ids;
+        subscriptions.insert(subscription);
+    }
+
+    /// Stop watching some data held by the Cells of an app
+    pub fn unsubscribe_data(
+        &self,
+        installed_app_id: &InstalledAppId,
+        subscription: &DataSubscription,
+    ) {
+        let mut data = self.data.write();
+        if let Some((_, subscriptions)) = data.get_mut(installed_app_id) {
+            subscriptions.remove(subscription);
+            if subscriptions.is_empty() {
+                data.remove(installed_app_id);
+            }
+        }
+    }
+
+    /// Whether a signal should be sent across the connection.
+    /// Signals from Cells which aren't in any subscribed app are always sent,
+    /// otherwise at least one of the subscriptions must allow the signal.
+    /// Data changes are only sent if a data subscription matches them.
+    pub fn allows(&self, signal: &Signal) -> bool {
+        let cell_id = match signal {
+            Signal::App { cell_id, .. } => cell_id,
+            Signal::System(SystemSignal::DataChanged { cell_id, change }) => {
+                return data_subscribed(&self.data.read(), cell_id, change)
+            }
+            Signal::System(_) => return true,
+        };
+        let subscriptions = self.filters.read();
+        let mut subscribed = subscriptions
+            .values()
+            .filter(|(cell_ids, _)| cell_ids.contains(cell_id))
+            .peekable();
+        subscribed.peek().is_none() || subscribed.any(|(_, filters)| filters.allows(signal))
+    }
+}
+
 /// A collection of Senders to be used for emitting Signals from a Cell.
 /// There is one Sender per attached Interface
 #[derive(Clone, Debug)]
 pub struct SignalBroadcaster {
     senders: Vec<broadcast::Sender<Signal>>,
+    data_subscribers: DataSubscribers,
 }
 
 impl SignalBroadcaster {
@@ -63,9 +186,18 @@ impl SignalBroadcaster {
         Ok(())
     }
 
+    /// Whether a connection is subscribed to this change in a Cell's data,
+    /// so it's worth sending as a signal
+    pub fn data_subscribed(&self, cell_id: &CellId, change: &DataChange) -> bool {
+        self.data_subscribers.watching(cell_id, change)
+    }
+
     /// internal constructor
-    pub fn new(senders: Vec<broadcast::Sender<Signal>>) -> Self {
-        Self { senders }
+    pub fn new(senders: Vec<broadcast::Sender<Signal>>, data_subscribers: DataSubscribers) -> Self {
+        Self {
+            senders,
+            data_subscribers,
+        }
     }
 
     #[cfg(test)]
@@ -73,6 +205,7 @@ impl SignalBroadcaster {
     pub fn noop() -> Self {
         Self {
             senders: Vec::new(),
+            data_subscribers: DataSubscribers::default(),
         }
     }
 
@@ -113,10 +113,11 @@ pub fn spawn_admin_interface_task<A: InterfaceApi>(
 
 /// Create an App Interface, which includes the ability to receive signals
 /// from Cells via a broadcast channel
-pub async fn spawn_app_interface_task<A: InterfaceApi>(
+pub async fn spawn_app_interface_task(
     port: u16,
-    api: A,
+    api: RealAppInterfaceApi,
     signal_broadcaster: broadcast::Sender<Signal>,
+    data_subscribers: DataSubscribers,
     mut stop_rx: StopReceiver,
 ) -> InterfaceResult<(u16, ManagedTaskHandle)> {
     trace!("Initializing App interface");
@@ -136,10 +137,15 @@ pub async fn spawn_app_interface_task<A: InterfaceApi>(
         let mut handle_connection =
             |tx_to_iface: WebsocketSender, rx_from_iface: WebsocketReceiver| {
                 let rx_from_cell = signal_broadcaster.subscribe();
+                // Each connection has its own signal subscriptions
+                let signal_subscriptions =
+                    SignalSubscriptions::default().registered(&data_subscribers);
                 listener_handles.push(tokio::task::spawn(recv_incoming_msgs_and_outgoing_signals(
-                    api.clone(),
+                    api.clone()
+                        .with_signal_subscriptions(signal_subscriptions.clone()),
                     rx_from_iface,
                     rx_from_cell,
+                    signal_subscriptions,
                     tx_to_iface,
                 )));
             };
@@ -225,10 +231,12 @@ async fn recv_incoming_admin_msgs<A: InterfaceApi>(
 /// Polls for messages coming in from the external client while simultaneously
 /// polling for signals being broadcast from the Cells associated with this
 /// App interface.
+/// Signals are only sent if the connection's subscriptions allow them.
 async fn recv_incoming_msgs_and_outgoing_signals<A: InterfaceApi>(
     api: A,
     mut rx_from_iface: WebsocketReceiver,
     mut rx_from_cell: broadcast::Receiver<Signal>,
+    signal_subscriptions: SignalSubscriptions,
     mut tx_to_iface: WebsocketSender,
 ) -> InterfaceResult<()> {
     trace!("CONNECTION: {}", rx_from_iface.remote_addr());
@@ -240,15 +248,23 @@ async fn recv_incoming_msgs_and_outgoing_signals<A: InterfaceApi>(
             // NOTE: we could just use futures::StreamExt::forward to hook this
             // tx and rx together in a new spawned task
             signal = rx_from_cell.next() => {
-                if let Some(signal) = signal {
-                    trace!(msg = "Sending signal!", ?signal);
-                    let bytes = SerializedBytes::try_from(
-                        signal.map_err(InterfaceError::SignalReceive)?,
-                    )?;
-                    tx_to_iface.signal(bytes).await?;
-                } else {
-                    debug!("Closing interface: signal stream empty");
-                    break;
+                match signal {
+                    Some(Ok(signal)) => {
+                        if signal_subscriptions.allows(&signal) {
+                            trace!(msg = "Sending signal!", ?signal);
+                            let bytes = SerializedBytes::try_from(signal)?;
+                            tx_to_iface.signal(bytes).await?;
+                        }
+                    }
+                    // A slow connection misses the signals it couldn't keep up with
+                    Some(Err(broadcast::RecvError::Lagged(missed))) => {
+                        warn!(msg = "Connection lagged behind its signals", missed);
+                    }
+                    Some(Err(e)) => return Err(InterfaceError::SignalReceive(e)),
+                    None => {
+                        debug!("Closing interface: signal stream empty");
+                        break;
+                    }
                 }
             },
 
@@ -354,6 +370,12 @@ pub mod test {
     use ::fixt::prelude::*;
     use fallible_iterator::FallibleIterator;
     use futures::future::FutureExt;
+    use holo_hash::fixt::EntryHashFixturator;
+    use holo_hash::fixt::HeaderHashFixturator;
+    use holochain_conductor_api::signal_subscription::DataSubscription;
+    use holochain_conductor_api::signal_subscription::SignalFilter;
+    use holochain_conductor_api::signal_subscription::SignalFilterSet;
+    use holochain_conductor_api::signal_subscription::SignalSubscription;
     use holochain_lmdb::buffer::KvStoreT;
     use holochain_lmdb::fresh_reader_test;
     use holochain_lmdb::test_utils::test_environments;
@@ -364,13 +386,22 @@ pub mod test {
     use holochain_types::app::InstalledCell;
     use holochain_types::dna::DnaDef;
     use holochain_types::dna::DnaFile;
+    use holochain_types::signal::test_signal;
+    use holochain_types::signal::DataChange;
+    use holochain_types::signal::SystemSignal;
     use holochain_types::test_utils::fake_agent_pubkey_1;
     use holochain_types::test_utils::fake_dna_file;
     use holochain_types::test_utils::fake_dna_zomes;
     use holochain_wasm_test_utils::TestWasm;
     use holochain_websocket::WebsocketMessage;
     use holochain_zome_types::cell::CellId;
+    use holochain_zome_types::link::LinkTag;
+    use holochain_zome_types::signal::AppSignal;
+    use holochain_zome_types::signature::Sign;
     use holochain_zome_types::test_utils::fake_agent_pubkey_2;
+    use holochain_zome_types::x_salsa20_poly1305::data::XSalsa20Poly1305Data;
+    use holochain_zome_types::x_salsa20_poly1305::X25519XSalsa20Poly1305Decrypt;
+    use holochain_zome_types::x_salsa20_poly1305::X25519XSalsa20Poly1305Encrypt;
     use holochain_zome_types::ExternIO;
     use kitsune_p2p::agent_store::AgentInfoSigned;
     use kitsune_p2p::fixt::AgentInfoSignedFixturator;
@@ -552,6 +583,292 @@ pub mod test {
         shutdown.await.unwrap();
     }
 
+    #[tokio::test(threaded_scheduler)]
+    async fn crypto_is_scoped_to_the_bound_app() {
+        observability::test_run().ok();
+        let uuid = Uuid::new_v4();
+        let dna = fake_dna_zomes(
+            &uuid.to_string(),
+            vec![(TestWasm::Foo.into(), TestWasm::Foo.into())],
+        );
+        let dna_hash = dna.dna_hash().clone();
+        let cell_id = CellId::from((dna_hash.clone(), fake_agent_pubkey_1()));
+        let installed_cell = InstalledCell::new(cell_id.clone(), "handle".into());
+
+        let mut dna_store = MockDnaStore::new();
+        dna_store
+            .expect_get()
+            .with(predicate::eq(dna_hash.clone()))
+            .returning(move |_| Some(dna.clone()));
+        dna_store
+            .expect_add_dnas::<Vec<_>>()
+            .times(1)
+            .return_const(());
+        dna_store
+            .expect_add_entry_defs::<Vec<_>>()
+            .times(1)
+            .return_const(());
+
+        let (_tmpdir, app_api, handle) = setup_app(vec![(installed_cell, None)], dna_store).await;
+
+        // Another app on the same Dna with its own agent
+        let other_cell_id = CellId::from((dna_hash, fake_agent_pubkey_2()));
+        handle
+            .clone()
+            .install_app(
+                "other app".to_string(),
+                vec![(InstalledCell::new(other_cell_id, "handle".into()), None)],
+            )
+            .await
+            .unwrap();
+        handle.activate_app("other app".to_string()).await.unwrap();
+        assert!(handle.clone().setup_cells().await.unwrap().is_empty());
+
+        async fn crypto(api: &RealAppInterfaceApi, request: CryptoRequest) -> AppResponse {
+            api.handle_app_request(AppRequest::Crypto(Box::new(request)))
+                .await
+        }
+        let sign = |key| CryptoRequest::Sign(Sign::new_raw(key, vec![1, 2, 3]));
+
+        // An interface that isn't bound to an app can't do any crypto
+        let response = crypto(&app_api, sign(fake_agent_pubkey_1())).await;
+        assert_matches!(
+            response,
+            AppResponse::Error(ExternalApiWireError::CryptoUnauthorized(_))
+        );
+
+        let our_api = app_api.clone().bound_to(Some("test app".to_string()));
+        let their_api = app_api.bound_to(Some("other app".to_string()));
+
+        // Each app can only sign for its own agents
+        let response = crypto(&our_api, sign(fake_agent_pubkey_1())).await;
+        assert_matches!(response, AppResponse::Signature(_));
+        let response = crypto(&their_api, sign(fake_agent_pubkey_1())).await;
+        assert_matches!(
+            response,
+            AppResponse::Error(ExternalApiWireError::CryptoUnauthorized(_))
+        );
+
+        let our_key = match crypto(&our_api, CryptoRequest::CreateX25519Keypair).await {
+            AppResponse::X25519KeypairCreated(key) => key,
+            r => panic!("{:?}", r),
+        };
+        let their_key = match crypto(&their_api, CryptoRequest::CreateX25519Keypair).await {
+            AppResponse::X25519KeypairCreated(key) => key,
+            r => panic!("{:?}", r),
+        };
+        let data = XSalsa20Poly1305Data::from(vec![1, 2, 3]);
+
+        // The other app can't encrypt with our key
+        let encrypt = CryptoRequest::Encrypt(X25519XSalsa20Poly1305Encrypt::new(
+            our_key,
+            their_key,
+            data.clone(),
+        ));
+        let response = crypto(&their_api, encrypt.clone()).await;
+        assert_matches!(
+            response,
+            AppResponse::Error(ExternalApiWireError::CryptoUnauthorized(_))
+        );
+        let encrypted = match crypto(&our_api, encrypt).await {
+            AppResponse::Encrypted(encrypted) => encrypted,
+            r => panic!("{:?}", r),
+        };
+
+        // Only the app holding the recipient key can decrypt
+        let decrypt = CryptoRequest::Decrypt(X25519XSalsa20Poly1305Decrypt::new(
+            our_key, their_key, encrypted,
+        ));
+        let response = crypto(&our_api, decrypt.clone()).await;
+        assert_matches!(
+            response,
+            AppResponse::Error(ExternalApiWireError::CryptoUnauthorized(_))
+        );
+        let response = crypto(&their_api, decrypt).await;
+        assert_matches!(response, AppResponse::Decrypted(Some(d)) if d == data);
+
+        let shutdown = handle.take_shutdown_handle().await.unwrap();
+        handle.shutdown().await;
+        shutdown.await.unwrap();
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn signal_subscription_filters_signals() {
+        observability::test_run().ok();
+        let uuid = Uuid::new_v4();
+        let dna = fake_dna_zomes(
+            &uuid.to_string(),
+            vec![(TestWasm::Foo.into(), TestWasm::Foo.into())],
+        );
+        let dna_hash = dna.dna_hash().clone();
+        let cell_id = CellId::from((dna_hash.clone(), fake_agent_pubkey_1()));
+        let other_cell_id = CellId::from((dna_hash.clone(), fake_agent_pubkey_2()));
+        let installed_cell = InstalledCell::new(cell_id.clone(), "handle".into());
+
+        let mut dna_store = MockDnaStore::new();
+        dna_store
+            .expect_get()
+            .with(predicate::eq(dna_hash))
+            .returning(move |_| Some(dna.clone()));
+        dna_store
+            .expect_add_dnas::<Vec<_>>()
+            .times(1)
+            .return_const(());
+        dna_store
+            .expect_add_entry_defs::<Vec<_>>()
+            .times(1)
+            .return_const(());
+
+        let (_tmpdir, app_api, handle) = setup_app(vec![(installed_cell, None)], dna_store).await;
+        let signal_subscriptions = SignalSubscriptions::default();
+        let app_api = app_api.with_signal_subscriptions(signal_subscriptions.clone());
+
+        let signal = |cell_id: &CellId, zome_name: &str| Signal::App {
+            cell_id: cell_id.clone(),
+            zome_name: zome_name.into(),
+            provenance: None,
+            signal: AppSignal::new(ExternIO::encode(()).unwrap()),
+        };
+
+        // Everything is let through before subscribing
+        assert!(signal_subscriptions.allows(&signal(&cell_id, "foo")));
+        assert!(signal_subscriptions.allows(&signal(&cell_id, "bar")));
+
+        let filters = SignalFilterSet::Include(
+            vec![(cell_id.clone(), SignalFilter::empty().zome("foo".into()))]
+                .into_iter()
+                .collect(),
+        );
+        let request = AppRequest::SignalSubscription(SignalSubscription {
+            installed_app_id: "test app".to_string(),
+            filters,
+        });
+        let response = app_api.handle_app_request(request).await;
+        assert_matches!(response, AppResponse::SignalSubscriptionUpdated);
+
+        assert!(signal_subscriptions.allows(&signal(&cell_id, "foo")));
+        assert!(!signal_subscriptions.allows(&signal(&cell_id, "bar")));
+        // Cells outside the subscribed app aren't filtered
+        assert!(signal_subscriptions.allows(&signal(&other_cell_id, "bar")));
+        assert!(signal_subscriptions.allows(&test_signal("system")));
+
+        // Unknown apps can't be subscribed to
+        let request = AppRequest::SignalSubscription(SignalSubscription {
+            installed_app_id: "other app".to_string(),
+            filters: SignalFilterSet::block_all(),
+        });
+        let response = app_api.handle_app_request(request).await;
+        assert_matches!(response, AppResponse::Error(_));
+
+        let shutdown = handle.take_shutdown_handle().await.unwrap();
+        handle.shutdown().await;
+        shutdown.await.unwrap();
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn data_subscription_sends_matching_changes() {
+        observability::test_run().ok();
+        let uuid = Uuid::new_v4();
+        let dna = fake_dna_zomes(
+            &uuid.to_string(),
+            vec![(TestWasm::Foo.into(), TestWasm::Foo.into())],
+        );
+        let dna_hash = dna.dna_hash().clone();
+        let cell_id = CellId::from((dna_hash.clone(), fake_agent_pubkey_1()));
+        let other_cell_id = CellId::from((dna_hash.clone(), fake_agent_pubkey_2()));
+        let installed_cell = InstalledCell::new(cell_id.clone(), "handle".into());
+
+        let mut dna_store = MockDnaStore::new();
+        dna_store
+            .expect_get()
+            .with(predicate::eq(dna_hash))
+            .returning(move |_| Some(dna.clone()));
+        dna_store
+            .expect_add_dnas::<Vec<_>>()
+            .times(1)
+            .return_const(());
+        dna_store
+            .expect_add_entry_defs::<Vec<_>>()
+            .times(1)
+            .return_const(());
+
+        let (_tmpdir, app_api, handle) = setup_app(vec![(installed_cell, None)], dna_store).await;
+        let data_subscribers = DataSubscribers::default();
+        let signal_subscriptions = SignalSubscriptions::default().registered(&data_subscribers);
+        let app_api = app_api.with_signal_subscriptions(signal_subscriptions.clone());
+
+        let base = fixt!(EntryHash);
+        let link_created = |cell_id: &CellId, tag: &str| -> Signal {
+            SystemSignal::DataChanged {
+                cell_id: cell_id.clone(),
+                change: DataChange::LinkCreated {
+                    base: base.clone(),
+                    tag: LinkTag::new(tag),
+                    target: fixt!(EntryHash),
+                    create_link_hash: fixt!(HeaderHash),
+                },
+            }
+            .into()
+        };
+
+        let watching = |signal: &Signal| match signal {
+            Signal::System(SystemSignal::DataChanged { cell_id, change }) => {
+                data_subscribers.watching(cell_id, change)
+            }
+            _ => unreachable!(),
+        };
+
+        // Nothing is sent before subscribing
+        let changed = link_created(&cell_id, "foo");
+        assert!(!signal_subscriptions.allows(&changed));
+        assert!(!watching(&changed));
+
+        let subscription = DataSubscription::Links {
+            base: base.clone(),
+            tag_prefix: Some(LinkTag::new("fo")),
+        };
+
+        // An interface that isn't bound to an app can't subscribe
+        let request = AppRequest::SubscribeData(subscription.clone());
+        let response = app_api.handle_app_request(request).await;
+        assert_matches!(
+            response,
+            AppResponse::Error(ExternalApiWireError::InterfaceNotBound(_))
+        );
+
+        let app_api = app_api.bound_to(Some("test app".to_string()));
+        let request = AppRequest::SubscribeData(subscription.clone());
+        let response = app_api.handle_app_request(request).await;
+        assert_matches!(response, AppResponse::DataSubscribed);
+
+        assert!(signal_subscriptions.allows(&changed));
+        assert!(watching(&changed));
+        assert!(!signal_subscriptions.allows(&link_created(&cell_id, "bar")));
+        assert!(!watching(&link_created(&cell_id, "bar")));
+        // Only changes in the app's Cells are sent
+        assert!(!signal_subscriptions.allows(&link_created(&other_cell_id, "foo")));
+        assert!(!watching(&link_created(&other_cell_id, "foo")));
+
+        let request = AppRequest::UnsubscribeData(subscription.clone());
+        let response = app_api.handle_app_request(request).await;
+        assert_matches!(response, AppResponse::DataUnsubscribed);
+        assert!(!signal_subscriptions.allows(&changed));
+        assert!(!watching(&changed));
+
+        // A closed connection's subscriptions are no longer watched
+        let request = AppRequest::SubscribeData(subscription);
+        let response = app_api.handle_app_request(request).await;
+        assert_matches!(response, AppResponse::DataSubscribed);
+        assert!(watching(&changed));
+        drop(app_api);
+        drop(signal_subscriptions);
+        assert!(!watching(&changed));
+
+        let shutdown = handle.take_shutdown_handle().await.unwrap();
+        handle.shutdown().await;
+        shutdown.await.unwrap();
+    }
+
     #[tokio::test(threaded_scheduler)]
     async fn activate_app() {
         observability::test_run().ok();
@@ -674,7 +991,10 @@ pub mod test {
         let (_tmpdir, conductor_handle) = setup_admin().await;
         let shutdown = conductor_handle.take_shutdown_handle().await.unwrap();
         let admin_api = RealAdminInterfaceApi::new(conductor_handle.clone());
-        let msg = AdminRequest::AttachAppInterface { port: None };
+        let msg = AdminRequest::AttachAppInterface {
+            port: None,
+            installed_app_id: None,
+        };
         let msg = msg.try_into().unwrap();
         let respond = |bytes: SerializedBytes| {
             let response: AdminResponse = bytes.try_into().unwrap();
@@ -746,7 +1066,9 @@ pub mod test {
                 name: "conductor_test".to_string(),
                 uuid: uuid.to_string(),
                 properties: SerializedBytes::try_from(()).unwrap(),
+                rate_limits: Vec::new(),
                 zomes: zomes.clone().into_iter().map(Into::into).collect(),
+                redundancy_target: None,
             },
             zomes.into_iter().map(Into::into),
         )
@@ -20,6 +20,7 @@ use std::convert::TryFrom;
 use std::convert::TryInto;
 use std::sync::Arc;
 
+use super::error::ConductorError;
 use super::error::ConductorResult;
 
 const AGENT_KEY_LEN: usize = 64;
@@ -303,11 +304,17 @@ pub fn query_agent_info_signed(
     Ok(out)
 }
 
-/// Put single agent info into store
-pub fn put_agent_info_signed(
+/// Put single agent info into store.
+/// The agent info is rejected if it was not signed by the agent it is for.
+pub async fn put_agent_info_signed(
     environ: EnvironmentWrite,
     agent_info_signed: kitsune_p2p::agent_store::AgentInfoSigned,
 ) -> ConductorResult<()> {
+    if !agent_info_signed.verify_signature().await? {
+        return Err(ConductorError::InvalidAgentInfoSignature(
+            agent_info_signed.into_agent(),
+        ));
+    }
     let p2p_kv = AgentKv::new(environ.clone().into())?;
     let env = environ.guard();
     Ok(env.with_commit(|writer| {
@@ -323,14 +330,21 @@ pub fn put_agent_info_signed(
 mod tests {
     use super::*;
     use ::fixt::prelude::*;
+    use holochain_keystore::test_keystore::spawn_test_keystore;
+    use holochain_keystore::AgentPubKeyExt;
     use holochain_lmdb::buffer::KvStoreT;
     use holochain_lmdb::env::ReadManager;
     use holochain_lmdb::env::WriteManager;
     use holochain_lmdb::fresh_reader_test;
     use holochain_lmdb::test_utils::test_p2p_env;
+    use holochain_p2p::agent_holo_to_kit;
+    use holochain_p2p::space_holo_to_kit;
+    use kitsune_p2p::dependencies::kitsune_p2p_types::codec::rmp_encode;
+    use kitsune_p2p::dht_arc::DhtArc;
     use kitsune_p2p::fixt::AgentInfoFixturator;
     use kitsune_p2p::fixt::AgentInfoSignedFixturator;
     use kitsune_p2p::KitsuneBinType;
+    use kitsune_p2p::KitsuneSignature;
     use std::convert::TryInto;
 
     #[test]
@@ -413,4 +427,56 @@ mod tests {
 
         assert_eq!(expect, agents);
     }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn put_agent_info_signed_rejects_bad_signature() {
+        observability::test_run().ok();
+        let t_env = test_p2p_env();
+        let env = t_env.env();
+
+        // - The fixture signature is random bytes so it can't verify
+        let agent_info_signed = fixt!(AgentInfoSigned);
+        let result = put_agent_info_signed(env.clone(), agent_info_signed).await;
+        assert!(result.is_err());
+
+        // - Nothing was stored
+        let agents = all_agent_infos(env.clone().into()).unwrap();
+        assert!(agents.is_empty());
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn put_agent_info_signed_accepts_keystore_signature() {
+        observability::test_run().ok();
+        let t_env = test_p2p_env();
+        let env = t_env.env();
+        let keystore = spawn_test_keystore().await.unwrap();
+        let agent = AgentPubKey::new_from_pure_entropy(&keystore).await.unwrap();
+
+        // - Sign the agent info the same way a cell does for the network
+        let agent_info = AgentInfo::new(
+            space_holo_to_kit(DnaHash::from_raw_32(vec![0; 32])),
+            agent_holo_to_kit(agent.clone()),
+            Vec::new(),
+            DhtArc::new(0, u32::MAX),
+            0,
+            1000,
+        );
+        let mut data = Vec::new();
+        rmp_encode(&mut data, &agent_info).unwrap();
+        let signature = agent.sign_raw(&keystore, &data).await.unwrap();
+        let agent_info_signed = AgentInfoSigned::try_new(
+            agent_holo_to_kit(agent),
+            KitsuneSignature(signature.0),
+            data,
+        )
+        .unwrap();
+        assert!(agent_info_signed.verify_signature().await.unwrap());
+
+        // - The agent info is stored
+        put_agent_info_signed(env.clone(), agent_info_signed.clone())
+            .await
+            .unwrap();
+        let agents = all_agent_infos(env.clone().into()).unwrap();
+        assert_eq!(agents, vec![agent_info_signed]);
+    }
 }
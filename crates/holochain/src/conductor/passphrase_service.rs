@@ -0,0 +1,83 @@
+//! Provides the passphrase that unlocks the keystore, as selected by the
+//! [PassphraseServiceConfig] in the conductor config.
+
+use futures::future::FutureExt;
+use holochain_conductor_api::config::conductor::PassphraseServiceConfig;
+use holochain_keystore::lair_keystore::PassphraseProvider;
+use holochain_keystore::KeystoreApiResult;
+use holochain_keystore::KeystoreError;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+
+/// The message sent over a unix socket to ask for the passphrase
+const PASSPHRASE_REQUEST: &[u8] = b"request_passphrase\n";
+
+/// Create the [PassphraseProvider] for this config
+pub fn passphrase_provider(config: PassphraseServiceConfig) -> PassphraseProvider {
+    Arc::new(move || match config.clone() {
+        PassphraseServiceConfig::Cmd => from_cmd().boxed(),
+        PassphraseServiceConfig::UnixSocket { path } => from_unix_socket(path).boxed(),
+        PassphraseServiceConfig::FromConfig { passphrase } => async move { Ok(passphrase) }.boxed(),
+    })
+}
+
+/// Prompt for the passphrase on stderr and read it from stdin without echoing it
+async fn from_cmd() -> KeystoreApiResult<String> {
+    tokio::task::spawn_blocking(|| {
+        rpassword::prompt_password_stderr("Enter the passphrase to unlock the keystore: ")
+            .map_err(|e| KeystoreError::Other(e.to_string()))
+    })
+    .await
+    .map_err(|e| KeystoreError::Other(e.to_string()))?
+}
+
+/// Send a request over the socket and read the passphrase up to a newline
+async fn from_unix_socket(path: PathBuf) -> KeystoreApiResult<String> {
+    let mut stream = tokio::net::UnixStream::connect(&path)
+        .await
+        .map_err(|e| KeystoreError::Other(format!("{:?}: {}", path, e)))?;
+    stream
+        .write_all(PASSPHRASE_REQUEST)
+        .await
+        .map_err(|e| KeystoreError::Other(e.to_string()))?;
+    let mut passphrase = String::new();
+    tokio::io::BufReader::new(stream)
+        .read_line(&mut passphrase)
+        .await
+        .map_err(|e| KeystoreError::Other(e.to_string()))?;
+    Ok(trim_newline(passphrase))
+}
+
+fn trim_newline(mut passphrase: String) -> String {
+    while passphrase.ends_with('\n') || passphrase.ends_with('\r') {
+        passphrase.pop();
+    }
+    passphrase
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn reads_passphrase_from_unix_socket() {
+        let dir = tempdir::TempDir::new("passphrase-service").unwrap();
+        let path = dir.path().join("passphrase.sock");
+        let mut listener = tokio::net::UnixListener::bind(&path).unwrap();
+
+        let server = tokio::task::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut stream = tokio::io::BufReader::new(stream);
+            let mut request = String::new();
+            stream.read_line(&mut request).await.unwrap();
+            assert_eq!(request.as_bytes(), PASSPHRASE_REQUEST);
+            stream.get_mut().write_all(b"hunter2\n").await.unwrap();
+        });
+
+        let provider = passphrase_provider(PassphraseServiceConfig::UnixSocket { path });
+        assert_eq!(provider().await.unwrap(), "hunter2");
+        server.await.unwrap();
+    }
+}
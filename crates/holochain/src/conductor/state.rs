@@ -25,6 +25,10 @@ pub struct ConductorState {
     /// List of interfaces any UI can use to access zome functions.
     #[serde(default)]
     pub app_interfaces: HashMap<AppInterfaceId, AppInterfaceConfig>,
+    /// The x25519 keys each app has created in the keystore
+    /// and may encrypt and decrypt with
+    #[serde(default)]
+    pub app_x25519_keys: HashMap<InstalledAppId, Vec<X25519PubKey>>,
 }
 
 /// A unique identifier used to refer to an App Interface internally.
@@ -61,6 +65,15 @@ impl ConductorState {
             })
     }
 
+    /// The ids of the cells of every installed App, active or not
+    pub fn installed_cell_ids(&self) -> impl Iterator<Item = &CellId> {
+        self.active_apps
+            .values()
+            .chain(self.inactive_apps.values())
+            .flatten()
+            .map(|cell| cell.as_id())
+    }
+
     /// Returns the interface configuration with the given ID if present
     pub fn interface_by_id(&self, id: &AppInterfaceId) -> Option<AppInterfaceConfig> {
         self.app_interfaces.get(id).cloned()
@@ -84,6 +97,12 @@ pub struct AppInterfaceConfig {
 
     /// The driver for the interface, e.g. Websocket
     pub driver: InterfaceDriver,
+
+    /// The app this interface is bound to, if any.
+    /// Crypto requests are only allowed on a bound interface
+    /// and are made on behalf of its app.
+    #[serde(default)]
+    pub installed_app_id: Option<InstalledAppId>,
 }
 
 impl AppInterfaceConfig {
@@ -92,8 +111,15 @@ impl AppInterfaceConfig {
         Self {
             signal_subscriptions: HashMap::new(),
             driver: InterfaceDriver::Websocket { port },
+            installed_app_id: None,
         }
     }
+
+    /// Bind the interface to an app
+    pub fn bound_to(mut self, installed_app_id: Option<InstalledAppId>) -> Self {
+        self.installed_app_id = installed_app_id;
+        self
+    }
 }
 
 // TODO: Tons of consistency check tests were ripped out in the great legacy code cleanup
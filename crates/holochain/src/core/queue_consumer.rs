@@ -19,6 +19,8 @@
 //! |                 **integration, common to both paths**                 |
 //! | DhtOpIntegr.   | IntegrationLimbo | IntegratedDhtOps | Publish        |
 //! | Publish        | AuthoredDhtOps   | *n/a*            | *n/a*          |
+//! |                        **scheduled path**                             |
+//! | Scheduler      | ScheduledFns     | ScheduledFns     | CallZome       |
 //!
 //! († Auth'd + IntQ is short for: AuthoredDhtOps + IntegrationLimbo)
 //!
@@ -45,41 +47,73 @@ use app_validation_consumer::*;
 mod produce_dht_ops_consumer;
 use produce_dht_ops_consumer::*;
 mod publish_dht_ops_consumer;
+mod scheduler_consumer;
 use crate::conductor::api::CellConductorApiT;
 use crate::conductor::manager::ManagedTaskAdd;
+use crate::conductor::manager::ManagedTaskHandle;
 use holochain_p2p::HolochainP2pCell;
 use holochain_state::workspace::WorkspaceError;
 use publish_dht_ops_consumer::*;
+use scheduler_consumer::*;
 
 /// Spawns several long-running tasks which are responsible for processing work
 /// which shows up on various databases.
 ///
 /// Waits for the initial loop to complete before returning, to prevent causing
 /// a race condition by trying to run a workflow too soon after cell creation.
+///
+/// The tasks stop when the conductor shuts down, or when the returned
+/// [QueueConsumerTasks] are stopped.
 pub async fn spawn_queue_consumer_tasks(
     env: &EnvironmentWrite,
     cell_network: HolochainP2pCell,
     conductor_api: impl CellConductorApiT + 'static,
-    mut task_sender: sync::mpsc::Sender<ManagedTaskAdd>,
-    stop: sync::broadcast::Sender<()>,
-) -> (QueueTriggers, InitialQueueTriggers) {
+    task_sender: sync::mpsc::Sender<ManagedTaskAdd>,
+    conductor_stop: sync::broadcast::Sender<()>,
+    receipt_bundle_size: u32,
+) -> (QueueTriggers, InitialQueueTriggers, QueueConsumerTasks) {
+    let (stop, _) = sync::broadcast::channel(1);
+    let (done_tx, done_rx) = mpsc::channel(1);
+    let mut consumers = ManagedConsumers {
+        task_sender,
+        done: done_tx,
+    };
+
+    // Pass the conductor shutting down on to this cell's consumers
+    {
+        let mut conductor_stop = conductor_stop.subscribe();
+        let mut cell_stop = stop.subscribe();
+        let stop = stop.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = conductor_stop.recv() => {
+                    stop.send(()).ok();
+                }
+                _ = cell_stop.recv() => {}
+            }
+        });
+    }
+
     // Publish
-    let (tx_publish, handle) =
-        spawn_publish_dht_ops_consumer(env.clone(), stop.subscribe(), cell_network.clone());
-    task_sender
-        .send(ManagedTaskAdd::dont_handle(handle))
-        .await
-        .expect("Failed to manage workflow handle");
+    let (tx_publish, handle) = spawn_publish_dht_ops_consumer(
+        env.clone(),
+        stop.subscribe(),
+        cell_network.clone(),
+        receipt_bundle_size,
+    );
+    consumers.add(handle).await;
 
     let (create_tx_sys, get_tx_sys) = tokio::sync::oneshot::channel();
 
     // Integration
-    let (tx_integration, handle) =
-        spawn_integrate_dht_ops_consumer(env.clone(), stop.subscribe(), get_tx_sys);
-    task_sender
-        .send(ManagedTaskAdd::dont_handle(handle))
-        .await
-        .expect("Failed to manage workflow handle");
+    let (tx_integration, handle) = spawn_integrate_dht_ops_consumer(
+        env.clone(),
+        stop.subscribe(),
+        get_tx_sys,
+        cell_network.clone(),
+        conductor_api.clone(),
+    );
+    consumers.add(handle).await;
 
     // App validation
     let (tx_app, handle) = spawn_app_validation_consumer(
@@ -89,10 +123,7 @@ pub async fn spawn_queue_consumer_tasks(
         conductor_api.clone(),
         cell_network.clone(),
     );
-    task_sender
-        .send(ManagedTaskAdd::dont_handle(handle))
-        .await
-        .expect("Failed to manage workflow handle");
+    consumers.add(handle).await;
 
     // Sys validation
     let (tx_sys, handle) = spawn_sys_validation_consumer(
@@ -100,12 +131,9 @@ pub async fn spawn_queue_consumer_tasks(
         stop.subscribe(),
         tx_app.clone(),
         cell_network,
-        conductor_api,
+        conductor_api.clone(),
     );
-    task_sender
-        .send(ManagedTaskAdd::dont_handle(handle))
-        .await
-        .expect("Failed to manage workflow handle");
+    consumers.add(handle).await;
     if create_tx_sys.send(tx_sys.clone()).is_err() {
         panic!("Failed to send tx_sys");
     }
@@ -113,17 +141,69 @@ pub async fn spawn_queue_consumer_tasks(
     // Produce
     let (tx_produce, handle) =
         spawn_produce_dht_ops_consumer(env.clone(), stop.subscribe(), tx_publish.clone());
-    task_sender
-        .send(ManagedTaskAdd::dont_handle(handle))
-        .await
-        .expect("Failed to manage workflow handle");
+    consumers.add(handle).await;
+
+    // Scheduler
+    let (tx_scheduler, handle) =
+        spawn_scheduler_consumer(env.clone(), stop.subscribe(), conductor_api);
+    consumers.add(handle).await;
 
     (
-        QueueTriggers::new(tx_sys.clone(), tx_produce.clone()),
-        InitialQueueTriggers::new(tx_sys, tx_produce, tx_publish, tx_app, tx_integration),
+        QueueTriggers::new(tx_sys.clone(), tx_produce.clone(), tx_publish.clone()),
+        InitialQueueTriggers::new(
+            tx_sys,
+            tx_produce,
+            tx_publish,
+            tx_app,
+            tx_integration,
+            tx_scheduler,
+        ),
+        QueueConsumerTasks {
+            stop,
+            done: sync::Mutex::new(done_rx),
+        },
     )
 }
 
+/// Hands the consumer tasks of a cell to the task manager, keeping track of
+/// when they have all exited.
+struct ManagedConsumers {
+    task_sender: sync::mpsc::Sender<ManagedTaskAdd>,
+    done: mpsc::Sender<()>,
+}
+
+impl ManagedConsumers {
+    async fn add(&mut self, handle: ManagedTaskHandle) {
+        // Held until the consumer has exited
+        let done = self.done.clone();
+        let handle = tokio::spawn(async move {
+            let result = handle.await;
+            drop(done);
+            result?
+        });
+        self.task_sender
+            .send(ManagedTaskAdd::dont_handle(handle))
+            .await
+            .expect("Failed to manage workflow handle");
+    }
+}
+
+/// The queue consumer tasks of a single cell
+pub struct QueueConsumerTasks {
+    stop: sync::broadcast::Sender<()>,
+    done: sync::Mutex<mpsc::Receiver<()>>,
+}
+
+impl QueueConsumerTasks {
+    /// Stop the consumers and wait for them to exit, letting any workflow
+    /// they are in the middle of finish first.
+    pub async fn stop(&self) {
+        self.stop.send(()).ok();
+        // Nothing is ever sent, this returns once every consumer has exited
+        self.done.lock().await.recv().await;
+    }
+}
+
 #[derive(Clone)]
 /// The entry points for kicking off a chain reaction of queue activity
 pub struct QueueTriggers {
@@ -131,6 +211,8 @@ pub struct QueueTriggers {
     pub sys_validation: TriggerSender,
     /// Notify the ProduceDhtOps workflow to run, i.e. after InvokeCallZome
     pub produce_dht_ops: TriggerSender,
+    /// Notify the PublishDhtOps workflow to run, i.e. after a health check
+    pub publish_dht_ops: TriggerSender,
 }
 
 /// The triggers to run once at the start of a cell
@@ -142,14 +224,20 @@ pub struct InitialQueueTriggers {
     publish_dht_ops: TriggerSender,
     app_validation: TriggerSender,
     integrate_dht_ops: TriggerSender,
+    scheduler: TriggerSender,
 }
 
 impl QueueTriggers {
     /// Create a new queue trigger
-    pub fn new(sys_validation: TriggerSender, produce_dht_ops: TriggerSender) -> Self {
+    pub fn new(
+        sys_validation: TriggerSender,
+        produce_dht_ops: TriggerSender,
+        publish_dht_ops: TriggerSender,
+    ) -> Self {
         Self {
             sys_validation,
             produce_dht_ops,
+            publish_dht_ops,
         }
     }
 }
@@ -161,6 +249,7 @@ impl InitialQueueTriggers {
         publish_dht_ops: TriggerSender,
         app_validation: TriggerSender,
         integrate_dht_ops: TriggerSender,
+        scheduler: TriggerSender,
     ) -> Self {
         Self {
             sys_validation,
@@ -168,6 +257,7 @@ impl InitialQueueTriggers {
             publish_dht_ops,
             app_validation,
             integrate_dht_ops,
+            scheduler,
         }
     }
 
@@ -178,6 +268,7 @@ impl InitialQueueTriggers {
         self.publish_dht_ops.trigger();
         self.integrate_dht_ops.trigger();
         self.produce_dht_ops.trigger();
+        self.scheduler.trigger();
     }
 }
 /// The means of nudging a queue consumer to tell it to look for more work
@@ -11,11 +11,13 @@ use tokio::task::JoinHandle;
 use tracing::*;
 
 /// Spawn the QueueConsumer for DhtOpIntegration workflow
-#[instrument(skip(env, stop, trigger_sys))]
+#[instrument(skip(env, stop, trigger_sys, network, conductor_api))]
 pub fn spawn_integrate_dht_ops_consumer(
     env: EnvironmentWrite,
     mut stop: sync::broadcast::Receiver<()>,
     trigger_sys: sync::oneshot::Receiver<TriggerSender>,
+    mut network: HolochainP2pCell,
+    conductor_api: impl CellConductorApiT + 'static,
 ) -> (TriggerSender, JoinHandle<ManagedTaskResult>) {
     let (tx, mut rx) = TriggerSender::new();
     let mut trigger_self = tx.clone();
@@ -33,10 +35,19 @@ pub fn spawn_integrate_dht_ops_consumer(
             // Run the workflow
             let workspace = IntegrateDhtOpsWorkspace::new(env.clone().into())
                 .expect("Could not create Workspace");
-            if let WorkComplete::Incomplete =
-                integrate_dht_ops_workflow(workspace, env.clone().into(), &mut trigger_sys)
-                    .await
-                    .expect("Error running Workflow")
+            // Interfaces can be attached at any time
+            // so get the current signal senders for every run
+            let signal_tx = conductor_api.signal_broadcaster().await;
+            if let WorkComplete::Incomplete = integrate_dht_ops_workflow(
+                workspace,
+                env.clone().into(),
+                &mut trigger_sys,
+                &mut network,
+                conductor_api.cell_id().clone(),
+                signal_tx,
+            )
+            .await
+            .expect("Error running Workflow")
             {
                 trigger_self.trigger()
             };
@@ -16,6 +16,7 @@ pub fn spawn_publish_dht_ops_consumer(
     env: EnvironmentWrite,
     mut stop: sync::broadcast::Receiver<()>,
     mut cell_network: HolochainP2pCell,
+    receipt_bundle_size: u32,
 ) -> (TriggerSender, JoinHandle<ManagedTaskResult>) {
     let (tx, mut rx) = TriggerSender::new();
     let mut trigger_self = tx.clone();
@@ -32,10 +33,14 @@ pub fn spawn_publish_dht_ops_consumer(
             // Run the workflow
             let workspace = PublishDhtOpsWorkspace::new(env.clone().into())
                 .expect("Could not create Workspace");
-            if let WorkComplete::Incomplete =
-                publish_dht_ops_workflow(workspace, env.clone().into(), &mut cell_network)
-                    .await
-                    .expect("Error running Workflow")
+            if let WorkComplete::Incomplete = publish_dht_ops_workflow(
+                workspace,
+                env.clone().into(),
+                &mut cell_network,
+                receipt_bundle_size,
+            )
+            .await
+            .expect("Error running Workflow")
             {
                 trigger_self.trigger()
             };
@@ -0,0 +1,54 @@
+//! The workflow and queue consumer for scheduled zome functions
+
+use super::*;
+
+use crate::conductor::manager::ManagedTaskResult;
+use crate::core::workflow::scheduler_workflow::scheduler_workflow;
+use crate::core::workflow::scheduler_workflow::SchedulerWorkspace;
+use holochain_lmdb::env::EnvironmentWrite;
+
+use tokio::task::JoinHandle;
+use tracing::*;
+
+/// How often the scheduler checks for functions that are due.
+pub const SCHEDULER_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Spawn the QueueConsumer for the Scheduler workflow.
+///
+/// Unlike the other consumers this one is driven by a timer
+/// as well as by triggers, because the work becomes due with time.
+#[instrument(skip(env, stop, conductor_api))]
+pub fn spawn_scheduler_consumer(
+    env: EnvironmentWrite,
+    mut stop: sync::broadcast::Receiver<()>,
+    conductor_api: impl CellConductorApiT + 'static,
+) -> (TriggerSender, JoinHandle<ManagedTaskResult>) {
+    let (tx, mut rx) = TriggerSender::new();
+    let handle = tokio::spawn(async move {
+        loop {
+            let tick = tokio::time::delay_for(SCHEDULER_INTERVAL);
+            tokio::select! {
+                _ = stop.recv() => {
+                    tracing::warn!(
+                        "Cell is shutting down: stopping scheduler_workflow queue consumer."
+                    );
+                    break;
+                }
+                r = rx.listen() => {
+                    if r.is_err() {
+                        break;
+                    }
+                }
+                _ = tick => {}
+            }
+
+            let workspace =
+                SchedulerWorkspace::new(env.clone().into()).expect("Could not create Workspace");
+            scheduler_workflow(workspace, env.clone().into(), &conductor_api)
+                .await
+                .expect("Error running Workflow");
+        }
+        Ok(())
+    });
+    (tx, handle)
+}
@@ -32,7 +32,6 @@ use crate::core::ribosome::guest_callback::validation_package::ValidationPackage
 use crate::core::ribosome::guest_callback::validation_package::ValidationPackageResult;
 use crate::core::ribosome::guest_callback::CallIterator;
 use crate::core::workflow::CallZomeWorkspaceLock;
-use derive_more::Constructor;
 use error::RibosomeResult;
 use guest_callback::entry_defs::EntryDefsHostAccess;
 use guest_callback::init::InitHostAccess;
@@ -80,7 +79,7 @@ pub enum HostAccess {
     EntryDefs(EntryDefsHostAccess),
     MigrateAgent(MigrateAgentHostAccess),
     ValidationPackage(ValidationPackageHostAccess),
-    PostCommit(PostCommitHostAccess), // TODO: add emit_signal access here?
+    PostCommit(PostCommitHostAccess),
 }
 
 impl From<&HostAccess> for HostFnAccess {
@@ -149,7 +148,8 @@ impl HostAccess {
     /// Get the signal broadcaster, panics if none was provided
     pub fn signal_tx(&mut self) -> &mut SignalBroadcaster {
         match self {
-            Self::ZomeCall(ZomeCallHostAccess { signal_tx, .. }) => signal_tx,
+            Self::ZomeCall(ZomeCallHostAccess { signal_tx, .. })
+            | Self::PostCommit(PostCommitHostAccess { signal_tx, .. }) => signal_tx,
             _ => panic!(
                 "Gave access to a host function that uses the signal broadcaster without providing one"
             ),
@@ -159,11 +159,21 @@ impl HostAccess {
     /// Get the associated CellId, panics if not applicable
     pub fn cell_id(&self) -> &CellId {
         match self {
-            Self::ZomeCall(ZomeCallHostAccess { cell_id, .. }) => cell_id,
+            Self::ZomeCall(ZomeCallHostAccess { cell_id, .. })
+            | Self::PostCommit(PostCommitHostAccess { cell_id, .. }) => cell_id,
             _ => panic!("Gave access to a host function that references a CellId"),
         }
     }
 
+    /// Get the capability that authorized the zome call,
+    /// if this is a zome call that has been authorized
+    pub fn capability(&self) -> Option<&CapabilityInfo> {
+        match self {
+            Self::ZomeCall(ZomeCallHostAccess { capability, .. }) => capability.as_ref(),
+            _ => None,
+        }
+    }
+
     /// Get the call zome handle, panics if none was provided
     pub fn call_zome_handle(&self) -> &CellConductorReadHandle {
         match self {
@@ -254,21 +264,24 @@ impl ZomeCallInvocation {
     /// - we need to find a live (committed and not deleted) cap grant that matches the secret
     /// - if the live cap grant is for the current author the call is ALWAYS authorized ELSE
     /// - the live cap grant needs to include the invocation's provenance AND zome/function name
+    ///
+    /// returns the grant that authorizes the call, or None if the call is not authorized
     #[allow(clippy::extra_unused_lifetimes)]
-    pub fn is_authorized<'a>(&self, host_access: &ZomeCallHostAccess) -> RibosomeResult<bool> {
+    pub fn authorized_grant<'a>(
+        &self,
+        host_access: &ZomeCallHostAccess,
+    ) -> RibosomeResult<Option<CapGrant>> {
         let check_function = (self.zome.zome_name().clone(), self.fn_name.clone());
         let check_agent = self.provenance.clone();
         let check_secret = self.cap;
 
         tokio_safe_block_on::tokio_safe_block_forever_on(async move {
-            let maybe_grant: Option<CapGrant> = host_access
+            Ok(host_access
                 .workspace
                 .read()
                 .await
                 .source_chain
-                .valid_cap_grant(&check_function, &check_agent, check_secret.as_ref())?;
-
-            Ok(maybe_grant.is_some())
+                .valid_cap_grant(&check_function, &check_agent, check_secret.as_ref())?)
         })
     }
 }
@@ -366,7 +379,7 @@ impl From<ZomeCallInvocation> for ZomeCall {
     }
 }
 
-#[derive(Clone, Constructor)]
+#[derive(Clone)]
 pub struct ZomeCallHostAccess {
     pub workspace: CallZomeWorkspaceLock,
     pub keystore: KeystoreSender,
@@ -377,6 +390,31 @@ pub struct ZomeCallHostAccess {
     // "resource" to give access to, but rather it's a bit of data that makes sense in
     // the context of zome calls, but not every CallContext
     pub cell_id: CellId,
+    /// The capability that authorized the call.
+    /// This is set by the ribosome once the call is authorized.
+    pub capability: Option<CapabilityInfo>,
+}
+
+impl ZomeCallHostAccess {
+    /// Constructor, the call is not authorized yet
+    pub fn new(
+        workspace: CallZomeWorkspaceLock,
+        keystore: KeystoreSender,
+        network: HolochainP2pCell,
+        signal_tx: SignalBroadcaster,
+        call_zome_handle: CellConductorReadHandle,
+        cell_id: CellId,
+    ) -> Self {
+        Self {
+            workspace,
+            keystore,
+            network,
+            signal_tx,
+            call_zome_handle,
+            cell_id,
+            capability: None,
+        }
+    }
 }
 
 impl From<ZomeCallHostAccess> for HostAccess {
@@ -1,3 +1,4 @@
+use crate::conductor::interface::SignalBroadcaster;
 use crate::core::ribosome::FnComponents;
 use crate::core::ribosome::HostAccess;
 use crate::core::ribosome::Invocation;
@@ -26,6 +27,8 @@ pub struct PostCommitHostAccess {
     pub workspace: CallZomeWorkspaceLock,
     pub keystore: KeystoreSender,
     pub network: HolochainP2pCell,
+    pub signal_tx: SignalBroadcaster,
+    pub cell_id: CellId,
 }
 
 impl From<PostCommitHostAccess> for HostAccess {
@@ -54,14 +54,14 @@ host_fn_api_impls! {
     // Header hash of the DeleteLink element.
     fn call_remote (zt::call_remote::CallRemote) -> zt::ZomeCallResponse;
 
-    // @todo List all the local capability claims.
-    fn capability_claims (()) -> ();
+    // List the live local capability claims, optionally only those with a tag.
+    fn capability_claims (Option<String>) -> Vec<zt::capability::CapClaimInfo>;
 
-    // @todo List all the local capability grants.
-    fn capability_grants (()) -> ();
+    // List the live local capability grants, optionally only those with a tag.
+    fn capability_grants (Option<String>) -> Vec<zt::capability::CapGrantInfo>;
 
-    // @todo Get the capability for the current zome call.
-    fn capability_info (()) -> ();
+    // Get the capability that authorized the current zome call.
+    fn capability_info (()) -> zt::capability::CapabilityInfo;
 
     // The EntryDefId determines how a create is handled on the host side.
     // CapGrant and CapClaim are handled natively.
@@ -94,8 +94,8 @@ host_fn_api_impls! {
     // Header hash of the CreateLink element.
     fn delete_link (holo_hash::HeaderHash) -> holo_hash::HeaderHash;
 
-    // @todo
-    fn entry_type_properties (()) -> ();
+    // Get the properties declared on one of this zome's entry defs.
+    fn entry_type_properties (zt::entry_def::EntryDefId) -> Option<SerializedBytes>;
 
     // Header hash of the newly committed element.
     // Emit a Signal::App to subscribers on the interface
@@ -117,11 +117,14 @@ host_fn_api_impls! {
 
     fn get_link_details (zt::link::GetLinksInput) -> zt::link::LinkDetails;
 
+    // Count the live links on a base without returning them.
+    fn count_links (zt::link::GetLinksInput) -> u32;
+
     // Hash an entry on the host.
     fn hash_entry (zt::entry::Entry) -> holo_hash::EntryHash;
 
-    // @todo
-    fn property (()) -> ();
+    // Get the properties of the dna.
+    fn property (()) -> SerializedBytes;
 
     // Query the source chain for data.
     fn query (zt::query::ChainQueryFilter) -> zt::element::ElementVec;
@@ -135,8 +138,8 @@ host_fn_api_impls! {
     // // @todo
     // fn send (()) -> ();
 
-    // @todo
-    fn schedule (core::time::Duration) -> ();
+    // Schedule a function in the current zome to be called later by the host.
+    fn schedule (zt::schedule::ScheduleInput) -> ();
 
     // @todo
     fn show_env (()) -> ();
@@ -59,7 +59,9 @@ pub mod wasm_test {
             name: "call_remote_test".to_string(),
             uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
             properties: SerializedBytes::try_from(()).unwrap(),
+            rate_limits: Vec::new(),
             zomes: vec![TestWasm::WhoAmI.into()].into(),
+            redundancy_target: None,
         };
         let dna_file = DnaFile::new(dna_def, vec![TestWasm::WhoAmI.into()])
             .await
@@ -1,13 +1,29 @@
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
-use std::sync::Arc;
+use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::WasmError;
+use std::sync::Arc;
 
 /// lists all the local claims filtered by tag
+/// this is only the current claims as per local CRUD
 pub fn capability_claims(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: (),
-) -> Result<(), WasmError> {
-    unimplemented!();
+    call_context: Arc<CallContext>,
+    input: Option<String>,
+) -> Result<Vec<CapClaimInfo>, WasmError> {
+    let claims = tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        call_context
+            .host_access
+            .workspace()
+            .read()
+            .await
+            .source_chain
+            .capabilities()
+            .claims()
+    })
+    .map_err(|database_error| WasmError::Host(database_error.to_string()))?;
+    Ok(claims
+        .into_iter()
+        .filter(|info| input.as_ref().map_or(true, |tag| info.claim.tag() == tag))
+        .collect())
 }
@@ -1,5 +1,6 @@
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
 use std::sync::Arc;
 use holochain_wasmer_host::prelude::WasmError;
 
@@ -7,10 +8,24 @@ use holochain_wasmer_host::prelude::WasmError;
 /// this is only the current grants as per local CRUD
 pub fn capability_grants(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: (),
-) -> Result<(), WasmError> {
-    unimplemented!();
+    call_context: Arc<CallContext>,
+    input: Option<String>,
+) -> Result<Vec<CapGrantInfo>, WasmError> {
+    let grants = tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        call_context
+            .host_access
+            .workspace()
+            .read()
+            .await
+            .source_chain
+            .capabilities()
+            .grants()
+    })
+    .map_err(|database_error| WasmError::Host(database_error.to_string()))?;
+    Ok(grants
+        .into_iter()
+        .filter(|info| input.as_ref().map_or(true, |tag| &info.grant.tag == tag))
+        .collect())
 }
 
 #[cfg(test)]
@@ -89,6 +104,61 @@ pub mod wasm_test {
         assert_eq!(entry_secret, secret,);
     }
 
+    #[tokio::test(threaded_scheduler)]
+    async fn ribosome_capability_grants_follow_crud() {
+        observability::test_run().ok();
+        // test workspace boilerplate
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let mut workspace = CallZomeWorkspace::new(env.clone().into()).unwrap();
+
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock.clone();
+
+        let grants: Vec<CapGrantInfo> =
+            crate::call_test_ribosome!(host_access, TestWasm::Capability, "list_cap_grants", ());
+        assert!(grants.is_empty());
+
+        let secret: CapSecret =
+            crate::call_test_ribosome!(host_access, TestWasm::Capability, "cap_secret", ());
+        let original_header: HeaderHash = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Capability,
+            "transferable_cap_grant",
+            secret
+        );
+        let grants: Vec<CapGrantInfo> =
+            crate::call_test_ribosome!(host_access, TestWasm::Capability, "list_cap_grants", ());
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].header_hash, original_header);
+
+        // rolling the grant replaces it
+        let rolled_header: HeaderHash = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Capability,
+            "roll_cap_grant",
+            original_header
+        );
+        let grants: Vec<CapGrantInfo> =
+            crate::call_test_ribosome!(host_access, TestWasm::Capability, "list_cap_grants", ());
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].header_hash, rolled_header);
+
+        let _: HeaderHash = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Capability,
+            "delete_cap_grant",
+            rolled_header
+        );
+        let grants: Vec<CapGrantInfo> =
+            crate::call_test_ribosome!(host_access, TestWasm::Capability, "list_cap_grants", ());
+        assert!(grants.is_empty());
+    }
+
     // TODO: [ B-03669 ] can move this to an integration test (may need to switch to using a RealDnaStore)
     #[tokio::test(threaded_scheduler)]
     async fn ribosome_authorized_call() {
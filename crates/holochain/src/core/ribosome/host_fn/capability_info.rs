@@ -1,5 +1,6 @@
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
 use std::sync::Arc;
 use holochain_wasmer_host::prelude::WasmError;
 
@@ -7,8 +8,14 @@ use holochain_wasmer_host::prelude::WasmError;
 /// also return who is originated the call (pubkey)
 pub fn capability_info(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
+    call_context: Arc<CallContext>,
     _input: (),
-) -> Result<(), WasmError> {
-    unimplemented!();
+) -> Result<CapabilityInfo, WasmError> {
+    call_context
+        .host_access
+        .capability()
+        .cloned()
+        .ok_or_else(|| {
+            WasmError::Host("capability_info is only available to authorized zome calls".into())
+        })
 }
@@ -0,0 +1,56 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::RibosomeT;
+use holochain_p2p::actor::GetLinksOptions;
+use holochain_state::metadata::LinkMetaKey;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::WasmError;
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+#[allow(clippy::extra_unused_lifetimes)]
+pub fn count_links<'a>(
+    ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: GetLinksInput,
+) -> Result<u32, WasmError> {
+    let GetLinksInput {
+        base_address,
+        tag_prefix,
+        query,
+    } = input;
+
+    // Get zome id
+    let zome_id = ribosome
+        .zome_to_id(&call_context.zome)
+        .expect("Failed to get ID for current zome.");
+
+    // Get the network from the context
+    let network = call_context.host_access.network().clone();
+
+    tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        // Create the key
+        let key = match tag_prefix.as_ref() {
+            Some(tag_prefix) => LinkMetaKey::BaseZomeTag(&base_address, zome_id, tag_prefix),
+            None => LinkMetaKey::BaseZome(&base_address, zome_id),
+        };
+
+        // Count the links on the dht
+        let count = call_context
+            .host_access
+            .workspace()
+            .write()
+            .await
+            .cascade(network)
+            .dht_count_links(
+                &key,
+                GetLinksOptions {
+                    query,
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(|cascade_error| WasmError::Host(cascade_error.to_string()))?;
+
+        Ok(u32::try_from(count).unwrap_or(u32::MAX))
+    })
+}
@@ -272,7 +272,9 @@ pub mod wasm_test {
                 name: "create_multi_test".to_string(),
                 uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
                 properties: SerializedBytes::try_from(()).unwrap(),
+                rate_limits: Vec::new(),
                 zomes: vec![TestWasm::MultipleCalls.into()].into(),
+                redundancy_target: None,
             },
             vec![TestWasm::MultipleCalls.into()],
         )
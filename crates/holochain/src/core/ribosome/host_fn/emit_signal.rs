@@ -11,7 +11,19 @@ pub fn emit_signal(
     input: AppSignal,
 ) -> Result<(), WasmError> {
     let cell_id = call_context.host_access().cell_id().clone();
-    let signal = Signal::App(cell_id, input);
+    // Signals emitted while handling a call from another agent,
+    // e.g. in `recv_remote_signal`, are tagged with that agent
+    let provenance = call_context
+        .host_access()
+        .capability()
+        .map(|info| info.provenance.clone())
+        .filter(|provenance| provenance != cell_id.agent_pubkey());
+    let signal = Signal::App {
+        cell_id,
+        zome_name: call_context.zome().zome_name().clone(),
+        provenance,
+        signal: input,
+    };
     call_context.host_access().signal_tx().send(signal).map_err(|interface_error| WasmError::Host(interface_error.to_string()))?;
     Ok(())
 }
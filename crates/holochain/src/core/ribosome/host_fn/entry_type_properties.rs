@@ -1,12 +1,78 @@
+use crate::core::ribosome::error::RibosomeError;
+use crate::core::ribosome::guest_callback::entry_defs::EntryDefsInvocation;
+use crate::core::ribosome::guest_callback::entry_defs::EntryDefsResult;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
 use std::sync::Arc;
 use holochain_wasmer_host::prelude::WasmError;
 
+/// The properties declared on an entry def of the calling zome.
 pub fn entry_type_properties(
-    _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: (),
-) -> Result<(), WasmError> {
-    unimplemented!();
+    ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: EntryDefId,
+) -> Result<Option<SerializedBytes>, WasmError> {
+    let entry_def = match ribosome
+        .run_entry_defs((&call_context.host_access).into(), EntryDefsInvocation)
+        .map_err(|ribosome_error| WasmError::Host(ribosome_error.to_string()))?
+    {
+        EntryDefsResult::Defs(defs) => {
+            defs.get(call_context.zome.zome_name())
+                .and_then(|entry_defs| {
+                    entry_defs
+                        .entry_def_index_from_id(input.clone())
+                        .map(|index| entry_defs[index.0 as usize].clone())
+                })
+        }
+        _ => None,
+    };
+    match entry_def {
+        Some(entry_def) => Ok(entry_def.properties),
+        None => Err(WasmError::Host(
+            RibosomeError::EntryDefs(
+                call_context.zome.zome_name().clone(),
+                format!("entry def not found for {:?}", input),
+            )
+            .to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod test {
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::prelude::*;
+    use holochain_serialized_bytes::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[derive(Serialize, Deserialize, SerializedBytes, Debug, PartialEq)]
+    struct PostProperties {
+        max_length: u32,
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn invoke_import_entry_type_properties_test() {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let mut workspace =
+            crate::core::workflow::CallZomeWorkspace::new(env.clone().into()).unwrap();
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock;
+        let properties: Option<PostProperties> =
+            crate::call_test_ribosome!(host_access, TestWasm::ZomeInfo, "post_properties", ());
+        assert_eq!(properties, Some(PostProperties { max_length: 140 }));
+
+        // An entry def without properties
+        let properties: Option<PostProperties> =
+            crate::call_test_ribosome!(host_access, TestWasm::ZomeInfo, "comment_properties", ());
+        assert_eq!(properties, None);
+    }
 }
@@ -12,7 +12,7 @@ pub fn get_link_details<'a>(
     call_context: Arc<CallContext>,
     input: GetLinksInput,
 ) -> Result<LinkDetails, WasmError> {
-    let GetLinksInput { base_address, tag_prefix } = input;
+    let GetLinksInput { base_address, tag_prefix, .. } = input;
 
     // Get zome id
     let zome_id = ribosome.zome_to_id(&call_context.zome).expect("Failed to get ID for current zome.");
@@ -12,7 +12,7 @@ pub fn get_links<'a>(
     call_context: Arc<CallContext>,
     input: GetLinksInput,
 ) -> Result<Links, WasmError> {
-    let GetLinksInput { base_address, tag_prefix } = input;
+    let GetLinksInput { base_address, tag_prefix, query } = input;
 
     // Get zome id
     let zome_id = ribosome.zome_to_id(&call_context.zome).expect("Failed to get ID for current zome.");
@@ -34,7 +34,7 @@ pub fn get_links<'a>(
             .write()
             .await
             .cascade(network)
-            .dht_get_links(&key, GetLinksOptions::default())
+            .dht_get_links(&key, GetLinksOptions { query, ..Default::default() })
             .await
             .map_err(|cascade_error| WasmError::Host(cascade_error.to_string()))?;
 
@@ -1,12 +1,52 @@
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
 use std::sync::Arc;
 use holochain_wasmer_host::prelude::WasmError;
 
+/// The properties the dna was installed with.
 pub fn property(
-    _ribosome: Arc<impl RibosomeT>,
+    ribosome: Arc<impl RibosomeT>,
     _call_context: Arc<CallContext>,
     _input: (),
-) -> Result<(), WasmError> {
-    unimplemented!();
+) -> Result<SerializedBytes, WasmError> {
+    Ok(ribosome.dna_def().properties.clone())
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod test {
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::prelude::*;
+    use holochain_serialized_bytes::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[derive(Serialize, Deserialize, SerializedBytes, Debug, PartialEq)]
+    struct DnaProperties {
+        p: String,
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn invoke_import_property_test() {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let mut workspace =
+            crate::core::workflow::CallZomeWorkspace::new(env.clone().into()).unwrap();
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock;
+        let properties: DnaProperties =
+            crate::call_test_ribosome!(host_access, TestWasm::ZomeInfo, "dna_properties", ());
+        assert_eq!(
+            properties,
+            DnaProperties {
+                p: "hi".to_string()
+            }
+        );
+    }
 }
@@ -10,13 +10,10 @@ pub fn query(
     input: ChainQueryFilter,
 ) -> Result<ElementVec, WasmError> {
     tokio_safe_block_on::tokio_safe_block_forever_on(async move {
-        let elements: Vec<Element> = call_context
-            .host_access
-            .workspace()
-            .write()
-            .await
+        let workspace = call_context.host_access.workspace().read().await;
+        let elements: Vec<Element> = workspace
             .source_chain
-            .query(&input).map_err(|source_chain_error| WasmError::Host(source_chain_error.to_string()))?;
+            .query(&input, &workspace.meta_authored).map_err(|source_chain_error| WasmError::Host(source_chain_error.to_string()))?;
         Ok(ElementVec(elements))
     })
 }
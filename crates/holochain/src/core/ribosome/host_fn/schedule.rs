@@ -1,12 +1,80 @@
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
-use std::sync::Arc;
+use crate::core::workflow::call_zome_workflow::CallZomeWorkspace;
+use holochain_state::scheduler::ScheduledFn;
+use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::WasmError;
+use std::sync::Arc;
 
+/// Schedule a function in the calling zome to be called later by the scheduler.
+/// The schedule is written to the workspace so it is only persisted if the
+/// zome call succeeds.
 pub fn schedule(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: core::time::Duration,
+    call_context: Arc<CallContext>,
+    input: ScheduleInput,
 ) -> Result<(), WasmError> {
-    unimplemented!()
+    let zome_name: ZomeName = call_context.zome().into();
+    let scheduled_fn = ScheduledFn::new(zome_name, input, timestamp::now())
+        .map_err(|timestamp_error| WasmError::Host(timestamp_error.to_string()))?;
+    tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        let mut guard = call_context.host_access.workspace().write().await;
+        let workspace: &mut CallZomeWorkspace = &mut guard;
+        workspace
+            .scheduled_fns
+            .schedule(scheduled_fn)
+            .map_err(|database_error| WasmError::Host(database_error.to_string()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::sweetest::SweetConductor;
+    use crate::test_utils::sweetest::SweetDnaFile;
+    use hdk3::prelude::*;
+    use holochain_types::dna::zome::inline_zome::InlineZome;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    fn zome(num_ticks: Arc<AtomicUsize>) -> InlineZome {
+        InlineZome::new_unique(vec![])
+            .callback("start", move |api, ()| {
+                api.schedule(ScheduleInput::new(
+                    "tick".into(),
+                    ExternIO::encode(())?,
+                    std::time::Duration::from_millis(10),
+                    Some(std::time::Duration::from_millis(10)),
+                ))?;
+                Ok(())
+            })
+            .callback("tick", move |_api, ()| {
+                num_ticks.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    #[cfg(feature = "test_utils")]
+    async fn scheduled_fn_is_called_repeatedly() -> anyhow::Result<()> {
+        observability::test_run().ok();
+        let num_ticks = Arc::new(AtomicUsize::new(0));
+
+        let (dna_file, _) =
+            SweetDnaFile::unique_from_inline_zome("zome1", zome(num_ticks.clone())).await?;
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let cell = conductor
+            .setup_app("app", &[dna_file])
+            .await
+            .into_cells()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        let _: () = conductor.call(&cell.zome("zome1"), "start", ()).await;
+
+        tokio::time::delay_for(std::time::Duration::from_millis(3000)).await;
+        assert!(num_ticks.load(Ordering::SeqCst) >= 2);
+        Ok(())
+    }
 }
@@ -32,12 +32,14 @@ use crate::core::ribosome::host_fn::call_remote::call_remote;
 use crate::core::ribosome::host_fn::capability_claims::capability_claims;
 use crate::core::ribosome::host_fn::capability_grants::capability_grants;
 use crate::core::ribosome::host_fn::capability_info::capability_info;
+use crate::core::ribosome::host_fn::count_links::count_links;
 use crate::core::ribosome::host_fn::create::create;
 use crate::core::ribosome::host_fn::create_link::create_link;
 use crate::core::ribosome::host_fn::create_x25519_keypair::create_x25519_keypair;
 use crate::core::ribosome::host_fn::delete::delete;
 use crate::core::ribosome::host_fn::delete_link::delete_link;
 use crate::core::ribosome::host_fn::emit_signal::emit_signal;
+use crate::core::ribosome::host_fn::entry_type_properties::entry_type_properties;
 use crate::core::ribosome::host_fn::get::get;
 use crate::core::ribosome::host_fn::get_details::get_details;
 use crate::core::ribosome::host_fn::get_link_details::get_link_details;
@@ -238,9 +240,17 @@ impl RealRibosome {
         {
             ns.insert("__zome_info", func!(invoke_host_function!(zome_info)));
             ns.insert("__property", func!(invoke_host_function!(property)));
+            ns.insert(
+                "__entry_type_properties",
+                func!(invoke_host_function!(entry_type_properties)),
+            );
         } else {
             ns.insert("__zome_info", func!(invoke_host_function!(unreachable)));
             ns.insert("__property", func!(invoke_host_function!(unreachable)));
+            ns.insert(
+                "__entry_type_properties",
+                func!(invoke_host_function!(unreachable)),
+            );
         }
 
         if let HostFnAccess {
@@ -303,6 +313,7 @@ impl RealRibosome {
                 "__get_link_details",
                 func!(invoke_host_function!(get_link_details)),
             );
+            ns.insert("__count_links", func!(invoke_host_function!(count_links)));
             ns.insert(
                 "__get_agent_activity",
                 func!(invoke_host_function!(get_agent_activity)),
@@ -316,6 +327,7 @@ impl RealRibosome {
                 "__get_link_details",
                 func!(invoke_host_function!(unreachable)),
             );
+            ns.insert("__count_links", func!(invoke_host_function!(unreachable)));
             ns.insert(
                 "__get_agent_activity",
                 func!(invoke_host_function!(unreachable)),
@@ -459,25 +471,33 @@ impl RibosomeT for RealRibosome {
         host_access: ZomeCallHostAccess,
         invocation: ZomeCallInvocation,
     ) -> RibosomeResult<ZomeCallResponse> {
-        Ok(if invocation.is_authorized(&host_access)? {
-            // make a copy of these for the error handling below
-            let zome_name = invocation.zome.zome_name().clone();
-            let fn_name = invocation.fn_name.clone();
-
-            let guest_output: ExternIO =
-                match self.call_iterator(host_access.into(), invocation).next()? {
-                    Some(result) => result.1,
-                    None => return Err(RibosomeError::ZomeFnNotExists(zome_name, fn_name)),
+        Ok(match invocation.authorized_grant(&host_access)? {
+            Some(grant) => {
+                // make a copy of these for the error handling below
+                let zome_name = invocation.zome.zome_name().clone();
+                let fn_name = invocation.fn_name.clone();
+
+                let host_access = ZomeCallHostAccess {
+                    capability: Some(CapabilityInfo {
+                        provenance: invocation.provenance.clone(),
+                        grant,
+                    }),
+                    ..host_access
                 };
+                let guest_output: ExternIO =
+                    match self.call_iterator(host_access.into(), invocation).next()? {
+                        Some(result) => result.1,
+                        None => return Err(RibosomeError::ZomeFnNotExists(zome_name, fn_name)),
+                    };
 
-            ZomeCallResponse::Ok(guest_output)
-        } else {
-            ZomeCallResponse::Unauthorized(
+                ZomeCallResponse::Ok(guest_output)
+            }
+            None => ZomeCallResponse::Unauthorized(
                 invocation.cell_id.clone(),
                 invocation.zome.zome_name().clone(),
                 invocation.fn_name.clone(),
                 invocation.provenance.clone(),
-            )
+            ),
         })
     }
 
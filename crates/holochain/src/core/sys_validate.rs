@@ -51,10 +51,31 @@ pub async fn verify_header_signature(
 }
 
 /// Verify the author key was valid at the time
-/// of signing with dpki
-/// TODO: This is just a stub until we have dpki.
-pub async fn author_key_is_valid(_author: &AgentPubKey) -> SysValidationResult<bool> {
-    Ok(true)
+/// of signing with dpki.
+/// Keys unknown to dpki, or without a dpki service, are valid.
+/// If dpki fails to answer the header can't be validated yet.
+pub async fn author_key_is_valid(
+    author: &AgentPubKey,
+    timestamp: &Timestamp,
+    conductor_api: &impl CellConductorApiT,
+) -> SysValidationResult<bool> {
+    let key_state = conductor_api
+        .dpki_key_state(author)
+        .await
+        .map_err(|e| ValidationOutcome::DpkiUnavailable(author.clone(), e.to_string()))?;
+    Ok(key_state.is_valid_at(timestamp))
+}
+
+/// Check the header wasn't signed after its author's key was revoked
+pub async fn check_author_key(
+    header: &Header,
+    conductor_api: &impl CellConductorApiT,
+) -> SysValidationResult<()> {
+    if author_key_is_valid(header.author(), &header.timestamp(), conductor_api).await? {
+        Ok(())
+    } else {
+        Err(ValidationOutcome::AuthorKeyRevoked(header.author().clone()).into())
+    }
 }
 
 /// Check that previous header makes sense
@@ -96,10 +117,11 @@ pub async fn check_valid_if_dna(
     })
 }
 
-// TODO: I think this can be removed now as rollbacks are detected when inserting
-// metadata into the metadata buf.
 /// Check if there are other headers at this
-/// sequence number
+/// sequence number.
+/// Forks are not a validation failure of this header on its own.
+/// The agent activity authority records the fork when the metadata
+/// is integrated and issues a warrant against the author.
 pub async fn check_chain_rollback(
     header: &Header,
     workspace: &SysValidationWorkspace,
@@ -133,8 +155,7 @@ pub async fn check_chain_rollback(
     } else {
         let s = tracing::warn_span!("agent_activity");
         let _g = s.enter();
-        // TODO: implement real rollback detection once we know what that looks like
-        tracing::error!(
+        tracing::warn!(
             "Chain rollback detected at position {} for agent {:?} from header {:?}
             There were {} headers at this position",
             header.header_seq(),
@@ -146,9 +167,49 @@ pub async fn check_chain_rollback(
     Ok(())
 }
 
-/// Placeholder for future spam check.
-/// Check header timestamps don't exceed MAX_PUBLISH_FREQUENCY
-pub async fn check_spam(_header: &Header) -> SysValidationResult<()> {
+/// Check the author hasn't exceeded any of the Dna's rate limits with this header.
+/// Counts the author's other headers we hold with timestamps in the window
+/// that ends at this header's timestamp.
+pub fn check_spam(
+    header: &Header,
+    rate_limits: &[RateLimit],
+    workspace: &SysValidationWorkspace,
+) -> SysValidationResult<()> {
+    if rate_limits.is_empty() {
+        return Ok(());
+    }
+    let header_hash = HeaderHash::with_data_sync(header);
+    let timestamp = header.timestamp();
+    let k = ChainItemKey::AgentStatus(header.author().clone(), ValidationStatus::Valid);
+    let env = workspace.meta_vault.env();
+    // Timestamps of the author's other headers that are
+    // valid or potentially valid, up to this header.
+    let timestamps: Vec<Timestamp> = fresh_reader!(env, |r| {
+        let mut timestamps = workspace
+            .meta_vault
+            .get_activity(&r, k.clone())?
+            .chain(workspace.meta_pending.get_activity(&r, k.clone())?)
+            .filter(|thh| Ok(thh.header_hash != header_hash && thh.timestamp <= timestamp))
+            .map(|thh| Ok(thh.timestamp))
+            .collect::<Vec<_>>()?;
+        // The same header can be in both stores
+        timestamps.sort();
+        timestamps.dedup();
+        DatabaseResult::Ok(timestamps)
+    })?;
+    for limit in rate_limits {
+        // If the window goes back before the earliest
+        // possible timestamp then every header is in it
+        let window_start = (timestamp - limit.window()).ok();
+        let in_window = timestamps
+            .iter()
+            .filter(|t| window_start.map_or(true, |start| **t > start))
+            .count();
+        // Include this header
+        if in_window + 1 > limit.max_headers as usize {
+            return Err(ValidationOutcome::RateLimited(limit.clone()).into());
+        }
+    }
     Ok(())
 }
 
@@ -456,7 +517,7 @@ impl IncomingDhtOpSender {
     ) -> SysValidationResult<()> {
         if let Some(op) = make_op(element) {
             let ops = vec![op];
-            incoming_dht_ops_workflow(&self.env, self.sys_validation_trigger, ops, None)
+            incoming_dht_ops_workflow(&self.env, self.sys_validation_trigger, ops, None, false)
                 .await
                 .map_err(Box::new)?;
         }
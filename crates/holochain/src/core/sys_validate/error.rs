@@ -86,10 +86,14 @@ impl<E> TryFrom<OutcomeOrError<ValidationOutcome, E>> for ValidationOutcome {
 /// failed validation.
 #[derive(Error, Debug)]
 pub enum ValidationOutcome {
+    #[error("The author key {0} was revoked before the header was signed")]
+    AuthorKeyRevoked(AgentPubKey),
     #[error("The element with signature {0:?} and header {1:?} was found to be counterfeit")]
     Counterfeit(Signature, Header),
     #[error("The dependency {0:?} was not found on the DHT")]
     DepMissingFromDht(AnyDhtHash),
+    #[error("DPKI couldn't give the state of the author key {0}: {1}")]
+    DpkiUnavailable(AgentPubKey, String),
     #[error("The app entry type {0:?} entry def id was out of range")]
     EntryDefId(AppEntryType),
     #[error("The entry has a different hash to the header's entry hash")]
@@ -112,6 +116,8 @@ pub enum ValidationOutcome {
     PrevHeaderError(#[from] PrevHeaderError),
     #[error("StoreEntry should not be gossiped for private entries")]
     PrivateEntry,
+    #[error("The author exceeded the rate limit of {} headers per {} seconds", .0.max_headers, .0.window_secs)]
+    RateLimited(RateLimit),
     #[error("Update original EntryType: {0:?} doesn't match new EntryType {1:?}")]
     UpdateTypeMismatch(EntryType, EntryType),
     #[error("Signature {0:?} failed to verify for Header {1:?}")]
@@ -276,7 +276,9 @@ async fn check_app_entry_type_test() {
             name: "app_entry_type_test".to_string(),
             uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
             properties: SerializedBytes::try_from(()).unwrap(),
+            rate_limits: Vec::new(),
             zomes: vec![TestWasm::EntryDefs.into()].into(),
+            redundancy_target: None,
         },
         vec![TestWasm::EntryDefs.into()],
     )
@@ -360,3 +362,201 @@ async fn check_entry_not_private_test() {
         ))
     );
 }
+
+#[tokio::test(threaded_scheduler)]
+async fn check_spam_test() {
+    let test_env = test_cell_env();
+    let mut workspace = SysValidationWorkspace::new(test_env.env().into()).unwrap();
+    let author = fake_agent_pubkey_1();
+    let header = |header_seq: u32, secs: i64| {
+        let mut create = fixt!(Create);
+        create.author = author.clone();
+        create.header_seq = header_seq;
+        create.timestamp = Timestamp(secs, 0);
+        Header::Create(create)
+    };
+    for (header_seq, secs) in [(1, 100), (2, 110), (3, 120)].iter() {
+        workspace
+            .meta_vault
+            .register_activity(&header(*header_seq, *secs), ValidationStatus::Valid)
+            .unwrap();
+    }
+    let three_a_minute = RateLimit {
+        max_headers: 3,
+        window_secs: 60,
+    };
+    let five_a_minute = RateLimit {
+        max_headers: 5,
+        window_secs: 60,
+    };
+
+    // No limits
+    assert_matches!(check_spam(&header(4, 125), &[], &workspace), Ok(()));
+
+    // Three headers already in the window
+    assert_matches!(
+        check_spam(&header(4, 125), &[three_a_minute.clone()], &workspace),
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::RateLimited(_)
+        ))
+    );
+    assert_matches!(
+        check_spam(&header(4, 125), &[five_a_minute.clone()], &workspace),
+        Ok(())
+    );
+    // Every limit must be met
+    assert_matches!(
+        check_spam(
+            &header(4, 125),
+            &[five_a_minute, three_a_minute.clone()],
+            &workspace
+        ),
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::RateLimited(_)
+        ))
+    );
+
+    // Only one header is still in the window
+    assert_matches!(
+        check_spam(&header(4, 175), &[three_a_minute.clone()], &workspace),
+        Ok(())
+    );
+
+    // A header that is already held isn't counted twice
+    assert_matches!(
+        check_spam(&header(3, 120), &[three_a_minute], &workspace),
+        Ok(())
+    );
+}
+
+/// Rate limits are part of the DnaDef so they are still
+/// enforced after the conductor reloads its Dnas on restart.
+#[tokio::test(threaded_scheduler)]
+async fn check_spam_after_restart_test() {
+    use crate::conductor::api::CellConductorApi;
+    use crate::conductor::handle::ConductorHandleT;
+    use crate::conductor::ConductorBuilder;
+    use holochain_lmdb::test_utils::test_environments;
+
+    let envs = test_environments();
+    let rate_limits = vec![RateLimit {
+        max_headers: 1,
+        window_secs: 60,
+    }];
+    let dna_file = DnaFile::new(
+        DnaDef {
+            name: "check_spam_after_restart_test".to_string(),
+            uuid: "0d7c8f5e-2b4a-4e3c-9f1d-6a5b8c7e9d21".to_string(),
+            properties: SerializedBytes::try_from(()).unwrap(),
+            rate_limits: rate_limits.clone(),
+            zomes: vec![TestWasm::Foo.into()].into(),
+            redundancy_target: None,
+        },
+        vec![TestWasm::Foo.into()],
+    )
+    .await
+    .unwrap();
+    let author = fake_agent_pubkey_1();
+    let cell_id = CellId::new(dna_file.dna_hash().clone(), author.clone());
+
+    let handle = ConductorBuilder::new().test(&envs).await.unwrap();
+    crate::test_utils::install_app(
+        "app",
+        vec![(InstalledCell::new(cell_id.clone(), "handle".into()), None)],
+        vec![dna_file],
+        handle.clone(),
+    )
+    .await;
+    handle.shutdown().await;
+
+    // Restart on the same environments
+    let handle = ConductorBuilder::new().test(&envs).await.unwrap();
+    let errors = handle.setup_cells().await.unwrap();
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    // Sys validation gets the limits from the reloaded Dna
+    let dna_file = CellConductorApi::new(handle.clone(), cell_id)
+        .get_this_dna()
+        .await
+        .unwrap();
+    assert_eq!(dna_file.dna_def().rate_limits, rate_limits);
+
+    let test_env = test_cell_env();
+    let mut workspace = SysValidationWorkspace::new(test_env.env().into()).unwrap();
+    let header = |header_seq: u32, secs: i64| {
+        let mut create = fixt!(Create);
+        create.author = author.clone();
+        create.header_seq = header_seq;
+        create.timestamp = Timestamp(secs, 0);
+        Header::Create(create)
+    };
+    workspace
+        .meta_vault
+        .register_activity(&header(1, 100), ValidationStatus::Valid)
+        .unwrap();
+    assert_matches!(
+        check_spam(&header(2, 110), &dna_file.dna_def().rate_limits, &workspace),
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::RateLimited(_)
+        ))
+    );
+
+    handle.shutdown().await;
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn check_author_key_test() {
+    let revoked = fake_agent_pubkey_1();
+    let header = |author: &AgentPubKey, secs: i64| {
+        let mut create = fixt!(Create);
+        create.author = author.clone();
+        create.timestamp = Timestamp(secs, 0);
+        Header::Create(create)
+    };
+    let mut conductor_api = MockCellConductorApi::new();
+    conductor_api
+        .expect_sync_dpki_key_state()
+        .returning(move |key| {
+            if *key == fake_agent_pubkey_1() {
+                Ok(KeyState::Revoked(Timestamp(100, 0)))
+            } else {
+                Ok(KeyState::NotFound)
+            }
+        });
+
+    // Signed before the key was revoked
+    assert_matches!(
+        check_author_key(&header(&revoked, 50), &conductor_api).await,
+        Ok(())
+    );
+    // Signed after the key was revoked
+    assert_matches!(
+        check_author_key(&header(&revoked, 150), &conductor_api).await,
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::AuthorKeyRevoked(_)
+        ))
+    );
+    assert_matches!(
+        check_author_key(&header(&revoked, 100), &conductor_api).await,
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::AuthorKeyRevoked(_)
+        ))
+    );
+    // Keys dpki doesn't know about are valid
+    assert_matches!(
+        check_author_key(&header(&fake_agent_pubkey_2(), 150), &conductor_api).await,
+        Ok(())
+    );
+
+    // Dpki failing means the header can't be validated yet
+    conductor_api.checkpoint();
+    conductor_api
+        .expect_sync_dpki_key_state()
+        .returning(|_| Err(ConductorApiError::DpkiError("offline".into())));
+    assert_matches!(
+        check_author_key(&header(&revoked, 50), &conductor_api).await,
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::DpkiUnavailable(_, _)
+        ))
+    );
+}
@@ -26,11 +26,17 @@ pub mod error;
 pub mod app_validation_workflow;
 pub mod call_zome_workflow;
 pub mod genesis_workflow;
+pub mod health_check_workflow;
 pub mod incoming_dht_ops_workflow;
+pub mod incoming_validation_receipt_workflow;
+pub mod incoming_warrant_workflow;
 pub mod initialize_zomes_workflow;
 pub mod integrate_dht_ops_workflow;
+pub mod migrate_agent_workflow;
 pub mod produce_dht_ops_workflow;
 pub mod publish_dht_ops_workflow;
+pub mod scheduler_workflow;
+pub mod slow_heal_workflow;
 pub mod sys_validation_workflow;
 
 // TODO: either remove wildcards or add wildcards for all above child modules
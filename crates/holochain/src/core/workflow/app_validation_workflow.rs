@@ -75,14 +75,18 @@ pub async fn app_validation_workflow(
     writer: OneshotWriter,
     trigger_integration: &mut TriggerSender,
     conductor_api: impl CellConductorApiT,
-    network: HolochainP2pCell,
+    mut network: HolochainP2pCell,
 ) -> WorkflowResult<WorkComplete> {
-    let complete = app_validation_workflow_inner(&mut workspace, conductor_api, &network).await?;
+    let (complete, receipts) =
+        app_validation_workflow_inner(&mut workspace, &conductor_api, &network).await?;
     // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
 
     // commit the workspace
     writer.with_writer(|writer| Ok(workspace.flush_to_txn(writer)?))?;
 
+    // send receipts for the ops that are now committed as valid
+    send_validation_receipts(receipts, &conductor_api, &mut network).await;
+
     // trigger other workflows
     trigger_integration.trigger();
 
@@ -90,9 +94,9 @@ pub async fn app_validation_workflow(
 }
 async fn app_validation_workflow_inner(
     workspace: &mut AppValidationWorkspace,
-    conductor_api: impl CellConductorApiT,
+    conductor_api: &impl CellConductorApiT,
     network: &HolochainP2pCell,
-) -> WorkflowResult<WorkComplete> {
+) -> WorkflowResult<(WorkComplete, Vec<(DhtOpHash, AgentPubKey)>)> {
     let env = workspace.validation_limbo.env().clone();
 
     // Drain the ops into a sorted binary heap
@@ -132,6 +136,9 @@ async fn app_validation_workflow_inner(
         sorted_ops
     })?;
 
+    // The ops that need a validation receipt sent to their author
+    let mut receipts = Vec::new();
+
     // Validate all the ops
     for so in sorted_ops.into_sorted_vec() {
         let OrderedOp {
@@ -147,7 +154,7 @@ async fn app_validation_workflow_inner(
                 let outcome = validate_op(
                     op.clone(),
                     vlv.from_agent.clone(),
-                    &conductor_api,
+                    conductor_api,
                     workspace,
                     &network,
                 )
@@ -157,12 +164,24 @@ async fn app_validation_workflow_inner(
 
                 match outcome {
                     Outcome::Accepted => {
+                        if vlv.send_receipt {
+                            receipts.push((hash.clone(), op.header().author().clone()));
+                        }
                         let iv = IntegrationLimboValue {
                             validation_status: ValidationStatus::Valid,
                             op: vlv.op,
                         };
                         workspace.put_int_limbo(hash, iv, op)?;
                     }
+                    Outcome::AwaitingDeps(_) if vlv.should_abandon() => {
+                        // Give up for now, the slow heal process will retry it
+                        debug!(?hash, num_tries = vlv.num_tries, "Abandoning op");
+                        let iv = IntegrationLimboValue {
+                            op: vlv.op,
+                            validation_status: ValidationStatus::Abandoned,
+                        };
+                        workspace.put_int_limbo(hash, iv, op)?;
+                    }
                     Outcome::AwaitingDeps(deps) => {
                         vlv.status = ValidationLimboStatus::AwaitingAppDeps(deps);
                         workspace.put_val_limbo(hash, vlv)?;
@@ -179,7 +198,39 @@ async fn app_validation_workflow_inner(
             _ => unreachable!("Should not contain any other status"),
         }
     }
-    Ok(WorkComplete::Complete)
+    Ok((WorkComplete::Complete, receipts))
+}
+
+/// Sign a validation receipt for each op and send it to the op's author.
+/// Failing to send a receipt is not an error, the author will just
+/// publish the op again.
+pub(crate) async fn send_validation_receipts(
+    receipts: Vec<(DhtOpHash, AgentPubKey)>,
+    conductor_api: &impl CellConductorApiT,
+    network: &mut HolochainP2pCell,
+) {
+    let validator = conductor_api.cell_id().agent_pubkey().clone();
+    for (dht_op_hash, author) in receipts {
+        // Don't send receipts to ourselves
+        if author == validator {
+            continue;
+        }
+        let receipt = ValidationReceipt {
+            dht_op_hash: dht_op_hash.clone(),
+            validation_result: ValidationResult::Valid,
+            validator: validator.clone(),
+        };
+        let result: WorkflowResult<()> = async {
+            let receipt: SerializedBytes =
+                receipt.sign(conductor_api.keystore()).await?.try_into()?;
+            network.send_validation_receipt(author, receipt).await?;
+            Ok(())
+        }
+        .await;
+        if let Err(e) = result {
+            warn!(msg = "Failed to send validation receipt", ?dht_op_hash, ?e);
+        }
+    }
 }
 
 fn to_single_zome(zomes_to_invoke: ZomesToInvoke) -> AppValidationResult<Zome> {
@@ -578,11 +629,16 @@ async fn get_validation_package_local(
                 header_seq,
                 app_entry_type,
                 &lock.source_chain,
+                &lock.meta_authored,
             )?))
         }
         RequiredValidationType::Full => {
             let lock = workspace_lock.write().await;
-            Ok(Some(get_as_author_full(header_seq, &lock.source_chain)?))
+            Ok(Some(get_as_author_full(
+                header_seq,
+                &lock.source_chain,
+                &lock.meta_authored,
+            )?))
         }
         RequiredValidationType::Custom => {
             {
@@ -914,6 +970,8 @@ pub struct AppValidationWorkspace {
     // Cached data
     pub element_cache: ElementBuf,
     pub meta_cache: MetadataBuf,
+    // Warrants received with agent activity
+    pub warrants: WarrantStore,
     pub call_zome_workspace_lock: Option<CallZomeWorkspaceLock>,
 }
 
@@ -930,6 +988,7 @@ impl AppValidationWorkspace {
         let meta_vault = MetadataBuf::vault(env.clone())?;
         let element_cache = ElementBuf::cache(env.clone())?;
         let meta_cache = MetadataBuf::cache(env.clone())?;
+        let warrants = WarrantStore::new(env.clone())?;
 
         let element_pending = ElementBuf::pending(env.clone())?;
         let meta_pending = MetadataBuf::pending(env.clone())?;
@@ -961,6 +1020,7 @@ impl AppValidationWorkspace {
             meta_rejected,
             element_cache,
             meta_cache,
+            warrants,
             call_zome_workspace_lock,
         })
     }
@@ -1024,6 +1084,7 @@ impl AppValidationWorkspace {
             .with_pending(pending_data)
             .with_cache(cache_data)
             .with_rejected(rejected_data)
+            .with_warrants(&mut self.warrants)
             .with_network(network)
     }
 }
@@ -1038,6 +1099,7 @@ impl Workspace for AppValidationWorkspace {
         // Flush for cascade
         self.element_cache.flush_to_txn_ref(writer)?;
         self.meta_cache.flush_to_txn_ref(writer)?;
+        self.warrants.flush_to_txn_ref(writer)?;
 
         // Need to flush the call zome workspace because of the cache.
         // TODO: If cache becomes a separate env then remove this
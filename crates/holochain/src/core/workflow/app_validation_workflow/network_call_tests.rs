@@ -230,6 +230,7 @@ async fn get_agent_activity_test() {
             status,
             highest_observed,
             agent: alice_agent_id.clone(),
+            warrants: Vec::new(),
         }
     };
 
@@ -272,6 +273,7 @@ async fn get_agent_activity_test() {
             rejected_activity,
             status: activity.status,
             highest_observed: activity.highest_observed,
+            warrants: activity.warrants,
         };
         activity
     };
@@ -289,7 +291,7 @@ async fn get_agent_activity_test() {
     )
     .await;
 
-    let agent_activity = alice_call_data
+    let mut agent_activity = alice_call_data
         .network
         .get_agent_activity(
             alice_agent_id.clone(),
@@ -305,6 +307,36 @@ async fn get_agent_activity_test() {
         .unwrap();
     assert_eq!(agent_activity.len(), 1);
 
+    // The authority returns the full signed headers
+    assert_eq!(agent_activity.pop().unwrap(), get_expected_full());
+
+    // A sequence range pages through the full headers
+    let mut agent_activity = alice_call_data
+        .network
+        .get_agent_activity(
+            alice_agent_id.clone(),
+            ChainQueryFilter::new().sequence_range(2..5),
+            GetActivityOptions {
+                include_full_headers: true,
+                timeout_ms: Some(GET_AGENT_ACTIVITY_TIMEOUT_MS),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    let agent_activity = agent_activity
+        .pop()
+        .expect("Failed to get any activity from alice");
+    let mut expected_activity = get_expected_full();
+    let valid_activity =
+        unwrap_to::unwrap_to!(expected_activity.valid_activity => ChainItems::Full)
+            .clone()
+            .into_iter()
+            .filter(|shh| (2..5).contains(&shh.header().header_seq()))
+            .collect();
+    expected_activity.valid_activity = ChainItems::Full(valid_activity);
+    assert_eq!(agent_activity, expected_activity);
+
     let mut agent_activity = alice_call_data
         .network
         .get_agent_activity(
@@ -36,12 +36,14 @@ async fn app_validation_workflow_test() {
             name: "app_validation_workflow_test".to_string(),
             uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
             properties: SerializedBytes::try_from(()).unwrap(),
+            rate_limits: Vec::new(),
             zomes: vec![
                 TestWasm::Validate.into(),
                 TestWasm::ValidateLink.into(),
                 TestWasm::Create.into(),
             ]
             .into(),
+            redundancy_target: None,
         },
         vec![
             TestWasm::Validate.into(),
@@ -1,3 +1,4 @@
+use holochain_lmdb::prelude::AuthoredPrefix;
 use holochain_p2p::HolochainP2pCell;
 use holochain_types::prelude::*;
 use holochain_zome_types::HeaderHashed;
@@ -9,6 +10,7 @@ use crate::core::ribosome::guest_callback::validation_package::ValidationPackage
 use crate::core::ribosome::RibosomeT;
 use crate::core::workflow::CallZomeWorkspaceLock;
 use crate::core::SourceChainResult;
+use holochain_state::metadata::MetadataBufT;
 use holochain_state::source_chain::SourceChain;
 use tracing::*;
 
@@ -16,6 +18,7 @@ pub fn get_as_author_sub_chain(
     header_seq: u32,
     app_entry_type: AppEntryType,
     source_chain: &SourceChain,
+    meta_authored: &impl MetadataBufT<AuthoredPrefix>,
 ) -> SourceChainResult<ValidationPackage> {
     // Collect and return the sub chain
     let elements = source_chain.query(
@@ -23,6 +26,7 @@ pub fn get_as_author_sub_chain(
             .include_entries(true)
             .entry_type(EntryType::App(app_entry_type))
             .sequence_range(0..header_seq),
+        meta_authored,
     )?;
     Ok(ValidationPackage::new(elements))
 }
@@ -30,11 +34,13 @@ pub fn get_as_author_sub_chain(
 pub fn get_as_author_full(
     header_seq: u32,
     source_chain: &SourceChain,
+    meta_authored: &impl MetadataBufT<AuthoredPrefix>,
 ) -> SourceChainResult<ValidationPackage> {
     let elements = source_chain.query(
         &ChainQueryFilter::default()
             .include_entries(true)
             .sequence_range(0..header_seq),
+        meta_authored,
     )?;
     Ok(ValidationPackage::new(elements))
 }
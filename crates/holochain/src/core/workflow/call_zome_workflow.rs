@@ -7,6 +7,9 @@ use crate::core::queue_consumer::OneshotWriter;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::ribosome::error::RibosomeError;
 use crate::core::ribosome::error::RibosomeResult;
+use crate::core::ribosome::guest_callback::post_commit::PostCommitHostAccess;
+use crate::core::ribosome::guest_callback::post_commit::PostCommitInvocation;
+use crate::core::ribosome::guest_callback::post_commit::PostCommitResult;
 use crate::core::ribosome::RibosomeT;
 use crate::core::ribosome::ZomeCallHostAccess;
 use crate::core::ribosome::ZomeCallInvocation;
@@ -21,8 +24,10 @@ use holochain_p2p::HolochainP2pCell;
 use holochain_state::element_buf::ElementBuf;
 use holochain_state::metadata::MetadataBuf;
 use holochain_state::metadata::MetadataBufT;
+use holochain_state::scheduler::ScheduledFnStore;
 use holochain_state::source_chain::SourceChain;
 use holochain_state::source_chain::SourceChainError;
+use holochain_state::warrants::WarrantStore;
 use holochain_state::workspace::Workspace;
 use holochain_state::workspace::WorkspaceResult;
 use holochain_zome_types::element::Element;
@@ -33,6 +38,8 @@ use tracing::instrument;
 
 pub mod call_zome_workspace_lock;
 
+#[cfg(test)]
+mod post_commit_test;
 #[cfg(test)]
 mod validation_test;
 
@@ -52,32 +59,69 @@ pub struct CallZomeWorkflowArgs<Ribosome: RibosomeT + Send, C: CellConductorApiT
     workspace_lock,
     network,
     keystore,
-    writer,
+    env,
     args,
     trigger_produce_dht_ops
 ))]
 pub async fn call_zome_workflow<
     'env,
     Ribosome: RibosomeT + Send + 'static,
-    C: CellConductorApiT,
+    C: CellConductorApiT + 'static,
 >(
     workspace_lock: CallZomeWorkspaceLock,
     network: HolochainP2pCell,
     keystore: KeystoreSender,
-    writer: OneshotWriter,
+    env: EnvironmentWrite,
     args: CallZomeWorkflowArgs<Ribosome, C>,
     mut trigger_produce_dht_ops: TriggerSender,
 ) -> WorkflowResult<ZomeCallResult> {
     let should_write = args.is_root_zome_call;
-    let result = call_zome_workflow_inner(workspace_lock.clone(), network, keystore, args).await?;
+    let zome = args.invocation.zome.clone();
+    let cell_id = args.invocation.cell_id.clone();
+    let signal_tx = args.signal_tx.clone();
+    let conductor_api = args.conductor_api.clone();
+    let chain_head_start_len = workspace_lock.read().await.source_chain.len();
+    let (ribosome, result) = call_zome_workflow_inner(
+        workspace_lock.clone(),
+        network.clone(),
+        keystore.clone(),
+        args,
+    )
+    .await?;
 
     // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
 
     // commit the workspace
     if should_write {
-        let mut guard = workspace_lock.write().await;
-        let workspace = &mut guard;
-        writer.with_writer(|writer| Ok(workspace.flush_to_txn_ref(writer)?))?;
+        let committed_headers = {
+            let mut guard = workspace_lock.write().await;
+            let workspace = &mut guard;
+            let mut headers = Vec::new();
+            let mut i = chain_head_start_len;
+            while let Some(element) = workspace.source_chain.get_at_index(i as u32)? {
+                headers.push(element.header_address().clone());
+                i += 1;
+            }
+            OneshotWriter::new(env.clone())
+                .with_writer(|writer| Ok(workspace.flush_to_txn_ref(writer)?))?;
+            headers
+        };
+
+        if matches!(result, Ok(ZomeCallResponse::Ok(_))) && !committed_headers.is_empty() {
+            // Don't hold up the response
+            tokio::spawn(post_commit_workflow(
+                env,
+                network,
+                keystore,
+                signal_tx,
+                conductor_api,
+                ribosome,
+                zome,
+                cell_id,
+                committed_headers,
+                trigger_produce_dht_ops.clone(),
+            ));
+        }
     }
 
     trigger_produce_dht_ops.trigger();
@@ -85,6 +129,104 @@ pub async fn call_zome_workflow<
     Ok(result)
 }
 
+/// Run the `post_commit` callback of the called zome with the headers
+/// a root zome call committed, once they have been flushed and the
+/// call has returned.
+/// The callback gets its own workspace, which is flushed separately, and
+/// anything it commits doesn't run `post_commit` again.
+/// Its commits are sys and app validated like any zome call's commits
+/// and none of them are kept if one is invalid.
+/// Failures are logged and sent as a [SystemSignal] but never undo the commit.
+#[allow(clippy::too_many_arguments)]
+async fn post_commit_workflow<Ribosome: RibosomeT + Send + 'static, C: CellConductorApiT>(
+    env: EnvironmentWrite,
+    network: HolochainP2pCell,
+    keystore: KeystoreSender,
+    mut signal_tx: SignalBroadcaster,
+    conductor_api: C,
+    ribosome: Ribosome,
+    zome: Zome,
+    cell_id: CellId,
+    headers: Vec<HeaderHash>,
+    mut trigger_produce_dht_ops: TriggerSender,
+) {
+    let zome_name = zome.zome_name().clone();
+    let result = post_commit_workflow_inner(
+        env,
+        network,
+        keystore,
+        signal_tx.clone(),
+        conductor_api,
+        ribosome,
+        zome,
+        cell_id.clone(),
+        headers.clone(),
+    )
+    .await;
+    if result.is_ok() {
+        trigger_produce_dht_ops.trigger();
+    }
+    let reason = match result {
+        Ok(PostCommitResult::Success) => return,
+        Ok(PostCommitResult::Fail(_, reason)) => reason,
+        Err(e) => e.to_string(),
+    };
+    tracing::error!(?cell_id, %zome_name, %reason, "post_commit failed");
+    let signal = SystemSignal::PostCommitFailed {
+        cell_id,
+        zome_name,
+        headers,
+        reason,
+    };
+    // Nobody may be listening for signals
+    signal_tx.send(signal.into()).ok();
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn post_commit_workflow_inner<Ribosome: RibosomeT + Send + 'static, C: CellConductorApiT>(
+    env: EnvironmentWrite,
+    network: HolochainP2pCell,
+    keystore: KeystoreSender,
+    signal_tx: SignalBroadcaster,
+    conductor_api: C,
+    ribosome: Ribosome,
+    zome: Zome,
+    cell_id: CellId,
+    headers: Vec<HeaderHash>,
+) -> WorkflowResult<PostCommitResult> {
+    let workspace_lock = CallZomeWorkspaceLock::new(CallZomeWorkspace::new(env.clone().into())?);
+    let chain_head_start_len = workspace_lock.read().await.source_chain.len();
+    let host_access = PostCommitHostAccess::new(
+        workspace_lock.clone(),
+        keystore,
+        network.clone(),
+        signal_tx,
+        cell_id,
+    );
+    let invocation = PostCommitInvocation::new(zome.clone(), headers.into());
+    let (ribosome, result) = tokio::task::spawn_blocking(move || {
+        let result = ribosome.run_post_commit(host_access, invocation);
+        (ribosome, result)
+    })
+    .await?;
+    let result = result?;
+
+    validate_new_elements(
+        &workspace_lock,
+        chain_head_start_len,
+        &zome,
+        &ribosome,
+        &network,
+        &conductor_api,
+    )
+    .await?;
+
+    let mut guard = workspace_lock.write().await;
+    let workspace = &mut guard;
+    OneshotWriter::new(env).with_writer(|writer| Ok(workspace.flush_to_txn_ref(writer)?))?;
+    Ok(result)
+}
+
 async fn call_zome_workflow_inner<
     'env,
     Ribosome: RibosomeT + Send + 'static,
@@ -94,7 +236,7 @@ async fn call_zome_workflow_inner<
     network: HolochainP2pCell,
     keystore: KeystoreSender,
     args: CallZomeWorkflowArgs<Ribosome, C>,
-) -> WorkflowResult<ZomeCallResult> {
+) -> WorkflowResult<(Ribosome, ZomeCallResult)> {
     let CallZomeWorkflowArgs {
         ribosome,
         invocation,
@@ -130,6 +272,30 @@ async fn call_zome_workflow_inner<
     .await?;
     tracing::trace!(line = line!());
 
+    validate_new_elements(
+        &workspace_lock,
+        chain_head_start_len,
+        &zome,
+        &ribosome,
+        &network,
+        &conductor_api,
+    )
+    .await?;
+
+    Ok((ribosome, result))
+}
+
+/// Sys and app validate the elements committed to the source chain
+/// since it was `chain_head_start_len` long.
+/// Any invalid element fails the whole commit.
+async fn validate_new_elements<Ribosome: RibosomeT + Send + 'static, C: CellConductorApiT>(
+    workspace_lock: &CallZomeWorkspaceLock,
+    chain_head_start_len: usize,
+    zome: &Zome,
+    ribosome: &Ribosome,
+    network: &HolochainP2pCell,
+    conductor_api: &C,
+) -> WorkflowResult<()> {
     let to_app_validate = {
         let mut workspace = workspace_lock.write().await;
         // Get the new head
@@ -144,7 +310,7 @@ async fn call_zome_workflow_inner<
             // Loop forwards through all the new elements
             let mut i = chain_head_start_len;
             while let Some(element) = workspace.source_chain.get_at_index(i as u32)? {
-                sys_validate_element(&element, &mut workspace, network.clone(), &conductor_api)
+                sys_validate_element(&element, &mut workspace, network.clone(), conductor_api)
                     .await
                     // If the was en error exit
                     // If the validation failed, exit with an InvalidCommit
@@ -200,7 +366,7 @@ async fn call_zome_workflow_inner<
                             link_add,
                             base,
                             target,
-                            &ribosome,
+                            ribosome,
                             workspace_lock.clone(),
                             network.clone(),
                         )?,
@@ -210,7 +376,7 @@ async fn call_zome_workflow_inner<
                     app_validation_workflow::run_delete_link_validation_callback(
                         zome.clone(),
                         delete_link.clone(),
-                        &ribosome,
+                        ribosome,
                         workspace_lock.clone(),
                         network.clone(),
                     )?,
@@ -219,10 +385,10 @@ async fn call_zome_workflow_inner<
                     app_validation_workflow::run_validation_callback_direct(
                         zome.clone(),
                         chain_element,
-                        &ribosome,
+                        ribosome,
                         workspace_lock.clone(),
                         network.clone(),
-                        &conductor_api,
+                        conductor_api,
                     )
                     .await?,
                 ),
@@ -257,7 +423,7 @@ async fn call_zome_workflow_inner<
         }
     }
 
-    Ok(result)
+    Ok(())
 }
 
 pub struct CallZomeWorkspace {
@@ -269,6 +435,8 @@ pub struct CallZomeWorkspace {
     pub meta_rejected: MetadataBuf<RejectedPrefix>,
     pub element_cache: ElementBuf,
     pub meta_cache: MetadataBuf,
+    pub scheduled_fns: ScheduledFnStore,
+    pub warrants: WarrantStore,
 }
 
 impl<'a> CallZomeWorkspace {
@@ -280,7 +448,9 @@ impl<'a> CallZomeWorkspace {
         let element_rejected = ElementBuf::rejected(env.clone())?;
         let meta_rejected = MetadataBuf::rejected(env.clone())?;
         let element_cache = ElementBuf::cache(env.clone())?;
-        let meta_cache = MetadataBuf::cache(env)?;
+        let meta_cache = MetadataBuf::cache(env.clone())?;
+        let scheduled_fns = ScheduledFnStore::new(env.clone())?;
+        let warrants = WarrantStore::new(env)?;
 
         Ok(CallZomeWorkspace {
             source_chain,
@@ -291,6 +461,8 @@ impl<'a> CallZomeWorkspace {
             meta_rejected,
             element_cache,
             meta_cache,
+            scheduled_fns,
+            warrants,
         })
     }
 
@@ -307,6 +479,7 @@ impl<'a> CallZomeWorkspace {
             &mut self.meta_cache,
             network,
         )
+        .with_warrants(&mut self.warrants)
     }
 
     /// Cascade without a network connection
@@ -318,6 +491,7 @@ impl<'a> CallZomeWorkspace {
             .with_authored(authored_data)
             .with_cache(cache_data)
             .with_integrated(integrated_data)
+            .with_warrants(&mut self.warrants)
     }
 
     pub fn env(&self) -> &EnvironmentRead {
@@ -331,6 +505,8 @@ impl Workspace for CallZomeWorkspace {
         self.meta_authored.flush_to_txn_ref(writer)?;
         self.element_cache.flush_to_txn_ref(writer)?;
         self.meta_cache.flush_to_txn_ref(writer)?;
+        self.scheduled_fns.flush_to_txn_ref(writer)?;
+        self.warrants.flush_to_txn_ref(writer)?;
         Ok(())
     }
 }
@@ -379,7 +555,9 @@ pub mod tests {
             conductor_api,
             is_root_zome_call: true,
         };
-        call_zome_workflow_inner(workspace.into(), network, keystore, args).await
+        call_zome_workflow_inner(workspace.into(), network, keystore, args)
+            .await
+            .map(|(_, result)| result)
     }
 
     // 1.  Check if there is a Capability token secret in the parameters.
@@ -0,0 +1,164 @@
+use crate::test_utils::sweetest::SweetConductor;
+use crate::test_utils::sweetest::SweetDnaFile;
+use crate::test_utils::sweetest::SweetZome;
+use hdk3::prelude::*;
+use holochain_types::dna::zome::inline_zome::InlineZome;
+use holochain_types::signal::Signal;
+use holochain_types::signal::SystemSignal;
+use matches::assert_matches;
+use tokio::stream::StreamExt;
+
+/// What the zome's `post_commit` callback does
+#[derive(Clone, Copy)]
+enum PostCommit {
+    /// Signal the headers it was given and commit a follow-up entry
+    Commit,
+    /// Commit an entry that fails validation
+    CommitInvalid,
+    /// Fail
+    Fail,
+}
+
+/// A zome with a "unit" entry, which is valid, and an "invalid" entry,
+/// which never is, that runs `post_commit` as given.
+fn zome(post_commit: PostCommit) -> InlineZome {
+    let unit_entry_def = EntryDef::default_with_id("unit");
+    let invalid_entry_def = EntryDef::default_with_id("invalid");
+    let entry_def_id: EntryDefId = unit_entry_def.id.clone();
+    let post_commit_entry_def_id = match post_commit {
+        PostCommit::CommitInvalid => invalid_entry_def.id.clone(),
+        _ => entry_def_id.clone(),
+    };
+
+    InlineZome::new_unique(vec![unit_entry_def, invalid_entry_def])
+        .callback("create_unit", move |api, ()| {
+            let entry = Entry::app(().try_into().unwrap()).unwrap();
+            let hash = api.create(EntryWithDefId::new(entry_def_id.clone(), entry))?;
+            Ok(hash)
+        })
+        .callback("post_commit", move |api, headers: HeaderHashes| {
+            if let PostCommit::Fail = post_commit {
+                return Ok(PostCommitCallbackResult::Fail(headers, "nope".into()));
+            }
+            api.emit_signal(AppSignal::new(ExternIO::encode(headers.clone())?))?;
+            let entry = Entry::app(().try_into().unwrap()).unwrap();
+            api.create(EntryWithDefId::new(post_commit_entry_def_id.clone(), entry))?;
+            Ok(PostCommitCallbackResult::Success)
+        })
+        .callback("validate", |_api, data: ValidateData| {
+            match data.element.header().entry_type() {
+                Some(EntryType::App(app_entry_type)) if app_entry_type.id() == EntryDefIndex(1) => {
+                    Ok(ValidateCallbackResult::Invalid("invalid entry".to_string()))
+                }
+                _ => Ok(ValidateCallbackResult::Valid),
+            }
+        })
+        .callback("chain_len", |api, ()| {
+            Ok(api.query(ChainQueryFilter::new())?.0.len())
+        })
+}
+
+/// post_commit runs after the call returns, so wait for its commits
+async fn wait_for_chain_len(conductor: &SweetConductor, zome: &SweetZome, expected: usize) {
+    for _ in 0..50 {
+        let len: usize = conductor.call(zome, "chain_len", ()).await;
+        if len == expected {
+            return;
+        }
+        tokio::time::delay_for(std::time::Duration::from_millis(100)).await;
+    }
+    let len: usize = conductor.call(zome, "chain_len", ()).await;
+    assert_eq!(len, expected);
+}
+
+#[tokio::test(threaded_scheduler)]
+#[cfg(feature = "test_utils")]
+async fn post_commit_runs_after_zome_call() -> anyhow::Result<()> {
+    observability::test_run().ok();
+
+    let (dna_file, _) =
+        SweetDnaFile::unique_from_inline_zome("zome1", zome(PostCommit::Commit)).await?;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", &[dna_file]).await;
+    let zome = &app.cells()[0].zome("zome1");
+    let mut signals = conductor.signals().await;
+
+    let len_before: usize = conductor.call(zome, "chain_len", ()).await;
+    let hash: HeaderHash = conductor.call(zome, "create_unit", ()).await;
+
+    // post_commit saw exactly the header committed by the call
+    let signal = signals.next().await.unwrap();
+    let expected = AppSignal::new(ExternIO::encode(HeaderHashes::from(vec![hash])).unwrap());
+    assert_matches!(signal, Signal::App { signal, .. } if signal == expected);
+
+    // The entry committed in post_commit was flushed too
+    wait_for_chain_len(&conductor, zome, len_before + 2).await;
+
+    Ok(())
+}
+
+#[tokio::test(threaded_scheduler)]
+#[cfg(feature = "test_utils")]
+async fn post_commit_failure_is_signalled_without_rollback() -> anyhow::Result<()> {
+    observability::test_run().ok();
+
+    let (dna_file, _) =
+        SweetDnaFile::unique_from_inline_zome("zome1", zome(PostCommit::Fail)).await?;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", &[dna_file]).await;
+    let cell_id = app.cells()[0].cell_id().clone();
+    let zome = &app.cells()[0].zome("zome1");
+    let mut signals = conductor.signals().await;
+
+    let len_before: usize = conductor.call(zome, "chain_len", ()).await;
+    let hash: HeaderHash = conductor.call(zome, "create_unit", ()).await;
+
+    let signal = signals.next().await.unwrap();
+    assert_eq!(
+        signal,
+        SystemSignal::PostCommitFailed {
+            cell_id,
+            zome_name: "zome1".into(),
+            headers: vec![hash],
+            reason: "nope".into(),
+        }
+        .into()
+    );
+
+    // The zome call's commit is kept
+    let len_after: usize = conductor.call(zome, "chain_len", ()).await;
+    assert_eq!(len_after, len_before + 1);
+
+    Ok(())
+}
+
+#[tokio::test(threaded_scheduler)]
+#[cfg(feature = "test_utils")]
+async fn invalid_post_commit_commit_is_rejected() -> anyhow::Result<()> {
+    observability::test_run().ok();
+
+    let (dna_file, _) =
+        SweetDnaFile::unique_from_inline_zome("zome1", zome(PostCommit::CommitInvalid)).await?;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", &[dna_file]).await;
+    let zome = &app.cells()[0].zome("zome1");
+    let mut signals = conductor.signals().await;
+
+    let len_before: usize = conductor.call(zome, "chain_len", ()).await;
+    let hash: HeaderHash = conductor.call(zome, "create_unit", ()).await;
+
+    // The app signal from post_commit, then the failure of its invalid commit
+    let signal = signals.next().await.unwrap();
+    assert_matches!(signal, Signal::App { .. });
+    let signal = signals.next().await.unwrap();
+    assert_matches!(
+        signal,
+        Signal::System(SystemSignal::PostCommitFailed { headers, .. }) if headers == vec![hash]
+    );
+
+    // The invalid entry never made it onto the chain
+    let len_after: usize = conductor.call(zome, "chain_len", ()).await;
+    assert_eq!(len_after, len_before + 1);
+
+    Ok(())
+}
@@ -20,7 +20,9 @@ async fn direct_validation_test() {
             name: "direct_validation_test".to_string(),
             uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
             properties: SerializedBytes::try_from(()).unwrap(),
+            rate_limits: Vec::new(),
             zomes: vec![TestWasm::Update.into()].into(),
+            redundancy_target: None,
         },
         vec![TestWasm::Update.into()],
     )
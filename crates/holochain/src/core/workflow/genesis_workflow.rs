@@ -55,12 +55,11 @@ async fn genesis_workflow_inner<Api: CellConductorApiT>(
         membrane_proof,
     } = args;
 
-    // TODO: this is a placeholder for a real DPKI request to show intent
-    if api
-        .dpki_request("is_agent_pubkey_valid".into(), agent_pubkey.to_string())
+    if !api
+        .dpki_key_state(&agent_pubkey)
         .await
-        .expect("TODO: actually implement this")
-        == "INVALID"
+        .map_err(Box::new)?
+        .is_valid_at(&timestamp::now())
     {
         return Err(WorkflowError::AgentInvalid(agent_pubkey.clone()));
     }
@@ -133,8 +132,8 @@ pub mod tests {
         {
             let workspace = GenesisWorkspace::new(arc.clone().into()).await?;
             let mut api = MockCellConductorApi::new();
-            api.expect_sync_dpki_request()
-                .returning(|_, _| Ok("mocked dpki request response".to_string()));
+            api.expect_sync_dpki_key_state()
+                .returning(|_| Ok(KeyState::NotFound));
             let args = GenesisWorkflowArgs {
                 dna_file: dna.clone(),
                 agent_pubkey: agent_pubkey.clone(),
@@ -197,7 +196,7 @@ Data X (data & structure) from Store Y:
 
 Functions / Workflows:
 
-- check that agent key is valid [MOCKED dpki] (via real dpki [LEAPFROG])
+- check that agent key is valid (via dpki)
 
 - retrieve DNA from file path [in the future from HCHC]
 
@@ -0,0 +1,194 @@
+//! # Health Check Workflow
+//!
+//! Runs as the [AutonomicProcess::HealthCheck] for a cell.
+//! For every op the cell has authored we estimate how many authorities
+//! are holding it, from the validation receipts we have received and
+//! from asking the authorities for the metadata the op would have added.
+//! Any op held by fewer authorities than the Dna's `redundancy_target`
+//! (or the conductor's, if the Dna doesn't set one) is marked for the
+//! publish workflow, which publishes it again even if it was published
+//! recently or has all its receipts.
+
+use super::error::WorkflowResult;
+use super::produce_dht_ops_workflow::dht_op_light::error::DhtOpConvertError;
+use super::produce_dht_ops_workflow::dht_op_light::light_to_op;
+use crate::core::queue_consumer::OneshotWriter;
+use crate::core::queue_consumer::TriggerSender;
+use fallible_iterator::FallibleIterator;
+use holo_hash::*;
+use holochain_lmdb::buffer::BufferedStore;
+use holochain_lmdb::buffer::KvBufFresh;
+use holochain_lmdb::db::AUTHORED_DHT_OPS;
+use holochain_lmdb::fresh_reader;
+use holochain_lmdb::prelude::*;
+use holochain_p2p::actor::GetMetaOptions;
+use holochain_p2p::HolochainP2pCell;
+use holochain_p2p::HolochainP2pCellT;
+use holochain_state::prelude::*;
+use holochain_types::prelude::*;
+use std::collections::HashMap;
+use tracing::*;
+
+#[cfg(test)]
+mod tests;
+
+/// Default number of authorities that should hold each op.
+/// Used when neither the Dna nor the conductor config set a `redundancy_target`.
+pub const DEFAULT_REDUNDANCY_TARGET: u32 = 5;
+
+/// Database buffers required for checking the health of authored ops
+pub struct HealthCheckWorkspace {
+    /// Database of authored DhtOps, with their receipt counts
+    authored_dht_ops: AuthoredDhtOpsStore,
+    /// Element store for looking up data to construct ops
+    elements: ElementBuf<AuthoredPrefix>,
+}
+
+impl HealthCheckWorkspace {
+    /// Constructor
+    pub fn new(env: EnvironmentRead) -> WorkspaceResult<Self> {
+        let db = env.get_db(&*AUTHORED_DHT_OPS)?;
+        let authored_dht_ops = KvBufFresh::new(env.clone(), db);
+        // Private entries are never published so they must not be loaded
+        let elements = ElementBuf::authored(env, false)?;
+        Ok(Self {
+            authored_dht_ops,
+            elements,
+        })
+    }
+}
+
+impl Workspace for HealthCheckWorkspace {
+    fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> WorkspaceResult<()> {
+        self.authored_dht_ops.flush_to_txn_ref(writer)?;
+        Ok(())
+    }
+}
+
+#[instrument(skip(workspace, writer, network, trigger_publish))]
+pub async fn health_check_workflow(
+    mut workspace: HealthCheckWorkspace,
+    writer: OneshotWriter,
+    network: &mut HolochainP2pCell,
+    trigger_publish: &mut TriggerSender,
+    redundancy_target: u32,
+) -> WorkflowResult<CellHealthReport> {
+    let now = timestamp::now();
+    let env = workspace.elements.headers().env().clone();
+    let authored = fresh_reader!(env, |r| workspace
+        .authored_dht_ops
+        .iter(&r)?
+        .map(|(k, v)| Ok((DhtOpHash::from_raw_39_panicky(k.to_vec()), v)))
+        .collect::<Vec<_>>())?;
+
+    // Ask the authorities of each basis for the metadata our ops add
+    let mut metadata: HashMap<AnyDhtHash, Vec<MetadataSet>> = HashMap::new();
+    for (_, value) in authored.iter() {
+        if !has_metadata(&value.op) || metadata.contains_key(value.op.dht_basis()) {
+            continue;
+        }
+        let basis = value.op.dht_basis().clone();
+        let options = GetMetaOptions {
+            remote_agent_count: Some(redundancy_target.min(u8::MAX as u32) as u8),
+            as_race: false,
+            metadata_request: MetadataRequest {
+                all_valid_headers: true,
+                all_deletes: true,
+                all_updates: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let response = match network.get_meta(basis.clone(), options).await {
+            Ok(response) => response,
+            Err(error) => {
+                warn!(
+                    ?basis,
+                    ?error,
+                    "Health check could not reach the authorities"
+                );
+                Vec::new()
+            }
+        };
+        metadata.insert(basis, response);
+    }
+
+    let mut report = CellHealthReport {
+        checked_at: now,
+        redundancy_target,
+        authored_ops: 0,
+        healthy_ops: 0,
+        republished_ops: 0,
+    };
+
+    for (op_hash, mut value) in authored {
+        match light_to_op(value.op.clone(), &workspace.elements) {
+            // Private entries are never published so they can't be held
+            Err(DhtOpConvertError::StoreEntryOnPrivate) => continue,
+            r => {
+                r?;
+            }
+        }
+        report.authored_ops += 1;
+
+        let held_by_metadata = metadata
+            .get(value.op.dht_basis())
+            .map(|sets| sets.iter().filter(|set| holds(set, &value.op)).count() as u32)
+            .unwrap_or(0);
+        let held_by = value.receipt_count.max(held_by_metadata);
+        if held_by >= redundancy_target {
+            report.healthy_ops += 1;
+            continue;
+        }
+
+        debug!(
+            ?op_hash,
+            held_by, redundancy_target, "Republishing under-held op"
+        );
+        // Mark the op so the publish workflow sends it again straight away.
+        // Its receipts and backoff are kept for the regular publishing.
+        value.republish = true;
+        workspace.authored_dht_ops.put(op_hash, value)?;
+        report.republished_ops += 1;
+    }
+
+    // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
+
+    // commit the workspace
+    writer.with_writer(|writer| Ok(workspace.flush_to_txn(writer)?))?;
+
+    // trigger other workflows
+    if report.republished_ops > 0 {
+        trigger_publish.trigger();
+    }
+
+    Ok(report)
+}
+
+/// Does holding this op add metadata to its basis that we can ask for?
+fn has_metadata(op: &DhtOpLight) -> bool {
+    matches!(
+        op,
+        DhtOpLight::StoreEntry(..)
+            | DhtOpLight::RegisterUpdatedContent(..)
+            | DhtOpLight::RegisterUpdatedElement(..)
+            | DhtOpLight::RegisterDeletedBy(..)
+            | DhtOpLight::RegisterDeletedEntryHeader(..)
+    )
+}
+
+/// Does this authority's metadata show that it holds the op?
+fn holds(set: &MetadataSet, op: &DhtOpLight) -> bool {
+    let contains = |headers: &std::collections::BTreeSet<TimedHeaderHash>, hash: &HeaderHash| {
+        headers.iter().any(|h| &h.header_hash == hash)
+    };
+    match op {
+        DhtOpLight::StoreEntry(h, _, _) => contains(&set.headers, h),
+        DhtOpLight::RegisterUpdatedContent(h, _, _)
+        | DhtOpLight::RegisterUpdatedElement(h, _, _) => contains(&set.updates, h),
+        DhtOpLight::RegisterDeletedBy(h, _) | DhtOpLight::RegisterDeletedEntryHeader(h, _) => {
+            contains(&set.deletes, h)
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,116 @@
+use super::*;
+use crate::core::workflow::publish_dht_ops_workflow::publish_dht_ops_workflow;
+use crate::core::workflow::publish_dht_ops_workflow::PublishDhtOpsWorkspace;
+use crate::core::workflow::publish_dht_ops_workflow::DEFAULT_RECEIPT_BUNDLE_SIZE;
+use crate::fixt::CreateLinkFixturator;
+use crate::test_utils::test_network_with_events;
+use ::fixt::prelude::*;
+use futures::future::FutureExt;
+use holochain_lmdb::test_utils::test_cell_env;
+
+/// Put a just published link op into the authored store
+/// with the given receipt count
+fn author_link_op(env: &EnvironmentWrite, receipt_count: u32) -> DhtOpHash {
+    let sig = fixt!(Signature);
+    let link_add = fixt!(CreateLink);
+    let op = DhtOp::RegisterAddLink(sig.clone(), link_add.clone());
+    let op_hash = DhtOpHash::with_data_sync(&op);
+    let header_hash = HeaderHashed::from_content_sync(link_add.clone().into());
+    let op_light =
+        DhtOpLight::RegisterAddLink(header_hash.as_hash().clone(), link_add.base_address.into());
+
+    let mut workspace = HealthCheckWorkspace::new(env.clone().into()).unwrap();
+    let mut value = AuthoredDhtOpsValue::from_light(op_light);
+    value.receipt_count = receipt_count;
+    value.last_publish_time = Some(timestamp::now());
+    value.publish_count = 1;
+    workspace
+        .authored_dht_ops
+        .put(op_hash.clone(), value)
+        .unwrap();
+    let signed_header = SignedHeaderHashed::with_presigned(header_hash, sig);
+    workspace.elements.put(signed_header, None).unwrap();
+    env.guard()
+        .with_commit::<DatabaseError, _, _>(|writer| {
+            workspace.authored_dht_ops.flush_to_txn(writer)?;
+            workspace.elements.flush_to_txn(writer)?;
+            Ok(())
+        })
+        .unwrap();
+    op_hash
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn under_held_ops_are_republished() {
+    observability::test_run().ok();
+    let test_env = test_cell_env();
+    let env = test_env.env();
+
+    // More than the receipts the publish workflow asks for
+    let redundancy_target = DEFAULT_RECEIPT_BUNDLE_SIZE + 2;
+    let healthy = author_link_op(&env, redundancy_target);
+    // Fully receipted so only the health check gets it published again
+    let under_held = author_link_op(&env, DEFAULT_RECEIPT_BUNDLE_SIZE);
+    let (mut trigger_publish, mut publish_rx) = TriggerSender::new();
+
+    let filter_events = |evt: &_| match evt {
+        holochain_p2p::event::HolochainP2pEvent::Publish { .. } => true,
+        _ => false,
+    };
+    let (tx, mut recv) = tokio::sync::mpsc::channel(10);
+    let test_network = test_network_with_events(None, None, filter_events, tx).await;
+    let published = tokio::task::spawn(async move {
+        use tokio::stream::StreamExt;
+        let mut published = Vec::new();
+        if let Some(holochain_p2p::event::HolochainP2pEvent::Publish { respond, ops, .. }) =
+            recv.next().await
+        {
+            respond.respond(Ok(async move { Ok(()) }.boxed().into()));
+            published.extend(ops.into_iter().map(|(hash, _)| hash));
+        }
+        published
+    });
+
+    let workspace = HealthCheckWorkspace::new(env.clone().into()).unwrap();
+    let report = health_check_workflow(
+        workspace,
+        env.clone().into(),
+        &mut test_network.cell_network(),
+        &mut trigger_publish,
+        redundancy_target,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(report.redundancy_target, redundancy_target);
+    assert_eq!(report.authored_ops, 2);
+    assert_eq!(report.healthy_ops, 1);
+    assert_eq!(report.republished_ops, 1);
+
+    // The publish workflow is asked to run and sends only the under-held op,
+    // even though both were published recently and have all their receipts
+    publish_rx.listen().await.unwrap();
+    let workspace = PublishDhtOpsWorkspace::new(env.clone().into()).unwrap();
+    publish_dht_ops_workflow(
+        workspace,
+        env.clone().into(),
+        &mut test_network.cell_network(),
+        DEFAULT_RECEIPT_BUNDLE_SIZE,
+    )
+    .await
+    .unwrap();
+
+    let published = tokio::time::timeout(std::time::Duration::from_secs(3), published)
+        .await
+        .expect("The under-held op was not published")
+        .unwrap();
+    assert_eq!(published, vec![under_held.clone()]);
+
+    // The republished op keeps its receipts and backoff
+    let workspace = HealthCheckWorkspace::new(env.clone().into()).unwrap();
+    let get = |hash| workspace.authored_dht_ops.get(hash).unwrap().unwrap();
+    assert!(!get(&under_held).republish);
+    assert_eq!(get(&under_held).publish_count, 1);
+    assert_eq!(get(&under_held).receipt_count, DEFAULT_RECEIPT_BUNDLE_SIZE);
+    assert_eq!(get(&healthy).receipt_count, redundancy_target);
+}
@@ -20,7 +20,7 @@ async fn incoming_ops_to_limbo() {
     let hash = DhtOpHash::with_data_sync(&op);
     let ops = vec![(hash.clone(), op.clone())];
 
-    incoming_dht_ops_workflow(&env, sys_validation_trigger.clone(), ops, None)
+    incoming_dht_ops_workflow(&env, sys_validation_trigger.clone(), ops, None, false)
         .await
         .unwrap();
     rx.listen().await.unwrap();
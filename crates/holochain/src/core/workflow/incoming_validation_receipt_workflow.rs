@@ -0,0 +1,125 @@
+//! The workflow for validation receipts sent to us by the validators
+//! of the ops we authored.
+
+use super::error::WorkflowResult;
+use crate::conductor::api::CellConductorApiT;
+use crate::core::queue_consumer::OneshotWriter;
+use holo_hash::AnyDhtHash;
+use holochain_lmdb::buffer::BufferedStore;
+use holochain_lmdb::buffer::KvBufFresh;
+use holochain_lmdb::db::AUTHORED_DHT_OPS;
+use holochain_lmdb::env::EnvironmentWrite;
+use holochain_lmdb::prelude::*;
+use holochain_state::prelude::*;
+use kitsune_p2p::agent_store::AgentInfo;
+use std::convert::TryFrom;
+use tracing::*;
+
+#[cfg(test)]
+mod test;
+
+#[instrument(skip(state_env, receipt, conductor_api))]
+/// Verify a receipt and store it against the op it is for.
+/// The receipt count of the authored op is updated so the
+/// publish workflow knows when to stop publishing it.
+pub async fn incoming_validation_receipt_workflow(
+    state_env: &EnvironmentWrite,
+    receipt: SignedValidationReceipt,
+    conductor_api: &impl CellConductorApiT,
+) -> WorkflowResult<()> {
+    if !receipt.verify().await? {
+        warn!(
+            msg = "Dropping validation receipt with an invalid signature",
+            ?receipt
+        );
+        return Ok(());
+    }
+
+    let mut workspace = IncomingValidationReceiptWorkspace::new(state_env.clone().into())?;
+    let dht_op_hash = receipt.receipt.dht_op_hash.clone();
+
+    // We only keep receipts for ops we authored
+    let mut authored_value = match workspace.authored_dht_ops.get(&dht_op_hash)? {
+        Some(v) => v,
+        None => {
+            warn!(
+                msg = "Dropping validation receipt for an op we did not author",
+                ?dht_op_hash
+            );
+            return Ok(());
+        }
+    };
+
+    // A receipt only counts towards redundancy if it comes from
+    // a validator that is actually holding the op
+    let validator = &receipt.receipt.validator;
+    if !agent_holds_basis(conductor_api, validator, authored_value.op.dht_basis()).await? {
+        warn!(
+            msg = "Dropping validation receipt from a validator not holding the op",
+            ?validator,
+            ?dht_op_hash
+        );
+        return Ok(());
+    }
+
+    workspace.validation_receipts.add_if_unique(receipt)?;
+
+    // commit our transaction
+    let writer: OneshotWriter = state_env.clone().into();
+    writer.with_writer(|writer| {
+        workspace.validation_receipts.flush_to_txn_ref(writer)?;
+        // Count the receipts including any that were already stored
+        let receipt_count = workspace
+            .validation_receipts
+            .count_valid(writer, &dht_op_hash)?;
+        authored_value.receipt_count = receipt_count as u32;
+        workspace
+            .authored_dht_ops
+            .put(dht_op_hash.clone(), authored_value)?;
+        workspace.authored_dht_ops.flush_to_txn_ref(writer)?;
+        Ok(())
+    })?;
+
+    Ok(())
+}
+
+/// Check we know the agent's info and its arc covers the basis
+pub(crate) async fn agent_holds_basis(
+    conductor_api: &impl CellConductorApiT,
+    agent: &AgentPubKey,
+    basis: &AnyDhtHash,
+) -> WorkflowResult<bool> {
+    let agent_info_signed = match conductor_api
+        .get_agent_info(agent)
+        .await
+        .map_err(Box::new)?
+    {
+        Some(a) => a,
+        None => return Ok(false),
+    };
+    match AgentInfo::try_from(&agent_info_signed) {
+        Ok(agent_info) => Ok(agent_info.dht_arc().contains(basis.get_loc())),
+        Err(e) => {
+            warn!(msg = "Skipping bad agent info", ?e);
+            Ok(false)
+        }
+    }
+}
+
+#[allow(missing_docs)]
+pub struct IncomingValidationReceiptWorkspace {
+    pub authored_dht_ops: AuthoredDhtOpsStore,
+    pub validation_receipts: ValidationReceiptsBuf,
+}
+
+impl IncomingValidationReceiptWorkspace {
+    pub fn new(env: EnvironmentRead) -> WorkspaceResult<Self> {
+        let db = env.get_db(&*AUTHORED_DHT_OPS)?;
+        let authored_dht_ops = KvBufFresh::new(env.clone(), db);
+        let validation_receipts = ValidationReceiptsBuf::new(&env)?;
+        Ok(Self {
+            authored_dht_ops,
+            validation_receipts,
+        })
+    }
+}
@@ -0,0 +1,181 @@
+use super::*;
+use crate::conductor::api::MockCellConductorApi;
+use ::fixt::prelude::*;
+use holo_hash::fixt::DhtOpHashFixturator;
+use holo_hash::fixt::DnaHashFixturator;
+use holo_hash::DhtOpHash;
+use holochain_keystore::KeystoreSender;
+use holochain_keystore::KeystoreSenderExt;
+use holochain_p2p::agent_holo_to_kit;
+use holochain_p2p::space_holo_to_kit;
+use holochain_types::prelude::*;
+use kitsune_p2p::agent_store::AgentInfoSigned;
+use kitsune_p2p::dependencies::kitsune_p2p_types::codec::rmp_encode;
+use kitsune_p2p::dht_arc::DhtArc;
+use kitsune_p2p::KitsuneSignature;
+use std::collections::HashMap;
+
+async fn new_validator(keystore: &KeystoreSender) -> AgentPubKey {
+    keystore
+        .clone()
+        .generate_sign_keypair_from_pure_entropy()
+        .await
+        .unwrap()
+}
+
+async fn signed_receipt(
+    dht_op_hash: &DhtOpHash,
+    validator: &AgentPubKey,
+    keystore: &KeystoreSender,
+) -> SignedValidationReceipt {
+    ValidationReceipt {
+        dht_op_hash: dht_op_hash.clone(),
+        validation_result: ValidationResult::Valid,
+        validator: validator.clone(),
+    }
+    .sign(keystore)
+    .await
+    .unwrap()
+}
+
+/// The stored agent infos were verified on the way in so
+/// the signature isn't checked again here
+fn agent_info(agent: &AgentPubKey, dht_arc: DhtArc) -> AgentInfoSigned {
+    let agent_info = AgentInfo::new(
+        space_holo_to_kit(fixt!(DnaHash)),
+        agent_holo_to_kit(agent.clone()),
+        Vec::new(),
+        dht_arc,
+        0,
+        1000,
+    );
+    let mut data = Vec::new();
+    rmp_encode(&mut data, &agent_info).unwrap();
+    AgentInfoSigned::try_new(
+        agent_holo_to_kit(agent.clone()),
+        KitsuneSignature(vec![0; 64]),
+        data,
+    )
+    .unwrap()
+}
+
+fn conductor_api(known: HashMap<AgentPubKey, AgentInfoSigned>) -> MockCellConductorApi {
+    let mut conductor_api = MockCellConductorApi::new();
+    conductor_api
+        .expect_sync_get_agent_info()
+        .returning(move |agent| Ok(known.get(agent).cloned()));
+    conductor_api
+}
+
+fn receipt_count(env: &EnvironmentWrite, dht_op_hash: &DhtOpHash) -> Option<u32> {
+    let workspace = IncomingValidationReceiptWorkspace::new(env.clone().into()).unwrap();
+    workspace
+        .authored_dht_ops
+        .get(dht_op_hash)
+        .unwrap()
+        .map(|v| v.receipt_count)
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn receipts_are_counted_once_per_validator() {
+    let test_env = holochain_lmdb::test_utils::test_cell_env();
+    let env = test_env.env();
+    let keystore = holochain_lmdb::test_utils::test_keystore();
+
+    // Author an op
+    let op = DhtOp::RegisterAgentActivity(fixt!(Signature), fixt!(Header));
+    let dht_op_hash = DhtOpHash::with_data_sync(&op);
+    {
+        let mut workspace = IncomingValidationReceiptWorkspace::new(env.clone().into()).unwrap();
+        workspace
+            .authored_dht_ops
+            .put(
+                dht_op_hash.clone(),
+                AuthoredDhtOpsValue::from_light(op.to_light()),
+            )
+            .unwrap();
+        env.guard()
+            .with_commit(|writer| workspace.authored_dht_ops.flush_to_txn(writer))
+            .unwrap();
+    }
+
+    let basis_loc = op.to_light().dht_basis().get_loc();
+    let validators = vec![
+        new_validator(&keystore).await,
+        new_validator(&keystore).await,
+    ];
+    let far_validator = new_validator(&keystore).await;
+    let unknown_validator = new_validator(&keystore).await;
+    let mut known: HashMap<_, _> = validators
+        .iter()
+        .map(|v| (v.clone(), agent_info(v, DhtArc::new(0, u32::MAX))))
+        .collect();
+    // An arc that only holds a location on the other side of the dht
+    known.insert(
+        far_validator.clone(),
+        agent_info(
+            &far_validator,
+            DhtArc::new(basis_loc.wrapping_add(u32::MAX / 2), 1),
+        ),
+    );
+    let conductor_api = conductor_api(known);
+
+    let receipt = signed_receipt(&dht_op_hash, &validators[0], &keystore).await;
+    incoming_validation_receipt_workflow(&env, receipt.clone(), &conductor_api)
+        .await
+        .unwrap();
+    assert_eq!(receipt_count(&env, &dht_op_hash), Some(1));
+
+    // The same receipt again doesn't count
+    incoming_validation_receipt_workflow(&env, receipt.clone(), &conductor_api)
+        .await
+        .unwrap();
+    assert_eq!(receipt_count(&env, &dht_op_hash), Some(1));
+
+    // A receipt with a bad signature is dropped
+    let mut counterfeit = signed_receipt(&dht_op_hash, &validators[1], &keystore).await;
+    counterfeit.validator_signature = receipt.validator_signature.clone();
+    incoming_validation_receipt_workflow(&env, counterfeit, &conductor_api)
+        .await
+        .unwrap();
+    assert_eq!(receipt_count(&env, &dht_op_hash), Some(1));
+
+    // A validator we have no agent info for doesn't count
+    let receipt = signed_receipt(&dht_op_hash, &unknown_validator, &keystore).await;
+    incoming_validation_receipt_workflow(&env, receipt, &conductor_api)
+        .await
+        .unwrap();
+    assert_eq!(receipt_count(&env, &dht_op_hash), Some(1));
+
+    // A validator whose arc doesn't cover the basis doesn't count
+    let receipt = signed_receipt(&dht_op_hash, &far_validator, &keystore).await;
+    incoming_validation_receipt_workflow(&env, receipt, &conductor_api)
+        .await
+        .unwrap();
+    assert_eq!(receipt_count(&env, &dht_op_hash), Some(1));
+
+    // A second validator holding the op does count
+    let receipt = signed_receipt(&dht_op_hash, &validators[1], &keystore).await;
+    incoming_validation_receipt_workflow(&env, receipt, &conductor_api)
+        .await
+        .unwrap();
+    assert_eq!(receipt_count(&env, &dht_op_hash), Some(2));
+
+    // Receipts for ops we didn't author are dropped
+    let other_hash = fixt!(DhtOpHash);
+    let receipt = signed_receipt(&other_hash, &validators[0], &keystore).await;
+    incoming_validation_receipt_workflow(&env, receipt, &conductor_api)
+        .await
+        .unwrap();
+    assert_eq!(receipt_count(&env, &other_hash), None);
+    let env_ref = env.guard();
+    let reader = env_ref.reader().unwrap();
+    let workspace = IncomingValidationReceiptWorkspace::new(env.clone().into()).unwrap();
+    assert_eq!(
+        workspace
+            .validation_receipts
+            .count_valid(&reader, &other_hash)
+            .unwrap(),
+        0
+    );
+}
@@ -0,0 +1,50 @@
+//! The workflow for warrants published to us by other
+//! agent activity authorities.
+
+use super::error::WorkflowResult;
+use super::incoming_validation_receipt_workflow::agent_holds_basis;
+use crate::conductor::api::CellConductorApiT;
+use crate::core::queue_consumer::OneshotWriter;
+use holochain_lmdb::buffer::BufferedStore;
+use holochain_lmdb::env::EnvironmentWrite;
+use holochain_state::prelude::*;
+use tracing::*;
+
+#[instrument(skip(state_env, warrant, conductor_api))]
+/// Verify a warrant and store it against the warranted agent.
+/// We don't trust the publisher so the warrant must carry its own proof.
+/// Warrants are only kept by the authorities for the warranted agent.
+pub async fn incoming_warrant_workflow(
+    state_env: &EnvironmentWrite,
+    warrant: Warrant,
+    conductor_api: &impl CellConductorApiT,
+) -> WorkflowResult<()> {
+    let us = conductor_api.cell_id().agent_pubkey();
+    let basis = warrant.warranted_agent().clone().into();
+    if !agent_holds_basis(conductor_api, us, &basis).await? {
+        warn!(
+            msg = "Dropping warrant for an agent outside our arc",
+            ?warrant
+        );
+        return Ok(());
+    }
+
+    if !verify_warrant(&warrant).await? {
+        warn!(msg = "Dropping warrant that doesn't hold up", ?warrant);
+        return Ok(());
+    }
+
+    let mut warrants = WarrantStore::new(state_env.clone().into())?;
+    if !warrants.add_if_unique(warrant)? {
+        return Ok(());
+    }
+
+    // commit our transaction
+    let writer: OneshotWriter = state_env.clone().into();
+    writer.with_writer(|writer| {
+        warrants.flush_to_txn_ref(writer)?;
+        Ok(())
+    })?;
+
+    Ok(())
+}
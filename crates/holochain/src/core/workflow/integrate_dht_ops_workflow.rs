@@ -1,6 +1,7 @@
 //! The workflow and queue consumer for DhtOp integration
 
 use super::*;
+use crate::conductor::interface::SignalBroadcaster;
 use crate::core::queue_consumer::OneshotWriter;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
@@ -15,6 +16,7 @@ use holochain_cascade::error::CascadeResult;
 use holochain_cascade::Cascade;
 use holochain_cascade::DbPair;
 use holochain_cascade::{error::CascadeError, integrate_single_metadata};
+use holochain_keystore::KeystoreSender;
 use holochain_lmdb::buffer::BufferedStore;
 use holochain_lmdb::buffer::KvBufFresh;
 use holochain_lmdb::db::INTEGRATED_DHT_OPS;
@@ -22,6 +24,8 @@ use holochain_lmdb::db::INTEGRATION_LIMBO;
 use holochain_lmdb::error::DatabaseResult;
 use holochain_lmdb::fresh_reader;
 use holochain_lmdb::prelude::*;
+use holochain_p2p::HolochainP2pCell;
+use holochain_p2p::HolochainP2pCellT;
 use holochain_state::prelude::*;
 use holochain_types::prelude::*;
 
@@ -31,6 +35,7 @@ use holochain_zome_types::ValidationStatus;
 use produce_dht_ops_workflow::dht_op_light::error::DhtOpConvertResult;
 use produce_dht_ops_workflow::dht_op_light::light_to_op;
 use std::collections::BinaryHeap;
+use std::collections::HashSet;
 use std::convert::TryInto;
 use tracing::*;
 
@@ -41,11 +46,14 @@ mod disintegrate;
 #[cfg(feature = "test_utils")]
 mod tests;
 
-#[instrument(skip(workspace, writer, trigger_sys))]
+#[instrument(skip(workspace, writer, trigger_sys, network, signal_tx))]
 pub async fn integrate_dht_ops_workflow(
     mut workspace: IntegrateDhtOpsWorkspace,
     writer: OneshotWriter,
     trigger_sys: &mut TriggerSender,
+    network: &mut HolochainP2pCell,
+    cell_id: CellId,
+    mut signal_tx: SignalBroadcaster,
 ) -> WorkflowResult<WorkComplete> {
     // one of many possible ways to access the env
     let env = workspace.elements.headers().env().clone();
@@ -73,6 +81,11 @@ pub async fn integrate_dht_ops_workflow(
     }
 
     let mut total_integrated: usize = 0;
+    // Agents whose activity we integrated so we
+    // can check their chains for forks afterwards
+    let mut activity_authors = HashSet::new();
+    // Changes to links and entries that app interfaces can subscribe to
+    let mut data_changes = Vec::new();
 
     // Try to process the queue over and over again, until we either exhaust
     // the queue, or we can no longer integrate anything in the queue.
@@ -89,6 +102,14 @@ pub async fn integrate_dht_ops_workflow(
                 value,
                 order,
             } = so;
+            let activity_author = match &op {
+                DhtOp::RegisterAgentActivity(_, header) => Some(header.author().clone()),
+                _ => None,
+            };
+            let data_change = match value.validation_status {
+                ValidationStatus::Valid => data_change(&op, &workspace)?,
+                _ => None,
+            };
             // Check validation status and put in correct dbs
             let outcome = integrate_single_dht_op(value.clone(), op, &mut workspace).await?;
             match outcome {
@@ -98,6 +119,8 @@ pub async fn integrate_dht_ops_workflow(
                     // Currently you need to check the IntegratedDhtOpsValue for
                     // the status
                     workspace.integrate(hash, integrated)?;
+                    activity_authors.extend(activity_author);
+                    data_changes.extend(data_change);
                     num_integrated += 1;
                     total_integrated += 1;
                 }
@@ -131,11 +154,39 @@ pub async fn integrate_dht_ops_workflow(
         WorkComplete::Complete
     };
 
+    let warrantor = cell_id.agent_pubkey();
+    let mut warrants = Vec::new();
+    for author in activity_authors {
+        warrants
+            .extend(warrant_chain_fork(&author, warrantor, env.keystore(), &mut workspace).await?);
+    }
+
     // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
 
     // commit the workspace
     writer.with_writer(|writer| Ok(workspace.flush_to_txn(writer)?))?;
 
+    // Send the warrants to the other authorities for the warranted agents.
+    // This is best effort because they can find the fork themselves.
+    for warrant in warrants {
+        if let Err(e) = network.publish_warrant(warrant, None).await {
+            warn!(msg = "Failed to publish warrant", ?e);
+        }
+    }
+
+    // Only signal the changes once they are committed,
+    // and only those which a connection is subscribed to.
+    for change in data_changes {
+        if !signal_tx.data_subscribed(&cell_id, &change) {
+            continue;
+        }
+        let signal = SystemSignal::DataChanged {
+            cell_id: cell_id.clone(),
+            change,
+        };
+        signal_tx.send(signal.into()).ok();
+    }
+
     // trigger other workflows
 
     if total_integrated > 0 {
@@ -155,6 +206,11 @@ async fn integrate_single_dht_op(
     op: DhtOp,
     workspace: &mut IntegrateDhtOpsWorkspace,
 ) -> WorkflowResult<Outcome> {
+    if let ValidationStatus::Abandoned = iv.validation_status {
+        // Abandoned ops are kept, whether or not their dependencies
+        // are held, so the slow heal process can try them again later
+        return Ok(integrate_data(iv, op, &mut workspace.element_abandoned)?);
+    }
     if op_dependencies_held(&op, workspace).await? {
         match iv.validation_status {
             ValidationStatus::Valid => Ok(integrate_data_and_meta(
@@ -168,17 +224,7 @@ async fn integrate_single_dht_op(
                 update_validation_status(&op, &mut workspace.meta)?;
                 Ok(integrate_data(iv, op, &mut workspace.element_rejected)?)
             }
-            ValidationStatus::Abandoned => {
-                // Throwing away abandoned ops
-                // TODO: keep abandoned ops but remove the entries
-                // and put them in a AbandonedPrefix db
-                let integrated = IntegratedDhtOpsValue {
-                    validation_status: iv.validation_status,
-                    op: iv.op,
-                    when_integrated: timestamp::now(),
-                };
-                Ok(Outcome::Integrated(integrated))
-            }
+            ValidationStatus::Abandoned => unreachable!("Abandoned ops are integrated above"),
         }
     } else {
         debug!("deferring");
@@ -203,6 +249,88 @@ fn integrate_data_and_meta<P: PrefixType>(
     Ok(Outcome::Integrated(integrated))
 }
 
+/// The change to links or entries made by integrating this valid op.
+/// Updates and deletes are taken from the ops sent to the entry authority,
+/// so each one is only reported once when we also hold the header.
+fn data_change(
+    op: &DhtOp,
+    workspace: &IntegrateDhtOpsWorkspace,
+) -> DatabaseResult<Option<DataChange>> {
+    let change = match op {
+        DhtOp::RegisterAddLink(_, create_link) => DataChange::LinkCreated {
+            base: create_link.base_address.clone(),
+            tag: create_link.tag.clone(),
+            target: create_link.target_address.clone(),
+            create_link_hash: HeaderHash::with_data_sync(&Header::CreateLink(create_link.clone())),
+        },
+        DhtOp::RegisterRemoveLink(_, delete_link) => {
+            // Only the link being deleted has the tag
+            let tag = match workspace.get_any_header(&delete_link.link_add_address)? {
+                Some(header) => match header.header() {
+                    Header::CreateLink(create_link) => create_link.tag.clone(),
+                    _ => return Ok(None),
+                },
+                None => return Ok(None),
+            };
+            DataChange::LinkDeleted {
+                base: delete_link.base_address.clone(),
+                tag,
+                create_link_hash: delete_link.link_add_address.clone(),
+                delete_link_hash: HeaderHash::with_data_sync(&Header::DeleteLink(
+                    delete_link.clone(),
+                )),
+            }
+        }
+        DhtOp::RegisterUpdatedContent(_, update, _) => DataChange::EntryUpdated {
+            entry_hash: update.original_entry_address.clone(),
+            original_header_hash: update.original_header_address.clone(),
+            update_hash: HeaderHash::with_data_sync(&Header::Update(update.clone())),
+            new_entry_hash: update.entry_hash.clone(),
+        },
+        DhtOp::RegisterDeletedEntryHeader(_, delete) => DataChange::EntryDeleted {
+            entry_hash: delete.deletes_entry_address.clone(),
+            deleted_header_hash: delete.deletes_address.clone(),
+            delete_hash: HeaderHash::with_data_sync(&Header::Delete(delete.clone())),
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(change))
+}
+
+/// If the agent's chain is forked and we haven't already
+/// warranted the fork then issue a warrant with both headers as proof.
+async fn warrant_chain_fork(
+    author: &AgentPubKey,
+    warrantor: &AgentPubKey,
+    keystore: &KeystoreSender,
+    workspace: &mut IntegrateDhtOpsWorkspace,
+) -> WorkflowResult<Option<Warrant>> {
+    let fork = match workspace.meta.get_activity_status(author)? {
+        Some(ChainStatus::Forked(fork)) => fork,
+        _ => return Ok(None),
+    };
+    let first = workspace.get_any_header(&fork.first_header)?;
+    let second = workspace.get_any_header(&fork.second_header)?;
+    let (first, second) = match (first, second) {
+        (Some(first), Some(second)) => (first, second),
+        _ => {
+            debug!(?fork, "Missing forked headers so can't issue a warrant");
+            return Ok(None);
+        }
+    };
+    let proof = WarrantProof::ChainFork {
+        chain_author: author.clone(),
+        header_pair: (first.into_inner().0, second.into_inner().0),
+    };
+    if workspace.warrants.contains(&proof)? {
+        return Ok(None);
+    }
+    warn!(agent = ?author, fork_seq = fork.fork_seq, "Issuing warrant for forked chain");
+    let warrant = issue_warrant(proof, warrantor.clone(), keystore).await?;
+    workspace.warrants.add_if_unique(warrant.clone())?;
+    Ok(Some(warrant))
+}
+
 /// Integrate data only
 fn integrate_data<P: PrefixType>(
     iv: IntegrationLimboValue,
@@ -466,6 +594,10 @@ pub struct IntegrateDhtOpsWorkspace {
     pub meta_pending: MetadataBuf<PendingPrefix>,
     pub element_rejected: ElementBuf<RejectedPrefix>,
     pub meta_rejected: MetadataBuf<RejectedPrefix>,
+    /// Data that could not be validated and is waiting for the slow heal process
+    pub element_abandoned: ElementBuf<AbandonedPrefix>,
+    /// Warrants against agents whose chains we found forked
+    pub warrants: WarrantStore,
     /// Ops to disintegrate
     pub to_disintegrate_pending: Vec<DhtOpLight>,
     /// READ ONLY
@@ -489,6 +621,8 @@ impl Workspace for IntegrateDhtOpsWorkspace {
         self.meta_pending.flush_to_txn_ref(writer)?;
         self.element_rejected.flush_to_txn_ref(writer)?;
         self.meta_rejected.flush_to_txn_ref(writer)?;
+        self.element_abandoned.flush_to_txn_ref(writer)?;
+        self.warrants.flush_to_txn_ref(writer)?;
         Ok(())
     }
 }
@@ -511,7 +645,11 @@ impl IntegrateDhtOpsWorkspace {
         let meta_pending = MetadataBuf::pending(env.clone())?;
 
         let element_rejected = ElementBuf::rejected(env.clone())?;
-        let meta_rejected = MetadataBuf::rejected(env)?;
+        let meta_rejected = MetadataBuf::rejected(env.clone())?;
+
+        let element_abandoned = ElementBuf::abandoned(env.clone())?;
+
+        let warrants = WarrantStore::new(env)?;
 
         Ok(Self {
             integration_limbo,
@@ -522,6 +660,8 @@ impl IntegrateDhtOpsWorkspace {
             meta_pending,
             element_rejected,
             meta_rejected,
+            element_abandoned,
+            warrants,
             validation_limbo,
             to_disintegrate_pending: Vec::new(),
         })
@@ -535,13 +675,22 @@ impl IntegrateDhtOpsWorkspace {
         Ok(())
     }
 
+    /// Get a header from the integrated, rejected or abandoned stores
+    fn get_any_header(&self, hash: &HeaderHash) -> DatabaseResult<Option<SignedHeaderHashed>> {
+        if let Some(header) = self.elements.get_header(hash)? {
+            return Ok(Some(header));
+        }
+        if let Some(header) = self.element_rejected.get_header(hash)? {
+            return Ok(Some(header));
+        }
+        self.element_abandoned.get_header(hash)
+    }
+
     pub fn op_exists(&self, hash: &DhtOpHash) -> DatabaseResult<bool> {
         Ok(self.integrated_dht_ops.contains(&hash)? || self.integration_limbo.contains(&hash)?)
     }
 
     /// Create a cascade through the integrated and rejected stores
-    // TODO: Might need to add abandoned here but will need some
-    // thought as abandoned entries are not stored.
     pub fn cascade(&self) -> Cascade<'_> {
         let integrated_data = DbPair {
             element: &self.elements,
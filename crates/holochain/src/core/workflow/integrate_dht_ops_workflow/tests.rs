@@ -3,6 +3,7 @@
 
 use super::*;
 
+use crate::conductor::interface::DataSubscribers;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsResult;
 use crate::core::ribosome::host_fn;
@@ -12,17 +13,22 @@ use crate::fixt::CallContextFixturator;
 use crate::fixt::ZomeCallHostAccessFixturator;
 use crate::fixt::*;
 use crate::here;
+use crate::test_utils::setup_app;
 use crate::test_utils::test_network;
 use ::fixt::prelude::*;
 
+use holochain_keystore::KeystoreSenderExt;
 use holochain_lmdb::env::EnvironmentWrite;
 use holochain_lmdb::env::ReadManager;
 use holochain_lmdb::env::WriteManager;
 use holochain_lmdb::error::DatabaseError;
 use holochain_lmdb::test_utils::test_cell_env;
+use holochain_p2p::actor::HolochainP2pRefToCell;
+use holochain_p2p::HolochainP2pCellFixturator;
 use holochain_state::metadata::ChainItemKey;
 use holochain_state::metadata::LinkMetaKey;
 use holochain_state::workspace::WorkspaceError;
+use holochain_wasm_test_utils::TestWasm;
 
 use holochain_zome_types::Entry;
 use holochain_zome_types::HeaderHashed;
@@ -530,9 +536,19 @@ impl Db {
 async fn call_workflow<'env>(env: EnvironmentWrite) {
     let workspace = IntegrateDhtOpsWorkspace::new(env.clone().into()).unwrap();
     let (mut qt, _rx) = TriggerSender::new();
-    integrate_dht_ops_workflow(workspace, env.clone().into(), &mut qt)
-        .await
+    let cell_id = holochain_types::fixt::CellIdFixturator::new(Unpredictable)
+        .next()
         .unwrap();
+    integrate_dht_ops_workflow(
+        workspace,
+        env.clone().into(),
+        &mut qt,
+        &mut fixt!(HolochainP2pCell),
+        cell_id,
+        SignalBroadcaster::new(Vec::new(), DataSubscribers::default()),
+    )
+    .await
+    .unwrap();
 }
 
 // Need to clear the data from the previous test
@@ -1283,6 +1299,127 @@ async fn test_integrate_single_register_delete_link() {
     todo!("write this test")
 }
 
+/// Integrating a forked chain issues a warrant that
+/// is published to the other agent activity authorities
+#[tokio::test(threaded_scheduler)]
+async fn forked_chain_warrant_reaches_other_authorities() {
+    observability::test_run().ok();
+    let dna_file = DnaFile::new(
+        DnaDef {
+            name: "forked_chain_warrant_test".to_string(),
+            uuid: "6b1f2a8e-3c5d-4f7a-9e0b-2d4c6a8e0f13".to_string(),
+            properties: SerializedBytes::try_from(()).unwrap(),
+            rate_limits: Vec::new(),
+            zomes: vec![TestWasm::Create.into()].into(),
+            redundancy_target: None,
+        },
+        vec![TestWasm::Create.into()],
+    )
+    .await
+    .unwrap();
+
+    let alice_cell_id = CellId::new(dna_file.dna_hash().to_owned(), fake_agent_pubkey_1());
+    let alice_installed_cell = InstalledCell::new(alice_cell_id.clone(), "alice_handle".into());
+    let bob_cell_id = CellId::new(dna_file.dna_hash().to_owned(), fake_agent_pubkey_2());
+    let bob_installed_cell = InstalledCell::new(bob_cell_id.clone(), "bob_handle".into());
+
+    let (_tmpdir, _app_api, conductor) = setup_app(
+        vec![(
+            "test_app",
+            vec![(alice_installed_cell, None), (bob_installed_cell, None)],
+        )],
+        vec![dna_file.clone()],
+    )
+    .await;
+
+    // Mallory signs two different dna headers at the start of their chain
+    let keystore = conductor.keystore().clone();
+    let mallory = keystore
+        .clone()
+        .generate_sign_keypair_from_pure_entropy()
+        .await
+        .unwrap();
+    let mut forked_headers = Vec::new();
+    for _ in 0..2 {
+        let mut dna = fixt!(Dna);
+        dna.author = mallory.clone();
+        let header = Header::Dna(dna);
+        let signature = mallory.sign(&keystore, header.clone()).await.unwrap();
+        forked_headers.push((header, signature));
+    }
+
+    // Alice is an agent activity authority for mallory and has validated both ops
+    let alice_env = conductor.get_cell_env(&alice_cell_id).await.unwrap();
+    {
+        let mut workspace = IntegrateDhtOpsWorkspace::new(alice_env.clone().into()).unwrap();
+        for (header, signature) in forked_headers {
+            let op = DhtOp::RegisterAgentActivity(signature.clone(), header.clone());
+            let signed_header = SignedHeaderHashed::with_presigned(
+                HeaderHashed::from_content_sync(header),
+                signature,
+            );
+            workspace.element_pending.put(signed_header, None).unwrap();
+            let val = IntegrationLimboValue {
+                validation_status: ValidationStatus::Valid,
+                op: op.to_light(),
+            };
+            let op_hash = DhtOpHashed::from_content_sync(op).into_hash();
+            workspace.integration_limbo.put(op_hash, val).unwrap();
+        }
+        alice_env
+            .guard()
+            .with_commit::<WorkspaceError, _, _>(|writer| {
+                workspace.flush_to_txn(writer)?;
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    let workspace = IntegrateDhtOpsWorkspace::new(alice_env.clone().into()).unwrap();
+    let (mut qt, _rx) = TriggerSender::new();
+    let mut alice_network = conductor.holochain_p2p().to_cell(
+        alice_cell_id.dna_hash().clone(),
+        alice_cell_id.agent_pubkey().clone(),
+    );
+    integrate_dht_ops_workflow(
+        workspace,
+        alice_env.clone().into(),
+        &mut qt,
+        &mut alice_network,
+        alice_cell_id.clone(),
+        SignalBroadcaster::new(Vec::new(), DataSubscribers::default()),
+    )
+    .await
+    .unwrap();
+
+    // Alice issued the warrant
+    let alice_warrants = WarrantStore::new(alice_env.clone().into())
+        .unwrap()
+        .get_warrants(&mallory)
+        .unwrap();
+    assert_eq!(alice_warrants.len(), 1);
+    assert_eq!(&alice_warrants[0].warrantor, alice_cell_id.agent_pubkey());
+
+    // Bob receives the published warrant and keeps it
+    let bob_env = conductor.get_cell_env(&bob_cell_id).await.unwrap();
+    let mut bob_warrants = Vec::new();
+    for _ in 0..100 {
+        bob_warrants = WarrantStore::new(bob_env.clone().into())
+            .unwrap()
+            .get_warrants(&mallory)
+            .unwrap();
+        if !bob_warrants.is_empty() {
+            break;
+        }
+        tokio::time::delay_for(std::time::Duration::from_millis(100)).await;
+    }
+    assert_eq!(bob_warrants, alice_warrants);
+
+    let shutdown = conductor.take_shutdown_handle().await.unwrap();
+    conductor.shutdown().await;
+    shutdown.await.unwrap();
+}
+
 #[cfg(feature = "slow_tests")]
 mod slow_tests {
     use std::convert::TryFrom;
@@ -1320,7 +1457,9 @@ mod slow_tests {
                 name: "integration_workflow_test".to_string(),
                 uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
                 properties: SerializedBytes::try_from(()).unwrap(),
+                rate_limits: Vec::new(),
                 zomes: vec![TestWasm::Create.into()].into(),
+                redundancy_target: None,
             },
             vec![TestWasm::Create.into()],
         )
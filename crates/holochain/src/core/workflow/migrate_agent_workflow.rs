@@ -0,0 +1,213 @@
+//! Run the migrate_agent callbacks when an agent moves between Dnas
+//! and close or open the chain to record the migration.
+
+use super::error::WorkflowResult;
+use super::CallZomeWorkspace;
+use super::CallZomeWorkspaceLock;
+use crate::core::queue_consumer::OneshotWriter;
+use crate::core::queue_consumer::TriggerSender;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentHostAccess;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentInvocation;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
+use crate::core::ribosome::RibosomeT;
+use derive_more::Constructor;
+use holo_hash::DnaHash;
+use holochain_state::workspace::Workspace;
+use holochain_types::dna::DnaDef;
+use holochain_zome_types::header::builder;
+use holochain_zome_types::header::HeaderType;
+use holochain_zome_types::migrate_agent::MigrateAgent;
+use holochain_zome_types::query::ChainQueryFilter;
+use tracing::*;
+
+#[derive(Constructor, Debug)]
+pub struct MigrateAgentWorkflowArgs<Ribosome: RibosomeT> {
+    pub dna_def: DnaDef,
+    pub ribosome: Ribosome,
+    /// Whether this chain is being closed or opened
+    pub migrate_agent: MigrateAgent,
+    /// The Dna the agent is migrating to when closing
+    /// or from when opening
+    pub other_dna_hash: DnaHash,
+}
+
+pub type MigrateAgentWorkspace = CallZomeWorkspace;
+
+#[instrument(skip(workspace, writer, trigger_produce_dht_ops))]
+pub async fn migrate_agent_workflow<'env, Ribosome: RibosomeT>(
+    workspace: MigrateAgentWorkspace,
+    writer: OneshotWriter,
+    mut trigger_produce_dht_ops: TriggerSender,
+    args: MigrateAgentWorkflowArgs<Ribosome>,
+) -> WorkflowResult<MigrateAgentResult> {
+    let workspace_lock = CallZomeWorkspaceLock::new(workspace);
+    let result = migrate_agent_workflow_inner(workspace_lock.clone(), args).await?;
+
+    // Nothing is committed if a callback failed
+    if let MigrateAgentResult::Fail(_, _) = result {
+        return Ok(result);
+    }
+
+    // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
+    {
+        let mut guard = workspace_lock.write().await;
+        let workspace: &mut CallZomeWorkspace = &mut guard;
+        // commit the workspace
+        writer.with_writer(|writer| Ok(workspace.flush_to_txn_ref(writer)?))?;
+    }
+
+    trigger_produce_dht_ops.trigger();
+
+    Ok(result)
+}
+
+async fn migrate_agent_workflow_inner<Ribosome: RibosomeT>(
+    workspace: CallZomeWorkspaceLock,
+    args: MigrateAgentWorkflowArgs<Ribosome>,
+) -> WorkflowResult<MigrateAgentResult> {
+    let MigrateAgentWorkflowArgs {
+        dna_def,
+        ribosome,
+        migrate_agent,
+        other_dna_hash,
+    } = args;
+
+    // A retried migration reuses the chain it already opened
+    if let MigrateAgent::Open = migrate_agent {
+        let opened = {
+            let workspace = workspace.read().await;
+            workspace.source_chain.query(
+                &ChainQueryFilter::new().header_type(HeaderType::OpenChain),
+                &workspace.meta_authored,
+            )?
+        };
+        if !opened.is_empty() {
+            return Ok(MigrateAgentResult::Pass);
+        }
+    }
+
+    // Call the migrate_agent callback
+    let result = {
+        let host_access = MigrateAgentHostAccess::new(workspace.clone());
+        let invocation = MigrateAgentInvocation::new(dna_def, migrate_agent.clone());
+        ribosome.run_migrate_agent(host_access, invocation)?
+    };
+    if let MigrateAgentResult::Fail(_, _) = result {
+        return Ok(result);
+    }
+
+    // Record the migration on the chain
+    let mut workspace = workspace.write().await;
+    match migrate_agent {
+        MigrateAgent::Close => {
+            workspace
+                .source_chain
+                .put(
+                    builder::CloseChain {
+                        new_dna_hash: other_dna_hash,
+                    },
+                    None,
+                )
+                .await?;
+        }
+        MigrateAgent::Open => {
+            workspace
+                .source_chain
+                .put(
+                    builder::OpenChain {
+                        prev_dna_hash: other_dna_hash,
+                    },
+                    None,
+                )
+                .await?;
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::core::ribosome::MockRibosomeT;
+    use crate::core::workflow::fake_genesis;
+    use crate::fixt::DnaDefFixturator;
+    use ::fixt::prelude::*;
+    use fixt::Unpredictable;
+    use holochain_lmdb::test_utils::test_cell_env;
+    use holochain_types::prelude::*;
+    use holochain_zome_types::Header;
+    use matches::assert_matches;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn migrate_agent_closes_and_opens_chains() {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let mut workspace = CallZomeWorkspace::new(env.clone().into()).unwrap();
+        fake_genesis(&mut workspace.source_chain).await.unwrap();
+        let workspace_lock = CallZomeWorkspaceLock::new(workspace);
+        let other_dna_hash = fixt!(DnaHash);
+        let dna_def = DnaDefFixturator::new(Unpredictable).next().unwrap();
+
+        // A failed callback leaves the chain alone
+        let mut ribosome = MockRibosomeT::new();
+        ribosome
+            .expect_run_migrate_agent()
+            .returning(|_, _| Ok(MigrateAgentResult::Fail("foo".into(), "no".into())));
+        let args = MigrateAgentWorkflowArgs::new(
+            dna_def.clone(),
+            ribosome,
+            MigrateAgent::Close,
+            other_dna_hash.clone(),
+        );
+        assert_matches!(
+            migrate_agent_workflow_inner(workspace_lock.clone(), args).await,
+            Ok(MigrateAgentResult::Fail(_, _))
+        );
+        assert_eq!(workspace_lock.read().await.source_chain.len(), 3);
+
+        for (migrate_agent, index) in vec![(MigrateAgent::Close, 3), (MigrateAgent::Open, 4)] {
+            let mut ribosome = MockRibosomeT::new();
+            ribosome
+                .expect_run_migrate_agent()
+                .returning(|_, _| Ok(MigrateAgentResult::Pass));
+            let args = MigrateAgentWorkflowArgs::new(
+                dna_def.clone(),
+                ribosome,
+                migrate_agent,
+                other_dna_hash.clone(),
+            );
+            assert_matches!(
+                migrate_agent_workflow_inner(workspace_lock.clone(), args).await,
+                Ok(MigrateAgentResult::Pass)
+            );
+            let header = workspace_lock
+                .read()
+                .await
+                .source_chain
+                .get_at_index(index)
+                .unwrap()
+                .unwrap()
+                .header()
+                .clone();
+            match header {
+                Header::CloseChain(h) => assert_eq!(h.new_dna_hash, other_dna_hash),
+                Header::OpenChain(h) => assert_eq!(h.prev_dna_hash, other_dna_hash),
+                h => panic!("Expected a chain migration header but got {:?}", h),
+            }
+        }
+
+        // Opening again doesn't run the callback or add a header
+        let args = MigrateAgentWorkflowArgs::new(
+            dna_def,
+            MockRibosomeT::new(),
+            MigrateAgent::Open,
+            other_dna_hash,
+        );
+        assert_matches!(
+            migrate_agent_workflow_inner(workspace_lock.clone(), args).await,
+            Ok(MigrateAgentResult::Pass)
+        );
+        assert_eq!(workspace_lock.read().await.source_chain.len(), 5);
+    }
+}
@@ -31,7 +31,8 @@ use std::collections::HashMap;
 use std::time;
 use tracing::*;
 
-/// Default redundancy factor for validation receipts
+/// Default redundancy factor for validation receipts.
+/// Used when the conductor config doesn't set a `receipt_bundle_size`.
 // TODO: Pull this from the wasm entry def and only use this if it's missing
 // TODO: Put a default in the DnaBundle
 // TODO: build zome_types/entry_def map to get the (AppEntryType map to entry def)
@@ -42,6 +43,12 @@ pub const DEFAULT_RECEIPT_BUNDLE_SIZE: u32 = 5;
 /// flooding the network with spurious publishes.
 pub const MIN_PUBLISH_INTERVAL: time::Duration = time::Duration::from_secs(5);
 
+/// The longest we wait between publishes of an op that still doesn't have
+/// enough receipts. The interval doubles with every publish up to this,
+/// so networks with fewer peers than the receipt bundle size don't
+/// republish everything every [MIN_PUBLISH_INTERVAL] forever.
+pub const MAX_PUBLISH_INTERVAL: time::Duration = time::Duration::from_secs(60 * 60);
+
 /// Database buffers required for publishing [DhtOp]s
 pub struct PublishDhtOpsWorkspace {
     /// Database of authored DhtOps, with data about prior publishing
@@ -55,8 +62,9 @@ pub async fn publish_dht_ops_workflow(
     mut workspace: PublishDhtOpsWorkspace,
     writer: OneshotWriter,
     network: &mut HolochainP2pCell,
+    receipt_bundle_size: u32,
 ) -> WorkflowResult<WorkComplete> {
-    let to_publish = publish_dht_ops_workflow_inner(&mut workspace).await?;
+    let to_publish = publish_dht_ops_workflow_inner(&mut workspace, receipt_bundle_size).await?;
 
     // Commit to the network
     for (basis, ops) in to_publish {
@@ -71,16 +79,15 @@ pub async fn publish_dht_ops_workflow(
 }
 
 /// Read the authored for ops with receipt count < R
+/// where R is the `receipt_bundle_size`
 pub async fn publish_dht_ops_workflow_inner(
     workspace: &mut PublishDhtOpsWorkspace,
+    receipt_bundle_size: u32,
 ) -> WorkflowResult<HashMap<AnyDhtHash, Vec<(DhtOpHash, DhtOp)>>> {
     // TODO: PERF: We need to check all ops every time this runs
     // instead we could have a queue of ops where count < R and a kv for count > R.
     // Then if the count for an ops reduces below R move it to the queue.
     let now = timestamp::now();
-    // chrono cannot create const durations
-    let interval =
-        chrono::Duration::from_std(MIN_PUBLISH_INTERVAL).expect("const interval must be positive");
 
     // one of many ways to access the env
     let env = workspace.elements.headers().env().clone();
@@ -89,19 +96,21 @@ pub async fn publish_dht_ops_workflow_inner(
         .authored()
         .iter(&r)?
         .filter_map(|(k, mut r)| {
-            Ok(if r.receipt_count < DEFAULT_RECEIPT_BUNDLE_SIZE {
+            Ok(if r.republish {
+                // The health check found too few authorities holding this op.
+                // Publish it once without touching its receipts or backoff.
+                r.republish = false;
+                r.last_publish_time = Some(now);
+                Some((DhtOpHash::from_raw_39_panicky(k.to_vec()), r))
+            } else if r.receipt_count < receipt_bundle_size {
                 let needs_publish = r
                     .last_publish_time
                     .and_then(|last| now.checked_difference_signed(&last))
-                    .map(|duration| duration > interval)
+                    .map(|duration| duration > publish_interval(r.publish_count))
                     .unwrap_or(true);
                 if needs_publish {
                     r.last_publish_time = Some(now);
-                    // HACK: Incrementing the receipt count to prevent publishing
-                    // forever although without receipts this could lead to data loss
-                    // and relies on gossip for data integrity.
-                    // This should be removed when receipts are implemented.
-                    r.receipt_count += 1;
+                    r.publish_count = r.publish_count.saturating_add(1);
                     Some((DhtOpHash::from_raw_39_panicky(k.to_vec()), r))
                 } else {
                     None
@@ -136,6 +145,18 @@ pub async fn publish_dht_ops_workflow_inner(
     Ok(to_publish)
 }
 
+/// How long to wait before publishing an op again
+/// after it has been published this many times
+fn publish_interval(publish_count: u32) -> chrono::Duration {
+    let backoff = 1u32 << publish_count.saturating_sub(1).min(16);
+    let interval = MIN_PUBLISH_INTERVAL
+        .checked_mul(backoff)
+        .map(|interval| interval.min(MAX_PUBLISH_INTERVAL))
+        .unwrap_or(MAX_PUBLISH_INTERVAL);
+    // chrono cannot create const durations
+    chrono::Duration::from_std(interval).expect("publish interval must be positive")
+}
+
 impl Workspace for PublishDhtOpsWorkspace {
     fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> WorkspaceResult<()> {
         self.authored_dht_ops.flush_to_txn_ref(writer)?;
@@ -318,9 +339,27 @@ mod tests {
     /// Call the workflow
     async fn call_workflow(env: EnvironmentWrite, mut cell_network: HolochainP2pCell) {
         let workspace = PublishDhtOpsWorkspace::new(env.clone().into()).unwrap();
-        publish_dht_ops_workflow(workspace, env.clone().into(), &mut cell_network)
-            .await
-            .unwrap();
+        publish_dht_ops_workflow(
+            workspace,
+            env.clone().into(),
+            &mut cell_network,
+            DEFAULT_RECEIPT_BUNDLE_SIZE,
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Ops that never get enough receipts are published less and less often
+    #[test]
+    fn publish_interval_backs_off() {
+        let min = chrono::Duration::from_std(MIN_PUBLISH_INTERVAL).unwrap();
+        let max = chrono::Duration::from_std(MAX_PUBLISH_INTERVAL).unwrap();
+        assert_eq!(publish_interval(0), min);
+        assert_eq!(publish_interval(1), min);
+        assert_eq!(publish_interval(2), min * 2);
+        assert_eq!(publish_interval(3), min * 4);
+        assert_eq!(publish_interval(20), max);
+        assert_eq!(publish_interval(u32::MAX), max);
     }
 
     /// There is a test that shows that network messages would be sent to all agents via broadcast.
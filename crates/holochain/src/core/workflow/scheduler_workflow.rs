@@ -0,0 +1,93 @@
+//! # Scheduler Workflow
+//!
+//! Calls the zome functions that zomes have scheduled with the `schedule`
+//! host function once they are due.
+//!
+//! The schedule is updated and committed before the functions are called,
+//! so a function that fails is not retried until it is next due.
+//! Scheduled functions are called as the author of the cell, so they
+//! always pass the capability check.
+
+use super::error::WorkflowResult;
+use crate::conductor::api::CellConductorApiT;
+use crate::core::queue_consumer::OneshotWriter;
+use crate::core::queue_consumer::WorkComplete;
+use holochain_conductor_api::ZomeCall;
+use holochain_lmdb::prelude::*;
+use holochain_state::prelude::*;
+use holochain_types::prelude::*;
+use tracing::*;
+
+/// Database buffers required for running scheduled functions
+pub struct SchedulerWorkspace {
+    /// The zome functions waiting to be called
+    pub scheduled_fns: ScheduledFnStore,
+}
+
+impl SchedulerWorkspace {
+    /// Constructor
+    pub fn new(env: EnvironmentRead) -> WorkspaceResult<Self> {
+        Ok(Self {
+            scheduled_fns: ScheduledFnStore::new(env)?,
+        })
+    }
+}
+
+impl Workspace for SchedulerWorkspace {
+    fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> WorkspaceResult<()> {
+        self.scheduled_fns.flush_to_txn_ref(writer)?;
+        Ok(())
+    }
+}
+
+#[instrument(skip(workspace, writer, conductor_api))]
+pub async fn scheduler_workflow<C: CellConductorApiT>(
+    mut workspace: SchedulerWorkspace,
+    writer: OneshotWriter,
+    conductor_api: &C,
+) -> WorkflowResult<WorkComplete> {
+    let due = workspace.scheduled_fns.take_due(timestamp::now())?;
+    if due.is_empty() {
+        return Ok(WorkComplete::Complete);
+    }
+
+    // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
+
+    // commit the workspace before calling so the schedule is moved on
+    // even if the calls fail
+    writer.with_writer(|writer| Ok(workspace.flush_to_txn(writer)?))?;
+
+    let cell_id = conductor_api.cell_id().clone();
+    for scheduled_fn in due {
+        let ScheduledFn {
+            zome_name,
+            fn_name,
+            payload,
+            ..
+        } = scheduled_fn;
+        let call = ZomeCall {
+            cell_id: cell_id.clone(),
+            zome_name: zome_name.clone(),
+            fn_name: fn_name.clone(),
+            cap: None,
+            payload,
+            provenance: cell_id.agent_pubkey().clone(),
+        };
+        match conductor_api.call_zome(&cell_id, call).await {
+            Ok(Ok(ZomeCallResponse::Ok(_))) => {
+                trace!(%zome_name, %fn_name, "Called scheduled fn");
+            }
+            Ok(Ok(response)) => {
+                warn!(%zome_name, %fn_name, ?response, "Scheduled fn was not called");
+            }
+            Ok(Err(error)) => {
+                warn!(%zome_name, %fn_name, ?error, "Scheduled fn failed");
+            }
+            Err(error) => {
+                warn!(%zome_name, %fn_name, ?error, "Failed to call scheduled fn");
+            }
+        }
+    }
+
+    Ok(WorkComplete::Complete)
+}
@@ -0,0 +1,109 @@
+//! # Slow Heal Workflow
+//!
+//! Runs as the [AutonomicProcess::SlowHeal] for a cell.
+//! Ops that could not be validated, usually because their dependencies
+//! could not be found, are integrated as abandoned. Every so often we
+//! move them out of the abandoned store and back into validation limbo
+//! so they are validated again. Whatever the outcome of that validation,
+//! the op is integrated again and gossiped with its new status.
+
+use super::error::WorkflowResult;
+use super::incoming_dht_ops_workflow::IncomingDhtOpsWorkspace;
+use super::produce_dht_ops_workflow::dht_op_light::light_to_op;
+use crate::core::queue_consumer::OneshotWriter;
+use crate::core::queue_consumer::TriggerSender;
+use fallible_iterator::FallibleIterator;
+use holo_hash::DhtOpHash;
+use holochain_lmdb::buffer::BufferedStore;
+use holochain_lmdb::buffer::KvBufFresh;
+use holochain_lmdb::db::INTEGRATED_DHT_OPS;
+use holochain_lmdb::fresh_reader;
+use holochain_lmdb::prelude::*;
+use holochain_state::prelude::*;
+use holochain_types::prelude::*;
+use tracing::*;
+
+#[cfg(test)]
+mod tests;
+
+/// Database buffers required for retrying abandoned ops
+pub struct SlowHealWorkspace {
+    /// Integrated ops, where the abandoned ops are recorded
+    pub integrated_dht_ops: IntegratedDhtOpsStore,
+    /// The data of the abandoned ops
+    pub element_abandoned: ElementBuf<AbandonedPrefix>,
+    /// Where the ops are put to be validated again
+    pub incoming: IncomingDhtOpsWorkspace,
+}
+
+impl SlowHealWorkspace {
+    /// Constructor
+    pub fn new(env: EnvironmentRead) -> WorkspaceResult<Self> {
+        let db = env.get_db(&*INTEGRATED_DHT_OPS)?;
+        let integrated_dht_ops = KvBufFresh::new(env.clone(), db);
+        let element_abandoned = ElementBuf::abandoned(env.clone())?;
+        let incoming = IncomingDhtOpsWorkspace::new(env)?;
+        Ok(Self {
+            integrated_dht_ops,
+            element_abandoned,
+            incoming,
+        })
+    }
+}
+
+impl Workspace for SlowHealWorkspace {
+    fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> WorkspaceResult<()> {
+        self.integrated_dht_ops.flush_to_txn_ref(writer)?;
+        self.element_abandoned.flush_to_txn_ref(writer)?;
+        self.incoming.flush_to_txn_ref(writer)?;
+        Ok(())
+    }
+}
+
+/// Put all abandoned ops back into validation limbo.
+/// Returns the number of ops that will be validated again.
+#[instrument(skip(workspace, writer, trigger_sys))]
+pub async fn slow_heal_workflow(
+    mut workspace: SlowHealWorkspace,
+    writer: OneshotWriter,
+    trigger_sys: &mut TriggerSender,
+) -> WorkflowResult<usize> {
+    let env = workspace.element_abandoned.headers().env().clone();
+    let abandoned = fresh_reader!(env, |r| workspace
+        .integrated_dht_ops
+        .iter(&r)?
+        .filter(|(_, v)| Ok(v.validation_status == ValidationStatus::Abandoned))
+        .map(|(k, v)| Ok((DhtOpHash::from_raw_39_panicky(k.to_vec()), v.op)))
+        .collect::<Vec<_>>())?;
+
+    // Get all the ops before removing any data because
+    // ops can share the same header or entry
+    let mut ops = Vec::with_capacity(abandoned.len());
+    for (hash, op_light) in abandoned {
+        let op = light_to_op(op_light.clone(), &workspace.element_abandoned)?;
+        ops.push((hash, op_light, op));
+    }
+
+    let num_ops = ops.len();
+    for (hash, op_light, op) in ops {
+        debug!(?hash, "Retrying abandoned op");
+        workspace.integrated_dht_ops.delete(hash.clone())?;
+        let entry_hash = op.header().entry_data().map(|(h, _)| h.clone());
+        workspace
+            .element_abandoned
+            .delete(op_light.header_hash().clone(), entry_hash);
+        workspace.incoming.add_to_pending(hash, op, None, false)?;
+    }
+
+    // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
+
+    // commit the workspace
+    writer.with_writer(|writer| Ok(workspace.flush_to_txn(writer)?))?;
+
+    // trigger other workflows
+    if num_ops > 0 {
+        trigger_sys.trigger();
+    }
+
+    Ok(num_ops)
+}
@@ -0,0 +1,70 @@
+use super::*;
+use crate::core::workflow::integrate_dht_ops_workflow::integrate_single_data;
+use ::fixt::prelude::*;
+use holochain_lmdb::test_utils::test_cell_env;
+
+#[tokio::test(threaded_scheduler)]
+async fn abandoned_ops_are_validated_again() {
+    observability::test_run().ok();
+    let test_env = test_cell_env();
+    let env = test_env.env();
+
+    // Integrate an abandoned op
+    let op = DhtOp::RegisterAgentActivity(fixt!(Signature), fixt!(Header));
+    let op_light = op.to_light();
+    let hash = DhtOpHash::with_data_sync(&op);
+    {
+        let mut workspace = SlowHealWorkspace::new(env.clone().into()).unwrap();
+        integrate_single_data(op.clone(), &mut workspace.element_abandoned).unwrap();
+        workspace
+            .integrated_dht_ops
+            .put(
+                hash.clone(),
+                IntegratedDhtOpsValue {
+                    validation_status: ValidationStatus::Abandoned,
+                    op: op_light.clone(),
+                    when_integrated: timestamp::now(),
+                },
+            )
+            .unwrap();
+        env.guard()
+            .with_commit(|writer| workspace.flush_to_txn(writer))
+            .unwrap();
+    }
+
+    let (mut trigger_sys, mut rx) = TriggerSender::new();
+    let workspace = SlowHealWorkspace::new(env.clone().into()).unwrap();
+    let num_ops = slow_heal_workflow(workspace, env.clone().into(), &mut trigger_sys)
+        .await
+        .unwrap();
+    assert_eq!(num_ops, 1);
+    rx.listen().await.expect("Sys validation was not triggered");
+
+    // The op is back in validation limbo with a fresh start
+    let workspace = SlowHealWorkspace::new(env.clone().into()).unwrap();
+    assert!(!workspace.integrated_dht_ops.contains(&hash).unwrap());
+    assert!(!workspace
+        .element_abandoned
+        .contains_header(op_light.header_hash())
+        .unwrap());
+    assert!(workspace
+        .incoming
+        .element_pending
+        .contains_header(op_light.header_hash())
+        .unwrap());
+    let vlv = workspace
+        .incoming
+        .validation_limbo
+        .get(&hash)
+        .unwrap()
+        .unwrap();
+    assert_eq!(vlv.status, ValidationLimboStatus::Pending);
+    assert_eq!(vlv.num_tries, 0);
+
+    // Nothing left to heal
+    let workspace = SlowHealWorkspace::new(env.clone().into()).unwrap();
+    let num_ops = slow_heal_workflow(workspace, env.clone().into(), &mut trigger_sys)
+        .await
+        .unwrap();
+    assert_eq!(num_ops, 0);
+}
@@ -8,9 +8,11 @@ use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
 use crate::core::sys_validate::*;
 use crate::core::validation::*;
+use crate::core::workflow::app_validation_workflow::send_validation_receipts;
 use error::WorkflowError;
 use error::WorkflowResult;
 use fallible_iterator::FallibleIterator;
+use holo_hash::AgentPubKey;
 use holo_hash::DhtOpHash;
 use holochain_cascade::Cascade;
 use holochain_cascade::DbPair;
@@ -56,13 +58,13 @@ pub async fn sys_validation_workflow(
     writer: OneshotWriter,
     trigger_app_validation: &mut TriggerSender,
     sys_validation_trigger: TriggerSender,
-    network: HolochainP2pCell,
+    mut network: HolochainP2pCell,
     conductor_api: impl CellConductorApiT,
 ) -> WorkflowResult<WorkComplete> {
-    let complete = sys_validation_workflow_inner(
+    let (complete, receipts) = sys_validation_workflow_inner(
         &mut workspace,
-        network,
-        conductor_api,
+        network.clone(),
+        &conductor_api,
         sys_validation_trigger,
     )
     .await?;
@@ -72,6 +74,10 @@ pub async fn sys_validation_workflow(
     // commit the workspace
     writer.with_writer(|writer| Ok(workspace.flush_to_txn_ref(writer)?))?;
 
+    // send receipts for the ops that skip app validation
+    // and are now committed as valid
+    send_validation_receipts(receipts, &conductor_api, &mut network).await;
+
     // trigger other workflows
     trigger_app_validation.trigger();
 
@@ -81,9 +87,9 @@ pub async fn sys_validation_workflow(
 async fn sys_validation_workflow_inner(
     workspace: &mut SysValidationWorkspace,
     network: HolochainP2pCell,
-    conductor_api: impl CellConductorApiT,
+    conductor_api: &impl CellConductorApiT,
     sys_validation_trigger: TriggerSender,
-) -> WorkflowResult<WorkComplete> {
+) -> WorkflowResult<(WorkComplete, Vec<(DhtOpHash, AgentPubKey)>)> {
     let env = workspace.validation_limbo.env().clone();
     // Drain all the ops
     let sorted_ops: BinaryHeap<OrderedOp<ValidationLimboValue>> = fresh_reader!(env, |r| {
@@ -121,6 +127,9 @@ async fn sys_validation_workflow_inner(
         sorted_ops
     })?;
 
+    // The ops that need a validation receipt sent to their author
+    let mut receipts = Vec::new();
+
     // Process each op
     for so in sorted_ops.into_sorted_vec() {
         let OrderedOp {
@@ -140,7 +149,7 @@ async fn sys_validation_workflow_inner(
             &op,
             workspace,
             network.clone(),
-            &conductor_api,
+            conductor_api,
             Some(incoming_dht_ops_sender),
         )
         .await?;
@@ -151,6 +160,9 @@ async fn sys_validation_workflow_inner(
                 workspace.put_val_limbo(op_hash, vlv)?;
             }
             Outcome::SkipAppValidation => {
+                if vlv.send_receipt {
+                    receipts.push((op_hash.clone(), op.header().author().clone()));
+                }
                 let iv = IntegrationLimboValue {
                     op: vlv.op,
                     validation_status: ValidationStatus::Valid,
@@ -167,12 +179,20 @@ async fn sys_validation_workflow_inner(
                 // We need to be holding the dependency because
                 // we were meant to get a StoreElement or StoreEntry or
                 // RegisterAgentActivity or RegisterAddLink.
-                vlv.status = ValidationLimboStatus::AwaitingSysDeps(missing_dep);
-                workspace.put_val_limbo(op_hash, vlv)?;
+                if vlv.should_abandon() {
+                    workspace.abandon(op_hash, vlv)?;
+                } else {
+                    vlv.status = ValidationLimboStatus::AwaitingSysDeps(missing_dep);
+                    workspace.put_val_limbo(op_hash, vlv)?;
+                }
             }
             Outcome::MissingDhtDep => {
-                vlv.status = ValidationLimboStatus::Pending;
-                workspace.put_val_limbo(op_hash, vlv)?;
+                if vlv.should_abandon() {
+                    workspace.abandon(op_hash, vlv)?;
+                } else {
+                    vlv.status = ValidationLimboStatus::Pending;
+                    workspace.put_val_limbo(op_hash, vlv)?;
+                }
             }
             Outcome::Rejected => {
                 let iv = IntegrationLimboValue {
@@ -183,7 +203,7 @@ async fn sys_validation_workflow_inner(
             }
         }
     }
-    Ok(WorkComplete::Complete)
+    Ok((WorkComplete::Complete, receipts))
 }
 
 async fn validate_op(
@@ -230,10 +250,13 @@ async fn validate_op(
 fn handle_failed(error: ValidationOutcome) -> Outcome {
     use Outcome::*;
     match error {
+        ValidationOutcome::AuthorKeyRevoked(_) => Rejected,
         ValidationOutcome::Counterfeit(_, _) => {
             unreachable!("Counterfeit ops are dropped before sys validation")
         }
         ValidationOutcome::DepMissingFromDht(_) => MissingDhtDep,
+        // Try again later when DPKI may be back
+        ValidationOutcome::DpkiUnavailable(_, _) => MissingDhtDep,
         ValidationOutcome::EntryDefId(_) => Rejected,
         ValidationOutcome::EntryHash => Rejected,
         ValidationOutcome::EntryTooLarge(_, _) => Rejected,
@@ -248,6 +271,7 @@ fn handle_failed(error: ValidationOutcome) -> Outcome {
         }
         ValidationOutcome::PrevHeaderError(_) => Rejected,
         ValidationOutcome::PrivateEntry => Rejected,
+        ValidationOutcome::RateLimited(_) => Rejected,
         ValidationOutcome::UpdateTypeMismatch(_, _) => Rejected,
         ValidationOutcome::VerifySignature(_, _) => Rejected,
         ValidationOutcome::ZomeId(_) => Rejected,
@@ -261,6 +285,7 @@ async fn validate_op_inner(
     conductor_api: &impl CellConductorApiT,
     incoming_dht_ops_sender: Option<IncomingDhtOpSender>,
 ) -> SysValidationResult<()> {
+    check_author_key(&op.header(), conductor_api).await?;
     match op {
         DhtOp::StoreElement(_, header, entry) => {
             store_element(header, workspace, network.clone()).await?;
@@ -293,8 +318,14 @@ async fn validate_op_inner(
             Ok(())
         }
         DhtOp::RegisterAgentActivity(_, header) => {
-            register_agent_activity(header, workspace, network.clone(), incoming_dht_ops_sender)
-                .await?;
+            register_agent_activity(
+                header,
+                workspace,
+                network.clone(),
+                conductor_api,
+                incoming_dht_ops_sender,
+            )
+            .await?;
             store_element(header, workspace, network).await?;
             Ok(())
         }
@@ -399,6 +430,7 @@ async fn sys_validate_element_inner(
     if !counterfeit_check(signature, header).await? {
         return Err(ValidationOutcome::Counterfeit(signature.clone(), header.clone()).into());
     }
+    check_author_key(header, conductor_api).await?;
     store_element(header, workspace, network.clone()).await?;
     if let Some((entry, EntryVisibility::Public)) =
         &entry.and_then(|e| header.entry_type().map(|et| (e, et.visibility())))
@@ -433,20 +465,22 @@ async fn sys_validate_element_inner(
     Ok(())
 }
 
-/// Check if the op has valid signature and author.
+/// Check if the op has a valid signature.
 /// Ops that fail this check should be dropped.
+/// Whether the author's key was valid is checked
+/// in sys validation, where dpki is available.
 pub async fn counterfeit_check(
     signature: &Signature,
     header: &Header,
 ) -> SysValidationResult<bool> {
-    Ok(verify_header_signature(&signature, &header).await?
-        && author_key_is_valid(header.author()).await?)
+    verify_header_signature(&signature, &header).await
 }
 
 async fn register_agent_activity(
     header: &Header,
     workspace: &mut SysValidationWorkspace,
     network: HolochainP2pCell,
+    conductor_api: &impl CellConductorApiT,
     incoming_dht_ops_sender: Option<IncomingDhtOpSender>,
 ) -> SysValidationResult<()> {
     // Get data ready to validate
@@ -466,6 +500,8 @@ async fn register_agent_activity(
         .await?;
     }
     check_chain_rollback(&header, &workspace).await?;
+    let dna_file = conductor_api.get_this_dna().await.map_err(Box::new)?;
+    check_spam(&header, &dna_file.dna_def().rate_limits, &workspace)?;
     Ok(())
 }
 
@@ -770,6 +806,17 @@ impl SysValidationWorkspace {
         Ok(())
     }
 
+    /// Give up on an op whose dependencies we couldn't find.
+    /// It will be retried by the slow heal process.
+    fn abandon(&mut self, hash: DhtOpHash, vlv: ValidationLimboValue) -> WorkflowResult<()> {
+        debug!(?hash, num_tries = vlv.num_tries, "Abandoning op");
+        let iv = IntegrationLimboValue {
+            op: vlv.op,
+            validation_status: ValidationStatus::Abandoned,
+        };
+        self.put_int_limbo(hash, iv)
+    }
+
     #[tracing::instrument(skip(self, hash))]
     fn put_int_limbo(&mut self, hash: DhtOpHash, iv: IntegrationLimboValue) -> WorkflowResult<()> {
         self.integration_limbo.put(hash, iv)?;
@@ -856,6 +903,7 @@ impl TryFrom<&CallZomeWorkspace> for SysValidationWorkspace {
             meta_rejected,
             element_cache,
             meta_cache,
+            ..
         } = call_zome;
         let mut sys_val = Self::new(call_zome.env().clone())?;
         sys_val.element_authored = source_chain.elements().into();
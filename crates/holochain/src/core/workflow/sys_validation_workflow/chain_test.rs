@@ -26,7 +26,9 @@ async fn sys_validation_agent_activity_test() {
             name: "chain_test".to_string(),
             uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
             properties: SerializedBytes::try_from(()).unwrap(),
+            rate_limits: Vec::new(),
             zomes: vec![TestWasm::Create.into()].into(),
+            redundancy_target: None,
         },
         vec![TestWasm::Create.into()],
     )
@@ -120,6 +122,7 @@ async fn run_test(alice_cell_id: CellId, handle: ConductorHandle) {
         sys_validation_trigger.clone(),
         ops,
         None,
+        false,
     )
     .await
     .unwrap();
@@ -176,6 +179,7 @@ async fn run_test(alice_cell_id: CellId, handle: ConductorHandle) {
         sys_validation_trigger,
         ops,
         None,
+        false,
     )
     .await
     .unwrap();
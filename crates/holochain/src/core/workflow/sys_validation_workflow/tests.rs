@@ -35,7 +35,9 @@ async fn sys_validation_workflow_test() {
             name: "sys_validation_workflow_test".to_string(),
             uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
             properties: SerializedBytes::try_from(()).unwrap(),
+            rate_limits: Vec::new(),
             zomes: vec![TestWasm::Create.into()].into(),
+            redundancy_target: None,
         },
         vec![TestWasm::Create.into()],
     )
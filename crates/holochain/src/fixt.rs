@@ -3,6 +3,7 @@ pub mod curve;
 use crate::conductor::api::CellConductorApi;
 use crate::conductor::api::CellConductorReadHandle;
 use crate::conductor::handle::MockConductorHandleT;
+use crate::conductor::interface::DataSubscribers;
 use crate::conductor::interface::SignalBroadcaster;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsHostAccess;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsInvocation;
@@ -201,7 +202,7 @@ fixturator!(
 
 fixturator!(
     LinkMetaVal;
-    constructor fn new(HeaderHash, EntryHash, Timestamp, u8, LinkTag);
+    constructor fn new(HeaderHash, EntryHash, Timestamp, u8, LinkTag, AgentPubKey);
 );
 
 impl Iterator for LinkMetaValFixturator<(EntryHash, LinkTag)> {
@@ -260,13 +261,13 @@ fixturator!(
 fixturator!(
     SignalBroadcaster;
     curve Empty {
-        SignalBroadcaster::new(Vec::new())
+        SignalBroadcaster::new(Vec::new(), DataSubscribers::default())
     };
     curve Unpredictable {
-        SignalBroadcaster::new(Vec::new())
+        SignalBroadcaster::new(Vec::new(), DataSubscribers::default())
     };
     curve Predictable {
-        SignalBroadcaster::new(Vec::new())
+        SignalBroadcaster::new(Vec::new(), DataSubscribers::default())
     };
 );
 
@@ -345,7 +346,7 @@ fixturator!(
 
 fixturator!(
     PostCommitHostAccess;
-    constructor fn new(CallZomeWorkspaceLock, KeystoreSender, HolochainP2pCell);
+    constructor fn new(CallZomeWorkspaceLock, KeystoreSender, HolochainP2pCell, SignalBroadcaster, CellId);
 );
 
 fixturator!(
@@ -480,7 +480,9 @@ async fn setup(
             name: "conductor_test".to_string(),
             uuid,
             properties: SerializedBytes::try_from(()).unwrap(),
+            rate_limits: Vec::new(),
             zomes: zomes.clone().into_iter().map(Into::into).collect(),
+            redundancy_target: None,
         },
         zomes.into_iter().map(Into::into),
     )
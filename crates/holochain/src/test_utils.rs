@@ -380,7 +380,8 @@ pub async fn consistency_envs(
     let query = ChainQueryFilter::new().include_entries(true);
     for &env in all_cell_envs.iter() {
         let chain = SourceChain::new(env.clone().into()).unwrap();
-        let elements = chain.query(&query).unwrap();
+        let meta_authored = MetadataBuf::authored(env.clone().into()).unwrap();
+        let elements = chain.query(&query, &meta_authored).unwrap();
         let elements = elements.iter().collect::<Vec<_>>();
         let count = produce_op_lights_from_elements(elements).unwrap().len();
         expected_count += count;
@@ -167,7 +167,9 @@ impl ConductorTestData {
                 name: "conductor_test".to_string(),
                 uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
                 properties: SerializedBytes::try_from(()).unwrap(),
+                rate_limits: Vec::new(),
                 zomes: zomes.clone().into_iter().map(Into::into).collect(),
+                redundancy_target: None,
             },
             zomes.into_iter().map(Into::into),
         )
@@ -171,7 +171,9 @@ async fn get_from_another_agent() {
             name: "dht_get_test".to_string(),
             uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
             properties: SerializedBytes::try_from(()).unwrap(),
+            rate_limits: Vec::new(),
             zomes: vec![TestWasm::Create.into()].into(),
+            redundancy_target: None,
         },
         vec![TestWasm::Create.into()],
     )
@@ -315,7 +317,9 @@ async fn get_links_from_another_agent() {
             name: "dht_get_test".to_string(),
             uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
             properties: SerializedBytes::try_from(()).unwrap(),
+            rate_limits: Vec::new(),
             zomes: vec![TestWasm::Create.into()].into(),
+            redundancy_target: None,
         },
         vec![TestWasm::Create.into()],
     )
@@ -349,7 +353,7 @@ async fn get_links_from_another_agent() {
     )
     .await;
 
-    let link_options = GetLinksOptions { timeout_ms: None };
+    let link_options = GetLinksOptions::default();
 
     // Bob store links
     let base = Post("Bananas are good for you".into());
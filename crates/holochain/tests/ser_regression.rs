@@ -52,7 +52,9 @@ async fn ser_regression_test() {
             name: "ser_regression_test".to_string(),
             uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
             properties: SerializedBytes::try_from(()).unwrap(),
+            rate_limits: Vec::new(),
             zomes: vec![TestWasm::SerRegression.into()].into(),
+            redundancy_target: None,
         },
         vec![TestWasm::SerRegression.into()],
     )
@@ -132,7 +132,9 @@ async fn speed_test(n: Option<usize>) -> TestEnvironments {
             name: "need_for_speed_test".to_string(),
             uuid: "ba1d046d-ce29-4778-914b-47e6010d2faf".to_string(),
             properties: SerializedBytes::try_from(()).unwrap(),
+            rate_limits: Vec::new(),
             zomes: vec![TestWasm::Anchor.into()].into(),
+            redundancy_target: None,
         },
         vec![TestWasm::Anchor.into()],
     )
@@ -192,7 +194,10 @@ async fn speed_test(n: Option<usize>) -> TestEnvironments {
 
     // Setup websocket handle and app interface
     let (mut client, _) = websocket_client(&handle).await.unwrap();
-    let request = AdminRequest::AttachAppInterface { port: None };
+    let request = AdminRequest::AttachAppInterface {
+        port: None,
+        installed_app_id: None,
+    };
     let response = client.request(request);
     let response = response.await.unwrap();
     let app_port = match response {
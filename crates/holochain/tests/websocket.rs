@@ -86,6 +86,9 @@ fn create_config(port: u16, environment_path: PathBuf) -> ConductorConfig {
         }),
         keystore_path: None,
         use_dangerous_test_keystore: true,
+        receipt_bundle_size: None,
+        redundancy_target: None,
+        encrypt_private_entries: false,
     }
 }
 
@@ -261,7 +264,10 @@ pub async fn attach_app_interface(
     holochain: &mut Child,
     port: Option<u16>,
 ) -> u16 {
-    let request = AdminRequest::AttachAppInterface { port };
+    let request = AdminRequest::AttachAppInterface {
+        port,
+        installed_app_id: None,
+    };
     let response = client.request(request);
     let response = check_timeout(holochain, response, 1000).await;
     match response {
@@ -438,7 +444,7 @@ async fn remote_signals() {
     for mut rx in rxs {
         let r = rx.try_recv();
         // Each handle should recv a signal
-        assert_matches!(r, Ok(Signal::App(_, a)) if a == signal);
+        assert_matches!(r, Ok(Signal::App { signal: a, .. }) if a == signal);
     }
 }
 
@@ -528,7 +534,12 @@ async fn emit_signals() {
     let sig2: SerializedBytes = unwrap_to::unwrap_to!(msg2 => WebsocketMessage::Signal).clone();
 
     assert_eq!(
-        Signal::App(cell_id, AppSignal::new(ExternIO::encode(()).unwrap())),
+        Signal::App {
+            cell_id,
+            zome_name: TestWasm::EmitSignal.into(),
+            provenance: None,
+            signal: AppSignal::new(ExternIO::encode(()).unwrap()),
+        },
         Signal::try_from(sig1.clone()).unwrap(),
     );
     assert_eq!(sig1, sig2);
@@ -2,7 +2,9 @@ use super::error::AuthorityDataError;
 use super::error::CascadeError;
 use super::error::CascadeResult;
 use fallible_iterator::FallibleIterator;
+use holo_hash::hash_type::AnyDht;
 use holo_hash::AgentPubKey;
+use holo_hash::AnyDhtHash;
 use holo_hash::EntryHash;
 use holo_hash::HeaderHash;
 use holochain_lmdb::env::EnvironmentRead;
@@ -17,6 +19,7 @@ use holochain_state::metadata::ChainItemKey;
 use holochain_state::metadata::LinkMetaKey;
 use holochain_state::metadata::MetadataBuf;
 use holochain_state::metadata::MetadataBufT;
+use holochain_state::warrants::WarrantStore;
 use holochain_types::prelude::*;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
@@ -105,6 +108,19 @@ pub fn handle_get_entry(
 
         // We only want the headers if they are live and all deletes
         } else {
+            // The requester needs the updates to follow redirects
+            if options.follow_redirects {
+                let updates_returns = meta_vault
+                    .get_updates(&reader, hash.clone().into())?
+                    .collect::<Vec<_>>()?;
+                for update in updates_returns {
+                    let update: WireHeaderStatus<WireUpdateRelationship> =
+                        render_header_and_status(update)?
+                            .try_into()
+                            .map_err(AuthorityDataError::from)?;
+                    updates.push(update);
+                }
+            }
             for hash in headers {
                 // Check for a delete
                 let is_deleted = meta_vault
@@ -246,6 +262,67 @@ pub fn handle_get_element(
     Ok(GetElementResponse::GetHeader(r))
 }
 
+#[instrument(skip(env, options))]
+pub fn handle_get_meta(
+    env: EnvironmentRead,
+    hash: AnyDhtHash,
+    options: holochain_p2p::event::GetMetaOptions,
+) -> CascadeResult<MetadataSet> {
+    let meta_vault = MetadataBuf::vault(env.clone())?;
+    let request = options.metadata_request;
+
+    fresh_reader!(env, |r| {
+        let mut headers = BTreeSet::new();
+        let mut invalid_headers = BTreeSet::new();
+        let mut deletes = BTreeSet::new();
+        let mut updates = BTreeSet::new();
+        let mut entry_dht_status = None;
+
+        match *hash.hash_type() {
+            AnyDht::Entry => {
+                let entry_hash: EntryHash = hash.clone().into();
+                if request.all_valid_headers {
+                    headers = meta_vault.get_headers(&r, entry_hash.clone())?.collect()?;
+                }
+                if request.all_invalid_headers {
+                    invalid_headers = meta_vault
+                        .get_rejected_headers(&r, entry_hash.clone())?
+                        .collect()?;
+                }
+                if request.all_deletes {
+                    deletes = meta_vault
+                        .get_deletes_on_entry(&r, entry_hash.clone())?
+                        .collect()?;
+                }
+                if request.entry_dht_status {
+                    entry_dht_status = Some(meta_vault.get_dht_status(&r, &entry_hash)?);
+                }
+            }
+            AnyDht::Header => {
+                let header_hash: HeaderHash = hash.clone().into();
+                // Headers are only registered on entries so there are
+                // none to return for a header basis
+                if request.all_deletes {
+                    deletes = meta_vault
+                        .get_deletes_on_header(&r, header_hash)?
+                        .collect()?;
+                }
+            }
+        }
+        if request.all_updates {
+            updates = meta_vault.get_updates(&r, hash)?.collect()?;
+        }
+
+        CascadeResult::Ok(MetadataSet {
+            headers,
+            invalid_headers,
+            deletes,
+            updates,
+            entry_dht_status,
+        })
+    })
+}
+
 #[instrument(skip(env))]
 pub fn handle_get_agent_activity(
     env: EnvironmentRead,
@@ -263,6 +340,7 @@ pub fn handle_get_agent_activity(
         .get_activity_status(&agent)?
         .unwrap_or(ChainStatus::Empty);
     let highest_observed = meta_integrated.get_activity_observed(&agent)?;
+    let warrants = WarrantStore::new(env.clone())?.get_warrants(&agent)?;
 
     // Valid headers
     let valid_activity = if options.include_valid_activity {
@@ -302,6 +380,7 @@ pub fn handle_get_agent_activity(
         agent,
         status,
         highest_observed,
+        warrants,
     })
 }
 
@@ -311,7 +390,13 @@ fn get_full_headers<'a, P: PrefixType + 'a, R: Readable>(
     database: ElementBuf<P>,
     reader: &'a R,
 ) -> impl FallibleIterator<Item = (u32, SignedHeaderHashed), Error = DatabaseError> + 'a {
+    let start = query.sequence_range.as_ref().map(|r| r.start);
+    let end = query.sequence_range.as_ref().map(|r| r.end);
     hashes
+        // The activity is in sequence order so a range can be paged
+        // through without reading the headers outside of it
+        .skip_while(move |(s, _)| Ok(start.map(|start| *s < start).unwrap_or(false)))
+        .take_while(move |(s, _)| Ok(end.map(|end| *s < end).unwrap_or(true)))
         .filter_map(move |(s, h)| {
             Ok(database
                 .get_header_with_reader(reader, &h)?
@@ -393,11 +478,11 @@ fn _show_agent_activity_read_times(env: EnvironmentRead, agent: AgentPubKey) {
     });
 }
 
-#[instrument(skip(env, _options))]
+#[instrument(skip(env, options))]
 pub fn handle_get_links(
     env: EnvironmentRead,
     link_key: WireLinkMetaKey,
-    _options: holochain_p2p::event::GetLinksOptions,
+    options: holochain_p2p::event::GetLinksOptions,
 ) -> CascadeResult<GetLinksResponse> {
     // Get the vaults
     let env_ref = env.guard();
@@ -405,22 +490,27 @@ pub fn handle_get_links(
     let element_vault = ElementBuf::vault(env.clone(), false)?;
     let meta_vault = MetadataBuf::vault(env.clone())?;
 
-    let links = meta_vault
-        .get_links_all(&reader, &LinkMetaKey::from(&link_key))?
-        .map(|link_add| {
-            // Collect the link removes on this link add
-            let link_removes = meta_vault
-                .get_link_removes_on_link_add(&reader, link_add.link_add_hash.clone())?
-                .collect::<BTreeSet<_>>()?;
-            // Create timed header hash
-            let link_add = TimedHeaderHash {
-                timestamp: link_add.timestamp,
-                header_hash: link_add.link_add_hash,
-            };
-            // Return all link removes with this link add
-            Ok((link_add, link_removes))
-        })
-        .collect::<BTreeMap<_, _>>()?;
+    let key = LinkMetaKey::from(&link_key);
+    let links = if options.query.is_empty() {
+        meta_vault.get_links_all(&reader, &key)?
+    } else {
+        // Only live links are paged so deleted links can't cut a page short
+        meta_vault.get_live_links_query(&reader, &key, &options.query)?
+    }
+    .map(|link_add| {
+        // Collect the link removes on this link add
+        let link_removes = meta_vault
+            .get_link_removes_on_link_add(&reader, link_add.link_add_hash.clone())?
+            .collect::<BTreeSet<_>>()?;
+        // Create timed header hash
+        let link_add = TimedHeaderHash {
+            timestamp: link_add.timestamp,
+            header_hash: link_add.link_add_hash,
+        };
+        // Return all link removes with this link add
+        Ok((link_add, link_removes))
+    })
+    .collect::<BTreeMap<_, _>>()?;
 
     // Get the headers from the element stores
     let mut result_adds: Vec<(CreateLink, Signature)> = Vec::with_capacity(links.len());
@@ -452,3 +542,21 @@ pub fn handle_get_links(
         link_removes: result_removes,
     })
 }
+
+#[instrument(skip(env, options))]
+/// Count the live links that match the query from the index
+/// without reading any headers
+pub fn handle_count_links(
+    env: EnvironmentRead,
+    link_key: WireLinkMetaKey,
+    options: holochain_p2p::event::GetLinksOptions,
+) -> CascadeResult<CountLinksResponse> {
+    let meta_vault = MetadataBuf::vault(env.clone())?;
+    let key = LinkMetaKey::from(&link_key);
+    let count = fresh_reader!(env, |r| {
+        meta_vault
+            .get_live_links_query(&r, &key, &options.query)?
+            .count()
+    })?;
+    Ok(CountLinksResponse(count))
+}
@@ -13,6 +13,7 @@ use holo_hash::AnyDhtHash;
 use holo_hash::EntryHash;
 use holo_hash::HasHash;
 use holo_hash::HeaderHash;
+use holochain_lmdb::error::DatabaseError;
 use holochain_lmdb::error::DatabaseResult;
 use holochain_lmdb::fresh_reader;
 use holochain_lmdb::prelude::*;
@@ -26,6 +27,7 @@ use holochain_state::prelude::*;
 use holochain_types::prelude::*;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::collections::HashSet;
 use tracing::*;
 use tracing_futures::Instrument;
 
@@ -134,6 +136,8 @@ pub struct Cascade<
     cache_data: Option<DbPairMut<'a, MetaCache>>,
     env: Option<EnvironmentRead>,
     network: Option<Network>,
+    /// Where to keep verified warrants received from agent activity authorities
+    warrants: Option<&'a mut WarrantStore>,
 }
 
 #[derive(Debug)]
@@ -202,6 +206,7 @@ where
             integrated_data,
             authored_data,
             cache_data,
+            warrants: None,
         }
     }
 }
@@ -217,6 +222,7 @@ impl<'a> Cascade<'a> {
             cache_data: None,
             env: None,
             network: None,
+            warrants: None,
         }
     }
 }
@@ -275,6 +281,13 @@ where
         self
     }
 
+    /// Add the [WarrantStore] to the cascade so warrants
+    /// received with agent activity are kept
+    pub fn with_warrants(mut self, warrants: &'a mut WarrantStore) -> Self {
+        self.warrants = Some(warrants);
+        self
+    }
+
     /// Add the integrated [ElementBuf] and [MetadataBuf] to the cascade
     pub fn with_network<N: HolochainP2pCellT + Clone>(
         self,
@@ -288,6 +301,7 @@ where
             cache_data: self.cache_data,
             env: self.env,
             network: Some(network),
+            warrants: self.warrants,
         }
     }
 
@@ -297,6 +311,18 @@ where
         &mut self,
         agent_activity: AgentActivityResponse,
     ) -> CascadeResult<()> {
+        // Keep any warrants against this agent that hold up.
+        // We don't trust the authority so each warrant must carry its own proof.
+        if let Some(warrants) = self.warrants.as_mut() {
+            for warrant in agent_activity.warrants.iter() {
+                if warrant.warranted_agent() == &agent_activity.agent
+                    && !warrants.contains(&warrant.proof)?
+                    && verify_warrant(warrant).await?
+                {
+                    warrants.add_if_unique(warrant.clone())?;
+                }
+            }
+        }
         let cache_data = ok_or_return!(self.cache_data.as_mut());
         let AgentActivityResponse {
             agent,
@@ -420,14 +446,13 @@ where
         &mut self,
         key: &'link LinkMetaKey<'link>,
         options: GetLinksOptions,
-    ) -> CascadeResult<()> {
+    ) -> CascadeResult<HashSet<HeaderHash>> {
         if self.cache_data.is_none() {
-            return Ok(());
+            return Ok(HashSet::new());
         }
-        let env = ok_or_return!(self.env.clone());
+        let env = ok_or_return!(self.env.clone(), HashSet::new());
         let response = authority::handle_get_links(env, key.into(), (&options).into())?;
-        self.put_link_in_cache(response)?;
-        Ok(())
+        self.put_link_in_cache(response)
     }
 
     #[instrument(skip(self, elements))]
@@ -651,18 +676,24 @@ where
         Ok(network.get_meta(basis.clone(), options).await?)
     }
 
-    fn put_link_in_cache(&mut self, response: GetLinksResponse) -> CascadeResult<()> {
+    /// Cache the links in a response and return the hashes of their [CreateLink]s
+    fn put_link_in_cache(
+        &mut self,
+        response: GetLinksResponse,
+    ) -> CascadeResult<HashSet<HeaderHash>> {
         let GetLinksResponse {
             link_adds,
             link_removes,
         } = response;
 
+        let mut link_add_hashes = HashSet::with_capacity(link_adds.len());
         for (link_add, signature) in link_adds {
             debug!(?link_add);
             let element = Element::new(
                 SignedHeaderHashed::from_content_sync(SignedHeader(link_add.into(), signature)),
                 None,
             );
+            link_add_hashes.insert(element.header_address().clone());
             // TODO: Assuming links are also valid headers.
             // We will need to prove this is the case in the future.
             self.update_stores(ElementStatus::new(element, ValidationStatus::Valid))?;
@@ -677,23 +708,26 @@ where
             // We will need to prove this is the case in the future.
             self.update_stores(ElementStatus::new(element, ValidationStatus::Valid))?;
         }
-        Ok(())
+        Ok(link_add_hashes)
     }
 
+    /// Fetch links from the network into the cache and
+    /// return the hashes of the [CreateLink]s that were returned
     #[instrument(skip(self, options))]
     async fn fetch_links(
         &mut self,
         link_key: WireLinkMetaKey,
         options: GetLinksOptions,
-    ) -> CascadeResult<()> {
+    ) -> CascadeResult<HashSet<HeaderHash>> {
         debug!("in get links");
-        let network = ok_or_return!(self.network.as_mut());
+        let network = ok_or_return!(self.network.as_mut(), HashSet::new());
         let results = network.get_links(link_key, options).await?;
 
+        let mut link_add_hashes = HashSet::new();
         for response in results {
-            self.put_link_in_cache(response)?;
+            link_add_hashes.extend(self.put_link_in_cache(response)?);
         }
-        Ok(())
+        Ok(link_add_hashes)
     }
 
     /// Get the element from any databases that the Cascade has been constructed with
@@ -1503,10 +1537,58 @@ where
         hash: AnyDhtHash,
         options: GetOptions,
     ) -> CascadeResult<Option<Element>> {
-        match *hash.hash_type() {
-            AnyDht::Entry => self.dht_get_entry(hash.into(), options).await,
-            AnyDht::Header => self.dht_get_header(hash.into(), options).await,
+        let mut hash = hash;
+        let mut seen = HashSet::new();
+        loop {
+            let element = match *hash.hash_type() {
+                AnyDht::Entry => {
+                    self.dht_get_entry(hash.clone().into(), options.clone())
+                        .await?
+                }
+                AnyDht::Header => {
+                    self.dht_get_header(hash.clone().into(), options.clone())
+                        .await?
+                }
+            };
+            if !options.follow_redirects {
+                return Ok(element);
+            }
+            seen.insert(hash.clone());
+            // The get has put any updates the authorities know
+            // about into our stores so we can follow them from here.
+            match self.get_redirect(&hash)? {
+                Some(next) if !seen.contains(&next) => hash = next,
+                _ => return Ok(element),
+            }
+        }
+    }
+
+    /// Find where this hash redirects to using the updates
+    /// in the cache and authored stores.
+    /// Returns None if there are no live updates to follow.
+    fn get_redirect(&self, hash: &AnyDhtHash) -> CascadeResult<Option<AnyDhtHash>> {
+        fn canonical<P: PrefixType, M: MetadataBufT<P>>(
+            meta: &M,
+            hash: &AnyDhtHash,
+        ) -> DatabaseResult<AnyDhtHash> {
+            Ok(match *hash.hash_type() {
+                AnyDht::Entry => meta.get_canonical_entry_hash(hash.clone().into())?.into(),
+                AnyDht::Header => meta.get_canonical_header_hash(hash.clone().into())?.into(),
+            })
+        }
+        if let Some(cache_data) = self.cache_data.as_ref() {
+            let next = canonical(cache_data.meta, hash)?;
+            if next != *hash {
+                return Ok(Some(next));
+            }
+        }
+        if let Some(authored_data) = self.authored_data.as_ref() {
+            let next = canonical(authored_data.meta, hash)?;
+            if next != *hash {
+                return Ok(Some(next));
+            }
         }
+        Ok(None)
     }
 
     #[instrument(skip(self))]
@@ -1536,39 +1618,102 @@ where
         key: &'link LinkMetaKey<'link>,
         options: GetLinksOptions,
     ) -> CascadeResult<Vec<Link>> {
-        if self.am_i_an_authority(key.base().clone().into()).await? {
+        let query = options.query.clone();
+        let fetched = if self.am_i_an_authority(key.base().clone().into()).await? {
             // Short circuit. This makes sense for full sharding.
-            self.update_link_cache_from_integrated(key, options)?;
+            self.update_link_cache_from_integrated(key, options)?
         } else {
             // Update the cache from the network
-            self.fetch_links(key.into(), options).await?;
-        }
+            self.fetch_links(key.into(), options).await?
+        };
 
         let cache_data = ok_or_return!(self.cache_data.as_ref(), vec![]);
         let authored_data = ok_or_return!(self.authored_data.as_ref(), vec![]);
         let env = ok_or_return!(self.env.as_ref(), vec![]);
         fresh_reader!(env, |r| {
-            // Meta Cache
-            // Return any links from the meta cache that don't have removes.
-            let mut links = cache_data
-                .meta
-                .get_live_links(&r, key)?
-                .map(|l| Ok(l.into_link()))
-                .chain(
-                    authored_data
-                        .meta
-                        .get_live_links(&r, key)?
-                        .map(|l| Ok(l.into_link())),
-                )
-                // Need to collect into a Set first to remove
-                // duplicates from authored and cache
-                .collect::<Vec<_>>()?;
-            links.sort_by_key(|l| l.timestamp);
+            let mut links = if query.is_empty() {
+                // Meta Cache
+                // Return any links from the meta cache that don't have removes.
+                cache_data
+                    .meta
+                    .get_live_links(&r, key)?
+                    .chain(authored_data.meta.get_live_links(&r, key)?)
+                    .map(|l| Ok(l.into_link()))
+                    .collect::<Vec<_>>()?
+            } else {
+                // Authorities only return live links for a query so the cache
+                // may be missing deletes for links it already had.
+                // Only trust the cached links that were just returned.
+                let cache_query = LinkQuery {
+                    limit: None,
+                    ..query.clone()
+                };
+                cache_data
+                    .meta
+                    .get_live_links_query(&r, key, &cache_query)?
+                    .filter(|l| Ok(fetched.contains(&l.link_add_hash)))
+                    .chain(authored_data.meta.get_live_links_query(&r, key, &query)?)
+                    .map(|l| Ok(l.into_link()))
+                    .collect::<Vec<_>>()?
+            };
+            if query.is_paged() {
+                links.sort_by(|a, b| LinkCursor::from(a).cmp(&LinkCursor::from(b)));
+            } else {
+                links.sort_by_key(|l| l.timestamp);
+            }
+            // Remove duplicates from authored and cache
             links.dedup();
+            if let Some(limit) = query.limit {
+                links.truncate(limit as usize);
+            }
             Ok(links)
         })
     }
 
+    #[instrument(skip(self, key, options))]
+    /// Count the live links that match the key and query.
+    /// Authorities count from their own index without reading any headers,
+    /// everyone else asks an authority to count them.
+    pub async fn dht_count_links<'link>(
+        &mut self,
+        key: &'link LinkMetaKey<'link>,
+        options: GetLinksOptions,
+    ) -> CascadeResult<usize> {
+        if self.am_i_an_authority(key.base().clone().into()).await? {
+            if let (Some(integrated_data), Some(authored_data), Some(env)) = (
+                self.integrated_data.as_ref(),
+                self.authored_data.as_ref(),
+                self.env.as_ref(),
+            ) {
+                let query = &options.query;
+                return fresh_reader!(env, |r| {
+                    // Authored links may not be integrated yet
+                    let mut link_add_hashes = HashSet::new();
+                    integrated_data
+                        .meta
+                        .get_live_links_query(&r, key, query)?
+                        .chain(authored_data.meta.get_live_links_query(&r, key, query)?)
+                        .for_each(|l| {
+                            link_add_hashes.insert(l.link_add_hash);
+                            Ok(())
+                        })?;
+                    let count = link_add_hashes.len();
+                    Ok(query
+                        .limit
+                        .map(|limit| count.min(limit as usize))
+                        .unwrap_or(count))
+                });
+            }
+        }
+        let network = ok_or_return!(self.network.as_mut(), 0);
+        let responses = network.count_links(key.into(), options).await?;
+        Ok(responses
+            .into_iter()
+            .map(|CountLinksResponse(count)| count)
+            .max()
+            .unwrap_or(0))
+    }
+
     #[instrument(skip(self, key, options))]
     /// Return all CreateLink headers
     /// and DeleteLink headers ordered by time.
@@ -1650,6 +1795,55 @@ where
         Ok(())
     }
 
+    /// Fetch the full headers of an agent's activity from the authorities.
+    /// Each authority's headers are verified before they are cached and the
+    /// longest verified chain is returned.
+    /// Returns `None` if no authority returned headers that verify.
+    async fn fetch_agent_activity_headers(
+        &mut self,
+        agent: AgentPubKey,
+        query: ChainQueryFilter,
+        mut options: GetActivityOptions,
+    ) -> CascadeResult<Option<Vec<SignedHeaderHashed>>> {
+        options.include_full_headers = true;
+        let network = ok_or_return!(self.network.as_mut(), None);
+        let all_agent_activity = network
+            .get_agent_activity(agent.clone(), query.clone(), options)
+            .await?;
+        self.cache_verified_activity_headers(&agent, &query.sequence_range, all_agent_activity)
+            .await
+    }
+
+    /// Cache the activity each authority returned, skipping
+    /// any authority whose headers fail verification.
+    /// Returns the longest verified chain.
+    async fn cache_verified_activity_headers(
+        &mut self,
+        agent: &AgentPubKey,
+        sequence_range: &Option<std::ops::Range<u32>>,
+        all_agent_activity: Vec<AgentActivityResponse>,
+    ) -> CascadeResult<Option<Vec<SignedHeaderHashed>>> {
+        let mut verified: Option<Vec<SignedHeaderHashed>> = None;
+        for agent_activity in all_agent_activity {
+            if let ChainItems::Full(headers) = &agent_activity.valid_activity {
+                if verify_activity_headers(agent, headers, sequence_range).await? {
+                    if verified.as_ref().map(|v| v.len()).unwrap_or(0) <= headers.len() {
+                        verified = Some(headers.clone());
+                    }
+                } else {
+                    warn!(
+                        ?agent,
+                        "Authority returned agent activity headers that failed verification"
+                    );
+                    // Don't cache anything from an authority we couldn't verify
+                    continue;
+                }
+            }
+            self.update_agent_activity_stores(agent_activity).await?;
+        }
+        Ok(verified)
+    }
+
     async fn fetch_agent_activity_status(
         &mut self,
         agent: AgentPubKey,
@@ -1777,7 +1971,47 @@ where
         // Skipping this for now.
         self.fetch_agent_activity(agent.clone(), query.clone(), options)
             .await?;
+        self.create_activity_from_cache(agent, &query)
+    }
 
+    /// Do a full fetch of the headers and return the activity
+    /// with the headers that match the query.
+    /// Falls back to just the hashes if none of the headers could be verified.
+    async fn fetch_and_create_full_activity(
+        &mut self,
+        agent: AgentPubKey,
+        query: ChainQueryFilter,
+        options: GetActivityOptions,
+    ) -> CascadeResult<AgentActivityResponse<Element>> {
+        // Continuity can only be checked on the whole range
+        // so the rest of the query is applied here.
+        let mut network_query = ChainQueryFilter::new();
+        network_query.sequence_range = query.sequence_range.clone();
+        let headers = self
+            .fetch_agent_activity_headers(agent.clone(), network_query, options)
+            .await?;
+
+        let mut activity = self.create_activity_from_cache(agent, &query)?;
+        match (headers, &activity.status) {
+            (_, ChainStatus::Empty) | (None, _) => {}
+            (Some(headers), _) => {
+                activity.valid_activity = ChainItems::Full(
+                    headers
+                        .into_iter()
+                        .filter(|shh| query.check(shh.header()))
+                        .map(|shh| Element::new(shh, None))
+                        .collect(),
+                );
+            }
+        }
+        Ok(activity)
+    }
+
+    fn create_activity_from_cache(
+        &self,
+        agent: AgentPubKey,
+        query: &ChainQueryFilter,
+    ) -> CascadeResult<AgentActivityResponse<Element>> {
         let cache_data = ok_or_return!(
             self.cache_data.as_ref(),
             AgentActivityResponse::empty(&agent)
@@ -1798,7 +2032,7 @@ where
     // are currently constraining the behavior to only serve getting validation
     // packages.
     // - [ ] Return the rejected activity (with or without caching it)
-    // - [ ] Be able to handle full headers as well as hashes (with or without caching)
+    // - [ ] Cache full headers as well as hashes
     // - [ ] Maybe Empty chains should not be set to NotRequested and set to the
     // value that reflects the requester
     fn create_activity(
@@ -1812,6 +2046,10 @@ where
         );
         // Now try getting the latest activity from cache
         let highest_observed = cache_data.meta.get_activity_observed(&agent)?;
+        let warrants = match &self.warrants {
+            Some(warrants) => warrants.get_warrants(&agent)?,
+            None => Vec::new(),
+        };
         match cache_data.meta.get_activity_status(&agent)? {
             Some(status) => Ok(AgentActivityResponse {
                 agent,
@@ -1819,6 +2057,7 @@ where
                 rejected_activity: ChainItems::NotRequested,
                 status,
                 highest_observed,
+                warrants,
             }),
             // If we don't have any status then we must return an empty chain
             None => Ok(AgentActivityResponse {
@@ -1827,6 +2066,7 @@ where
                 rejected_activity: ChainItems::NotRequested,
                 status: ChainStatus::Empty,
                 highest_observed,
+                warrants,
             }),
         }
     }
@@ -1840,7 +2080,9 @@ where
     /// Options:
     /// - include_valid_activity will include the valid chain hashes.     
     /// - include_rejected_activity will include the valid chain hashes. (unimplemented)
-    /// - include_full_headers will fetch the valid headers in parallel (requires include_valid_activity)
+    /// - include_full_headers will fetch the valid headers from the authorities and verify
+    /// their signatures and hash chain, falling back to fetching them in parallel
+    /// (requires include_valid_activity)
     /// Query:
     /// - include_entries will also fetch the entries in parallel (requires include_full_headers)
     /// - sequence_range will get all the activity in the exclusive range
@@ -1854,7 +2096,8 @@ where
         const DEFAULT_ACTIVITY_TIMEOUT_MS: u64 = 1000;
         // Get the request options
         let requester_options = options.clone();
-        // For now only fetching hashes until caching is worked out.
+        // Full headers are only fetched from authorities when they are needed,
+        // the cache only holds hashes.
         options.include_full_headers = false;
         // Get agent activity takes longer then other calls so we
         // will give it a larger default timeout
@@ -1885,6 +2128,15 @@ where
 
         // Create the activity
         let mut activity = match chain_hashes {
+            // If there was no activity in the cache and only headers
+            // are needed then fetch them straight from the authorities
+            None if requester_options.include_full_headers
+                && requester_options.include_valid_activity
+                && !query.include_entries =>
+            {
+                self.fetch_and_create_full_activity(agent.clone(), query.clone(), options.clone())
+                    .await?
+            }
             // If there was no activity in the cache then try fetching it
             None => {
                 self.fetch_and_create_activity(agent.clone(), query.clone(), options.clone())
@@ -1916,7 +2168,8 @@ where
         }
 
         match &activity.valid_activity {
-            ChainItems::Full(_) => todo!(),
+            // The headers have already been fetched and verified
+            ChainItems::Full(_) => Ok(activity),
             ChainItems::Hashes(hashes) => {
                 // If full headers and include entries is requested
                 // retrieve them in parallel
@@ -2169,6 +2422,47 @@ pub fn get_header<P: PrefixType>(
         .into_content())
 }
 
+/// Check that the headers an authority returned for an agent's activity
+/// are authored and signed by that agent, match the hashes they claim
+/// and form an unbroken hash chain from the start of the sequence range.
+async fn verify_activity_headers(
+    agent: &AgentPubKey,
+    headers: &[SignedHeaderHashed],
+    sequence_range: &Option<std::ops::Range<u32>>,
+) -> CascadeResult<bool> {
+    let mut prev: Option<&SignedHeaderHashed> = None;
+    for shh in headers {
+        let header = shh.header();
+        if header.author() != agent || HeaderHash::with_data_sync(header) != *shh.as_hash() {
+            return Ok(false);
+        }
+        if !agent
+            .verify_signature(shh.signature(), header.clone())
+            .await
+            .map_err(DatabaseError::from)?
+        {
+            return Ok(false);
+        }
+        let continues_chain = match prev {
+            Some(prev) => {
+                header.header_seq() == prev.header().header_seq() + 1
+                    && header.prev_header() == Some(prev.as_hash())
+            }
+            // The first header must be at the start of the range
+            // and only the Dna header can have no previous header
+            None => {
+                header.header_seq() == sequence_range.as_ref().map(|r| r.start).unwrap_or(0)
+                    && (header.header_seq() > 0 || header.prev_header().is_none())
+            }
+        };
+        if !continues_chain {
+            return Ok(false);
+        }
+        prev = Some(shh);
+    }
+    Ok(true)
+}
+
 #[cfg(test)]
 /// Helper function for easily setting up cascades during tests
 pub fn test_dbs_and_mocks(
@@ -2185,3 +2479,207 @@ pub fn test_dbs_and_mocks(
     let metadata_cache = holochain_state::metadata::MockMetadataBuf::new();
     (cas, metadata, element_cache, metadata_cache)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_lmdb::fresh_reader_test;
+    use holochain_lmdb::test_utils::test_cell_env;
+    use holochain_lmdb::test_utils::test_keystore;
+    use holochain_types::test_utils::fake_agent_pubkey_1;
+    use holochain_types::test_utils::fake_agent_pubkey_2;
+    use holochain_types::test_utils::fake_dna_hash;
+    use holochain_types::test_utils::fake_header_hash;
+
+    async fn sign(keystore: &KeystoreSender, header: Header) -> SignedHeaderHashed {
+        SignedHeaderHashed::new(keystore, HeaderHashed::from_content_sync(header))
+            .await
+            .unwrap()
+    }
+
+    async fn next_header(
+        keystore: &KeystoreSender,
+        author: &AgentPubKey,
+        header_seq: u32,
+        prev_header: HeaderHash,
+    ) -> SignedHeaderHashed {
+        sign(
+            keystore,
+            Header::InitZomesComplete(InitZomesComplete {
+                author: author.clone(),
+                timestamp: timestamp::now(),
+                header_seq,
+                prev_header,
+            }),
+        )
+        .await
+    }
+
+    /// A valid chain of `len` headers authored by `author`
+    async fn chain(
+        keystore: &KeystoreSender,
+        author: &AgentPubKey,
+        len: u32,
+    ) -> Vec<SignedHeaderHashed> {
+        let dna = Header::Dna(Dna {
+            author: author.clone(),
+            timestamp: timestamp::now(),
+            hash: fake_dna_hash(1),
+        });
+        let mut headers = vec![sign(keystore, dna).await];
+        for header_seq in 1..len {
+            let prev_header = headers.last().unwrap().as_hash().clone();
+            headers.push(next_header(keystore, author, header_seq, prev_header).await);
+        }
+        headers
+    }
+
+    fn activity(agent: &AgentPubKey, headers: Vec<SignedHeaderHashed>) -> AgentActivityResponse {
+        let head = headers.last().unwrap();
+        AgentActivityResponse {
+            agent: agent.clone(),
+            status: ChainStatus::Valid(ChainHead {
+                header_seq: head.header().header_seq(),
+                hash: head.as_hash().clone(),
+            }),
+            valid_activity: ChainItems::Full(headers),
+            rejected_activity: ChainItems::NotRequested,
+            highest_observed: None,
+            warrants: Vec::new(),
+        }
+    }
+
+    /// Run the activity from these authorities through a cascade with an empty cache,
+    /// returning the verified headers and what ended up in the cache.
+    async fn cache_activity(
+        agent: &AgentPubKey,
+        all_agent_activity: Vec<AgentActivityResponse>,
+    ) -> (
+        Option<Vec<SignedHeaderHashed>>,
+        Vec<(u32, HeaderHash)>,
+        Option<ChainStatus>,
+    ) {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let mut element_cache = ElementBuf::cache(env.clone().into()).unwrap();
+        let mut meta_cache = MetadataBuf::cache(env.clone().into()).unwrap();
+        let verified = Cascade::empty()
+            .with_cache(DbPairMut {
+                element: &mut element_cache,
+                meta: &mut meta_cache,
+            })
+            .cache_verified_activity_headers(agent, &None, all_agent_activity)
+            .await
+            .unwrap();
+        let cached = fresh_reader_test!(env, |r| meta_cache
+            .get_activity_sequence(
+                &r,
+                ChainItemKey::AgentStatus(agent.clone(), ValidationStatus::Valid),
+            )
+            .unwrap()
+            .collect::<Vec<_>>()
+            .unwrap());
+        let status = meta_cache.get_activity_status(agent).unwrap();
+        (verified, cached, status)
+    }
+
+    async fn assert_rejected(agent: &AgentPubKey, headers: Vec<SignedHeaderHashed>) {
+        assert!(!verify_activity_headers(agent, &headers, &None)
+            .await
+            .unwrap());
+        let (verified, cached, status) =
+            cache_activity(agent, vec![activity(agent, headers)]).await;
+        assert_eq!(verified, None);
+        assert!(cached.is_empty());
+        assert_eq!(status, None);
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn verified_activity_headers_are_cached() {
+        let keystore = test_keystore();
+        let alice = fake_agent_pubkey_1();
+        let headers = chain(&keystore, &alice, 4).await;
+        let (verified, cached, status) =
+            cache_activity(&alice, vec![activity(&alice, headers.clone())]).await;
+        assert_eq!(verified, Some(headers.clone()));
+        assert_eq!(
+            cached,
+            headers
+                .iter()
+                .map(|shh| (shh.header().header_seq(), shh.as_hash().clone()))
+                .collect::<Vec<_>>()
+        );
+        assert!(matches!(status, Some(ChainStatus::Valid(_))));
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn activity_headers_by_another_author_are_rejected() {
+        let keystore = test_keystore();
+        let alice = fake_agent_pubkey_1();
+        let bob = fake_agent_pubkey_2();
+        assert_rejected(&alice, chain(&keystore, &bob, 4).await).await;
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn activity_headers_with_a_bad_signature_are_rejected() {
+        let keystore = test_keystore();
+        let alice = fake_agent_pubkey_1();
+        let mut headers = chain(&keystore, &alice, 4).await;
+        headers[2] = SignedHeaderHashed::with_presigned(
+            headers[2].header_hashed().clone(),
+            headers[1].signature().clone(),
+        );
+        assert_rejected(&alice, headers).await;
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn activity_headers_with_the_wrong_hash_are_rejected() {
+        let keystore = test_keystore();
+        let alice = fake_agent_pubkey_1();
+        let mut headers = chain(&keystore, &alice, 4).await;
+        headers[3] = SignedHeaderHashed::with_presigned(
+            HeaderHashed::with_pre_hashed(headers[3].header().clone(), fake_header_hash(1)),
+            headers[3].signature().clone(),
+        );
+        assert_rejected(&alice, headers).await;
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn activity_headers_with_a_sequence_gap_are_rejected() {
+        let keystore = test_keystore();
+        let alice = fake_agent_pubkey_1();
+        let mut headers = chain(&keystore, &alice, 3).await;
+        // Links to the previous header but skips a sequence number
+        let prev_header = headers[2].as_hash().clone();
+        headers.push(next_header(&keystore, &alice, 4, prev_header).await);
+        assert_rejected(&alice, headers).await;
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn activity_headers_with_a_broken_prev_header_are_rejected() {
+        let keystore = test_keystore();
+        let alice = fake_agent_pubkey_1();
+        let mut headers = chain(&keystore, &alice, 3).await;
+        // Next in sequence but links back past the previous header
+        let prev_header = headers[1].as_hash().clone();
+        headers.push(next_header(&keystore, &alice, 3, prev_header).await);
+        assert_rejected(&alice, headers).await;
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn only_the_authorities_that_verify_are_cached() {
+        let keystore = test_keystore();
+        let alice = fake_agent_pubkey_1();
+        let bob = fake_agent_pubkey_2();
+        let headers = chain(&keystore, &alice, 3).await;
+        // A longer chain that doesn't verify doesn't win
+        let forged = chain(&keystore, &bob, 5).await;
+        let (verified, cached, _) = cache_activity(
+            &alice,
+            vec![activity(&alice, forged), activity(&alice, headers.clone())],
+        )
+        .await;
+        assert_eq!(verified, Some(headers));
+        assert_eq!(cached.len(), 3);
+    }
+}
@@ -123,22 +123,70 @@ pub enum AdminRequest {
         /// The InstalledAppId to deactivate
         installed_app_id: InstalledAppId,
     },
+    /// Removes the `App` specified by argument `installed_app_id` from the conductor, whether it
+    /// is active or not. Its `Cell`s leave the network and are removed from the conductor state,
+    /// unless another `App` also uses them. Any `Dna`s and wasm which no other `App` uses are
+    /// removed as well.
+    /// If `delete_cell_data` is true, the environments of the removed `Cell`s are deleted from disk.
+    ///
+    /// Will be responded to with an [`AdminResponse::AppUninstalled`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::AppUninstalled`]: enum.AdminResponse.html#variant.AppUninstalled
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    UninstallApp {
+        /// The InstalledAppId to uninstall
+        installed_app_id: InstalledAppId,
+        /// Whether to delete the environments of the app's cells
+        #[serde(default)]
+        delete_cell_data: bool,
+    },
+    /// Migrate the agent of one of an active `App`'s `Cell`s to a new version of its `Dna`.
+    /// The new `Dna` must already be registered.
+    ///
+    /// The `migrate_agent` callback is run in every zome of the old `Dna` and the old chain is
+    /// closed with a `CloseChain` header. A new `Cell` for the same agent is created on the new
+    /// `Dna`, the `migrate_agent` callback is run in every zome of the new `Dna` and the new chain
+    /// is opened with an `OpenChain` header that references the old `Dna`.
+    /// The new `Cell` replaces the old one in the `App` under the same nick.
+    ///
+    /// Will be responded to with an [`AdminResponse::AgentMigrated`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::AgentMigrated`]: enum.AdminResponse.html#variant.AgentMigrated
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    MigrateAgent {
+        /// The InstalledAppId of the app with the cell to migrate
+        installed_app_id: InstalledAppId,
+        /// The nick of the cell to migrate
+        cell_nick: CellNick,
+        /// The hash of the registered Dna to migrate to
+        new_dna_hash: DnaHash,
+        /// Proof of membership for the new Dna, if it needs one
+        membrane_proof: Option<MembraneProof>,
+    },
     /// Open up a new websocket interface at the networking port
     /// (optionally) specified by argument `port` (or using any free port if argument `port` is `None`)
     /// over which you can then use the [`AppRequest`] API.
     /// Any active `App` will be callable via this interface.
+    /// If an `installed_app_id` is given the interface is bound to that app
+    /// and can make [`AppRequest::Crypto`] requests on its behalf.
     /// The successful [`AdminResponse::AppInterfaceAttached`] message will contain
     /// the port chosen by the conductor if `None` was passed.
     ///
     /// Will be responded to with an [`AdminResponse::AppInterfaceAttached`]
     /// or an [`AdminResponse::Error`]
     ///
+    /// [`AppRequest::Crypto`]: ../app_interface/enum.AppRequest.html#variant.Crypto
     /// [`AdminResponse::AppInterfaceAttached`]: enum.AdminResponse.html#variant.AppInterfaceAttached
     /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
     AttachAppInterface {
         /// Optional port, use None to let the
         /// OS choose a free port
         port: Option<u16>,
+        /// Optional app to bind the interface to
+        #[serde(default)]
+        installed_app_id: Option<InstalledAppId>,
     },
     /// Dump the full state of the `Cell` specified by argument `cell_id`,
     /// including its chain, as a string containing JSON.
@@ -152,6 +200,19 @@ pub enum AdminRequest {
         /// The `CellId` for which to dump state
         cell_id: Box<CellId>,
     },
+    /// Get the report from the last health check of the `Cell` specified by `cell_id`.
+    /// The health check runs periodically and republishes any authored ops
+    /// that are held by fewer authorities than the conductor's redundancy target.
+    ///
+    /// Will be responded to with an [`AdminResponse::CellHealthReported`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::CellHealthReported`]: enum.AdminResponse.html#variant.CellHealthReported
+    GetCellHealth {
+        /// The `CellId` to get the health report for
+        cell_id: Box<CellId>,
+    },
     /// Add a list [AgentInfoSigned] to this conductor's peer store.
     /// This is another way of finding peers on a dht.
     ///
@@ -269,6 +330,19 @@ pub enum AdminResponse {
     ///
     /// [`AdminRequest::DeactivateApp`]: enum.AdminRequest.html#variant.DeactivateApp
     AppDeactivated,
+    /// The succesful response to an [`AdminRequest::UninstallApp`].
+    ///
+    /// It means the `App` was uninstalled successfully.
+    ///
+    /// [`AdminRequest::UninstallApp`]: enum.AdminRequest.html#variant.UninstallApp
+    AppUninstalled,
+    /// The succesful response to an [`AdminRequest::MigrateAgent`].
+    ///
+    /// The resulting [`InstalledApp`] has the new `Cell` in place of the old one.
+    ///
+    /// [`AdminRequest::MigrateAgent`]: enum.AdminRequest.html#variant.MigrateAgent
+    /// [`InstalledApp`]: ../../../holochain_types/app/struct.InstalledApp.html
+    AgentMigrated(InstalledApp),
     /// The succesful response to an [`AdminRequest::DumpState`].
     ///
     /// The result contains a string of serialized JSON data which can be deserialized to access the
@@ -276,6 +350,12 @@ pub enum AdminResponse {
     ///
     /// [`AdminRequest::DumpState`]: enum.AdminRequest.html#variant.DumpState
     StateDumped(String),
+    /// The succesful response to an [`AdminRequest::GetCellHealth`].
+    ///
+    /// The result is `None` if the health check hasn't run on the cell yet.
+    ///
+    /// [`AdminRequest::GetCellHealth`]: enum.AdminRequest.html#variant.GetCellHealth
+    CellHealthReported(Option<CellHealthReport>),
     /// The succesful response to an [`AdminRequest::AddAgentInfo`].
     ///
     /// This means the agent info was successfully added to the peer store.
@@ -311,6 +391,12 @@ pub enum ExternalApiWireError {
     ActivateApp(String),
     /// The zome call is unauthorized
     ZomeCallUnauthorized(String),
+    /// A migrate_agent callback failed
+    MigrateAgent(String),
+    /// The crypto request is not allowed for the app bound to the interface
+    CryptoUnauthorized(String),
+    /// The request needs the interface to be bound to an app
+    InterfaceNotBound(String),
 }
 
 impl ExternalApiWireError {
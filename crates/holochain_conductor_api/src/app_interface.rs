@@ -1,4 +1,7 @@
-use crate::{signal_subscription::SignalSubscription, ExternalApiWireError};
+use crate::{
+    signal_subscription::{DataSubscription, SignalSubscription},
+    ExternalApiWireError,
+};
 use holo_hash::AgentPubKey;
 use holochain_types::prelude::*;
 
@@ -20,10 +23,20 @@ pub enum AppRequest {
         /// The InstalledAppId for which to get information
         installed_app_id: InstalledAppId,
     },
-    /// Asks the conductor to do some crypto.
+    /// Asks the conductor to do some crypto with the keystore on behalf of
+    /// the app this interface is bound to.
+    /// See the inner [`CryptoRequest`] enum for the available operations.
     ///
-    /// Is currently unimplemented and will return
-    /// an [`AppResponse::Unimplemented`](enum.AppResponse.html#variant.Unimplemented)
+    /// Will be responded to with an [`AppResponse::Signature`],
+    /// [`AppResponse::X25519KeypairCreated`], [`AppResponse::Encrypted`],
+    /// [`AppResponse::Decrypted`] or an [`AppResponse::Error`]
+    ///
+    /// [`CryptoRequest`]: enum.CryptoRequest.html
+    /// [`AppResponse::Signature`]: enum.AppResponse.html#variant.Signature
+    /// [`AppResponse::X25519KeypairCreated`]: enum.AppResponse.html#variant.X25519KeypairCreated
+    /// [`AppResponse::Encrypted`]: enum.AppResponse.html#variant.Encrypted
+    /// [`AppResponse::Decrypted`]: enum.AppResponse.html#variant.Decrypted
+    /// [`AppResponse::Error`]: enum.AppResponse.html#variant.Error
     Crypto(Box<CryptoRequest>),
     /// Call a zome function. See the inner [`ZomeCall`]
     /// struct to understand the data that must be provided.
@@ -39,11 +52,39 @@ pub enum AppRequest {
     /// DEPRECATED. Use `ZomeCall`.
     ZomeCallInvocation(Box<ZomeCall>),
 
-    /// Update signal subscriptions.
+    /// Update the signal subscription of this connection for one app.
+    /// Replaces any previous subscription for the same app.
+    /// Signals from the Cells of apps without a subscription are all let through.
+    ///
+    /// Will be responded to with an [`AppResponse::SignalSubscriptionUpdated`]
+    /// or an [`AppResponse::Error`]
     ///
-    /// Is currently unimplemented and will return
-    /// an [`AppResponse::Unimplemented`](enum.AppResponse.html#variant.Unimplemented)
+    /// [`AppResponse::SignalSubscriptionUpdated`]: enum.AppResponse.html#variant.SignalSubscriptionUpdated
+    /// [`AppResponse::Error`]: enum.AppResponse.html#variant.Error
     SignalSubscription(SignalSubscription),
+
+    /// Subscribe this connection to changes in some DHT data held by the Cells
+    /// of the app the interface is bound to.
+    /// When a Cell integrates a matching link create or delete, or an entry
+    /// update or delete, a `SystemSignal::DataChanged` signal is pushed across
+    /// this connection. Changes to data nobody subscribed to are not signalled.
+    ///
+    /// Will be responded to with an [`AppResponse::DataSubscribed`]
+    /// or an [`AppResponse::Error`]
+    ///
+    /// [`AppResponse::DataSubscribed`]: enum.AppResponse.html#variant.DataSubscribed
+    /// [`AppResponse::Error`]: enum.AppResponse.html#variant.Error
+    SubscribeData(DataSubscription),
+
+    /// Remove a subscription made with [`AppRequest::SubscribeData`].
+    ///
+    /// Will be responded to with an [`AppResponse::DataUnsubscribed`]
+    /// or an [`AppResponse::Error`]
+    ///
+    /// [`AppRequest::SubscribeData`]: enum.AppRequest.html#variant.SubscribeData
+    /// [`AppResponse::DataUnsubscribed`]: enum.AppResponse.html#variant.DataUnsubscribed
+    /// [`AppResponse::Error`]: enum.AppResponse.html#variant.Error
+    UnsubscribeData(DataSubscription),
 }
 
 /// Responses to requests received on an App interface
@@ -83,6 +124,43 @@ pub enum AppResponse {
 
     /// DEPRECATED. See `ZomeCall`.
     ZomeCallInvocation(Box<ExternIO>),
+
+    /// The successful response to an [`AppRequest::SignalSubscription`].
+    ///
+    /// [`AppRequest::SignalSubscription`]: enum.AppRequest.html#variant.SignalSubscription
+    SignalSubscriptionUpdated,
+
+    /// The successful response to an [`AppRequest::SubscribeData`].
+    ///
+    /// [`AppRequest::SubscribeData`]: enum.AppRequest.html#variant.SubscribeData
+    DataSubscribed,
+
+    /// The successful response to an [`AppRequest::UnsubscribeData`].
+    ///
+    /// [`AppRequest::UnsubscribeData`]: enum.AppRequest.html#variant.UnsubscribeData
+    DataUnsubscribed,
+
+    /// The successful response to a [`CryptoRequest::Sign`].
+    ///
+    /// [`CryptoRequest::Sign`]: enum.CryptoRequest.html#variant.Sign
+    Signature(Signature),
+
+    /// The successful response to a [`CryptoRequest::CreateX25519Keypair`].
+    ///
+    /// [`CryptoRequest::CreateX25519Keypair`]: enum.CryptoRequest.html#variant.CreateX25519Keypair
+    X25519KeypairCreated(X25519PubKey),
+
+    /// The successful response to a [`CryptoRequest::Encrypt`].
+    ///
+    /// [`CryptoRequest::Encrypt`]: enum.CryptoRequest.html#variant.Encrypt
+    Encrypted(XSalsa20Poly1305EncryptedData),
+
+    /// The successful response to a [`CryptoRequest::Decrypt`].
+    ///
+    /// Option will be `None` if the data could not be decrypted with the given keys.
+    ///
+    /// [`CryptoRequest::Decrypt`]: enum.CryptoRequest.html#variant.Decrypt
+    Decrypted(Option<XSalsa20Poly1305Data>),
 }
 
 /// The data provided across an App interface in order to make a zome call
@@ -109,11 +187,25 @@ pub struct ZomeCall {
     pub provenance: AgentPubKey,
 }
 
-#[allow(missing_docs)]
+/// Keystore operations that can be requested over an App interface.
+///
+/// They are made on behalf of the app the interface is bound to
+/// and are refused on interfaces that aren't bound to an active app.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case", tag = "type", content = "data")]
 pub enum CryptoRequest {
-    Sign(String),
-    Decrypt(String),
-    Encrypt(String),
+    /// Sign some data with the private key of one of the app's agents.
+    /// The `key` in the [`Sign`] input must be the agent of one of the app's cells.
+    ///
+    /// [`Sign`]: ../../../holochain_zome_types/signature/struct.Sign.html
+    Sign(Sign),
+    /// Create a new x25519 keypair in the keystore for the app.
+    /// Only keys created this way can be used by the app to encrypt and decrypt.
+    CreateX25519Keypair,
+    /// Encrypt some data for a recipient with x25519 / xsalsa20-poly1305.
+    /// The sender must be one of the app's x25519 keys.
+    Encrypt(X25519XSalsa20Poly1305Encrypt),
+    /// Decrypt some data sent to us with x25519 / xsalsa20-poly1305.
+    /// The recipient must be one of the app's x25519 keys.
+    Decrypt(X25519XSalsa20Poly1305Decrypt),
 }
@@ -56,8 +56,8 @@ pub struct ConductorConfig {
     /// over this websocket.
     pub decryption_service_uri: Option<String>,
 
-    /// Optional DPKI configuration if conductor is using a DPKI app to initalize and manage
-    /// keys for new instances
+    /// Optional DPKI configuration if conductor is using an installed DPKI app
+    /// to check the state of agent keys
     pub dpki: Option<DpkiConfig>,
 
     /// Optional path for keystore directory.  If not specified will use the default provided
@@ -75,6 +75,24 @@ pub struct ConductorConfig {
 
     /// Config options for the network module. Optional.
     pub network: Option<holochain_p2p::kitsune_p2p::KitsuneP2pConfig>,
+
+    /// How many validation receipts from distinct validators an op needs
+    /// before its author stops publishing it.
+    /// If omitted, a default of 5 is used.
+    pub receipt_bundle_size: Option<u32>,
+
+    /// How many authorities should hold each op authored by the cells of this
+    /// conductor, for Dnas that don't set their own `redundancy_target`.
+    /// The health check republishes any op that is held by fewer.
+    /// If omitted, a default of 5 is used.
+    pub redundancy_target: Option<u32>,
+
+    /// Encrypt the private entries of every Cell at rest.
+    /// Each Cell's key is stored sealed by a keypair in the keystore, so the
+    /// keystore must be unlocked through the `passphrase_service` at startup.
+    /// Private entries written before this was enabled can still be read.
+    #[serde(default)]
+    pub encrypt_private_entries: bool,
     //
     //
     // /// Which signals to emit
@@ -150,6 +168,9 @@ pub mod tests {
                 keystore_path: None,
                 admin_interfaces: None,
                 use_dangerous_test_keystore: false,
+                receipt_bundle_size: None,
+                redundancy_target: None,
+                encrypt_private_entries: false,
             }
         );
     }
@@ -169,8 +190,8 @@ pub mod tests {
       type: cmd
 
     dpki:
-      instance_id: some_id
-      init_params: some_params
+      installed_app_id: some_id
+      zome_name: some_zome
 
     admin_interfaces:
       - driver:
@@ -189,6 +210,9 @@ pub mod tests {
             proxy_accept_config: reject_all
       tuning_params:
         gossip_loop_iteration_delay_ms: 42
+        arc_target_redundancy: 42
+        arc_min_coverage: 0.25
+        arc_max_coverage: 0.5
         default_notify_remote_agent_count: 42
         default_notify_timeout_ms: 42
         default_rpc_single_timeout_ms: 42
@@ -196,6 +220,9 @@ pub mod tests {
         default_rpc_multi_timeout_ms: 42
         agent_info_expires_after_ms: 42
 
+    receipt_bundle_size: 3
+    redundancy_target: 7
+    encrypt_private_entries: true
     "#;
         let result: ConductorConfigResult<ConductorConfig> = config_from_yaml(yaml);
         use holochain_p2p::kitsune_p2p::*;
@@ -214,6 +241,9 @@ pub mod tests {
         {
             let mut tuning_params = &mut network_config.tuning_params;
             tuning_params.gossip_loop_iteration_delay_ms = 42;
+            tuning_params.arc_target_redundancy = 42;
+            tuning_params.arc_min_coverage = 0.25;
+            tuning_params.arc_max_coverage = 0.5;
             tuning_params.default_notify_remote_agent_count = 42;
             tuning_params.default_notify_timeout_ms = 42;
             tuning_params.default_rpc_single_timeout_ms = 42;
@@ -230,8 +260,8 @@ pub mod tests {
                 encryption_service_uri: Some("ws://localhost:9002".into()),
                 decryption_service_uri: Some("ws://localhost:9003".into()),
                 dpki: Some(DpkiConfig {
-                    instance_id: "some_id".into(),
-                    init_params: "some_params".into()
+                    installed_app_id: "some_id".into(),
+                    zome_name: "some_zome".into()
                 }),
                 passphrase_service: Some(PassphraseServiceConfig::Cmd),
                 keystore_path: None,
@@ -239,6 +269,9 @@ pub mod tests {
                     driver: InterfaceDriver::Websocket { port: 1234 }
                 }]),
                 network: Some(network_config),
+                receipt_bundle_size: Some(3),
+                redundancy_target: Some(7),
+                encrypt_private_entries: true,
             }
         );
     }
@@ -270,6 +303,9 @@ pub mod tests {
                 keystore_path: Some(PathBuf::from("/path/to/keystore").into()),
                 admin_interfaces: None,
                 use_dangerous_test_keystore: true,
+                receipt_bundle_size: None,
+                redundancy_target: None,
+                encrypt_private_entries: false,
             }
         );
     }
@@ -1,13 +1,14 @@
-// Legacy config that will probably change
-#![allow(missing_docs)]
-
 use serde::Deserialize;
 use serde::Serialize;
 
-/// Configure which app instance id to treat as the DPKI application handler
-/// as well as what parameters to pass it on its initialization
+/// Configure which installed app to treat as the DPKI service.
+/// The conductor asks this app for the state of agent keys
+/// during genesis and sys validation.
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
 pub struct DpkiConfig {
-    pub instance_id: String,
-    pub init_params: String,
+    /// The id of the installed DPKI app.
+    /// The DPKI zome is called on the app's first cell.
+    pub installed_app_id: String,
+    /// The zome with the `key_state` function.
+    pub zome_name: String,
 }
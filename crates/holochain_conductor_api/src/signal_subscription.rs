@@ -1,16 +1,93 @@
+use holo_hash::AgentPubKey;
+use holo_hash::EntryHash;
 use holochain_serialized_bytes::prelude::*;
 use holochain_types::app::InstalledAppId;
+use holochain_types::signal::DataChange;
+use holochain_types::signal::Signal;
 use holochain_zome_types::cell::CellId;
+use holochain_zome_types::link::LinkTag;
+use holochain_zome_types::signal::AppSignal;
+use holochain_zome_types::zome::ZomeName;
 use std::collections::HashMap;
+use std::collections::HashSet;
 
 /// Declares updated Signal subscription settings for an App.
 /// This message is part of the AppInterfaceApi
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
 pub struct SignalSubscription {
     /// The app for which to manage subscription
-    installed_app_id: InstalledAppId,
+    pub installed_app_id: InstalledAppId,
     /// Fine-grained per-cell filters
-    filters: SignalFilterSet,
+    pub filters: SignalFilterSet,
+}
+
+/// DHT data whose changes are pushed to a connection as
+/// [`SystemSignal::DataChanged`] signals when a Cell integrates them.
+///
+/// [`SystemSignal::DataChanged`]: ../../holochain_types/signal/enum.SystemSignal.html#variant.DataChanged
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, SerializedBytes,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DataSubscription {
+    /// Links created or deleted on a base,
+    /// optionally only those whose tags start with a prefix
+    Links {
+        /// The base of the links
+        base: EntryHash,
+        /// Only links whose tags start with these bytes
+        tag_prefix: Option<LinkTag>,
+    },
+    /// Updates and deletes of an entry
+    Entry {
+        /// The entry to watch
+        entry_hash: EntryHash,
+    },
+}
+
+impl DataSubscription {
+    /// Whether a change is to the data this subscription watches
+    pub fn matches(&self, change: &DataChange) -> bool {
+        match (self, change) {
+            (
+                DataSubscription::Links { base, tag_prefix },
+                DataChange::LinkCreated {
+                    base: changed_base,
+                    tag,
+                    ..
+                },
+            )
+            | (
+                DataSubscription::Links { base, tag_prefix },
+                DataChange::LinkDeleted {
+                    base: changed_base,
+                    tag,
+                    ..
+                },
+            ) => {
+                base == changed_base
+                    && tag_prefix
+                        .as_ref()
+                        .map(|prefix| tag.0.starts_with(&prefix.0))
+                        .unwrap_or(true)
+            }
+            (
+                DataSubscription::Entry { entry_hash },
+                DataChange::EntryUpdated {
+                    entry_hash: changed_entry_hash,
+                    ..
+                },
+            )
+            | (
+                DataSubscription::Entry { entry_hash },
+                DataChange::EntryDeleted {
+                    entry_hash: changed_entry_hash,
+                    ..
+                },
+            ) => entry_hash == changed_entry_hash,
+            _ => false,
+        }
+    }
 }
 
 /// Associate a SignalFilter with each Cell in an App.
@@ -46,22 +123,213 @@ impl SignalFilterSet {
     pub fn block_all() -> Self {
         SignalFilterSet::Include(HashMap::new())
     }
-}
-
-/// Specifies fine-grained filter controls for the signals
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
-pub struct SignalFilter;
 
-impl Default for SignalFilter {
-    fn default() -> Self {
-        // The default is no filter
-        Self::empty()
+    /// Whether a signal should be let through.
+    /// System signals are never filtered.
+    pub fn allows(&self, signal: &Signal) -> bool {
+        let (cell_id, zome_name, provenance, signal) = match signal {
+            Signal::App {
+                cell_id,
+                zome_name,
+                provenance,
+                signal,
+            } => (cell_id, zome_name, provenance.as_ref(), signal),
+            Signal::System(_) => return true,
+        };
+        match self {
+            SignalFilterSet::Include(filters) => filters
+                .get(cell_id)
+                .map(|f| f.matches(zome_name, provenance, signal))
+                .unwrap_or(false),
+            SignalFilterSet::Exclude(filters) => !filters
+                .get(cell_id)
+                .map(|f| f.matches(zome_name, provenance, signal))
+                .unwrap_or(false),
+        }
     }
 }
 
+/// Specifies fine-grained filter controls for the signals.
+///
+/// A signal matches the filter if it matches every non-empty set.
+/// An empty set places no restriction, so the empty filter matches every signal.
+#[derive(
+    Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes,
+)]
+pub struct SignalFilter {
+    /// The Zomes whose signals match
+    #[serde(default)]
+    pub zomes: HashSet<ZomeName>,
+    /// The signal type tags that match.
+    /// The tag of a signal is the string in its `type` field, which is where
+    /// an enum annotated with `#[serde(tag = "type")]` puts its variant name.
+    /// Signals without a tag never match a non-empty set.
+    #[serde(default)]
+    pub tags: HashSet<String>,
+    /// The agents whose remote signals match.
+    /// Signals emitted by a Cell's own agent never match a non-empty set.
+    #[serde(default)]
+    pub agents: HashSet<AgentPubKey>,
+}
+
 impl SignalFilter {
     /// A passthrough filter which filters nothing
     pub fn empty() -> Self {
-        SignalFilter
+        Self::default()
+    }
+
+    /// Only match signals from this Zome
+    pub fn zome(mut self, zome_name: ZomeName) -> Self {
+        self.zomes.insert(zome_name);
+        self
+    }
+
+    /// Only match signals with this type tag
+    pub fn tag(mut self, tag: String) -> Self {
+        self.tags.insert(tag);
+        self
+    }
+
+    /// Only match remote signals from this agent
+    pub fn agent(mut self, agent: AgentPubKey) -> Self {
+        self.agents.insert(agent);
+        self
+    }
+
+    /// Whether a signal emitted by this Zome, on behalf of this agent, matches
+    pub fn matches(
+        &self,
+        zome_name: &ZomeName,
+        provenance: Option<&AgentPubKey>,
+        signal: &AppSignal,
+    ) -> bool {
+        (self.zomes.is_empty() || self.zomes.contains(zome_name))
+            && (self.agents.is_empty()
+                || provenance
+                    .map(|agent| self.agents.contains(agent))
+                    .unwrap_or(false))
+            && (self.tags.is_empty()
+                || signal_tag(signal)
+                    .map(|tag| self.tags.contains(&tag))
+                    .unwrap_or(false))
+    }
+}
+
+/// A field of a signal, which is only read if it's a string
+#[derive(Debug, serde::Deserialize)]
+#[serde(untagged)]
+enum SignalField {
+    String(String),
+    Other(serde::de::IgnoredAny),
+}
+
+/// Get the type tag of a signal, if it has one
+fn signal_tag(signal: &AppSignal) -> Option<String> {
+    let mut fields: HashMap<String, SignalField> = signal.as_extern_io().decode().ok()?;
+    match fields.remove("type")? {
+        SignalField::String(tag) => Some(tag),
+        SignalField::Other(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_zome_types::test_utils::fake_agent_pubkey_1;
+    use holochain_zome_types::test_utils::fake_agent_pubkey_2;
+    use holochain_zome_types::test_utils::fake_entry_hash;
+    use holochain_zome_types::test_utils::fake_header_hash;
+    use holochain_zome_types::ExternIO;
+
+    #[derive(Debug, serde::Serialize)]
+    #[serde(tag = "type")]
+    enum TestSignal {
+        Ping,
+        Message { text: String },
+    }
+
+    fn signal(s: TestSignal) -> AppSignal {
+        AppSignal::new(ExternIO::encode(s).unwrap())
+    }
+
+    #[test]
+    fn signal_tags() {
+        assert_eq!(signal_tag(&signal(TestSignal::Ping)), Some("Ping".into()));
+        assert_eq!(
+            signal_tag(&signal(TestSignal::Message { text: "hi".into() })),
+            Some("Message".into())
+        );
+        assert_eq!(
+            signal_tag(&AppSignal::new(ExternIO::encode(42).unwrap())),
+            None
+        );
+    }
+
+    #[test]
+    fn filter_matches() {
+        let foo: ZomeName = "foo".into();
+        let bar: ZomeName = "bar".into();
+        let ping = signal(TestSignal::Ping);
+        let message = signal(TestSignal::Message { text: "hi".into() });
+        let alice = fake_agent_pubkey_1();
+        let bob = fake_agent_pubkey_2();
+
+        assert!(SignalFilter::empty().matches(&foo, None, &ping));
+
+        let filter = SignalFilter::empty().zome(foo.clone());
+        assert!(filter.matches(&foo, None, &ping));
+        assert!(!filter.matches(&bar, None, &ping));
+
+        let filter = SignalFilter::empty().tag("Message".into());
+        assert!(filter.matches(&foo, None, &message));
+        assert!(!filter.matches(&foo, None, &ping));
+
+        let filter = SignalFilter::empty().agent(alice.clone());
+        assert!(filter.matches(&foo, Some(&alice), &ping));
+        assert!(!filter.matches(&foo, Some(&bob), &ping));
+        assert!(!filter.matches(&foo, None, &ping));
+
+        let filter = SignalFilter::empty().zome(foo.clone()).tag("Ping".into());
+        assert!(filter.matches(&foo, Some(&bob), &ping));
+        assert!(!filter.matches(&foo, None, &message));
+        assert!(!filter.matches(&bar, None, &ping));
+    }
+
+    #[test]
+    fn data_subscription_matches() {
+        let link_created = |base, tag: &[u8]| DataChange::LinkCreated {
+            base,
+            tag: LinkTag::new(tag),
+            target: fake_entry_hash(9),
+            create_link_hash: fake_header_hash(1),
+        };
+        let entry_deleted = |entry_hash| DataChange::EntryDeleted {
+            entry_hash,
+            deleted_header_hash: fake_header_hash(1),
+            delete_hash: fake_header_hash(2),
+        };
+
+        let sub = DataSubscription::Links {
+            base: fake_entry_hash(1),
+            tag_prefix: None,
+        };
+        assert!(sub.matches(&link_created(fake_entry_hash(1), b"tag")));
+        assert!(!sub.matches(&link_created(fake_entry_hash(2), b"tag")));
+        assert!(!sub.matches(&entry_deleted(fake_entry_hash(1))));
+
+        let sub = DataSubscription::Links {
+            base: fake_entry_hash(1),
+            tag_prefix: Some(LinkTag::new(b"ta".to_vec())),
+        };
+        assert!(sub.matches(&link_created(fake_entry_hash(1), b"tag")));
+        assert!(!sub.matches(&link_created(fake_entry_hash(1), b"t")));
+        assert!(!sub.matches(&link_created(fake_entry_hash(1), b"other")));
+
+        let sub = DataSubscription::Entry {
+            entry_hash: fake_entry_hash(1),
+        };
+        assert!(sub.matches(&entry_deleted(fake_entry_hash(1))));
+        assert!(!sub.matches(&entry_deleted(fake_entry_hash(2))));
+        assert!(!sub.matches(&link_created(fake_entry_hash(1), b"tag")));
     }
 }
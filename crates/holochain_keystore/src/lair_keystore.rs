@@ -6,9 +6,20 @@ use ghost_actor::dependencies::futures::stream::StreamExt;
 use lair_keystore_api::actor::*;
 use lair_keystore_api::*;
 
+/// Provides the passphrase lair asks for when it needs to unlock the keystore.
+pub type PassphraseProvider = std::sync::Arc<
+    dyn Fn() -> ghost_actor::dependencies::futures::future::BoxFuture<
+            'static,
+            KeystoreApiResult<String>,
+        > + Send
+        + Sync,
+>;
+
 /// Spawn a new keystore backed by lair_keystore_client.
+/// If no passphrase provider is given, a blank passphrase is used to unlock lair.
 pub async fn spawn_lair_keystore(
     lair_dir: Option<&std::path::Path>,
+    passphrase_provider: Option<PassphraseProvider>,
 ) -> KeystoreApiResult<KeystoreSender> {
     let mut config = Config::builder();
     if let Some(lair_dir) = lair_dir {
@@ -17,14 +28,19 @@ pub async fn spawn_lair_keystore(
     let config = config.build();
     let (api, mut evt) = lair_keystore_client::assert_running_lair_and_connect(config).await?;
 
-    // TODO - actual passphrase handling
     tokio::task::spawn(async move {
         while let Some(r) = evt.next().await {
             match r {
                 LairClientEvent::RequestUnlockPassphrase { respond, .. } => {
-                    respond.respond(Ok(async move { Ok("[blank-passphrase]".to_string()) }
-                        .boxed()
-                        .into()));
+                    let passphrase_provider = passphrase_provider.clone();
+                    respond.respond(Ok(async move {
+                        match passphrase_provider {
+                            Some(provider) => provider().await.map_err(LairError::from),
+                            None => Ok("[blank-passphrase]".to_string()),
+                        }
+                    }
+                    .boxed()
+                    .into()));
                 }
             }
         }
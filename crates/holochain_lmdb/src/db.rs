@@ -70,6 +70,14 @@ pub enum DbName {
     ValidationReceipts,
     /// Single store for all known agents on the network
     Agent,
+    /// KV store of zome functions scheduled to be called by the host
+    ScheduledFns,
+    /// KV store of the live capability grants on the source chain, keyed by header hash
+    CapGrants,
+    /// KV store of the live capability claims on the source chain, keyed by header hash
+    CapClaims,
+    /// KV store of warrants against other agents, keyed by the warranted agent
+    Warrants,
 }
 
 impl DbName {
@@ -100,6 +108,10 @@ impl DbName {
             ValidationLimbo => Single,
             ValidationReceipts => Multi,
             Agent => Single,
+            ScheduledFns => Single,
+            CapGrants => Single,
+            CapClaims => Single,
+            Warrants => Single,
         }
     }
 }
@@ -172,6 +184,14 @@ lazy_static! {
     pub static ref VALIDATION_RECEIPTS: DbKey<MultiStore> = DbKey::new(DbName::ValidationReceipts);
     /// The key to access the Agent database
     pub static ref AGENT: DbKey<SingleStore> = DbKey::new(DbName::Agent);
+    /// The key to access the ScheduledFns database
+    pub static ref SCHEDULED_FNS: DbKey<SingleStore> = DbKey::new(DbName::ScheduledFns);
+    /// The key to access the CapGrants database
+    pub static ref CAP_GRANTS: DbKey<SingleStore> = DbKey::new(DbName::CapGrants);
+    /// The key to access the CapClaims database
+    pub static ref CAP_CLAIMS: DbKey<SingleStore> = DbKey::new(DbName::CapClaims);
+    /// The key to access the Warrants database
+    pub static ref WARRANTS: DbKey<SingleStore> = DbKey::new(DbName::Warrants);
 }
 
 lazy_static! {
@@ -230,6 +250,10 @@ fn register_databases(env: &Rkv, kind: &EnvironmentKind, um: &mut DbMap) -> Data
             register_db(env, um, &*INTEGRATION_LIMBO)?;
             register_db(env, um, &*VALIDATION_LIMBO)?;
             register_db(env, um, &*VALIDATION_RECEIPTS)?;
+            register_db(env, um, &*SCHEDULED_FNS)?;
+            register_db(env, um, &*CAP_GRANTS)?;
+            register_db(env, um, &*CAP_CLAIMS)?;
+            register_db(env, um, &*WARRANTS)?;
         }
         EnvironmentKind::Conductor => {
             register_db(env, um, &*CONDUCTOR_STATE)?;
@@ -0,0 +1,189 @@
+//! Symmetric encryption of values at rest, for databases which hold private data.
+//!
+//! The key for a Cell is random and is written next to the Cell's environment
+//! sealed with an x25519 keypair held in the keystore, so it can only be read
+//! back by a keystore which holds (and has unlocked) that keypair. The key
+//! itself is never written to disk and can't be reproduced from anything
+//! the keystore hands out.
+
+use crate::env::EnvironmentKind;
+use crate::error::DatabaseError;
+use crate::error::DatabaseResult;
+use holochain_keystore::KeystoreSender;
+use holochain_keystore::KeystoreSenderExt;
+use holochain_zome_types::cell::CellId;
+use holochain_zome_types::x_salsa20_poly1305::encrypted_data::XSalsa20Poly1305EncryptedData;
+use holochain_zome_types::x_salsa20_poly1305::nonce::NONCE_BYTES;
+use holochain_zome_types::x_salsa20_poly1305::x25519::X25519PubKey;
+use holochain_zome_types::x_salsa20_poly1305::X25519XSalsa20Poly1305Decrypt;
+use holochain_zome_types::x_salsa20_poly1305::X25519XSalsa20Poly1305Encrypt;
+use ring::rand::SecureRandom;
+use std::convert::TryInto;
+use std::path::Path;
+use std::sync::Arc;
+use xsalsa20poly1305::aead::generic_array::GenericArray;
+use xsalsa20poly1305::aead::Aead;
+use xsalsa20poly1305::aead::NewAead;
+use xsalsa20poly1305::XSalsa20Poly1305;
+
+/// The file in a Cell's environment directory that holds
+/// the sealed key for its private entries
+const PRIVATE_ENTRIES_KEY_FILE: &str = "private_entries.key";
+
+/// A secret key for encrypting values with xsalsa20-poly1305
+#[derive(Clone)]
+pub struct EncryptionKey(Arc<[u8; 32]>);
+
+impl std::fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("EncryptionKey(..)")
+    }
+}
+
+/// An [EncryptionKey] sealed by a keystore keypair to itself, as it is stored
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+struct SealedKey {
+    sealed_by: X25519PubKey,
+    sealed_key: XSalsa20Poly1305EncryptedData,
+}
+
+impl EncryptionKey {
+    /// Generate a new random key
+    pub fn generate() -> DatabaseResult<Self> {
+        let mut key = [0; 32];
+        ring::rand::SystemRandom::new()
+            .fill(&mut key)
+            .map_err(|_| DatabaseError::Encryption)?;
+        Ok(Self(Arc::new(key)))
+    }
+
+    /// Seal this key with a new x25519 keypair in the keystore
+    async fn seal(&self, keystore: &KeystoreSender) -> DatabaseResult<SealedKey> {
+        let sealed_by = keystore.create_x25519_keypair().await?;
+        let sealed_key = keystore
+            .x_25519_x_salsa20_poly1305_encrypt(X25519XSalsa20Poly1305Encrypt::new(
+                sealed_by,
+                sealed_by,
+                self.0.to_vec().into(),
+            ))
+            .await?;
+        Ok(SealedKey {
+            sealed_by,
+            sealed_key,
+        })
+    }
+
+    /// Open a key sealed by a keypair in this keystore
+    async fn unseal(keystore: &KeystoreSender, sealed: SealedKey) -> DatabaseResult<Self> {
+        let SealedKey {
+            sealed_by,
+            sealed_key,
+        } = sealed;
+        let key = keystore
+            .x_25519_x_salsa20_poly1305_decrypt(X25519XSalsa20Poly1305Decrypt::new(
+                sealed_by, sealed_by, sealed_key,
+            ))
+            .await?
+            .ok_or(DatabaseError::Decryption)?;
+        let key: [u8; 32] = key
+            .as_ref()
+            .try_into()
+            .map_err(|_| DatabaseError::Decryption)?;
+        Ok(Self(Arc::new(key)))
+    }
+
+    /// Get the key for the private entries of a Cell whose environment is under
+    /// this directory. A new key is generated and stored sealed the first time.
+    pub async fn for_private_entries(
+        keystore: &KeystoreSender,
+        path_prefix: &Path,
+        cell_id: &CellId,
+    ) -> DatabaseResult<Self> {
+        let dir = path_prefix.join(EnvironmentKind::Cell(cell_id.clone()).path());
+        let key_path = dir.join(PRIVATE_ENTRIES_KEY_FILE);
+        if key_path.is_file() {
+            let sealed = rmp_serde::from_read_ref(&std::fs::read(&key_path)?)?;
+            return Self::unseal(keystore, sealed).await;
+        }
+        let key = Self::generate()?;
+        let sealed = key.seal(keystore).await?;
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(&key_path, rmp_serde::to_vec_named(&sealed)?)?;
+        Ok(key)
+    }
+
+    /// Encrypt some data with a random nonce
+    pub fn encrypt(&self, data: &[u8]) -> DatabaseResult<XSalsa20Poly1305EncryptedData> {
+        let mut nonce = [0; NONCE_BYTES];
+        ring::rand::SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| DatabaseError::Encryption)?;
+        let cipher = XSalsa20Poly1305::new(GenericArray::from_slice(&self.0[..]));
+        let encrypted_data = cipher
+            .encrypt(GenericArray::from_slice(&nonce), data)
+            .map_err(|_| DatabaseError::Encryption)?;
+        Ok(XSalsa20Poly1305EncryptedData::new(
+            (&nonce[..])
+                .try_into()
+                .map_err(|_| DatabaseError::Encryption)?,
+            encrypted_data,
+        ))
+    }
+
+    /// Decrypt data that was encrypted with this key
+    pub fn decrypt(&self, data: &XSalsa20Poly1305EncryptedData) -> DatabaseResult<Vec<u8>> {
+        let cipher = XSalsa20Poly1305::new(GenericArray::from_slice(&self.0[..]));
+        cipher
+            .decrypt(
+                GenericArray::from_slice(data.as_nonce_ref().as_ref()),
+                data.as_encrypted_data_ref(),
+            )
+            .map_err(|_| DatabaseError::Decryption)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_keystore::test_keystore::spawn_test_keystore;
+    use holochain_zome_types::test_utils::fake_agent_pubkey_1;
+    use holochain_zome_types::test_utils::fake_agent_pubkey_2;
+    use holochain_zome_types::test_utils::fake_cell_id;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn stored_keys_round_trip() {
+        let keystore = spawn_test_keystore().await.unwrap();
+        let dir = tempdir::TempDir::new("private-entries-key").unwrap();
+        let cell_id = CellId::new(fake_cell_id(1).dna_hash().clone(), fake_agent_pubkey_1());
+        let key = EncryptionKey::for_private_entries(&keystore, dir.path(), &cell_id)
+            .await
+            .unwrap();
+
+        let encrypted = key.encrypt(b"private").unwrap();
+        assert_ne!(encrypted.as_encrypted_data_ref(), b"private");
+
+        // The key isn't stored in the clear
+        let key_path = dir
+            .path()
+            .join(EnvironmentKind::Cell(cell_id.clone()).path())
+            .join(PRIVATE_ENTRIES_KEY_FILE);
+        let stored = std::fs::read(key_path).unwrap();
+        assert!(!stored.windows(32).any(|w| *w == key.0[..]));
+
+        // The same cell always gets the same key back
+        let same_key = EncryptionKey::for_private_entries(&keystore, dir.path(), &cell_id)
+            .await
+            .unwrap();
+        assert_eq!(same_key.decrypt(&encrypted).unwrap(), b"private".to_vec());
+
+        // Another cell's key can't decrypt it
+        let other_cell_id = CellId::new(cell_id.dna_hash().clone(), fake_agent_pubkey_2());
+        let other_key = EncryptionKey::for_private_entries(&keystore, dir.path(), &other_cell_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            other_key.decrypt(&encrypted),
+            Err(DatabaseError::Decryption)
+        );
+    }
+}
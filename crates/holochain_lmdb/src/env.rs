@@ -4,6 +4,7 @@ use crate::db::get_db;
 use crate::db::initialize_databases;
 use crate::db::DbKey;
 use crate::db::GetDb;
+use crate::encryption::EncryptionKey;
 use crate::error::DatabaseError;
 use crate::error::DatabaseResult;
 use crate::transaction::Reader;
@@ -95,6 +96,7 @@ pub struct EnvironmentRead {
     kind: EnvironmentKind,
     path: PathBuf,
     keystore: KeystoreSender,
+    private_entry_key: Option<EncryptionKey>,
 }
 
 impl EnvironmentRead {
@@ -122,6 +124,11 @@ impl EnvironmentRead {
     pub fn path(&self) -> &PathBuf {
         &self.path
     }
+
+    /// The key private entries are encrypted with, if they are encrypted at rest
+    pub fn private_entry_key(&self) -> Option<&EncryptionKey> {
+        self.private_entry_key.as_ref()
+    }
 }
 
 impl GetDb for EnvironmentWrite {
@@ -156,6 +163,18 @@ impl EnvironmentWrite {
         path_prefix: &Path,
         kind: EnvironmentKind,
         keystore: KeystoreSender,
+    ) -> DatabaseResult<EnvironmentWrite> {
+        Self::new_with_private_entry_key(path_prefix, kind, keystore, None)
+    }
+
+    /// Create an environment whose private entries are encrypted with this key.
+    /// The key is fixed when the environment is first opened,
+    /// later calls for the same path return the existing environment.
+    pub fn new_with_private_entry_key(
+        path_prefix: &Path,
+        kind: EnvironmentKind,
+        keystore: KeystoreSender,
+        private_entry_key: Option<EncryptionKey>,
     ) -> DatabaseResult<EnvironmentWrite> {
         let mut map = ENVIRONMENTS.write();
         let path = path_prefix.join(kind.path());
@@ -175,6 +194,7 @@ impl EnvironmentWrite {
                         kind,
                         keystore,
                         path,
+                        private_entry_key,
                     })
                 })
                 .clone(),
@@ -223,7 +243,7 @@ pub enum EnvironmentKind {
 
 impl EnvironmentKind {
     /// Constuct a partial Path based on the kind
-    fn path(&self) -> PathBuf {
+    pub(crate) fn path(&self) -> PathBuf {
         match self {
             EnvironmentKind::Cell(cell_id) => PathBuf::from(cell_id.to_string()),
             EnvironmentKind::Conductor => PathBuf::from("conductor"),
@@ -81,6 +81,12 @@ pub enum DatabaseError {
 
     #[error("Unable to construct a value key")]
     KeyConstruction,
+
+    #[error("Failed to encrypt a value")]
+    Encryption,
+
+    #[error("Failed to decrypt a value, the encryption key is missing or wrong")]
+    Decryption,
 }
 
 impl PartialEq for DatabaseError {
@@ -9,6 +9,8 @@ const PENDING_PREFIX: u8 = 0x1;
 const REJECTED_PREFIX: u8 = 0x2;
 /// Prefix for authored database
 const AUTHORED_PREFIX: u8 = 0x3;
+/// Prefix for the database of abandoned data (could not be judged)
+const ABANDONED_PREFIX: u8 = 0x4;
 
 /// Prefix length 1 + hash length 39
 const PREFIX_KEY_SIZE: usize = HOLO_HASH_FULL_LEN + 1;
@@ -58,6 +60,10 @@ pub struct RejectedPrefix;
 /// Prefix key for data that has been authored
 pub struct AuthoredPrefix;
 
+#[derive(PartialOrd, Clone, Ord, PartialEq, Eq, Debug)]
+/// Prefix key for data that has been abandoned
+pub struct AbandonedPrefix;
+
 impl PrefixType for IntegratedPrefix {
     const PREFIX: u8 = INTEGRATED_PREFIX;
 }
@@ -74,6 +80,10 @@ impl PrefixType for AuthoredPrefix {
     const PREFIX: u8 = AUTHORED_PREFIX;
 }
 
+impl PrefixType for AbandonedPrefix {
+    const PREFIX: u8 = ABANDONED_PREFIX;
+}
+
 impl<P: PrefixType> PrefixHashKey<P> {
     /// Create prefix key from a hash
     pub fn new<C>(hash: &HoloHash<C>) -> Self
@@ -1,5 +1,6 @@
 //! Helpers for unit tests
 
+use crate::encryption::EncryptionKey;
 use crate::env::EnvironmentKind;
 use crate::env::EnvironmentWrite;
 use crate::prelude::BufKey;
@@ -15,6 +16,37 @@ pub fn test_cell_env() -> TestEnvironment {
     test_env(EnvironmentKind::Cell(cell_id))
 }
 
+/// Create a [TestEnvironment] of [EnvironmentKind::Cell], backed by a temp directory,
+/// whose private entries are encrypted with the cell's key.
+pub fn test_encrypted_cell_env() -> TestEnvironment {
+    let cell_id = fake_cell_id(1);
+    let keystore = test_keystore();
+    let tmpdir = Arc::new(TempDir::new("holochain-test-environments").unwrap());
+    let key = {
+        let keystore = keystore.clone();
+        let cell_id = cell_id.clone();
+        let tmpdir = tmpdir.clone();
+        tokio_safe_block_on::tokio_safe_block_on(
+            async move {
+                EncryptionKey::for_private_entries(&keystore, tmpdir.path(), &cell_id).await
+            },
+            std::time::Duration::from_secs(1),
+        )
+        .unwrap()
+        .expect("Couldn't get the private entry key")
+    };
+    TestEnvironment {
+        env: EnvironmentWrite::new_with_private_entry_key(
+            tmpdir.path(),
+            EnvironmentKind::Cell(cell_id),
+            keystore,
+            Some(key),
+        )
+        .expect("Couldn't create test LMDB environment"),
+        tmpdir,
+    }
+}
+
 /// Create a [TestEnvironment] of [EnvironmentKind::Conductor], backed by a temp directory.
 pub fn test_conductor_env() -> TestEnvironment {
     test_env(EnvironmentKind::Conductor)
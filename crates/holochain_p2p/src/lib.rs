@@ -57,6 +57,14 @@ pub trait HolochainP2pCellT {
         timeout_ms: Option<u64>,
     ) -> actor::HolochainP2pResult<()>;
 
+    /// Publish a warrant to the agent activity
+    /// authorities of the warranted agent.
+    async fn publish_warrant(
+        &mut self,
+        warrant: Warrant,
+        timeout_ms: Option<u64>,
+    ) -> actor::HolochainP2pResult<()>;
+
     /// Request a validation package.
     async fn get_validation_package(
         &mut self,
@@ -85,6 +93,13 @@ pub trait HolochainP2pCellT {
         options: actor::GetLinksOptions,
     ) -> actor::HolochainP2pResult<Vec<GetLinksResponse>>;
 
+    /// Count links on the DHT.
+    async fn count_links(
+        &mut self,
+        link_key: WireLinkMetaKey,
+        options: actor::GetLinksOptions,
+    ) -> actor::HolochainP2pResult<Vec<CountLinksResponse>>;
+
     /// Get agent activity from the DHT.
     async fn get_agent_activity(
         &mut self,
@@ -178,6 +193,23 @@ impl HolochainP2pCellT for HolochainP2pCell {
             .await
     }
 
+    /// Publish a warrant to the agent activity
+    /// authorities of the warranted agent.
+    async fn publish_warrant(
+        &mut self,
+        warrant: Warrant,
+        timeout_ms: Option<u64>,
+    ) -> actor::HolochainP2pResult<()> {
+        self.sender
+            .publish_warrant(
+                (*self.dna_hash).clone(),
+                (*self.from_agent).clone(),
+                warrant,
+                timeout_ms,
+            )
+            .await
+    }
+
     /// Request a validation package.
     async fn get_validation_package(
         &mut self,
@@ -243,6 +275,22 @@ impl HolochainP2pCellT for HolochainP2pCell {
             .await
     }
 
+    /// Count links on the DHT.
+    async fn count_links(
+        &mut self,
+        link_key: WireLinkMetaKey,
+        options: actor::GetLinksOptions,
+    ) -> actor::HolochainP2pResult<Vec<CountLinksResponse>> {
+        self.sender
+            .count_links(
+                (*self.dna_hash).clone(),
+                (*self.from_agent).clone(),
+                link_key,
+                options,
+            )
+            .await
+    }
+
     /// Get agent activity from the DHT.
     async fn get_agent_activity(
         &mut self,
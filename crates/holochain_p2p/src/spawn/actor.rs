@@ -134,6 +134,27 @@ impl HolochainP2pActor {
         .into())
     }
 
+    /// receiving an incoming count_links request from a remote node
+    fn handle_incoming_count_links(
+        &mut self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        link_key: WireLinkMetaKey,
+        options: event::GetLinksOptions,
+    ) -> kitsune_p2p::actor::KitsuneP2pHandlerResult<Vec<u8>> {
+        let evt_sender = self.evt_sender.clone();
+        Ok(async move {
+            let res = evt_sender
+                .count_links(dna_hash, to_agent, link_key, options)
+                .await;
+            res.and_then(|r| Ok(SerializedBytes::try_from(r)?))
+                .map_err(kitsune_p2p::KitsuneP2pError::from)
+                .map(|res| UnsafeBytes::from(res).into())
+        }
+        .boxed()
+        .into())
+    }
+
     /// receiving an incoming get_links request from a remote node
     fn handle_incoming_get_agent_activity(
         &mut self,
@@ -184,6 +205,25 @@ impl HolochainP2pActor {
         .into())
     }
 
+    /// receiving an incoming warrant from a remote node
+    fn handle_incoming_publish_warrant(
+        &mut self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        from_agent: AgentPubKey,
+        warrant: Warrant,
+    ) -> kitsune_p2p::actor::KitsuneP2pHandlerResult<()> {
+        let evt_sender = self.evt_sender.clone();
+        Ok(async move {
+            evt_sender
+                .publish_warrant(dna_hash, to_agent, from_agent, warrant)
+                .await?;
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
     /// receiving an incoming validation receipt from a remote node
     fn handle_incoming_validation_receipt(
         &mut self,
@@ -325,6 +365,9 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
             crate::wire::WireMessage::GetLinks { link_key, options } => {
                 self.handle_incoming_get_links(space, to_agent, link_key, options)
             }
+            crate::wire::WireMessage::CountLinks { link_key, options } => {
+                self.handle_incoming_count_links(space, to_agent, link_key, options)
+            }
             crate::wire::WireMessage::GetAgentActivity {
                 agent,
                 query,
@@ -332,7 +375,8 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
             } => self.handle_incoming_get_agent_activity(space, to_agent, agent, query, options),
             // holochain_p2p never publishes via request
             // these only occur on broadcasts
-            crate::wire::WireMessage::Publish { .. } => {
+            crate::wire::WireMessage::Publish { .. }
+            | crate::wire::WireMessage::PublishWarrant { .. } => {
                 Err(HolochainP2pError::invalid_p2p_message(
                     "invalid: publish is a broadcast type, not a request".to_string(),
                 )
@@ -368,6 +412,7 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
             | crate::wire::WireMessage::Get { .. }
             | crate::wire::WireMessage::GetMeta { .. }
             | crate::wire::WireMessage::GetLinks { .. }
+            | crate::wire::WireMessage::CountLinks { .. }
             | crate::wire::WireMessage::GetAgentActivity { .. }
             | crate::wire::WireMessage::GetValidationPackage { .. }
             | crate::wire::WireMessage::ValidationReceipt { .. } => {
@@ -388,6 +433,9 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
                 dht_hash,
                 ops,
             ),
+            crate::wire::WireMessage::PublishWarrant { warrant } => {
+                self.handle_incoming_publish_warrant(space, to_agent, from_agent, warrant)
+            }
         }
     }
 
@@ -608,6 +656,41 @@ impl HolochainP2pHandler for HolochainP2pActor {
         .into())
     }
 
+    #[tracing::instrument(skip(self), level = "trace")]
+    fn handle_publish_warrant(
+        &mut self,
+        dna_hash: DnaHash,
+        from_agent: AgentPubKey,
+        warrant: Warrant,
+        timeout_ms: Option<u64>,
+    ) -> HolochainP2pHandlerResult<()> {
+        let space = dna_hash.into_kitsune();
+        let from_agent = from_agent.into_kitsune();
+        // Send the warrant to the agent activity authorities
+        // by using the warranted agent as the basis
+        let agent_hash: AnyDhtHash = warrant.warranted_agent().clone().into();
+        let basis = agent_hash.to_kitsune();
+
+        let payload = crate::wire::WireMessage::publish_warrant(warrant).encode()?;
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(async move {
+            kitsune_p2p
+                .notify_multi(kitsune_p2p::actor::NotifyMulti {
+                    space,
+                    from_agent,
+                    basis,
+                    remote_agent_count: None, // default best-effort
+                    timeout_ms,
+                    payload,
+                })
+                .await?;
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
     #[tracing::instrument(skip(self), level = "trace")]
     fn handle_get_validation_package(
         &mut self,
@@ -761,6 +844,50 @@ impl HolochainP2pHandler for HolochainP2pActor {
         .into())
     }
 
+    #[tracing::instrument(skip(self), level = "trace")]
+    fn handle_count_links(
+        &mut self,
+        dna_hash: DnaHash,
+        from_agent: AgentPubKey,
+        link_key: WireLinkMetaKey,
+        options: actor::GetLinksOptions,
+    ) -> HolochainP2pHandlerResult<Vec<CountLinksResponse>> {
+        let space = dna_hash.into_kitsune();
+        let from_agent = from_agent.into_kitsune();
+        let basis = link_key.basis().to_kitsune();
+        let r_options: event::GetLinksOptions = (&options).into();
+
+        let payload = crate::wire::WireMessage::count_links(link_key, r_options).encode()?;
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(async move {
+            // TODO - We're just targeting a single remote node for now
+            //        Setting up RpcMulti to act like RpcSingle
+            let result = kitsune_p2p
+                .rpc_multi(kitsune_p2p::actor::RpcMulti {
+                    space,
+                    from_agent,
+                    basis,
+                    remote_agent_count: Some(1),
+                    timeout_ms: options.timeout_ms,
+                    as_race: false,
+                    race_timeout_ms: options.timeout_ms,
+                    payload,
+                })
+                .await?;
+
+            let mut out = Vec::new();
+            for item in result {
+                let kitsune_p2p::actor::RpcMultiResponse { response, .. } = item;
+                out.push(SerializedBytes::from(UnsafeBytes::from(response)).try_into()?);
+            }
+
+            Ok(out)
+        }
+        .boxed()
+        .into())
+    }
+
     #[tracing::instrument(skip(self), level = "trace")]
     fn handle_get_agent_activity(
         &mut self,
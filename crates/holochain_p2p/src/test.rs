@@ -51,6 +51,15 @@ impl HolochainP2pHandler for StubNetwork {
     ) -> HolochainP2pHandlerResult<()> {
         Err("stub".into())
     }
+    fn handle_publish_warrant(
+        &mut self,
+        dna_hash: DnaHash,
+        from_agent: AgentPubKey,
+        warrant: Warrant,
+        timeout_ms: Option<u64>,
+    ) -> HolochainP2pHandlerResult<()> {
+        Err("stub".into())
+    }
     fn handle_get_validation_package(
         &mut self,
         input: actor::GetValidationPackage,
@@ -84,6 +93,15 @@ impl HolochainP2pHandler for StubNetwork {
     ) -> HolochainP2pHandlerResult<Vec<GetLinksResponse>> {
         Err("stub".into())
     }
+    fn handle_count_links(
+        &mut self,
+        dna_hash: DnaHash,
+        from_agent: AgentPubKey,
+        link_key: WireLinkMetaKey,
+        options: actor::GetLinksOptions,
+    ) -> HolochainP2pHandlerResult<Vec<CountLinksResponse>> {
+        Err("stub".into())
+    }
     fn handle_get_agent_activity(
         &mut self,
         dna_hash: DnaHash,
@@ -485,4 +503,58 @@ mod tests {
         p2p.ghost_actor_shutdown().await.unwrap();
         r_task.await.unwrap();
     }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn test_count_links_workflow() {
+        let (dna, a1, a2, _) = test_setup();
+
+        let (p2p, mut evt) = spawn_holochain_p2p(
+            KitsuneP2pConfig::default(),
+            TlsConfig::new_ephemeral().await.unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let r_task = tokio::task::spawn(async move {
+            use tokio::stream::StreamExt;
+            while let Some(evt) = evt.next().await {
+                use crate::types::event::HolochainP2pEvent::*;
+                match evt {
+                    CountLinks { respond, .. } => {
+                        respond.r(Ok(async move { Ok(CountLinksResponse(3)) }.boxed().into()));
+                    }
+                    SignNetworkData { respond, .. } => {
+                        respond.r(Ok(async move { Ok(vec![0; 64].into()) }.boxed().into()));
+                    }
+                    PutAgentInfoSigned { respond, .. } => {
+                        respond.r(Ok(async move { Ok(()) }.boxed().into()));
+                    }
+                    _ => {}
+                }
+            }
+        });
+
+        p2p.join(dna.clone(), a1.clone()).await.unwrap();
+        p2p.join(dna.clone(), a2.clone()).await.unwrap();
+
+        let hash = holo_hash::EntryHash::from_raw_36_and_type(
+            b"eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee".to_vec(),
+            holo_hash::hash_type::Entry,
+        );
+        let link_key = WireLinkMetaKey::Base(hash);
+
+        let res = p2p
+            .count_links(dna, a1, link_key, actor::GetLinksOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(2, res.len());
+
+        for r in res {
+            assert_eq!(r, CountLinksResponse(3));
+        }
+
+        p2p.ghost_actor_shutdown().await.unwrap();
+        r_task.await.unwrap();
+    }
 }
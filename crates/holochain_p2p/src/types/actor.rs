@@ -48,8 +48,8 @@ pub struct GetOptions {
     pub race_timeout_ms: Option<u64>,
 
     /// [Remote]
-    /// Whether the remote-end should follow redirects or just return the
-    /// requested entry.
+    /// Whether the remote-end should return the updates needed
+    /// to follow redirects or just return the requested entry.
     pub follow_redirects: bool,
 
     /// [Remote]
@@ -65,15 +65,18 @@ impl Default for GetOptions {
             timeout_ms: None,
             as_race: true,
             race_timeout_ms: None,
-            follow_redirects: true,
+            follow_redirects: false,
             all_live_headers_with_metadata: false,
         }
     }
 }
 
 impl From<holochain_zome_types::entry::GetOptions> for GetOptions {
-    fn from(_: holochain_zome_types::entry::GetOptions) -> Self {
-        Self::default()
+    fn from(options: holochain_zome_types::entry::GetOptions) -> Self {
+        Self {
+            follow_redirects: options.follow_redirects,
+            ..Self::default()
+        }
     }
 }
 
@@ -136,11 +139,19 @@ pub struct GetLinksOptions {
     /// Note - if all requests time-out you will receive an empty result,
     /// not a timeout error.
     pub timeout_ms: Option<u64>,
+
+    /// [Remote]
+    /// Filter and page the links on the authority,
+    /// so it only sends back the live links that were asked for.
+    pub query: LinkQuery,
 }
 
 impl Default for GetLinksOptions {
     fn default() -> Self {
-        Self { timeout_ms: None }
+        Self {
+            timeout_ms: None,
+            query: LinkQuery::default(),
+        }
     }
 }
 
@@ -218,6 +229,15 @@ ghost_actor::ghost_chan! {
             timeout_ms: Option<u64>,
         ) -> ();
 
+        /// Publish a warrant to the agent activity
+        /// authorities of the warranted agent.
+        fn publish_warrant(
+            dna_hash: DnaHash,
+            from_agent: AgentPubKey,
+            warrant: Warrant,
+            timeout_ms: Option<u64>,
+        ) -> ();
+
         /// Request a validation package.
         fn get_validation_package(input: GetValidationPackage) -> ValidationPackageResponse;
 
@@ -245,6 +265,14 @@ ghost_actor::ghost_chan! {
             options: GetLinksOptions,
         ) -> Vec<GetLinksResponse>;
 
+        /// Count links on the DHT.
+        fn count_links(
+            dna_hash: DnaHash,
+            from_agent: AgentPubKey,
+            link_key: WireLinkMetaKey,
+            options: GetLinksOptions,
+        ) -> Vec<CountLinksResponse>;
+
         /// Get agent activity from the DHT.
         fn get_agent_activity(
             dna_hash: DnaHash,
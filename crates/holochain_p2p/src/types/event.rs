@@ -8,8 +8,8 @@ use kitsune_p2p::agent_store::AgentInfoSigned;
 /// Get options help control how the get is processed at various levels.
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct GetOptions {
-    /// Whether the remote-end should follow redirects or just return the
-    /// requested entry.
+    /// Whether the remote-end should return the updates needed
+    /// to follow redirects or just return the requested entry.
     pub follow_redirects: bool,
     /// Return all live headers even if there is deletes.
     /// Useful for metadata calls.
@@ -27,21 +27,33 @@ impl From<&actor::GetOptions> for GetOptions {
 
 /// GetMeta options help control how the get is processed at various levels.
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
-pub struct GetMetaOptions {}
+pub struct GetMetaOptions {
+    /// Which metadata the remote-end should return.
+    pub metadata_request: MetadataRequest,
+}
 
 impl From<&actor::GetMetaOptions> for GetMetaOptions {
-    fn from(_a: &actor::GetMetaOptions) -> Self {
-        Self {}
+    fn from(a: &actor::GetMetaOptions) -> Self {
+        Self {
+            metadata_request: a.metadata_request.clone(),
+        }
     }
 }
 
 /// GetLinks options help control how the get is processed at various levels.
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
-pub struct GetLinksOptions {}
+pub struct GetLinksOptions {
+    /// Only return the live links that match this query.
+    /// An empty query returns every link and its deletes.
+    #[serde(default)]
+    pub query: LinkQuery,
+}
 
 impl From<&actor::GetLinksOptions> for GetLinksOptions {
-    fn from(_a: &actor::GetLinksOptions) -> Self {
-        Self {}
+    fn from(a: &actor::GetLinksOptions) -> Self {
+        Self {
+            query: a.query.clone(),
+        }
     }
 }
 
@@ -101,6 +113,15 @@ ghost_actor::ghost_chan! {
             ops: Vec<(holo_hash::DhtOpHash, holochain_types::dht_op::DhtOp)>,
         ) -> ();
 
+        /// A remote node is publishing a warrant against
+        /// an agent whose activity we claim to be holding.
+        fn publish_warrant(
+            dna_hash: DnaHash,
+            to_agent: AgentPubKey,
+            from_agent: AgentPubKey,
+            warrant: Warrant,
+        ) -> ();
+
         /// A remote node is requesting a validation package.
         fn get_validation_package(
             // The dna_hash / space_hash context.
@@ -134,6 +155,14 @@ ghost_actor::ghost_chan! {
             options: GetLinksOptions,
         ) -> GetLinksResponse;
 
+        /// A remote node is asking us to count the links we hold.
+        fn count_links(
+            dna_hash: DnaHash,
+            to_agent: AgentPubKey,
+            link_key: WireLinkMetaKey,
+            options: GetLinksOptions,
+        ) -> CountLinksResponse;
+
         /// A remote node is requesting agent activity from us.
         fn get_agent_activity(
             dna_hash: DnaHash,
@@ -184,10 +213,12 @@ macro_rules! match_p2p_evt {
         match $h {
             HolochainP2pEvent::CallRemote { $i, .. } => { $($t)* }
             HolochainP2pEvent::Publish { $i, .. } => { $($t)* }
+            HolochainP2pEvent::PublishWarrant { $i, .. } => { $($t)* }
             HolochainP2pEvent::GetValidationPackage { $i, .. } => { $($t)* }
             HolochainP2pEvent::Get { $i, .. } => { $($t)* }
             HolochainP2pEvent::GetMeta { $i, .. } => { $($t)* }
             HolochainP2pEvent::GetLinks { $i, .. } => { $($t)* }
+            HolochainP2pEvent::CountLinks { $i, .. } => { $($t)* }
             HolochainP2pEvent::GetAgentActivity { $i, .. } => { $($t)* }
             HolochainP2pEvent::ValidationReceiptReceived { $i, .. } => { $($t)* }
             HolochainP2pEvent::FetchOpHashesForConstraints { $i, .. } => { $($t)* }
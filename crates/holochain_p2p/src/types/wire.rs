@@ -34,6 +34,9 @@ pub(crate) enum WireMessage {
         dht_hash: holo_hash::AnyDhtHash,
         ops: Vec<(holo_hash::DhtOpHash, holochain_types::dht_op::DhtOp)>,
     },
+    PublishWarrant {
+        warrant: Warrant,
+    },
     ValidationReceipt {
         #[serde(with = "serde_bytes")]
         receipt: Vec<u8>,
@@ -50,6 +53,10 @@ pub(crate) enum WireMessage {
         link_key: WireLinkMetaKey,
         options: event::GetLinksOptions,
     },
+    CountLinks {
+        link_key: WireLinkMetaKey,
+        options: event::GetLinksOptions,
+    },
     GetAgentActivity {
         agent: AgentPubKey,
         query: ChainQueryFilter,
@@ -95,6 +102,10 @@ impl WireMessage {
         }
     }
 
+    pub fn publish_warrant(warrant: Warrant) -> WireMessage {
+        Self::PublishWarrant { warrant }
+    }
+
     pub fn validation_receipt(receipt: SerializedBytes) -> WireMessage {
         Self::ValidationReceipt {
             receipt: UnsafeBytes::from(receipt).into(),
@@ -116,6 +127,10 @@ impl WireMessage {
         Self::GetLinks { link_key, options }
     }
 
+    pub fn count_links(link_key: WireLinkMetaKey, options: event::GetLinksOptions) -> WireMessage {
+        Self::CountLinks { link_key, options }
+    }
+
     pub fn get_agent_activity(
         agent: AgentPubKey,
         query: ChainQueryFilter,
@@ -31,6 +31,14 @@ pub struct AuthoredDhtOpsValue {
     pub receipt_count: u32,
     /// Time last published, None if never published
     pub last_publish_time: Option<Timestamp>,
+    /// Number of times the op has been published,
+    /// used to back off publishing ops that never get enough receipts
+    #[serde(default)]
+    pub publish_count: u32,
+    /// Set by the health check when too few authorities hold the op,
+    /// so it's published once more whatever its receipts and backoff
+    #[serde(default)]
+    pub republish: bool,
 }
 
 impl AuthoredDhtOpsValue {
@@ -40,6 +48,8 @@ impl AuthoredDhtOpsValue {
             op,
             receipt_count: 0,
             last_publish_time: None,
+            publish_count: 0,
+            republish: false,
         }
     }
 }
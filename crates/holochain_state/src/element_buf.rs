@@ -7,6 +7,10 @@
 /// using the ElementBuf for caching non-authored data, or for situations where
 /// it is known that private entries should be protected, such as when handling
 /// a get_entry request from the network.
+///
+/// If the environment has a private entry key, private entries are encrypted
+/// at rest. There is no cache for private entries, so the cache never holds
+/// private data.
 use crate::source_chain::SourceChainResult;
 use holo_hash::hash_type::AnyDht;
 use holo_hash::AnyDhtHash;
@@ -27,6 +31,10 @@ use holochain_lmdb::prelude::*;
 use holochain_types::prelude::*;
 use tracing::*;
 
+pub use private_entry_buf::*;
+
+mod private_entry_buf;
+
 /// A CasBufFresh with Entries for values
 pub type EntryCas<P> = CasBufFreshSync<Entry, P>;
 /// A CasBufFresh with SignedHeaders for values
@@ -39,7 +47,7 @@ where
     P: PrefixType,
 {
     public_entries: EntryCas<P>,
-    private_entries: Option<EntryCas<P>>,
+    private_entries: Option<PrivateEntryBuf<P>>,
     headers: HeaderCas<P>,
 }
 
@@ -77,6 +85,14 @@ impl ElementBuf<RejectedPrefix> {
     }
 }
 
+impl ElementBuf<AbandonedPrefix> {
+    /// Create a element buf for all elements that have been abandoned.
+    /// This reuses the database but is the data is completely separate.
+    pub fn abandoned(env: EnvironmentRead) -> DatabaseResult<Self> {
+        ElementBuf::new_vault(env, true)
+    }
+}
+
 impl ElementBuf<AuthoredPrefix> {
     /// Create a element buf for all authored elements.
     /// This reuses the database but is the data is completely separate.
@@ -96,7 +112,7 @@ where
         headers_store: SingleStore,
     ) -> DatabaseResult<Self> {
         let private_entries = if let Some(store) = private_entries_store {
-            Some(CasBufFreshSync::new(env.clone(), store))
+            Some(PrivateEntryBuf::new(env.clone(), store))
         } else {
             None
         };
@@ -243,7 +259,7 @@ where
                     EntryVisibility::Public => self.public_entries.put(entry),
                     EntryVisibility::Private => {
                         if let Some(db) = self.private_entries.as_mut() {
-                            db.put(entry);
+                            db.put(entry)?;
                         } else {
                             error!(
                                 "Attempted ElementBuf::put on a private entry with a disabled private DB: {}",
@@ -273,7 +289,7 @@ where
             EntryVisibility::Public => self.public_entries.put(entry),
             EntryVisibility::Private => {
                 if let Some(db) = self.private_entries.as_mut() {
-                    db.put(entry);
+                    db.put(entry)?;
                 } else {
                     error!(
                         "Attempted ElementBuf::put on a private entry with a disabled private DB: {}",
@@ -314,7 +330,7 @@ where
         &self.public_entries
     }
 
-    pub fn private_entries(&self) -> Option<&EntryCas<P>> {
+    pub fn private_entries(&self) -> Option<&PrivateEntryBuf<P>> {
         self.private_entries.as_ref()
     }
 
@@ -358,11 +374,13 @@ impl<P: PrefixType> BufferedStore for ElementBuf<P> {
 #[cfg(test)]
 mod tests {
     use super::ElementBuf;
+    use super::PrivateEntry;
     use holo_hash::*;
     use holochain_keystore::test_keystore::spawn_test_keystore;
     use holochain_keystore::AgentPubKeyExt;
     use holochain_lmdb::prelude::*;
     use holochain_lmdb::test_utils::test_cell_env;
+    use holochain_lmdb::test_utils::test_encrypted_cell_env;
     use holochain_types::test_utils::fake_unique_element;
     use holochain_zome_types::entry_def::EntryVisibility;
 
@@ -453,6 +471,41 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn private_entries_are_encrypted_at_rest() -> anyhow::Result<()> {
+        let keystore = spawn_test_keystore().await?;
+        let test_env = test_encrypted_cell_env();
+        let arc = test_env.env();
+        let env = arc.guard();
+
+        let agent_key = AgentPubKey::new_from_pure_entropy(&keystore).await?;
+        let (header_priv, entry_priv) =
+            fake_unique_element(&keystore, agent_key.clone(), EntryVisibility::Private).await?;
+
+        env.with_commit(|txn| {
+            let mut store = ElementBuf::vault(arc.clone().into(), true)?;
+            store.put(header_priv, Some(entry_priv.clone()))?;
+            store.flush_to_txn(txn)
+        })?;
+
+        // Reads decrypt transparently
+        let store = ElementBuf::vault(arc.clone().into(), true)?;
+        assert_eq!(
+            store.get_entry(entry_priv.as_hash()),
+            Ok(Some(entry_priv.clone()))
+        );
+
+        // The entry is not stored in plaintext
+        let raw: KvBufFresh<PrefixHashKey<IntegratedPrefix>, PrivateEntry> = KvBufFresh::new(
+            arc.clone().into(),
+            arc.get_db(&*ELEMENT_VAULT_PRIVATE_ENTRIES)?,
+        );
+        let stored = raw.get(&PrefixHashKey::new(entry_priv.as_hash()))?;
+        matches::assert_matches!(stored, Some(PrivateEntry::Encrypted(_)));
+
+        Ok(())
+    }
 }
 
 /// Create an ElementBuf with a clone of the scratch
@@ -0,0 +1,151 @@
+//! The store for private entries, which are optionally encrypted at rest.
+//!
+//! If the environment has a private entry key, entries are encrypted as they
+//! are put into the scratch space, so they are never written to LMDB in
+//! plaintext, and decrypted as they are read. Entries which were stored
+//! in plaintext, before encryption was turned on, can still be read.
+
+use holo_hash::EntryHash;
+use holo_hash::HasHash;
+use holochain_lmdb::buffer::KvBufUsed;
+use holochain_lmdb::encryption::EncryptionKey;
+use holochain_lmdb::error::DatabaseError;
+use holochain_lmdb::error::DatabaseResult;
+use holochain_lmdb::exports::SingleStore;
+use holochain_lmdb::fatal_db_hash_integrity_check;
+use holochain_lmdb::fresh_reader;
+use holochain_lmdb::prelude::*;
+use holochain_types::prelude::*;
+
+/// A private entry as it is stored
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum PrivateEntry {
+    /// The serialized entry, encrypted with the environment's private entry key
+    Encrypted(XSalsa20Poly1305EncryptedData),
+    /// The entry in plaintext.
+    /// This serializes exactly like a bare [Entry].
+    Plain(Entry),
+}
+
+/// A buffer of private entries, keyed by entry hash
+pub struct PrivateEntryBuf<P = IntegratedPrefix>
+where
+    P: PrefixType,
+{
+    env: EnvironmentRead,
+    key: Option<EncryptionKey>,
+    inner: KvBufUsed<PrefixHashKey<P>, PrivateEntry>,
+}
+
+impl<P: PrefixType> PrivateEntryBuf<P> {
+    /// Create a new PrivateEntryBuf, which encrypts entries if the
+    /// environment has a private entry key
+    pub fn new(env: EnvironmentRead, db: SingleStore) -> Self {
+        Self {
+            key: env.private_entry_key().cloned(),
+            env,
+            inner: KvBufUsed::new(db),
+        }
+    }
+
+    /// Get an entry, decrypting it if needed
+    pub fn get(&self, hash: &EntryHash) -> DatabaseResult<Option<EntryHashed>> {
+        let k = PrefixHashKey::new(hash.as_hash());
+        match fresh_reader!(self.env, |r| self.inner.get(&r, &k))? {
+            Some(stored) => Ok(Some(self.open(hash, stored)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Put an entry, encrypting it if there is a key
+    pub fn put(&mut self, entry: EntryHashed) -> DatabaseResult<()> {
+        let k = PrefixHashKey::new(entry.as_hash());
+        let stored = match &self.key {
+            Some(key) => PrivateEntry::Encrypted(
+                key.encrypt(&holochain_serialized_bytes::encode(entry.as_content())?)?,
+            ),
+            None => PrivateEntry::Plain(entry.into_content()),
+        };
+        self.inner.put(k, stored)
+    }
+
+    /// Delete an entry
+    pub fn delete(&mut self, hash: EntryHash) {
+        let k = PrefixHashKey::new(hash.as_hash());
+        // These expects seem valid as it means the hashing is broken
+        self.inner.delete(k).expect("Hash key is empty");
+    }
+
+    /// Remove a delete from the scratch space
+    pub fn cancel_delete(&mut self, hash: EntryHash) {
+        let k = PrefixHashKey::new(hash.as_hash());
+        // These expects seem valid as it means the hashing is broken
+        self.inner.cancel_delete(k).expect("Hash key is empty");
+    }
+
+    /// Check if an entry is stored, without decrypting it
+    pub fn contains(&self, hash: &EntryHash) -> DatabaseResult<bool> {
+        let k = PrefixHashKey::new(hash.as_hash());
+        fresh_reader!(self.env, |r| self.inner.contains(&r, &k))
+    }
+
+    /// Check if an entry is in the scratch space
+    pub fn contains_in_scratch(&self, hash: &EntryHash) -> DatabaseResult<bool> {
+        let k = PrefixHashKey::new(hash.as_hash());
+        self.inner.contains_in_scratch(&k)
+    }
+
+    /// Whether entries are encrypted at rest
+    pub fn is_encrypted(&self) -> bool {
+        self.key.is_some()
+    }
+
+    // TODO: This should be cfg test but can't because it's in a different crate
+    /// Clear all scratch and db, useful for tests
+    pub fn clear_all(&mut self, writer: &mut Writer) -> DatabaseResult<()> {
+        self.inner.clear_all(writer)
+    }
+
+    fn open(&self, hash: &EntryHash, stored: PrivateEntry) -> DatabaseResult<EntryHashed> {
+        let entry = match stored {
+            PrivateEntry::Plain(entry) => entry,
+            PrivateEntry::Encrypted(data) => {
+                let key = self.key.as_ref().ok_or(DatabaseError::Decryption)?;
+                holochain_serialized_bytes::decode(&key.decrypt(&data)?)?
+            }
+        };
+        let entry = EntryHashed::from_content_sync(entry);
+        fatal_db_hash_integrity_check!(
+            "PrivateEntryBuf::get",
+            hash.get_raw_39(),
+            entry.as_hash().get_raw_39(),
+            entry.as_content(),
+        );
+        Ok(entry)
+    }
+}
+
+impl<P: PrefixType> BufferedStore for PrivateEntryBuf<P> {
+    type Error = DatabaseError;
+
+    fn is_clean(&self) -> bool {
+        self.inner.is_clean()
+    }
+
+    fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> DatabaseResult<()> {
+        self.inner.flush_to_txn_ref(writer)
+    }
+}
+
+/// Create a PrivateEntryBuf with a clone of the scratch
+/// from another PrivateEntryBuf
+impl<P: PrefixType> From<&PrivateEntryBuf<P>> for PrivateEntryBuf<P> {
+    fn from(other: &PrivateEntryBuf<P>) -> Self {
+        Self {
+            env: other.env.clone(),
+            key: other.key.clone(),
+            inner: (&other.inner).into(),
+        }
+    }
+}
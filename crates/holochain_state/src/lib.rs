@@ -40,10 +40,12 @@ pub mod element_buf;
 pub mod metadata;
 #[allow(missing_docs)]
 pub mod prelude;
+pub mod scheduler;
 #[allow(missing_docs)]
 pub mod source_chain;
 pub mod validation_db;
 pub mod validation_receipts_db;
+pub mod warrants;
 #[allow(missing_docs)]
 pub mod wasm;
 pub mod workspace;
@@ -24,6 +24,7 @@ use holochain_lmdb::fresh_reader;
 use holochain_lmdb::prelude::*;
 use holochain_serialized_bytes::prelude::*;
 use holochain_types::prelude::*;
+use holochain_zome_types::link::LinkQuery;
 use holochain_zome_types::HeaderHashed;
 use std::collections::HashSet;
 use std::fmt::Debug;
@@ -77,6 +78,19 @@ where
         key: &'k LinkMetaKey<'k>,
     ) -> DatabaseResult<Box<dyn FallibleIterator<Item = LinkMetaVal, Error = DatabaseError> + 'r>>;
 
+    /// Get the live links on this base that match the tag and the query,
+    /// in key order.
+    ///
+    /// Iteration starts at the query's cursor or the start of its tag range,
+    /// whichever is later, and stops once the query's limit is reached.
+    /// Keys without a zome can't be seeked so the query only filters them.
+    fn get_live_links_query<'r, 'k, R: Readable>(
+        &'r self,
+        r: &'r R,
+        key: &'k LinkMetaKey<'k>,
+        query: &LinkQuery,
+    ) -> DatabaseResult<Box<dyn FallibleIterator<Item = LinkMetaVal, Error = DatabaseError> + 'r>>;
+
     /// Add a link
     fn add_link(&mut self, link_add: CreateLink) -> DatabaseResult<()>;
 
@@ -467,6 +481,39 @@ where
         Ok(())
     }
 
+    /// The redirect from an updated [Entry] to the new [Entry]
+    fn entry_redirect(update: header::Update) -> DatabaseResult<SysMetaVal> {
+        let new_entry_hash = update.entry_hash.clone();
+        let update = EntryHeader::Update(update.into()).into_hash()?;
+        Ok(SysMetaVal::EntryRedirect(update, new_entry_hash))
+    }
+
+    /// Of all the live [Update]s, choose the one to redirect to.
+    /// Updates are ordered by timestamp and then by hash so every
+    /// authority holding the same metadata resolves to the same update.
+    /// [Update]s that have been deleted are not followed.
+    fn latest_live_update<R: Readable, T, I>(
+        &self,
+        r: &R,
+        mut updates: I,
+    ) -> DatabaseResult<Option<T>>
+    where
+        I: FallibleIterator<Item = (TimedHeaderHash, T), Error = DatabaseError>,
+    {
+        let mut latest: Option<(TimedHeaderHash, T)> = None;
+        while let Some((update, redirect)) = updates.next()? {
+            if latest.as_ref().map(|(l, _)| update > *l).unwrap_or(true)
+                && self
+                    .get_deletes_on_header(r, update.header_hash.clone())?
+                    .next()?
+                    .is_none()
+            {
+                latest = Some((update, redirect));
+            }
+        }
+        Ok(latest.map(|(_, redirect)| redirect))
+    }
+
     #[instrument(skip(self))]
     fn update_entry_dht_status(&mut self, basis: EntryHash) -> DatabaseResult<()> {
         let status = fresh_reader!(self.env, |r| self.get_headers(&r, basis.clone())?.find_map(
@@ -643,6 +690,74 @@ where
         ))
     }
 
+    fn get_live_links_query<'r, 'k, R: Readable>(
+        &'r self,
+        r: &'r R,
+        key: &'k LinkMetaKey<'k>,
+        query: &LinkQuery,
+    ) -> DatabaseResult<Box<dyn FallibleIterator<Item = LinkMetaVal, Error = DatabaseError> + 'r>>
+    {
+        let prefix = PrefixBytesKey::<P>::from(key);
+        let mut from = prefix.clone();
+        // Where the tag starts in the keys and the end of the tag range
+        let mut tag_end: Option<(usize, Vec<u8>)> = None;
+        if let Some(base_zome) = key.base_zome() {
+            let base_zome = BytesKey::from(&base_zome).0;
+            let seek = |tail: &[&[u8]]| {
+                PrefixBytesKey::<P>::new(
+                    base_zome
+                        .iter()
+                        .chain(tail.iter().flat_map(|b| b.iter()))
+                        .cloned(),
+                )
+            };
+            if let Some(range) = &query.tag_range {
+                from = from.max(seek(&[&range.start.0[..]]));
+                tag_end = Some((seek(&[]).as_ref().len(), range.end.0.clone()));
+            }
+            if let Some(cursor) = &query.after {
+                from = from.max(seek(&[&cursor.tag.0[..], cursor.create_link_hash.as_ref()]));
+            }
+        }
+        let limit = query.limit;
+        let query = query.clone();
+        let links = self
+            .links_meta
+            .iter_from(r, from)?
+            .take_while(move |(k, _)| {
+                Ok(k.starts_with(prefix.as_ref())
+                    && tag_end
+                        .as_ref()
+                        .map(|(tag_start, end)| {
+                            may_be_before_tag_end(k.get(*tag_start..).unwrap_or(&[]), end)
+                        })
+                        .unwrap_or(true))
+            })
+            .map(|(_, link)| Ok(link))
+            // Skips the cursor itself and anything outside the tag range
+            .filter(move |link| {
+                Ok(query.check(
+                    &link.tag,
+                    &link.link_add_hash,
+                    &link.timestamp,
+                    link.author.as_ref(),
+                ))
+            })
+            .filter_map(move |link| {
+                match self
+                    .get_link_removes_on_link_add(r, link.link_add_hash.clone())?
+                    .next()?
+                {
+                    Some(_) => Ok(None),
+                    None => Ok(Some(link)),
+                }
+            });
+        Ok(match limit {
+            Some(limit) => Box::new(links.take(limit as usize)),
+            None => Box::new(links),
+        })
+    }
+
     fn add_link(&mut self, link_add: CreateLink) -> DatabaseResult<()> {
         // Register the add link onto the base
         let link_add_hash =
@@ -659,6 +774,7 @@ where
                 timestamp: link_add.timestamp,
                 zome_id: link_add.zome_id,
                 tag: link_add.tag,
+                author: Some(link_add.author),
             },
         )
     }
@@ -781,15 +897,23 @@ where
     fn register_update(&mut self, update: header::Update) -> DatabaseResult<()> {
         let header_hash = update.original_header_address.clone();
         let entry_hash = update.original_entry_address.clone();
+        let redirect = Self::entry_redirect(update.clone())?;
         self.register_header_on_basis(header_hash, update.clone())?;
-        self.register_header_on_basis(entry_hash, update)
+        self.register_header_on_basis(entry_hash.clone(), update)?;
+        self.system_meta
+            .insert(PrefixBytesKey::new(SysMetaKey::from(entry_hash)), redirect);
+        Ok(())
     }
 
     fn deregister_update(&mut self, update: header::Update) -> DatabaseResult<()> {
         let header_hash = update.original_header_address.clone();
         let entry_hash = update.original_entry_address.clone();
+        let redirect = Self::entry_redirect(update.clone())?;
         self.deregister_header_on_basis(header_hash, update.clone())?;
-        self.deregister_header_on_basis(entry_hash, update)
+        self.deregister_header_on_basis(entry_hash.clone(), update)?;
+        self.system_meta
+            .delete(PrefixBytesKey::new(SysMetaKey::from(entry_hash)), redirect);
+        Ok(())
     }
 
     fn register_delete(&mut self, delete: header::Delete) -> DatabaseResult<()> {
@@ -1181,12 +1305,45 @@ where
         .into())
     }
 
-    fn get_canonical_entry_hash(&self, _entry_hash: EntryHash) -> DatabaseResult<EntryHash> {
-        todo!("Cannot implement until redirects are implemented")
+    fn get_canonical_entry_hash(&self, entry_hash: EntryHash) -> DatabaseResult<EntryHash> {
+        fresh_reader!(self.env, |r| -> DatabaseResult<EntryHash> {
+            let mut canonical = entry_hash;
+            // An update can point back at content we have already seen
+            // so track the path to avoid walking in circles.
+            let mut seen = HashSet::new();
+            seen.insert(canonical.clone());
+            loop {
+                let redirects = fallible_iterator::convert(
+                    self.system_meta
+                        .get(&r, &SysMetaKey::from(canonical.clone()).into())?,
+                )
+                .filter_map(|v| {
+                    Ok(match v {
+                        SysMetaVal::EntryRedirect(update, entry_hash) => Some((update, entry_hash)),
+                        _ => None,
+                    })
+                });
+                match self.latest_live_update(&r, redirects)? {
+                    Some(next) if seen.insert(next.clone()) => canonical = next,
+                    _ => return Ok(canonical),
+                }
+            }
+        })
     }
 
-    fn get_canonical_header_hash(&self, _header_hash: HeaderHash) -> DatabaseResult<HeaderHash> {
-        todo!("Cannot implement until redirects are implemented")
+    fn get_canonical_header_hash(&self, header_hash: HeaderHash) -> DatabaseResult<HeaderHash> {
+        fresh_reader!(self.env, |r| -> DatabaseResult<HeaderHash> {
+            let mut canonical = header_hash;
+            loop {
+                let updates = self
+                    .get_updates(&r, canonical.clone().into())?
+                    .map(|update| Ok((update.clone(), update.header_hash)));
+                match self.latest_live_update(&r, updates)? {
+                    Some(next) => canonical = next,
+                    None => return Ok(canonical),
+                }
+            }
+        })
     }
 
     fn get_link_removes_on_link_add<'r, R: Readable>(
@@ -1278,3 +1435,18 @@ where
         }
     }
 }
+
+/// Whether a link key, from the tag onwards, may still be for a tag
+/// before the exclusive end of a tag range.
+///
+/// Keys are ordered by the tag bytes followed by the link add hash so the
+/// key for a tag that is a prefix of the end can come after keys for longer
+/// tags that are past the end. Keys for tags before the end never go past
+/// the end without its last byte, so the scan can stop there.
+fn may_be_before_tag_end(tag_and_hash: &[u8], end: &[u8]) -> bool {
+    match end.len().checked_sub(1) {
+        Some(len) => tag_and_hash[..len.min(tag_and_hash.len())] <= end[..len],
+        // Nothing is before an empty tag
+        None => false,
+    }
+}
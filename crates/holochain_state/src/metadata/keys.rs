@@ -35,6 +35,10 @@ pub struct LinkMetaVal {
     pub zome_id: ZomeId,
     /// A tag used to find this link
     pub tag: LinkTag,
+    /// The agent that created this link.
+    /// Links stored before authors were recorded don't have one.
+    #[serde(default)]
+    pub author: Option<AgentPubKey>,
 }
 
 /// Key for the LinkMeta database.
@@ -78,6 +82,9 @@ pub enum SysMetaVal {
     CustomPackage(HeaderHash),
     /// Validation Status
     ValidationStatus(ValidationStatus),
+    /// An [Update] registered on the [Entry] it updates,
+    /// along with the hash of the new [Entry] it redirects to
+    EntryRedirect(TimedHeaderHash, EntryHash),
 }
 
 // #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -124,6 +131,7 @@ impl LinkMetaVal {
         timestamp: Timestamp,
         zome_id: ZomeId,
         tag: LinkTag,
+        author: AgentPubKey,
     ) -> Self {
         Self {
             link_add_hash,
@@ -131,6 +139,7 @@ impl LinkMetaVal {
             timestamp,
             zome_id,
             tag,
+            author: Some(author),
         }
     }
 }
@@ -163,6 +172,15 @@ impl<'a> LinkMetaKey<'a> {
             Base(b) | BaseZome(b, _) | BaseZomeTag(b, _, _) | Full(b, _, _, _) => b,
         }
     }
+
+    /// Return the base and zome of this key, if it has a zome
+    pub fn base_zome(&self) -> Option<LinkMetaKey<'a>> {
+        use LinkMetaKey::*;
+        match self {
+            Base(_) => None,
+            BaseZome(b, z) | BaseZomeTag(b, z, _) | Full(b, z, _, _) => Some(BaseZome(b, *z)),
+        }
+    }
 }
 
 impl From<&LinkMetaKey<'_>> for BytesKey {
@@ -212,7 +230,8 @@ impl From<SysMetaVal> for HeaderHash {
             | SysMetaVal::Update(h)
             | SysMetaVal::Delete(h)
             | SysMetaVal::DeleteLink(h)
-            | SysMetaVal::Activity(h) => h.header_hash,
+            | SysMetaVal::Activity(h)
+            | SysMetaVal::EntryRedirect(h, _) => h.header_hash,
             SysMetaVal::CustomPackage(h) => h,
             SysMetaVal::ValidationStatus(_) => {
                 unreachable!("Tried to get the header hash from a validation status")
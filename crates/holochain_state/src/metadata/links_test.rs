@@ -52,6 +52,7 @@ async fn fixtures(env: EnvironmentWrite, n: usize) -> Vec<TestData> {
             timestamp: link_add.timestamp.clone().into(),
             zome_id,
             tag: tag.clone(),
+            author: Some(link_add.author.clone()),
         };
 
         let link_remove = KnownDeleteLink {
@@ -900,3 +901,132 @@ async fn links_on_same_tag() {
         );
     }
 }
+
+fn query_links(
+    env: &EnvironmentWrite,
+    meta_buf: &MetadataBuf,
+    key: &LinkMetaKey,
+    query: &LinkQuery,
+) -> Vec<LinkMetaVal> {
+    fresh_reader_test!(env, |r| meta_buf
+        .get_live_links_query(&r, key, query)
+        .unwrap()
+        .collect::<Vec<_>>()
+        .unwrap())
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn links_query_pages_and_filters() {
+    observability::test_run().ok();
+    let test_env = test_cell_env();
+    let arc = test_env.env();
+    let env = arc.guard();
+
+    let mut td = fixtures(arc.clone(), 10).await;
+    let base_hash = td[0].base_hash.clone();
+    let zome_id = td[0].zome_id;
+    let authors = AgentPubKeyFixturator::new(Predictable)
+        .take(2)
+        .collect::<Vec<_>>();
+
+    // Same base and zome, tags "tag0".."tag9",
+    // alternating authors and increasing timestamps
+    for (i, d) in td.iter_mut().enumerate() {
+        let tag = LinkTag::new(format!("tag{}", i));
+        d.base_hash = base_hash.clone();
+        d.zome_id = zome_id;
+        d.tag = tag.clone();
+        d.link_add.base_address = base_hash.clone();
+        d.link_add.zome_id = zome_id;
+        d.link_add.tag = tag.clone();
+        d.link_add.author = authors[i % 2].clone();
+        d.link_add.timestamp = Timestamp(i as i64, 0);
+
+        let link_add_hash =
+            HeaderHashed::from_content_sync(Header::CreateLink(d.link_add.clone())).into_hash();
+        d.expected_link.link_add_hash = link_add_hash.clone();
+        d.expected_link.zome_id = zome_id;
+        d.expected_link.tag = tag;
+        d.expected_link.author = Some(d.link_add.author.clone());
+        d.expected_link.timestamp = d.link_add.timestamp;
+        d.link_remove.link_add_address = link_add_hash;
+    }
+    let expected = |range: std::ops::Range<usize>| {
+        td[range]
+            .iter()
+            .map(|d| d.expected_link.clone())
+            .collect::<Vec<_>>()
+    };
+    {
+        let mut meta_buf = MetadataBuf::vault(arc.clone().into()).unwrap();
+        for d in td.iter() {
+            d.add_link(&mut meta_buf).await;
+        }
+        env.with_commit(|writer| meta_buf.flush_to_txn(writer))
+            .unwrap();
+    }
+    let key = LinkMetaKey::BaseZome(&base_hash, zome_id);
+    {
+        let meta_buf = MetadataBuf::vault(arc.clone().into()).unwrap();
+
+        // Page through every link three at a time
+        let mut pages = Vec::new();
+        let mut query = LinkQuery::new().limit(3);
+        loop {
+            let page = query_links(&arc, &meta_buf, &key, &query);
+            match page.last() {
+                Some(last) => {
+                    query = query.after(&last.clone().into_link());
+                    pages.push(page);
+                }
+                None => break,
+            }
+        }
+        assert_eq!(
+            pages.iter().map(|p| p.len()).collect::<Vec<_>>(),
+            vec![3, 3, 3, 1]
+        );
+        assert_eq!(pages.concat(), expected(0..10));
+
+        // Only tags from the start up to the end of the range
+        let query = LinkQuery::new().tag_range(LinkTag::new("tag2")..LinkTag::new("tag5"));
+        assert_eq!(query_links(&arc, &meta_buf, &key, &query), expected(2..5));
+
+        // "tag1" is before "tag10" but its key sorts after "tag10"
+        // so the scan can't stop at the first key past the end
+        let query = LinkQuery::new().tag_range(LinkTag::new("tag0")..LinkTag::new("tag10"));
+        assert_eq!(query_links(&arc, &meta_buf, &key, &query), expected(0..2));
+
+        let query = LinkQuery::new().author(authors[1].clone()).limit(2);
+        assert_eq!(
+            query_links(&arc, &meta_buf, &key, &query),
+            vec![td[1].expected_link.clone(), td[3].expected_link.clone()]
+        );
+
+        let query = LinkQuery::new().timestamp_range(Timestamp(3, 0)..Timestamp(6, 0));
+        assert_eq!(query_links(&arc, &meta_buf, &key, &query), expected(3..6));
+
+        // The tag prefix of the key still applies
+        let tag = LinkTag::new("tag7");
+        let tag_key = LinkMetaKey::BaseZomeTag(&base_hash, zome_id, &tag);
+        assert_eq!(
+            query_links(&arc, &meta_buf, &tag_key, &LinkQuery::new()),
+            expected(7..8)
+        );
+    }
+    {
+        // Deleted links don't count towards the limit
+        let mut meta_buf = MetadataBuf::vault(arc.clone().into()).unwrap();
+        td[0].delete_link(&mut meta_buf).await;
+        td[2].delete_link(&mut meta_buf).await;
+        let query = LinkQuery::new().limit(3);
+        assert_eq!(
+            query_links(&arc, &meta_buf, &key, &query),
+            vec![
+                td[1].expected_link.clone(),
+                td[3].expected_link.clone(),
+                td[4].expected_link.clone()
+            ]
+        );
+    }
+}
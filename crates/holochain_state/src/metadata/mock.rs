@@ -11,6 +11,11 @@ mock! {
             &self,
             key: &'a LinkMetaKey<'a>,
         ) -> DatabaseResult<Box<dyn FallibleIterator<Item = LinkMetaVal, Error = DatabaseError>>>;
+        fn get_live_links_query<'a>(
+            &self,
+            key: &'a LinkMetaKey<'a>,
+            query: &LinkQuery,
+        ) -> DatabaseResult<Box<dyn FallibleIterator<Item = LinkMetaVal, Error = DatabaseError>>>;
         fn add_link(&mut self, link_add: CreateLink) -> DatabaseResult<()>;
         fn delete_link(&mut self, link_remove: DeleteLink) -> DatabaseResult<()>;
         fn register_header(&mut self, new_entry_header: NewEntryHeader) -> DatabaseResult<()>;
@@ -149,6 +154,16 @@ impl MetadataBufT for MockMetadataBuf {
         MockMetadataBuf::get_links_all(&self, key)
     }
 
+    fn get_live_links_query<'r, 'k, R: Readable>(
+        &'r self,
+        _r: &'r R,
+        key: &'k LinkMetaKey<'k>,
+        query: &LinkQuery,
+    ) -> DatabaseResult<Box<dyn FallibleIterator<Item = LinkMetaVal, Error = DatabaseError> + 'r>>
+    {
+        MockMetadataBuf::get_live_links_query(&self, key, query)
+    }
+
     fn get_canonical_entry_hash(&self, entry_hash: EntryHash) -> DatabaseResult<EntryHash> {
         self.get_canonical_entry_hash(entry_hash)
     }
@@ -112,7 +112,6 @@ mod tests {
     }
 
     #[tokio::test(threaded_scheduler)]
-    #[ignore = "can't be tested until redirects are implemented"]
     /// Test that a header can be redirected a single hop
     async fn test_redirect_header_one_hop() -> anyhow::Result<()> {
         let test_env = test_cell_env();
@@ -137,7 +136,6 @@ mod tests {
     }
 
     #[tokio::test(threaded_scheduler)]
-    #[ignore = "can't be tested until redirects are implemented"]
     /// Test that a header can be redirected three hops
     async fn test_redirect_header_three_hops() -> anyhow::Result<()> {
         let test_env = test_cell_env();
@@ -179,7 +177,6 @@ mod tests {
     }
 
     #[tokio::test(threaded_scheduler)]
-    #[ignore = "can't be tested until redirects are implemented"]
     /// Test that an entry can be redirected a single hop
     async fn test_redirect_entry_one_hop() -> anyhow::Result<()> {
         let test_env = test_cell_env();
@@ -212,7 +209,6 @@ mod tests {
     }
 
     #[tokio::test(threaded_scheduler)]
-    #[ignore = "can't be tested until redirects are implemented"]
     /// Test that an entry can be redirected three hops
     async fn test_redirect_entry_three_hops() -> anyhow::Result<()> {
         let test_env = test_cell_env();
@@ -236,14 +232,14 @@ mod tests {
             let (update2, _) = test_update(
                 update1.original_header_address.clone(),
                 fx.entry_hash(),
-                original_entry.clone(),
+                update1.entry_hash.clone(),
                 &mut fx,
             )
             .await;
             let (update3, _) = test_update(
                 update2.original_header_address.clone(),
                 fx.entry_hash(),
-                original_entry.clone(),
+                update2.entry_hash.clone(),
                 &mut fx,
             )
             .await;
@@ -260,7 +256,6 @@ mod tests {
     }
 
     #[tokio::test(threaded_scheduler)]
-    #[ignore = "can't be tested until redirects are implemented"]
     /// Test that a header can be redirected a single hop
     async fn test_redirect_header_and_entry() -> anyhow::Result<()> {
         let test_env = test_cell_env();
@@ -286,7 +281,7 @@ mod tests {
             let (update_entry, _) = test_update(
                 header_hash,
                 fx.entry_hash(),
-                original_entry.clone(),
+                original_entry_1.clone(),
                 &mut fx,
             )
             .await;
@@ -306,6 +301,39 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test(threaded_scheduler)]
+    /// Test that concurrent updates resolve to the latest live update
+    async fn test_redirect_header_concurrent_updates() -> anyhow::Result<()> {
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let mut fx = TestFixtures::new();
+        {
+            let mut buf = MetadataBuf::vault(arc.clone().into())?;
+            let original = fx.header_hash();
+            let (update1, header1) =
+                test_update(original.clone(), fx.entry_hash(), fx.entry_hash(), &mut fx).await;
+            let (update2, header2) =
+                test_update(original.clone(), fx.entry_hash(), fx.entry_hash(), &mut fx).await;
+            buf.register_update(update1)?;
+            buf.register_update(update2)?;
+
+            let mut updates: Vec<TimedHeaderHash> = vec![header1.into(), header2.into()];
+            updates.sort();
+            let earliest = updates[0].header_hash.clone();
+            let latest = updates[1].header_hash.clone();
+
+            let canonical = buf.get_canonical_header_hash(original.clone())?;
+            assert_eq!(canonical, latest);
+
+            // Deleting the latest update falls back to the other one
+            let (delete, _) = test_delete(latest, fx.entry_hash(), &mut fx).await;
+            buf.register_delete(delete)?;
+            let canonical = buf.get_canonical_header_hash(original)?;
+            assert_eq!(canonical, earliest);
+        }
+        Ok(())
+    }
+
     #[tokio::test(threaded_scheduler)]
     async fn add_entry_get_headers() {
         let test_env = test_cell_env();
@@ -2,9 +2,11 @@ pub use crate::chain_sequence::*;
 pub use crate::dht_op_integration::*;
 pub use crate::element_buf::*;
 pub use crate::metadata::*;
+pub use crate::scheduler::*;
 pub use crate::source_chain::*;
 pub use crate::validation_db::*;
 pub use crate::validation_receipts_db::*;
+pub use crate::warrants::*;
 pub use crate::wasm::*;
 pub use crate::workspace::*;
 pub use crate::*;
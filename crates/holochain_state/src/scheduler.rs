@@ -0,0 +1,219 @@
+//! # Scheduled zome functions
+//!
+//! Zome functions that a zome has asked the host to call later, via the
+//! `schedule` host function. They are persisted so that they survive a
+//! conductor restart.
+
+use fallible_iterator::FallibleIterator;
+use holochain_lmdb::buffer::BufferedStore;
+use holochain_lmdb::buffer::KvBufFresh;
+use holochain_lmdb::db::SCHEDULED_FNS;
+use holochain_lmdb::error::DatabaseError;
+use holochain_lmdb::error::DatabaseResult;
+use holochain_lmdb::fresh_reader;
+use holochain_lmdb::prelude::*;
+use holochain_serialized_bytes::prelude::*;
+use holochain_types::prelude::*;
+
+/// Key for the [ScheduledFnStore].
+///
+/// A zome can only have one schedule per function so the key is
+/// the zome name and function name.
+#[derive(Debug, Clone, Hash, Eq, PartialEq, PartialOrd, Ord)]
+pub struct ScheduledFnKey(Vec<u8>);
+
+impl AsRef<[u8]> for ScheduledFnKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl BufKey for ScheduledFnKey {
+    fn from_key_bytes_or_friendly_panic(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+impl From<(&ZomeName, &FunctionName)> for ScheduledFnKey {
+    fn from((zome_name, fn_name): (&ZomeName, &FunctionName)) -> Self {
+        Self(
+            holochain_serialized_bytes::encode(&(zome_name, fn_name))
+                .expect("ScheduledFnKey serialization cannot fail"),
+        )
+    }
+}
+
+/// A zome function waiting to be called by the scheduler.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledFn {
+    /// The zome the function is in.
+    pub zome_name: ZomeName,
+    /// The function to call.
+    pub fn_name: FunctionName,
+    /// The payload to call the function with.
+    pub payload: ExternIO,
+    /// The next time the function is due to be called.
+    pub next_run: Timestamp,
+    /// If set the function is rescheduled with this interval after each call.
+    pub interval: Option<core::time::Duration>,
+}
+
+impl ScheduledFn {
+    /// Create a new scheduled fn for a zome from the input to the `schedule` host fn.
+    pub fn new(zome_name: ZomeName, input: ScheduleInput, now: Timestamp) -> TimestampResult<Self> {
+        let ScheduleInput {
+            fn_name,
+            payload,
+            delay,
+            interval,
+        } = input;
+        Ok(Self {
+            zome_name,
+            fn_name,
+            payload,
+            next_run: (now + delay)?,
+            interval,
+        })
+    }
+
+    /// Is this function due to be called at this time?
+    pub fn is_due(&self, now: &Timestamp) -> bool {
+        self.next_run <= *now
+    }
+
+    /// The key this function is stored under.
+    pub fn key(&self) -> ScheduledFnKey {
+        (&self.zome_name, &self.fn_name).into()
+    }
+
+    /// The schedule for this function after it is called at `now`.
+    /// Returns `None` if the function should not be called again.
+    pub fn reschedule(&self, now: Timestamp) -> Option<Self> {
+        let interval = self.interval?;
+        let next_run = (now + interval).ok()?;
+        Some(Self {
+            next_run,
+            ..self.clone()
+        })
+    }
+}
+
+/// The database of zome functions scheduled to be called by the host.
+pub struct ScheduledFnStore(KvBufFresh<ScheduledFnKey, ScheduledFn>);
+
+impl ScheduledFnStore {
+    /// Create a new scheduled fn store
+    pub fn new(env: EnvironmentRead) -> DatabaseResult<Self> {
+        let db = env.get_db(&*SCHEDULED_FNS)?;
+        Ok(Self(KvBufFresh::new(env, db)))
+    }
+
+    /// Schedule a function, replacing any existing schedule for it.
+    pub fn schedule(&mut self, scheduled_fn: ScheduledFn) -> DatabaseResult<()> {
+        self.0.put(scheduled_fn.key(), scheduled_fn)
+    }
+
+    /// Remove the schedule for a function.
+    pub fn unschedule(
+        &mut self,
+        zome_name: &ZomeName,
+        fn_name: &FunctionName,
+    ) -> DatabaseResult<()> {
+        self.0.delete((zome_name, fn_name).into())
+    }
+
+    /// Get the schedule for a function.
+    pub fn get(
+        &self,
+        zome_name: &ZomeName,
+        fn_name: &FunctionName,
+    ) -> DatabaseResult<Option<ScheduledFn>> {
+        self.0.get(&(zome_name, fn_name).into())
+    }
+
+    /// Take all the functions that are due to be called at `now`.
+    ///
+    /// Recurring functions are rescheduled and one off functions are removed,
+    /// so the returned functions will not be returned again until they are next due.
+    pub fn take_due(&mut self, now: Timestamp) -> DatabaseResult<Vec<ScheduledFn>> {
+        let due: Vec<ScheduledFn> = fresh_reader!(self.0.env(), |r| self
+            .0
+            .iter(&r)?
+            .map(|(_, v)| Ok(v))
+            .filter(|v: &ScheduledFn| Ok(v.is_due(&now)))
+            .collect::<Vec<_>>())?;
+        for scheduled_fn in due.iter() {
+            match scheduled_fn.reschedule(now) {
+                Some(next) => self.schedule(next)?,
+                None => self.0.delete(scheduled_fn.key())?,
+            }
+        }
+        Ok(due)
+    }
+}
+
+impl BufferedStore for ScheduledFnStore {
+    type Error = DatabaseError;
+
+    fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> DatabaseResult<()> {
+        self.0.flush_to_txn_ref(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_lmdb::test_utils::test_cell_env;
+
+    fn scheduled_fn(fn_name: &str, next_run: Timestamp, interval: Option<u64>) -> ScheduledFn {
+        ScheduledFn {
+            zome_name: "zome".into(),
+            fn_name: fn_name.into(),
+            payload: ExternIO::encode(()).unwrap(),
+            next_run,
+            interval: interval.map(core::time::Duration::from_secs),
+        }
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn take_due_removes_once_and_reschedules_recurring() -> DatabaseResult<()> {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let now = Timestamp(1000, 0);
+
+        {
+            let mut store = ScheduledFnStore::new(env.clone().into())?;
+            store.schedule(scheduled_fn("once", Timestamp(900, 0), None))?;
+            store.schedule(scheduled_fn("every", Timestamp(1000, 0), Some(10)))?;
+            store.schedule(scheduled_fn("later", Timestamp(1100, 0), None))?;
+            env.guard()
+                .with_commit(|writer| store.flush_to_txn(writer))?;
+        }
+
+        {
+            let mut store = ScheduledFnStore::new(env.clone().into())?;
+            let mut due = store
+                .take_due(now)?
+                .into_iter()
+                .map(|f| f.fn_name.0)
+                .collect::<Vec<_>>();
+            due.sort();
+            assert_eq!(due, vec!["every".to_string(), "once".to_string()]);
+            env.guard()
+                .with_commit(|writer| store.flush_to_txn(writer))?;
+        }
+
+        let store = ScheduledFnStore::new(env.clone().into())?;
+        let zome_name = "zome".into();
+        assert_eq!(store.get(&zome_name, &"once".into())?, None);
+        assert_eq!(
+            store.get(&zome_name, &"every".into())?.map(|f| f.next_run),
+            Some(Timestamp(1010, 0))
+        );
+        assert_eq!(
+            store.get(&zome_name, &"later".into())?.map(|f| f.next_run),
+            Some(Timestamp(1100, 0))
+        );
+        Ok(())
+    }
+}
@@ -3,6 +3,8 @@
 //! which would return Option in the SourceChainBuf, like getting the source chain head, or the AgentPubKey,
 //! cannot fail, so the function return types reflect that.
 
+use crate::metadata::MetadataBufT;
+pub use capability_index::*;
 pub use error::*;
 use fallible_iterator::FallibleIterator;
 use holo_hash::*;
@@ -13,8 +15,9 @@ use holochain_lmdb::prelude::*;
 use holochain_types::prelude::*;
 use shrinkwraprs::Shrinkwrap;
 pub use source_chain_buffer::*;
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 
+mod capability_index;
 mod error;
 mod source_chain_buffer;
 
@@ -79,18 +82,11 @@ impl SourceChain {
         self.put(header_builder, Some(entry)).await
     }
 
-    /// Fetch a relevant CapGrant from the private entries.
+    /// Fetch a relevant CapGrant from the capability index.
     ///
     /// If a function has an Unrestricted grant against it, this may be returned.
     ///
     /// Else the secret and assignees of a grant will be checked and may be returned.
-    ///
-    /// @todo this is not particularly fast, there are several ways to speed this up in the future
-    /// such as indexing secrets and prefixing cap grants in lmdb for direct lookup
-    ///
-    /// NB: [B-01676] the entry must be persisted for this to work. Once we have a
-    /// proper capability index DB, OR a proper iterator that respects the
-    /// scratch space, that will no longer be the case.
     pub fn valid_cap_grant(
         &self,
         check_function: &GrantedFunction,
@@ -98,99 +94,26 @@ impl SourceChain {
         check_secret: Option<&CapSecret>,
     ) -> SourceChainResult<Option<CapGrant>> {
         // most calls for most apps are going to be the local agent calling itself locally
-        // for this case we want to short circuit without iterating the whole source chain
+        // for this case we want to short circuit without looking up any grants
         let author_grant = CapGrant::from(self.agent_pubkey()?);
         if author_grant.is_valid(check_function, check_agent, check_secret) {
             return Ok(Some(author_grant));
         }
 
-        // if we are here then the caller is not the current agent so we need to search the source
-        // chain to see if there is a local grant that is valid for the provided secret/agent
-        // combination
-        let committed_valid_grant = fresh_reader!(self.env(), |r| {
-            let (references, headers): (
-                HashSet<HeaderHash>,
-                Vec<HoloHashed<holochain_zome_types::element::SignedHeader>>,
-            ) = self
-                .0
-                .headers()
-                .iter_fail(&r)?
-                .filter(|header| {
-                    Ok(match header.as_content().header() {
-                        Header::Create(create) => {
-                            // filter out authorship and everything else
-                            matches!(create.entry_type, EntryType::CapGrant)
-                        }
-                        Header::Update(update) => matches!(
-                            update.entry_type,
-                            // filter out authorship and everything else
-                            EntryType::CapGrant
-                        ),
-                        Header::Delete(_) => true,
-                        // no other headers are relevant
-                        _ => false,
-                    })
-                })
-                // extract all the header references
-                // if a header is referenced by an update/delete then it is no longer valid
-                // with all the references in a bucket we can use it to filter out entries below
-                .fold(
-                    (HashSet::new(), vec![]),
-                    |(mut references, mut headers), header| {
-                        match header.as_content().header() {
-                            Header::Update(update) => {
-                                references.insert(update.original_header_address.clone());
-                            }
-                            Header::Delete(delete) => {
-                                references.insert(delete.deletes_address.clone());
-                            }
-                            _ => {}
-                        }
-                        // this is a best-effort attempt to avoid putting things we already know as
-                        // referenced into the returned vec
-                        // it isn't comprehensive because it relies on ordering but it's an easy
-                        // and relatively safe optimisation to not do further processing here
-                        if !references.contains(header.as_hash()) {
-                            headers.push(header);
-                        }
-
-                        Ok((references, headers))
-                    },
-                )?;
-
-            // second pass over the headers to make sure that all referenced headers are removed
-            // this makes the process reliable even if the iterators don't follow the chain order
-            let live_cap_grants: HashSet<_> = headers
-                .iter()
-                .filter(|header| !references.contains(header.as_hash()))
-                .filter_map(|header| match header.as_content().header() {
-                    Header::Create(create) => Some(create.entry_hash.clone()),
-                    Header::Update(update) => Some(update.entry_hash.clone()),
-                    _ => None,
-                })
-                .collect();
-
-            self
+        // if we are here then the caller is not the current agent so we need to find a live
+        // grant that is valid for the provided secret/agent combination
+        let committed_valid_grant = self
             .0
-            .elements()
-            .private_entries()
-            .expect(
-                "SourceChainBuf must have access to private entries in order to access CapGrants",
-            )
-            .iter_fail(&r)?
-            // ensure we respect the header filtering we already did above
-            .filter(|entry| {
-                Ok(live_cap_grants.contains(entry.as_hash()))
-            })
-            .filter_map(|entry| Ok(entry.as_cap_grant()))
+            .capabilities()
+            .grants()?
+            .into_iter()
+            .map(|info| CapGrant::from(info.grant))
             // filter down to only the grants for this function
-            .filter(|grant| {
-                Ok(grant.is_valid(check_function, check_agent, check_secret))
-            })
+            .filter(|grant| grant.is_valid(check_function, check_agent, check_secret))
             // if there are still multiple grants, fold them down based on specificity
             // authorship > assigned > transferable > unrestricted
-            .fold(None, |mut acc, grant| {
-                acc = match &grant {
+            .fold(None, |acc, grant| {
+                match &grant {
                     CapGrant::RemoteAgent(zome_call_cap_grant) => {
                         match &zome_call_cap_grant.access {
                             CapAccess::Assigned { .. } => match &acc {
@@ -220,83 +143,101 @@ impl SourceChain {
                                 None => Some(grant),
                                 // authorship should be short circuited and filtered by now
                                 _ => unreachable!(),
-                            }
+                            },
                             CapAccess::Unrestricted => match acc {
                                 Some(_) => acc,
                                 None => Some(grant),
-                            }
+                            },
                         }
-                    },
+                    }
                     // ChainAuthor should have short circuited and be filtered out already
                     _ => unreachable!(),
-                };
-                Ok(acc)
-            })
-        })?;
+                }
+            });
+
         Ok(committed_valid_grant)
     }
 
-    // @todo bring all this back when we want to administer cap claims better
-    //         /// Fetch a CapClaim from the private entries.
-    //         ///
-    //         /// NB: [B-01676] the entry must be persisted for this to work. Once we have a
-    //         /// proper capability index DB, OR a proper iterator that respects the
-    //         /// scratch space, that will no longer be the case.
-    //         pub async fn get_persisted_cap_claim_by_secret(
-    //             &self,
-    //             query: &CapSecret,
-    //         ) -> SourceChainResult<Option<CapClaim>> {
-    //             let hashes_n_claims: Vec<_> = fresh_reader!(self.env(), |r| {
-    //                 self
-    //                 .0
-    //                 .elements()
-    //                 .private_entries()
-    //                 .expect(
-    //                     "SourceChainBuf must have access to private entries in order to access CapClaims",
-    //                 )
-    //                 .iter_fail(&r)?
-    //                 .filter_map(|entry| {
-    //                     if let (Entry::CapClaim(claim), entry_hash) = entry.into_inner() {
-    //                         Ok(Some((entry_hash, claim)))
-    //                     } else {
-    //                         Ok(None)
-    //                     }
-    //                 })
-    //                 .filter(|(_entry_hash, claim)| Ok(claim.secret() == query))
-    //                 .collect()
-    //             })?;
-    //
-    //             let answer = if hashes_n_claims.len() == 0 {
-    //                 None
-    //             } else if hashes_n_claims.len() == 1 {
-    //                 hashes_n_claims.first().map(|p| p.1.clone())
-    //             } else {
-    //                 // FIXME[B-01676]: we SHOULD iterate through the chain now to find the most
-    //                 // recent claim with this secret, in case it was updated.
-    //                 // This will be handled in the future with an index, for simple
-    //                 // lookup by secret
-    //                 todo!("Find proper claim or implement capability index")
-    //             };
-    //             Ok(answer)
-    //         }
-    //     }
-    // }
-
     /// Query Headers in the source chain.
     /// This returns a Vec rather than an iterator because it is intended to be
     /// used by the `query` host function, which crosses the wasm boundary
-    pub fn query(&self, query: &ChainQueryFilter) -> SourceChainResult<Vec<Element>> {
-        let include_entries = query.include_entries;
-        self.iter_back()
-            .filter(|shh| Ok(query.check(shh.header())))
-            .map(|shh| {
-                let entry = match shh.header().entry_hash() {
-                    Some(eh) if include_entries => self.0.get_entry(eh)?,
-                    _ => None,
-                };
-                Ok(Element::new(shh, entry.map(|e| e.into_content())))
-            })
-            .collect()
+    ///
+    /// Headers for the query's `entry_hashes` are found through the entry
+    /// index of the authored metadata.
+    pub fn query<M: MetadataBufT<AuthoredPrefix>>(
+        &self,
+        query: &ChainQueryFilter,
+        meta_authored: &M,
+    ) -> SourceChainResult<Vec<Element>> {
+        // Only read the headers in the sequence range from the chain sequence index
+        let len = self.0.len() as u32;
+        let (start, end) = match &query.sequence_range {
+            Some(range) => (range.start, range.end.min(len)),
+            None => (0, len),
+        };
+        let indices: Box<dyn DoubleEndedIterator<Item = u32>> = match &query.entry_hashes {
+            Some(entry_hashes) => {
+                // Genesis isn't integrated into the authored metadata
+                // so the genesis headers are always read
+                let mut seqs: BTreeSet<u32> = (0..3).collect();
+                fresh_reader!(self.0.env(), |r| -> SourceChainResult<()> {
+                    for entry_hash in entry_hashes {
+                        let mut headers = meta_authored.get_headers(&r, entry_hash.clone())?;
+                        while let Some(header) = headers.next()? {
+                            if let Some(shh) = self.0.get_header(&header.header_hash)? {
+                                seqs.insert(shh.header().header_seq());
+                            }
+                        }
+                    }
+                    Ok(())
+                })?;
+                Box::new(
+                    seqs.into_iter()
+                        .filter(move |seq| (start..end).contains(seq)),
+                )
+            }
+            None => Box::new(start..end),
+        };
+        let indices: Box<dyn Iterator<Item = u32>> = if query.order_ascending {
+            Box::new(indices)
+        } else {
+            Box::new(indices.rev())
+        };
+        let limit = query.limit.map(|limit| limit as usize);
+
+        let mut elements = Vec::new();
+        for i in indices {
+            if limit.map(|limit| elements.len() >= limit).unwrap_or(false) {
+                break;
+            }
+            let shh = match self.0.sequence().get(i)? {
+                Some(header_address) => match self.0.get_header(&header_address)? {
+                    Some(shh) => shh,
+                    None => continue,
+                },
+                None => continue,
+            };
+            // Timestamps always increase along the chain so once we're
+            // past the end of the range there's nothing left to match
+            if let Some(range) = &query.timestamp_range {
+                let timestamp = shh.header().timestamp();
+                if (query.order_ascending && timestamp >= range.end)
+                    || (!query.order_ascending && timestamp < range.start)
+                {
+                    break;
+                }
+            }
+            if !query.check(shh.header()) {
+                continue;
+            }
+            // Entries are only read for the headers that match
+            let entry = match shh.header().entry_hash() {
+                Some(eh) if query.include_entries => self.0.get_entry(eh)?,
+                _ => None,
+            };
+            elements.push(Element::new(shh, entry.map(|e| e.into_content())));
+        }
+        Ok(elements)
     }
 }
 
@@ -318,9 +259,12 @@ impl BufferedStore for SourceChain {
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::metadata::MetadataBuf;
     use ::fixt::prelude::*;
     use hdk3::prelude::*;
+    use holochain_lmdb::buffer::KvStore;
     use holochain_lmdb::test_utils::test_cell_env;
+    use holochain_types::test_utils::fake_agent_pubkey_1;
     use holochain_types::test_utils::fake_dna_hash;
     use holochain_zome_types::capability::CapAccess;
     use holochain_zome_types::capability::ZomeCallCapGrant;
@@ -484,47 +428,194 @@ pub mod tests {
         Ok(())
     }
 
-    // @todo bring all this back when we want to administer cap claims better
-    // #[tokio::test(threaded_scheduler)]
-    // async fn test_get_cap_claim() -> SourceChainResult<()> {
-    //     let test_env = test_cell_env();
-    //     let env = test_env.env();
-    //     let env = env.guard().await;
-    //     let secret = CapSecretFixturator::new(Unpredictable).next().unwrap();
-    //     let agent_pubkey = fake_agent_pubkey_1().into();
-    //     let claim = CapClaim::new("tag".into(), agent_pubkey, secret.clone());
-    //     {
-    //         let mut store = SourceChainBuf::new(env.clone().into(), &env).await?;
-    //         store
-    //             .genesis(fake_dna_hash(1), fake_agent_pubkey_1(), None)
-    //             .await?;
-    //         env.with_commit(|writer| store.flush_to_txn(writer))?;
-    //     }
-    //
-    //     {
-    //         let mut chain = SourceChain::new(env.clone().into(), &env).await?;
-    //         chain.put_cap_claim(claim.clone()).await?;
-    //
-    // // ideally the following would work, but it won't because currently
-    // // we can't get claims from the scratch space
-    // // this will be fixed once we add the capability index
-    //
-    // // assert_eq!(
-    // //     chain.get_persisted_cap_claim_by_secret(&secret)?,
-    // //     Some(claim.clone())
-    // // );
-    //
-    //         env.with_commit(|writer| chain.flush_to_txn(writer))?;
-    //     }
-    //
-    //     {
-    //         let chain = SourceChain::new(env.clone().into(), &env).await?;
-    //         assert_eq!(
-    //             chain.get_persisted_cap_claim_by_secret(&secret).await?,
-    //             Some(claim)
-    //         );
-    //     }
-    //
-    //     Ok(())
-    // }
+    #[tokio::test(threaded_scheduler)]
+    async fn test_backfill_cap_grant_index() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let secret = Some(CapSecretFixturator::new(Unpredictable).next().unwrap());
+        let function: GrantedFunction = ("foo".into(), "bar".into());
+        let mut functions: GrantedFunctions = HashSet::new();
+        functions.insert(function.clone());
+        let grant =
+            ZomeCallCapGrant::new("tag".into(), CapAccess::from(secret.unwrap()), functions);
+        let mut agents = AgentPubKeyFixturator::new(Predictable);
+        let alice = agents.next().unwrap();
+        let bob = agents.next().unwrap();
+        {
+            let mut store = SourceChainBuf::new(env.clone().into())?;
+            store.genesis(fake_dna_hash(1), alice.clone(), None).await?;
+            env.guard()
+                .with_commit(|writer| store.flush_to_txn(writer))?;
+        }
+        {
+            let mut chain = SourceChain::new(env.clone().into())?;
+            let (entry, entry_hash) =
+                EntryHashed::from_content_sync(Entry::CapGrant(grant.clone())).into_inner();
+            let header_builder = builder::Create {
+                entry_type: EntryType::CapGrant,
+                entry_hash,
+            };
+            chain.put(header_builder, Some(entry)).await?;
+            env.guard()
+                .with_commit(|writer| chain.flush_to_txn(writer))?;
+        }
+
+        // Wipe the index so the grant is only on the chain,
+        // like a chain written before the index existed
+        env.guard().with_commit(|writer| {
+            KvStore::<HeaderHash, CapGrantEntry>::new(env.get_db(&*CAP_GRANTS)?).delete_all(writer)
+        })?;
+        {
+            let chain = SourceChain::new(env.clone().into())?;
+            assert_eq!(
+                chain.valid_cap_grant(&function, &bob, secret.as_ref())?,
+                None
+            );
+        }
+
+        // Reopening the chain backfills the index
+        {
+            let mut store = SourceChainBuf::new(env.clone().into())?;
+            assert!(store.backfill_capabilities()?);
+            env.guard()
+                .with_commit(|writer| store.flush_to_txn(writer))?;
+        }
+        {
+            let chain = SourceChain::new(env.clone().into())?;
+            assert_eq!(
+                chain.valid_cap_grant(&function, &bob, secret.as_ref())?,
+                Some(grant.into())
+            );
+        }
+
+        // Once the index is filled there is nothing to backfill
+        let mut store = SourceChainBuf::new(env.clone().into())?;
+        assert!(!store.backfill_capabilities()?);
+
+        Ok(())
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn test_get_cap_claim() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let secret = CapSecretFixturator::new(Unpredictable).next().unwrap();
+        let agent_pubkey = fake_agent_pubkey_1();
+        let claim = CapClaim::new("tag".into(), agent_pubkey.clone(), secret);
+        {
+            let mut store = SourceChainBuf::new(env.clone().into())?;
+            store.genesis(fake_dna_hash(1), agent_pubkey, None).await?;
+            env.guard()
+                .with_commit(|writer| store.flush_to_txn(writer))?;
+        }
+
+        let header_hash = {
+            let mut chain = SourceChain::new(env.clone().into())?;
+            let header_hash = chain.put_cap_claim(claim.clone()).await?;
+
+            // the claim is in the index before it is persisted
+            assert_eq!(chain.capabilities().claims()?.len(), 1);
+
+            env.guard()
+                .with_commit(|writer| chain.flush_to_txn(writer))?;
+            header_hash
+        };
+
+        {
+            let chain = SourceChain::new(env.clone().into())?;
+            assert_eq!(
+                chain.capabilities().claims()?,
+                vec![CapClaimInfo { header_hash, claim }]
+            );
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn test_query_order_and_limit() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let agent_pubkey = fake_agent_pubkey_1();
+        {
+            let mut store = SourceChainBuf::new(env.clone().into())?;
+            store
+                .genesis(fake_dna_hash(1), agent_pubkey.clone(), None)
+                .await?;
+            env.guard()
+                .with_commit(|writer| store.flush_to_txn(writer))?;
+        }
+
+        let mut chain = SourceChain::new(env.clone().into())?;
+        for _ in 0..2 {
+            let secret = CapSecretFixturator::new(Unpredictable).next().unwrap();
+            let claim = CapClaim::new("tag".into(), agent_pubkey.clone(), secret);
+            chain.put_cap_claim(claim).await?;
+        }
+
+        // Index the claims' entries like the host functions do
+        let mut meta_authored = MetadataBuf::authored(env.clone().into())?;
+        let mut claim_entries = Vec::new();
+        for i in 3..5 {
+            let header = chain.get_at_index(i)?.unwrap().header().clone();
+            claim_entries.push(header.entry_hash().unwrap().clone());
+            meta_authored.register_header(header.try_into().unwrap())?;
+        }
+
+        let seqs = |query: ChainQueryFilter| -> SourceChainResult<Vec<u32>> {
+            Ok(chain
+                .query(&query, &meta_authored)?
+                .iter()
+                .map(|el| el.header().header_seq())
+                .collect())
+        };
+
+        assert_eq!(seqs(ChainQueryFilter::new())?, vec![4, 3, 2, 1, 0]);
+        assert_eq!(seqs(ChainQueryFilter::new().limit(2))?, vec![4, 3]);
+        assert_eq!(
+            seqs(ChainQueryFilter::new().ascending().limit(2))?,
+            vec![0, 1]
+        );
+        assert_eq!(
+            seqs(ChainQueryFilter::new().sequence_range(1..))?,
+            vec![4, 3, 2, 1]
+        );
+        assert_eq!(
+            seqs(
+                ChainQueryFilter::new()
+                    .header_type(HeaderType::Dna)
+                    .header_type(HeaderType::AgentValidationPkg)
+            )?,
+            vec![1, 0]
+        );
+        assert_eq!(
+            seqs(
+                ChainQueryFilter::new().entry_hashes(claim_entries[..1].iter().cloned().collect())
+            )?,
+            vec![3]
+        );
+        // The agent key is found in genesis
+        assert_eq!(
+            seqs(
+                ChainQueryFilter::new()
+                    .entry_hashes(
+                        vec![agent_pubkey.clone().into(), claim_entries[1].clone()]
+                            .into_iter()
+                            .collect()
+                    )
+                    .ascending()
+            )?,
+            vec![2, 4]
+        );
+        assert_eq!(
+            seqs(
+                ChainQueryFilter::new()
+                    .entry_hashes(claim_entries.iter().cloned().collect())
+                    .sequence_range(0..4)
+            )?,
+            vec![3]
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,215 @@
+//! # Capability index
+//!
+//! The capability grants and claims that are live on a source chain,
+//! keyed by the header that created or last updated them.
+//! The index is updated as elements are put on the chain, so looking up
+//! grants and claims doesn't need to scan the whole chain.
+
+use fallible_iterator::FallibleIterator;
+use holochain_lmdb::buffer::BufferedStore;
+use holochain_lmdb::buffer::KvBufFresh;
+use holochain_lmdb::db::CAP_CLAIMS;
+use holochain_lmdb::db::CAP_GRANTS;
+use holochain_lmdb::error::DatabaseError;
+use holochain_lmdb::error::DatabaseResult;
+use holochain_lmdb::fresh_reader;
+use holochain_lmdb::prelude::*;
+use holochain_types::prelude::*;
+
+/// The live capability grants and claims on a source chain.
+pub struct CapabilityIndex {
+    grants: KvBufFresh<HeaderHash, CapGrantEntry>,
+    claims: KvBufFresh<HeaderHash, CapClaimEntry>,
+}
+
+impl CapabilityIndex {
+    /// Create a new capability index
+    pub fn new(env: EnvironmentRead) -> DatabaseResult<Self> {
+        let grants = env.get_db(&*CAP_GRANTS)?;
+        let claims = env.get_db(&*CAP_CLAIMS)?;
+        Ok(Self {
+            grants: KvBufFresh::new(env.clone(), grants),
+            claims: KvBufFresh::new(env, claims),
+        })
+    }
+
+    /// Update the index with an element that is being put on the chain.
+    ///
+    /// Creating or updating a grant or claim makes it live and
+    /// updating or deleting one removes the original from the index.
+    pub fn put(
+        &mut self,
+        header_hash: &HeaderHash,
+        header: &Header,
+        maybe_entry: Option<&Entry>,
+    ) -> DatabaseResult<()> {
+        match header {
+            Header::Update(update) => self.remove(update.original_header_address.clone())?,
+            Header::Delete(delete) => self.remove(delete.deletes_address.clone())?,
+            _ => {}
+        }
+        match (header, maybe_entry) {
+            (Header::Create(_), Some(Entry::CapGrant(grant)))
+            | (Header::Update(_), Some(Entry::CapGrant(grant))) => {
+                self.grants.put(header_hash.clone(), grant.clone())
+            }
+            (Header::Create(_), Some(Entry::CapClaim(claim)))
+            | (Header::Update(_), Some(Entry::CapClaim(claim))) => {
+                self.claims.put(header_hash.clone(), claim.clone())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// True if there are no live grants or claims
+    pub fn is_empty(&self) -> DatabaseResult<bool> {
+        Ok(self.grants()?.is_empty() && self.claims()?.is_empty())
+    }
+
+    fn remove(&mut self, header_hash: HeaderHash) -> DatabaseResult<()> {
+        self.grants.delete(header_hash.clone())?;
+        self.claims.delete(header_hash)
+    }
+
+    /// All the live grants
+    pub fn grants(&self) -> DatabaseResult<Vec<CapGrantInfo>> {
+        fresh_reader!(self.grants.env(), |r| self
+            .grants
+            .iter(&r)?
+            .map(|(k, grant)| {
+                Ok(CapGrantInfo {
+                    header_hash: HeaderHash::from_raw_39_panicky(k.to_vec()),
+                    grant,
+                })
+            })
+            .collect())
+    }
+
+    /// All the live claims
+    pub fn claims(&self) -> DatabaseResult<Vec<CapClaimInfo>> {
+        fresh_reader!(self.claims.env(), |r| self
+            .claims
+            .iter(&r)?
+            .map(|(k, claim)| {
+                Ok(CapClaimInfo {
+                    header_hash: HeaderHash::from_raw_39_panicky(k.to_vec()),
+                    claim,
+                })
+            })
+            .collect())
+    }
+}
+
+impl BufferedStore for CapabilityIndex {
+    type Error = DatabaseError;
+
+    fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> DatabaseResult<()> {
+        self.grants.flush_to_txn_ref(writer)?;
+        self.claims.flush_to_txn_ref(writer)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use holochain_lmdb::test_utils::test_cell_env;
+    use holochain_types::fixt::*;
+
+    fn grant(tag: &str) -> CapGrantEntry {
+        ZomeCallCapGrant::new(tag.into(), CapAccess::Unrestricted, Default::default())
+    }
+
+    fn create(entry: &Entry) -> (HeaderHash, Header) {
+        let mut create = fixt!(Create);
+        create.entry_hash = EntryHash::with_data_sync(entry);
+        let header = Header::Create(create);
+        (HeaderHash::with_data_sync(&header), header)
+    }
+
+    fn update(original: &HeaderHash, entry: &Entry) -> (HeaderHash, Header) {
+        let mut update = fixt!(Update);
+        update.original_header_address = original.clone();
+        update.entry_hash = EntryHash::with_data_sync(entry);
+        let header = Header::Update(update);
+        (HeaderHash::with_data_sync(&header), header)
+    }
+
+    fn delete(original: &HeaderHash) -> (HeaderHash, Header) {
+        let mut delete = fixt!(Delete);
+        delete.deletes_address = original.clone();
+        let header = Header::Delete(delete);
+        (HeaderHash::with_data_sync(&header), header)
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn index_follows_create_update_and_delete() -> DatabaseResult<()> {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+
+        let first = Entry::CapGrant(grant("first"));
+        let second = Entry::CapGrant(grant("second"));
+        let rolled = Entry::CapGrant(grant("rolled"));
+        let claim = CapClaim::new("claim".into(), fixt!(AgentPubKey), fixt!(CapSecret));
+        let claim_entry = Entry::CapClaim(claim.clone());
+
+        let (first_hash, first_header) = create(&first);
+        let (second_hash, second_header) = create(&second);
+        let (claim_hash, claim_header) = create(&claim_entry);
+        let (rolled_hash, rolled_header) = update(&first_hash, &rolled);
+
+        {
+            let mut index = CapabilityIndex::new(env.clone().into())?;
+            index.put(&first_hash, &first_header, Some(&first))?;
+            index.put(&second_hash, &second_header, Some(&second))?;
+            index.put(&claim_hash, &claim_header, Some(&claim_entry))?;
+            // Other entries are not indexed
+            let (agent_hash, agent_header) = create(&Entry::Agent(fixt!(AgentPubKey)));
+            index.put(&agent_hash, &agent_header, None)?;
+
+            // The scratch space is visible before flushing
+            assert_eq!(index.grants()?.len(), 2);
+
+            index.put(&rolled_hash, &rolled_header, Some(&rolled))?;
+            env.guard()
+                .with_commit(|writer| index.flush_to_txn(writer))?;
+        }
+
+        {
+            let mut index = CapabilityIndex::new(env.clone().into())?;
+            let mut tags = index
+                .grants()?
+                .into_iter()
+                .map(|info| info.grant.tag)
+                .collect::<Vec<_>>();
+            tags.sort();
+            assert_eq!(tags, vec!["rolled".to_string(), "second".to_string()]);
+            assert_eq!(
+                index.claims()?,
+                vec![CapClaimInfo {
+                    header_hash: claim_hash.clone(),
+                    claim,
+                }]
+            );
+
+            let (delete_hash, delete_header) = delete(&second_hash);
+            index.put(&delete_hash, &delete_header, None)?;
+            let (delete_hash, delete_header) = delete(&claim_hash);
+            index.put(&delete_hash, &delete_header, None)?;
+            env.guard()
+                .with_commit(|writer| index.flush_to_txn(writer))?;
+        }
+
+        let index = CapabilityIndex::new(env.clone().into())?;
+        assert_eq!(
+            index.grants()?,
+            vec![CapGrantInfo {
+                header_hash: rolled_hash,
+                grant: grant("rolled"),
+            }]
+        );
+        assert!(index.claims()?.is_empty());
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use super::CapabilityIndex;
 use super::ChainInvalidReason;
 use crate::chain_sequence::ChainSequenceBuf;
 use crate::element_buf::ElementBuf;
@@ -15,6 +16,7 @@ use tracing::*;
 pub struct SourceChainBuf {
     elements: ElementBuf<AuthoredPrefix>,
     sequence: ChainSequenceBuf,
+    capabilities: CapabilityIndex,
     keystore: KeystoreSender,
 
     env: EnvironmentRead,
@@ -25,6 +27,7 @@ impl SourceChainBuf {
         Ok(Self {
             elements: ElementBuf::authored(env.clone(), true)?,
             sequence: ChainSequenceBuf::new(env.clone())?,
+            capabilities: CapabilityIndex::new(env.clone())?,
             keystore: env.keystore().clone(),
             env,
         })
@@ -34,6 +37,7 @@ impl SourceChainBuf {
         Ok(Self {
             elements: ElementBuf::authored(env.clone(), false)?,
             sequence: ChainSequenceBuf::new(env.clone())?,
+            capabilities: CapabilityIndex::new(env.clone())?,
             keystore: env.keystore().clone(),
             env,
         })
@@ -115,6 +119,32 @@ impl SourceChainBuf {
         &self.sequence
     }
 
+    /// The live capability grants and claims on this chain
+    pub fn capabilities(&self) -> &CapabilityIndex {
+        &self.capabilities
+    }
+
+    /// Build the capability index from the elements already on the chain.
+    ///
+    /// Chains written before the index existed have grants and claims that
+    /// are missing from it. Nothing is done if the index already has entries.
+    /// Returns true if the index was backfilled and needs flushing.
+    pub fn backfill_capabilities(&mut self) -> SourceChainResult<bool> {
+        if self.is_empty() || !self.capabilities.is_empty()? {
+            return Ok(false);
+        }
+        for i in 0..self.len() as u32 {
+            if let Some(element) = self.get_at_index(i)? {
+                self.capabilities.put(
+                    element.header_address(),
+                    element.header(),
+                    element.entry().as_option(),
+                )?;
+            }
+        }
+        Ok(!self.capabilities.is_empty()?)
+    }
+
     /// Add a Element to the source chain, using a fully-formed Header
     pub async fn put_raw(
         &mut self,
@@ -124,6 +154,11 @@ impl SourceChainBuf {
         let header = HeaderHashed::from_content_sync(header);
         let header_address = header.as_hash().to_owned();
         let signed_header = SignedHeaderHashed::new(&self.keystore, header).await?;
+        self.capabilities.put(
+            &header_address,
+            signed_header.header(),
+            maybe_entry.as_ref(),
+        )?;
         let maybe_entry = match maybe_entry {
             None => None,
             Some(entry) => Some(EntryHashed::from_content_sync(entry)),
@@ -266,6 +301,7 @@ impl BufferedStore for SourceChainBuf {
     fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> Result<(), Self::Error> {
         self.elements.flush_to_txn_ref(writer)?;
         self.sequence.flush_to_txn_ref(writer)?;
+        self.capabilities.flush_to_txn_ref(writer)?;
         Ok(())
     }
 }
@@ -17,6 +17,10 @@ use shrinkwraprs::Shrinkwrap;
 /// The database for putting ops into to await validation
 pub struct ValidationLimboStore(pub KvBufFresh<ValidationLimboKey, ValidationLimboValue>);
 
+/// The number of times we try to validate an op that is missing dependencies
+/// before it is abandoned and left for the slow heal process.
+pub const MAX_VALIDATION_TRIES: u32 = 20;
+
 /// Key to the validation limbo
 pub type ValidationLimboKey = DhtOpHash;
 
@@ -37,6 +41,16 @@ pub struct ValidationLimboValue {
     pub num_tries: u32,
     /// The agent that sent you this op
     pub from_agent: Option<AgentPubKey>,
+    /// Send a validation receipt to the author once the op is validated
+    #[serde(default)]
+    pub send_receipt: bool,
+}
+
+impl ValidationLimboValue {
+    /// Have we tried to validate this op enough times to give up on it?
+    pub fn should_abandon(&self) -> bool {
+        self.num_tries >= MAX_VALIDATION_TRIES
+    }
 }
 
 /// The status of a [DhtOp] in limbo
@@ -14,6 +14,7 @@ use holochain_lmdb::prelude::Readable;
 use holochain_lmdb::prelude::Writer;
 use holochain_serialized_bytes::prelude::*;
 use holochain_zome_types::signature::Signature;
+use std::collections::HashSet;
 
 /// The result of a DhtOp Validation.
 #[derive(
@@ -84,6 +85,17 @@ pub struct SignedValidationReceipt {
     pub validator_signature: Signature,
 }
 
+impl SignedValidationReceipt {
+    /// Check that the receipt was signed by the validator it names.
+    pub async fn verify(&self) -> DatabaseResult<bool> {
+        Ok(self
+            .receipt
+            .validator
+            .verify_signature(&self.validator_signature, self.receipt.clone())
+            .await?)
+    }
+}
+
 /// The database/buffer for aggregating validation_receipts sent by remote
 /// nodes in charge of storage thereof.
 pub struct ValidationReceiptsBuf(KvvBufUsed<DhtOpHash, SignedValidationReceipt>);
@@ -112,20 +124,21 @@ impl ValidationReceiptsBuf {
     }
 
     /// Get the current valid receipt count for a given hash.
+    /// Only one receipt is counted per validator.
     pub fn count_valid<'r, R: Readable>(
         &'r self,
         r: &'r R,
         dht_op_hash: &DhtOpHash,
     ) -> DatabaseResult<usize> {
-        let mut count = 0;
+        let mut validators = HashSet::new();
 
         let mut iter = self.list_receipts(r, dht_op_hash)?;
         while let Some(v) = iter.next()? {
             if v.receipt.validation_result == ValidationResult::Valid {
-                count += 1;
+                validators.insert(v.receipt.validator);
             }
         }
-        Ok(count)
+        Ok(validators.len())
     }
 
     /// Add this receipt if it isn't already in the database.
@@ -0,0 +1,247 @@
+//! # Warrants
+//!
+//! Warrants against agents that have broken the rules of the network.
+//! A warrant is issued by the authority that finds the problem and can
+//! be checked by anyone, so warrants are kept whether we issued them
+//! or received them from another authority.
+
+use fallible_iterator::FallibleIterator;
+use holochain_keystore::AgentPubKeyExt;
+use holochain_keystore::KeystoreSender;
+use holochain_lmdb::buffer::BufferedStore;
+use holochain_lmdb::buffer::KvBufFresh;
+use holochain_lmdb::db::WARRANTS;
+use holochain_lmdb::error::DatabaseError;
+use holochain_lmdb::error::DatabaseResult;
+use holochain_lmdb::fresh_reader;
+use holochain_lmdb::prelude::*;
+use holochain_types::prelude::*;
+
+/// Key for the [WarrantStore].
+///
+/// Warrants are keyed by the warranted agent followed by the position
+/// in their chain, so there is only one warrant for each problem and
+/// all the warrants against an agent can be found with the agent alone.
+#[derive(Debug, Clone, Hash, Eq, PartialEq, PartialOrd, Ord)]
+pub struct WarrantKey(Vec<u8>);
+
+impl AsRef<[u8]> for WarrantKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl BufKey for WarrantKey {
+    fn from_key_bytes_or_friendly_panic(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+impl From<&AgentPubKey> for WarrantKey {
+    fn from(agent: &AgentPubKey) -> Self {
+        Self(agent.get_raw_39().to_vec())
+    }
+}
+
+impl From<&WarrantProof> for WarrantKey {
+    fn from(proof: &WarrantProof) -> Self {
+        let mut key = Self::from(proof.warranted_agent());
+        key.0.extend_from_slice(&proof.header_seq().to_be_bytes());
+        key
+    }
+}
+
+/// Sign a proof as the warrantor to create a [Warrant].
+pub async fn issue_warrant(
+    proof: WarrantProof,
+    warrantor: AgentPubKey,
+    keystore: &KeystoreSender,
+) -> DatabaseResult<Warrant> {
+    let signature = warrantor.sign(keystore, proof.clone()).await?;
+    Ok(Warrant {
+        proof,
+        warrantor,
+        signature,
+    })
+}
+
+/// Check a warrant was signed by its warrantor and that the
+/// proof holds up, including the warranted agent's signatures.
+pub async fn verify_warrant(warrant: &Warrant) -> DatabaseResult<bool> {
+    if !warrant.proof.is_consistent() {
+        return Ok(false);
+    }
+    if !warrant
+        .warrantor
+        .verify_signature(&warrant.signature, warrant.proof.clone())
+        .await?
+    {
+        return Ok(false);
+    }
+    match &warrant.proof {
+        WarrantProof::ChainFork {
+            chain_author,
+            header_pair: (first, second),
+        } => {
+            for signed_header in [first, second].iter() {
+                if !chain_author
+                    .verify_signature(signed_header.signature(), signed_header.header().clone())
+                    .await?
+                {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+    }
+}
+
+/// The database of warrants against other agents.
+pub struct WarrantStore(KvBufFresh<WarrantKey, Warrant>);
+
+impl WarrantStore {
+    /// Create a new warrant store
+    pub fn new(env: EnvironmentRead) -> DatabaseResult<Self> {
+        let db = env.get_db(&*WARRANTS)?;
+        Ok(Self(KvBufFresh::new(env, db)))
+    }
+
+    /// Add a warrant if we don't already have one for the same problem.
+    /// The warrant should have been verified.
+    /// Returns true if the warrant was added.
+    pub fn add_if_unique(&mut self, warrant: Warrant) -> DatabaseResult<bool> {
+        let key = WarrantKey::from(&warrant.proof);
+        if self.0.contains(&key)? {
+            return Ok(false);
+        }
+        self.0.put(key, warrant)?;
+        Ok(true)
+    }
+
+    /// Do we have a warrant for this problem?
+    pub fn contains(&self, proof: &WarrantProof) -> DatabaseResult<bool> {
+        self.0.contains(&proof.into())
+    }
+
+    /// All the warrants against this agent.
+    pub fn get_warrants(&self, agent: &AgentPubKey) -> DatabaseResult<Vec<Warrant>> {
+        fresh_reader!(self.0.env(), |r| self
+            .0
+            .iter_all_key_matches(&r, agent.into())?
+            .map(|(_, warrant)| Ok(warrant))
+            .collect())
+    }
+
+    /// Is there any warrant against this agent?
+    pub fn is_warranted(&self, agent: &AgentPubKey) -> DatabaseResult<bool> {
+        fresh_reader!(self.0.env(), |r| Ok(self
+            .0
+            .iter_all_key_matches(&r, agent.into())?
+            .next()?
+            .is_some()))
+    }
+}
+
+impl BufferedStore for WarrantStore {
+    type Error = DatabaseError;
+
+    fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> DatabaseResult<()> {
+        self.0.flush_to_txn_ref(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use holochain_keystore::KeystoreSenderExt;
+    use holochain_lmdb::test_utils::test_cell_env;
+    use holochain_types::fixt::*;
+
+    async fn signed_create(
+        author: &AgentPubKey,
+        header_seq: u32,
+        keystore: &KeystoreSender,
+    ) -> SignedHeader {
+        let mut create = fixt!(Create);
+        create.author = author.clone();
+        create.header_seq = header_seq;
+        let header = Header::Create(create);
+        let signature = author.sign(keystore, header.clone()).await.unwrap();
+        SignedHeader(header, signature)
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn issue_verify_and_store_warrants() -> DatabaseResult<()> {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let keystore = holochain_lmdb::test_utils::test_keystore();
+        let mut agents = Vec::new();
+        for _ in 0..3 {
+            agents.push(
+                keystore
+                    .clone()
+                    .generate_sign_keypair_from_pure_entropy()
+                    .await?,
+            );
+        }
+        let (forker, warrantor, honest) = (&agents[0], &agents[1], &agents[2]);
+
+        let proof = WarrantProof::ChainFork {
+            chain_author: forker.clone(),
+            header_pair: (
+                signed_create(forker, 5, &keystore).await,
+                signed_create(forker, 5, &keystore).await,
+            ),
+        };
+        let warrant = issue_warrant(proof.clone(), warrantor.clone(), &keystore).await?;
+        assert!(verify_warrant(&warrant).await?);
+
+        // A warrant signed by someone other than the warrantor is rejected
+        let forged = Warrant {
+            warrantor: honest.clone(),
+            ..warrant.clone()
+        };
+        assert!(!verify_warrant(&forged).await?);
+
+        // Headers at different positions are not a fork
+        let not_a_fork = WarrantProof::ChainFork {
+            chain_author: forker.clone(),
+            header_pair: (
+                signed_create(forker, 5, &keystore).await,
+                signed_create(forker, 6, &keystore).await,
+            ),
+        };
+        let warrant_without_proof = issue_warrant(not_a_fork, warrantor.clone(), &keystore).await?;
+        assert!(!verify_warrant(&warrant_without_proof).await?);
+
+        // Headers that weren't signed by the chain author are not proof
+        let framed = WarrantProof::ChainFork {
+            chain_author: honest.clone(),
+            header_pair: (
+                signed_create(forker, 5, &keystore).await,
+                signed_create(forker, 5, &keystore).await,
+            ),
+        };
+        let framed = issue_warrant(framed, warrantor.clone(), &keystore).await?;
+        assert!(!verify_warrant(&framed).await?);
+
+        {
+            let mut store = WarrantStore::new(env.clone().into())?;
+            assert!(store.add_if_unique(warrant.clone())?);
+            // Only one warrant is kept for the same fork
+            let again = issue_warrant(proof.clone(), honest.clone(), &keystore).await?;
+            assert!(!store.add_if_unique(again)?);
+            env.guard()
+                .with_commit(|writer| store.flush_to_txn(writer))?;
+        }
+
+        let store = WarrantStore::new(env.clone().into())?;
+        assert!(store.contains(&proof)?);
+        assert!(store.is_warranted(forker)?);
+        assert!(!store.is_warranted(honest)?);
+        assert_eq!(store.get_warrants(forker)?, vec![warrant]);
+        assert!(store.get_warrants(honest)?.is_empty());
+        Ok(())
+    }
+}
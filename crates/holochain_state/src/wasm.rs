@@ -23,6 +23,10 @@ impl WasmBuf {
     pub fn put(&mut self, v: DnaWasmHashed) {
         self.0.put(v);
     }
+
+    pub fn delete(&mut self, wasm_hash: WasmHash) {
+        self.0.delete(wasm_hash);
+    }
 }
 
 impl BufferedStore for WasmBuf {
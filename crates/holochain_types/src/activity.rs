@@ -20,6 +20,9 @@ pub struct AgentActivityResponse<T = SignedHeaderHashed> {
     /// The highest chain header that has
     /// been observed by this authority.
     pub highest_observed: Option<HighestObserved>,
+    /// Warrants against this agent that the authority holds.
+    #[serde(default)]
+    pub warrants: Vec<Warrant>,
 }
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
@@ -56,7 +59,7 @@ impl From<AgentActivityResponse<Element>> for holochain_zome_types::query::Agent
             rejected_activity,
             status: a.status,
             highest_observed: a.highest_observed,
-            warrants: Vec::with_capacity(0),
+            warrants: a.warrants,
         }
     }
 }
@@ -1,7 +1,10 @@
 //! Holochain autonomic type helpers.
 
+use crate::Timestamp;
+use holochain_serialized_bytes::prelude::*;
+
 /// The various processes which run "autonomically", aka subconsciously.
-/// These are currently not used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AutonomicProcess {
     /// Validation / Correction may propagate much slower.
     SlowHeal,
@@ -24,3 +27,18 @@ impl From<AutonomicCue> for AutonomicProcess {
         match cue {}
     }
 }
+
+/// The outcome of the last [AutonomicProcess::HealthCheck] run on a cell.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct CellHealthReport {
+    /// When the health check ran.
+    pub checked_at: Timestamp,
+    /// How many authorities each op should be held by, from the Dna.
+    pub redundancy_target: u32,
+    /// The number of ops this cell has authored.
+    pub authored_ops: u32,
+    /// The number of authored ops held by at least `redundancy_target` authorities.
+    pub healthy_ops: u32,
+    /// The number of under-held ops that were published again.
+    pub republished_ops: u32,
+}
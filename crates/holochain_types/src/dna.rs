@@ -27,6 +27,25 @@ impl JsonProperties {
     }
 }
 
+/// A limit on how many headers each agent may author in a window of time.
+///
+/// Agent activity authorities reject any header that would take its author
+/// over the limit, counted by the timestamps of the author's other headers.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RateLimit {
+    /// The most headers an agent may author within the window.
+    pub max_headers: u32,
+    /// The length of the window in seconds.
+    pub window_secs: u64,
+}
+
+impl RateLimit {
+    /// The window as a duration
+    pub fn window(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.window_secs)
+    }
+}
+
 /// Represents the top-level holochain dna object.
 #[derive(
     Serialize, Deserialize, Clone, Debug, PartialEq, Eq, SerializedBytes, derive_builder::Builder,
@@ -45,8 +64,21 @@ pub struct DnaDef {
     #[builder(default = "().try_into().unwrap()")]
     pub properties: SerializedBytes,
 
+    /// The limits on how fast each agent may author headers.
+    /// A header must be within all of them.
+    #[builder(default = "Vec::new()")]
+    #[serde(default)]
+    pub rate_limits: Vec<RateLimit>,
+
     /// An array of zomes associated with your holochain application.
     pub zomes: Zomes,
+
+    /// How many authorities should hold each op authored in this Dna.
+    /// The health check republishes any op that is held by fewer.
+    /// If not set the conductor's `redundancy_target` is used.
+    #[builder(default)]
+    #[serde(default)]
+    pub redundancy_target: Option<u32>,
 }
 
 #[cfg(feature = "test_utils")]
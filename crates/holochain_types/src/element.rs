@@ -123,7 +123,9 @@ impl<'a> ElementGroup<'a> {
                     wire.header
                         .into_header(entry_type.clone(), entry_hash.clone()),
                 )),
-                ValidationStatus::Abandoned => todo!(),
+                // Abandoned headers were never judged so they are
+                // neither valid nor rejected
+                ValidationStatus::Abandoned => {}
             }
         }
 
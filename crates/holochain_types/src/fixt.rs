@@ -153,9 +153,11 @@ fixturator!(
         properties: SerializedBytesFixturator::new_indexed(Empty, get_fixt_index!())
             .next()
             .unwrap(),
+        rate_limits: Vec::new(),
         zomes: ZomesFixturator::new_indexed(Empty, get_fixt_index!())
             .next()
             .unwrap(),
+        redundancy_target: None,
     };
 
     curve Unpredictable DnaDef {
@@ -168,9 +170,11 @@ fixturator!(
         properties: SerializedBytesFixturator::new_indexed(Unpredictable, get_fixt_index!())
             .next()
             .unwrap(),
+        rate_limits: Vec::new(),
         zomes: ZomesFixturator::new_indexed(Unpredictable, get_fixt_index!())
             .next()
             .unwrap(),
+        redundancy_target: None,
     };
 
     curve Predictable DnaDef {
@@ -183,8 +187,10 @@ fixturator!(
         properties: SerializedBytesFixturator::new_indexed(Predictable, get_fixt_index!())
             .next()
             .unwrap(),
+        rate_limits: Vec::new(),
         zomes: ZomesFixturator::new_indexed(Predictable, get_fixt_index!())
             .next()
             .unwrap(),
+        redundancy_target: None,
     };
 );
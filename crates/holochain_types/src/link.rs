@@ -40,6 +40,10 @@ pub struct GetLinksResponse {
     pub link_removes: Vec<(DeleteLink, Signature)>,
 }
 
+/// Link response to count links
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes)]
+pub struct CountLinksResponse(pub usize);
+
 impl WireLinkMetaKey {
     /// Get the basis of this key
     pub fn basis(&self) -> AnyDhtHash {
@@ -4,6 +4,9 @@
 //! - System-defined signals are produced in various places in the system
 
 use crate::impl_from;
+use holo_hash::AgentPubKey;
+use holo_hash::EntryHash;
+use holo_hash::HeaderHash;
 use holochain_serialized_bytes::prelude::*;
 use holochain_zome_types::prelude::*;
 
@@ -12,7 +15,17 @@ use holochain_zome_types::prelude::*;
 #[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq, Eq)]
 pub enum Signal {
     /// Signal from a Cell, generated by `emit_signal`
-    App(CellId, AppSignal),
+    App {
+        /// The Cell which emitted the signal
+        cell_id: CellId,
+        /// The Zome which emitted the signal
+        zome_name: ZomeName,
+        /// The agent whose remote signal caused this signal to be emitted.
+        /// `None` if the signal was emitted during a call made by the Cell's own agent.
+        provenance: Option<AgentPubKey>,
+        /// The app-defined signal
+        signal: AppSignal,
+    },
     /// System-defined signals
     System(SystemSignal),
 }
@@ -27,6 +40,74 @@ pub enum SystemSignal {
     /// Since we have no real system signals, we use a test signal for testing
     /// TODO: replace instances of this with something real
     Test(String),
+    /// A zome's `post_commit` callback failed after a zome call's commits
+    /// were flushed. The commits themselves are not rolled back.
+    PostCommitFailed {
+        /// The Cell whose commits were passed to `post_commit`
+        cell_id: CellId,
+        /// The Zome whose `post_commit` callback failed
+        zome_name: ZomeName,
+        /// The headers that were committed by the zome call
+        headers: Vec<HeaderHash>,
+        /// Why the callback failed
+        reason: String,
+    },
+    /// Integration changed DHT data held by a Cell.
+    /// Only sent to connections with a matching data subscription.
+    DataChanged {
+        /// The Cell which integrated the change
+        cell_id: CellId,
+        /// What changed
+        change: DataChange,
+    },
+}
+
+/// A change to the links on a base or to an entry,
+/// made by integrating a valid op
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq, Eq)]
+pub enum DataChange {
+    /// A link was created on a base
+    LinkCreated {
+        /// The base of the link
+        base: EntryHash,
+        /// The tag of the link
+        tag: LinkTag,
+        /// The target of the link
+        target: EntryHash,
+        /// The [CreateLink] header
+        create_link_hash: HeaderHash,
+    },
+    /// A link on a base was deleted
+    LinkDeleted {
+        /// The base of the link
+        base: EntryHash,
+        /// The tag of the link
+        tag: LinkTag,
+        /// The [CreateLink] header that was deleted
+        create_link_hash: HeaderHash,
+        /// The [DeleteLink] header
+        delete_link_hash: HeaderHash,
+    },
+    /// An entry was updated
+    EntryUpdated {
+        /// The entry that was updated
+        entry_hash: EntryHash,
+        /// The header of the entry that was updated
+        original_header_hash: HeaderHash,
+        /// The [Update] header
+        update_hash: HeaderHash,
+        /// The new entry
+        new_entry_hash: EntryHash,
+    },
+    /// A header which created an entry was deleted
+    EntryDeleted {
+        /// The entry whose header was deleted
+        entry_hash: EntryHash,
+        /// The header that was deleted
+        deleted_header_hash: HeaderHash,
+        /// The [Delete] header
+        delete_hash: HeaderHash,
+    },
 }
 
 /// Create a test signal
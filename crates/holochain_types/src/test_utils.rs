@@ -36,7 +36,9 @@ pub fn fake_dna_zomes(uuid: &str, zomes: Vec<(ZomeName, DnaWasm)>) -> DnaFile {
             .try_into()
             .unwrap(),
         uuid: uuid.to_string(),
+        rate_limits: Vec::new(),
         zomes: Vec::new(),
+        redundancy_target: None,
     };
     tokio_safe_block_on::tokio_safe_block_forever_on(async move {
         let mut wasm_code = Vec::new();
@@ -44,3 +44,13 @@ impl CapClaim {
         &self.grantor
     }
 }
+
+/// A claim that is live on the local source chain,
+/// i.e. it has been created or updated and not since updated or deleted.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, SerializedBytes)]
+pub struct CapClaimInfo {
+    /// The header that created or last updated the claim.
+    pub header_hash: HeaderHash,
+    /// The claim itself.
+    pub claim: CapClaim,
+}
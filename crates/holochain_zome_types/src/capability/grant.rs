@@ -76,6 +76,27 @@ impl ZomeCallCapGrant {
     }
 }
 
+/// A grant that is live on the local source chain,
+/// i.e. it has been created or updated and not since updated or deleted.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CapGrantInfo {
+    /// The header that created or last updated the grant.
+    /// Update or delete this header to revoke the grant.
+    pub header_hash: HeaderHash,
+    /// The grant itself.
+    pub grant: ZomeCallCapGrant,
+}
+
+/// The capability that authorized the current zome call.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CapabilityInfo {
+    /// The agent that made the call.
+    pub provenance: AgentPubKey,
+    /// The grant that authorized the call.
+    /// This is `ChainAuthor` if the agent is calling their own chain.
+    pub grant: CapGrant,
+}
+
 impl From<ZomeCallCapGrant> for CapGrant {
     /// Create a new ZomeCall capability grant
     fn from(zccg: ZomeCallCapGrant) -> Self {
@@ -0,0 +1,31 @@
+//! Types for the distributed public key infrastructure (DPKI)
+use crate::timestamp::Timestamp;
+pub use holochain_serialized_bytes::prelude::*;
+
+/// The name of the zome function a DPKI app exposes
+/// for the conductor to look up the state of a key.
+/// It takes an `AgentPubKey` and returns a [KeyState].
+pub const DPKI_KEY_STATE_FN: &str = "key_state";
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+/// The state of an agent key according to DPKI.
+pub enum KeyState {
+    /// DPKI has no record of this key.
+    NotFound,
+    /// The key is valid.
+    Valid,
+    /// The key was revoked at this time.
+    /// Anything signed by the key after this time is invalid.
+    Revoked(Timestamp),
+}
+
+impl KeyState {
+    /// Was the key valid at this time?
+    /// Keys that DPKI doesn't know about are treated as valid.
+    pub fn is_valid_at(&self, timestamp: &Timestamp) -> bool {
+        match self {
+            KeyState::NotFound | KeyState::Valid => true,
+            KeyState::Revoked(revoked_at) => timestamp < revoked_at,
+        }
+    }
+}
@@ -40,6 +40,13 @@ pub struct GetOptions {
     /// If it is false you will get whatever is locally
     /// available on this conductor.
     pub strategy: GetStrategy,
+    /// If this is true the get will follow any `Update`s
+    /// to the latest version of the entry or header.
+    /// When there are concurrent updates the latest by
+    /// timestamp (then by hash) is chosen.
+    /// Deleted updates are not followed.
+    #[serde(default)]
+    pub follow_redirects: bool,
 }
 
 impl GetOptions {
@@ -54,6 +61,7 @@ impl GetOptions {
     pub fn latest() -> Self {
         Self {
             strategy: GetStrategy::Latest,
+            follow_redirects: false,
         }
     }
     /// Gets the content but does not
@@ -66,8 +74,16 @@ impl GetOptions {
     pub fn content() -> Self {
         Self {
             strategy: GetStrategy::Content,
+            follow_redirects: false,
         }
     }
+
+    /// Follow any `Update`s to the latest version
+    /// of the requested entry or header.
+    pub fn follow_redirects(mut self) -> Self {
+        self.follow_redirects = true;
+        self
+    }
 }
 
 impl Default for GetOptions {
@@ -78,6 +78,9 @@ pub struct EntryDef {
     pub required_validations: RequiredValidations,
     /// The required validation package for this entry
     pub required_validation_type: RequiredValidationType,
+    /// Properties of this entry type that zomes can read at runtime
+    #[serde(default)]
+    pub properties: Option<SerializedBytes>,
 }
 
 impl EntryDef {
@@ -94,9 +97,16 @@ impl EntryDef {
             crdt_type,
             required_validations,
             required_validation_type,
+            properties: None,
         }
     }
 
+    /// Declare properties for this entry type
+    pub fn with_properties(mut self, properties: SerializedBytes) -> Self {
+        self.properties = Some(properties);
+        self
+    }
+
     #[cfg(any(test, feature = "test_utils"))]
     pub fn default_with_id<I: Into<EntryDefId>>(id: I) -> Self {
         EntryDef::new(
@@ -190,6 +200,7 @@ mod tests {
                 crdt_type: CrdtType,
                 required_validations: 5.into(),
                 required_validation_type: RequiredValidationType::default(),
+                properties: None,
             }]
             .into(),
         );
@@ -20,6 +20,7 @@ pub mod capability;
 pub mod cell;
 #[allow(missing_docs)]
 pub mod crdt;
+pub mod dpki;
 pub mod element;
 pub mod entry;
 #[allow(missing_docs)]
@@ -38,6 +39,7 @@ pub mod post_commit;
 pub mod prelude;
 pub mod query;
 pub mod request;
+pub mod schedule;
 pub mod signal;
 pub mod signature;
 pub mod timestamp;
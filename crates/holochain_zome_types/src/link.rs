@@ -1,6 +1,9 @@
 use crate::element::SignedHeaderHashed;
+use holo_hash::AgentPubKey;
 use holo_hash::HeaderHash;
 use holochain_serialized_bytes::prelude::*;
+use std::cmp::Ordering;
+use std::ops::Range;
 
 /// Opaque tag for the link applied at the app layer, used to differentiate
 /// between different semantics and validation rules for different links
@@ -69,6 +72,152 @@ pub struct Link {
     pub create_link_hash: HeaderHash,
 }
 
+/// The position of a link on its base, used to page through links.
+///
+/// Links are ordered by the bytes of their tag followed by the bytes of
+/// their create link hash, which is the order they are stored in.
+/// So a tag comes after the longer tags it is a prefix of,
+/// because hashes start with a byte above any ascii.
+#[derive(Debug, Clone, Hash, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct LinkCursor {
+    /// The tag of the link
+    pub tag: LinkTag,
+    /// The hash of the link's create header
+    pub create_link_hash: HeaderHash,
+}
+
+impl LinkCursor {
+    /// Cursor for the link with this tag and create link hash
+    pub fn new(tag: LinkTag, create_link_hash: HeaderHash) -> Self {
+        Self {
+            tag,
+            create_link_hash,
+        }
+    }
+
+    fn position(&self) -> impl Iterator<Item = &u8> {
+        self.tag.0.iter().chain(self.create_link_hash.get_raw_39())
+    }
+}
+
+impl Ord for LinkCursor {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.position().cmp(other.position())
+    }
+}
+
+impl PartialOrd for LinkCursor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<&Link> for LinkCursor {
+    fn from(link: &Link) -> Self {
+        Self::new(link.tag.clone(), link.create_link_hash.clone())
+    }
+}
+
+/// Filters and pagination for getting or counting the live links on a base.
+///
+/// Without a cursor or limit links are returned oldest first.
+/// With either they are returned in [LinkCursor] order so the last link of
+/// a page can be used as the cursor for the next one.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct LinkQuery {
+    /// Only links with tags in this range.
+    /// Tags compare by their bytes, so `"a".."b"` is every tag starting with `"a"`.
+    pub tag_range: Option<Range<LinkTag>>,
+    /// Only links created by this agent
+    pub author: Option<AgentPubKey>,
+    /// Only links created within this time range
+    pub timestamp_range: Option<Range<crate::Timestamp>>,
+    /// Only links that come after this one
+    pub after: Option<LinkCursor>,
+    /// Return at most this many links
+    pub limit: Option<u32>,
+}
+
+impl LinkQuery {
+    /// A query that matches every link
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only links with tags in this range
+    pub fn tag_range(mut self, tag_range: Range<LinkTag>) -> Self {
+        self.tag_range = Some(tag_range);
+        self
+    }
+
+    /// Only links created by this agent
+    pub fn author(mut self, author: AgentPubKey) -> Self {
+        self.author = Some(author);
+        self
+    }
+
+    /// Only links created within this time range
+    pub fn timestamp_range(mut self, timestamp_range: Range<crate::Timestamp>) -> Self {
+        self.timestamp_range = Some(timestamp_range);
+        self
+    }
+
+    /// Only links after this one, e.g. the last link of the previous page
+    pub fn after(mut self, cursor: impl Into<LinkCursor>) -> Self {
+        self.after = Some(cursor.into());
+        self
+    }
+
+    /// Return at most this many links
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// True if this query has no filters and no pagination
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// True if results are paged in [LinkCursor] order rather than by time
+    pub fn is_paged(&self) -> bool {
+        self.after.is_some() || self.limit.is_some()
+    }
+
+    /// Check if a link matches this query, apart from its limit.
+    /// A link without a known author never matches an author filter.
+    pub fn check(
+        &self,
+        tag: &LinkTag,
+        create_link_hash: &HeaderHash,
+        timestamp: &crate::Timestamp,
+        author: Option<&AgentPubKey>,
+    ) -> bool {
+        self.tag_range
+            .as_ref()
+            .map(|r| r.contains(tag))
+            .unwrap_or(true)
+            && self
+                .author
+                .as_ref()
+                .map(|a| Some(a) == author)
+                .unwrap_or(true)
+            && self
+                .timestamp_range
+                .as_ref()
+                .map(|r| r.contains(timestamp))
+                .unwrap_or(true)
+            && self
+                .after
+                .as_ref()
+                .map(|c| {
+                    LinkCursor::new(tag.clone(), create_link_hash.clone()).cmp(c)
+                        == Ordering::Greater
+                })
+                .unwrap_or(true)
+    }
+}
+
 /// Zome IO inner type for link creation.
 #[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub struct CreateLinkInput {
@@ -95,6 +244,8 @@ impl CreateLinkInput {
 pub struct GetLinksInput {
     pub base_address: holo_hash::EntryHash,
     pub tag_prefix: Option<crate::link::LinkTag>,
+    #[serde(default)]
+    pub query: LinkQuery,
 }
 
 impl GetLinksInput {
@@ -105,8 +256,15 @@ impl GetLinksInput {
         Self {
             base_address,
             tag_prefix,
+            query: LinkQuery::default(),
         }
     }
+
+    /// Filter and page the links with a query
+    pub fn query(mut self, query: LinkQuery) -> Self {
+        self.query = query;
+        self
+    }
 }
 
 #[derive(serde::Serialize, serde::Deserialize, SerializedBytes, PartialEq, Clone, Debug)]
@@ -153,3 +311,63 @@ impl LinkDetails {
         self.into()
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "fixturators")]
+mod tests {
+    use super::*;
+    use crate::fixt::*;
+    use crate::timestamp::Timestamp;
+    use ::fixt::prelude::*;
+
+    #[test]
+    fn cursor_orders_by_tag_then_hash() {
+        let mut hashes = HeaderHashFixturator::new(Unpredictable);
+        let a = LinkCursor::new(LinkTag::new("a"), hashes.next().unwrap());
+        let b = LinkCursor::new(LinkTag::new("b"), hashes.next().unwrap());
+        assert!(a < b);
+        // A tag's hash bytes follow it, so "a" comes after "ab"
+        // because hashes start with a byte greater than any ascii
+        let ab = LinkCursor::new(LinkTag::new("ab"), hashes.next().unwrap());
+        assert!(ab < a);
+        // Same tag falls back to the hash
+        let a2 = LinkCursor::new(LinkTag::new("a"), hashes.next().unwrap());
+        assert_eq!(
+            a.cmp(&a2),
+            a.create_link_hash
+                .get_raw_39()
+                .cmp(a2.create_link_hash.get_raw_39())
+        );
+    }
+
+    #[test]
+    fn check_link_query() {
+        let hash = fixt!(HeaderHash);
+        let author = fixt!(AgentPubKey);
+        let other_author = fixt!(AgentPubKey);
+        let tag = LinkTag::new("b");
+        let ts = Timestamp(5, 0);
+
+        assert!(LinkQuery::new().check(&tag, &hash, &ts, Some(&author)));
+        assert!(LinkQuery::new().is_empty());
+
+        let tags = LinkQuery::new().tag_range(LinkTag::new("a")..LinkTag::new("c"));
+        assert!(tags.check(&tag, &hash, &ts, Some(&author)));
+        assert!(!tags.check(&LinkTag::new("c"), &hash, &ts, Some(&author)));
+
+        let authored = LinkQuery::new().author(author.clone());
+        assert!(authored.check(&tag, &hash, &ts, Some(&author)));
+        assert!(!authored.check(&tag, &hash, &ts, Some(&other_author)));
+        assert!(!authored.check(&tag, &hash, &ts, None));
+
+        let timed = LinkQuery::new().timestamp_range(Timestamp(0, 0)..Timestamp(5, 0));
+        assert!(!timed.check(&tag, &hash, &ts, Some(&author)));
+
+        // The cursor itself is excluded
+        let after = LinkQuery::new().after(LinkCursor::new(tag.clone(), hash.clone()));
+        assert!(after.is_paged());
+        assert!(!after.check(&tag, &hash, &ts, Some(&author)));
+        assert!(after.check(&LinkTag::new("c"), &hash, &ts, Some(&author)));
+        assert!(!after.check(&LinkTag::new("a"), &hash, &ts, Some(&author)));
+    }
+}
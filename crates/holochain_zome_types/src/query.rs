@@ -3,9 +3,14 @@
 use crate::header::EntryType;
 use crate::header::Header;
 use crate::header::HeaderType;
+use crate::timestamp::Timestamp;
 use crate::warrant::Warrant;
+use holo_hash::EntryHash;
 use holo_hash::HeaderHash;
 pub use holochain_serialized_bytes::prelude::*;
+use std::collections::HashSet;
+use std::ops::Bound;
+use std::ops::RangeBounds;
 
 /// Query arguments
 #[derive(
@@ -15,14 +20,48 @@ pub use holochain_serialized_bytes::prelude::*;
 pub struct ChainQueryFilter {
     /// The range of source chain sequence numbers to match.
     /// Inclusive start, exclusive end.
-    // TODO: can we generalize this over RangeBounds to allow unbounded ranges?
     pub sequence_range: Option<std::ops::Range<u32>>,
-    /// Filter by EntryType
-    pub entry_type: Option<EntryType>,
-    /// Filter by HeaderType
-    pub header_type: Option<HeaderType>,
+    /// Filter by EntryType, matching any of these types
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub entry_type: Option<Vec<EntryType>>,
+    /// Filter by HeaderType, matching any of these types
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub header_type: Option<Vec<HeaderType>>,
     /// Include the entries in the elements
     pub include_entries: bool,
+    /// The range of header timestamps to match.
+    /// Inclusive start, exclusive end.
+    #[serde(default)]
+    pub timestamp_range: Option<std::ops::Range<Timestamp>>,
+    /// Only match headers for these entries
+    #[serde(default)]
+    pub entry_hashes: Option<HashSet<EntryHash>>,
+    /// Return the oldest elements first
+    #[serde(default)]
+    pub order_ascending: bool,
+    /// The most elements to return, after filtering
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+/// Filters used to hold a single entry or header type
+/// so accept either one type or a list of them.
+fn one_or_many<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+{
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        One(T),
+        Many(Vec<T>),
+    }
+    let types: Option<OneOrMany<T>> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(types.map(|types| match types {
+        OneOrMany::One(t) => vec![t],
+        OneOrMany::Many(types) => types,
+    }))
 }
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
@@ -37,8 +76,8 @@ pub struct AgentActivity {
     /// The highest chain header that has
     /// been observed by this authority.
     pub highest_observed: Option<HighestObserved>,
-    /// Warrants about this AgentActivity.
-    /// Placeholder for future.
+    /// Warrants against the agent that
+    /// the authorities have verified.
     pub warrants: Vec<Warrant>,
 }
 
@@ -116,21 +155,62 @@ impl ChainQueryFilter {
         }
     }
 
-    /// Filter on sequence range
-    pub fn sequence_range(mut self, sequence_range: std::ops::Range<u32>) -> Self {
-        self.sequence_range = Some(sequence_range);
+    /// Filter on sequence range.
+    /// Either end of the range can be unbounded.
+    pub fn sequence_range(mut self, sequence_range: impl RangeBounds<u32>) -> Self {
+        let start = match sequence_range.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match sequence_range.end_bound() {
+            Bound::Included(end) => end.saturating_add(1),
+            Bound::Excluded(end) => *end,
+            Bound::Unbounded => u32::MAX,
+        };
+        self.sequence_range = Some(start..end);
         self
     }
 
-    /// Filter on entry type
+    /// Filter on entry type.
+    /// Call again to match more than one entry type.
     pub fn entry_type(mut self, entry_type: EntryType) -> Self {
-        self.entry_type = Some(entry_type);
+        self.entry_type
+            .get_or_insert_with(Vec::new)
+            .push(entry_type);
         self
     }
 
-    /// Filter on header type
+    /// Filter on header type.
+    /// Call again to match more than one header type.
     pub fn header_type(mut self, header_type: HeaderType) -> Self {
-        self.header_type = Some(header_type);
+        self.header_type
+            .get_or_insert_with(Vec::new)
+            .push(header_type);
+        self
+    }
+
+    /// Filter on timestamp range
+    pub fn timestamp_range(mut self, timestamp_range: std::ops::Range<Timestamp>) -> Self {
+        self.timestamp_range = Some(timestamp_range);
+        self
+    }
+
+    /// Filter on the hashes of the entries the headers are for
+    pub fn entry_hashes(mut self, entry_hashes: HashSet<EntryHash>) -> Self {
+        self.entry_hashes = Some(entry_hashes);
+        self
+    }
+
+    /// Return the oldest elements first
+    pub fn ascending(mut self) -> Self {
+        self.order_ascending = true;
+        self
+    }
+
+    /// Return at most this many elements
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
         self
     }
 
@@ -140,7 +220,8 @@ impl ChainQueryFilter {
         self
     }
 
-    /// Perform the boolean check which this filter represents
+    /// Perform the boolean check which this filter represents.
+    /// The order and limit apply to the whole query, not to a single header.
     pub fn check(&self, header: &Header) -> bool {
         let check_range = self
             .sequence_range
@@ -150,19 +231,34 @@ impl ChainQueryFilter {
         let check_header_type = self
             .header_type
             .as_ref()
-            .map(|header_type| header.header_type() == *header_type)
+            .map(|header_types| header_types.contains(&header.header_type()))
             .unwrap_or(true);
         let check_entry_type = self
             .entry_type
             .as_ref()
-            .map(|entry_type| {
+            .map(|entry_types| {
                 header
                     .entry_type()
-                    .map(|header_entry_type| *header_entry_type == *entry_type)
+                    .map(|header_entry_type| entry_types.contains(header_entry_type))
                     .unwrap_or(false)
             })
             .unwrap_or(true);
-        check_range && check_header_type && check_entry_type
+        let check_timestamp = self
+            .timestamp_range
+            .as_ref()
+            .map(|range| range.contains(&header.timestamp()))
+            .unwrap_or(true);
+        let check_entry_hash = self
+            .entry_hashes
+            .as_ref()
+            .map(|entry_hashes| {
+                header
+                    .entry_hash()
+                    .map(|entry_hash| entry_hashes.contains(entry_hash))
+                    .unwrap_or(false)
+            })
+            .unwrap_or(true);
+        check_range && check_header_type && check_entry_type && check_timestamp && check_entry_hash
     }
 }
 
@@ -172,8 +268,12 @@ mod tests {
     use crate::fixt::AppEntryTypeFixturator;
     use crate::fixt::*;
     use crate::header::EntryType;
+    use crate::header::HeaderType;
+    use crate::timestamp::Timestamp;
     use crate::Header;
     use ::fixt::prelude::*;
+    use holochain_serialized_bytes::prelude::*;
+    use std::collections::HashSet;
 
     use super::ChainQueryFilter;
 
@@ -205,6 +305,13 @@ mod tests {
         let mut h6 = fixt!(CreateLink);
         h6.header_seq = 5;
 
+        h1.timestamp = Timestamp(0, 0);
+        h2.timestamp = Timestamp(1, 0);
+        h3.timestamp = Timestamp(2, 0);
+        h4.timestamp = Timestamp(3, 0);
+        h5.timestamp = Timestamp(4, 0);
+        h6.timestamp = Timestamp(5, 0);
+
         let headers = [
             h1.into(),
             h2.into(),
@@ -288,6 +395,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn filter_by_multiple_types() {
+        let headers = fixtures();
+
+        let query_1 = ChainQueryFilter::new()
+            .entry_type(headers[0].entry_type().unwrap().to_owned())
+            .entry_type(headers[1].entry_type().unwrap().to_owned());
+        let query_2 = ChainQueryFilter::new()
+            .header_type(headers[1].header_type())
+            .header_type(headers[2].header_type());
+
+        assert_eq!(
+            map_query(&query_1, &headers),
+            [true, true, false, true, true, false].to_vec()
+        );
+        assert_eq!(
+            map_query(&query_2, &headers),
+            [false, true, true, false, true, true].to_vec()
+        );
+    }
+
+    #[test]
+    fn filter_by_unbounded_chain_sequence() {
+        let headers = fixtures();
+
+        let query_1 = ChainQueryFilter::new().sequence_range(4..);
+        let query_2 = ChainQueryFilter::new().sequence_range(..=1);
+
+        assert_eq!(
+            map_query(&query_1, &headers),
+            [false, false, false, false, true, true].to_vec()
+        );
+        assert_eq!(
+            map_query(&query_2, &headers),
+            [true, true, false, false, false, false].to_vec()
+        );
+    }
+
+    #[test]
+    fn filter_by_timestamp() {
+        let headers = fixtures();
+
+        let query = ChainQueryFilter::new().timestamp_range(Timestamp(1, 0)..Timestamp(3, 0));
+
+        assert_eq!(
+            map_query(&query, &headers),
+            [false, true, true, false, false, false].to_vec()
+        );
+    }
+
+    #[test]
+    fn filter_by_entry_hashes() {
+        let headers = fixtures();
+
+        let entry_hashes: HashSet<_> = vec![
+            headers[0].entry_hash().unwrap().clone(),
+            headers[3].entry_hash().unwrap().clone(),
+        ]
+        .into_iter()
+        .collect();
+        let query = ChainQueryFilter::new().entry_hashes(entry_hashes);
+
+        assert_eq!(
+            map_query(&query, &headers),
+            [true, false, false, true, false, false].to_vec()
+        );
+    }
+
     #[test]
     fn filter_by_multi() {
         let headers = fixtures();
@@ -324,4 +499,34 @@ mod tests {
             [true, false, false, false, true, false].to_vec()
         );
     }
+
+    /// A filter from before the types were lists
+    /// and the other fields were added.
+    #[derive(serde::Serialize, serde::Deserialize, SerializedBytes, Debug)]
+    struct OldChainQueryFilter {
+        sequence_range: Option<std::ops::Range<u32>>,
+        entry_type: Option<EntryType>,
+        header_type: Option<HeaderType>,
+        include_entries: bool,
+    }
+
+    #[test]
+    fn deserialize_old_filter() {
+        let old = OldChainQueryFilter {
+            sequence_range: Some(0..3),
+            entry_type: Some(EntryType::AgentPubKey),
+            header_type: Some(HeaderType::Create),
+            include_entries: true,
+        };
+        let sb = SerializedBytes::try_from(old).unwrap();
+        let filter = ChainQueryFilter::try_from(sb).unwrap();
+        assert_eq!(
+            filter,
+            ChainQueryFilter::new()
+                .sequence_range(0..3)
+                .entry_type(EntryType::AgentPubKey)
+                .header_type(HeaderType::Create)
+                .include_entries(true)
+        );
+    }
 }
@@ -0,0 +1,39 @@
+//! Types for scheduling zome functions to be called later by the host.
+
+use crate::zome::FunctionName;
+use crate::zome_io::ExternIO;
+use holochain_serialized_bytes::prelude::*;
+
+/// Ask the host to call a function in the current zome at some point in the future.
+///
+/// The function is called by the host as the chain author, so it is always
+/// authorized, and the schedule is persisted so it survives a restart.
+/// Scheduling the same function again replaces the previous schedule.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes)]
+pub struct ScheduleInput {
+    /// The function in the current zome to call.
+    pub fn_name: FunctionName,
+    /// The payload to call the function with.
+    pub payload: ExternIO,
+    /// How long to wait before the first call.
+    pub delay: core::time::Duration,
+    /// If set, keep calling the function with this interval after the first call.
+    pub interval: Option<core::time::Duration>,
+}
+
+impl ScheduleInput {
+    /// Constructor.
+    pub fn new(
+        fn_name: FunctionName,
+        payload: ExternIO,
+        delay: core::time::Duration,
+        interval: Option<core::time::Duration>,
+    ) -> Self {
+        Self {
+            fn_name,
+            payload,
+            delay,
+            interval,
+        }
+    }
+}
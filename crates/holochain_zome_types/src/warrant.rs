@@ -1,6 +1,73 @@
 //! Types for warrants
+use crate::element::SignedHeader;
+use crate::signature::Signature;
+use holo_hash::AgentPubKey;
 pub use holochain_serialized_bytes::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
-/// Placeholder for warrant type
-pub struct Warrant;
+/// A statement from an authority that an agent has broken the
+/// rules of the network, along with the proof.
+///
+/// Anyone can check a warrant without trusting the warrantor
+/// because the proof is made of data signed by the warranted agent.
+pub struct Warrant {
+    /// What the agent did wrong and the proof of it.
+    pub proof: WarrantProof,
+    /// The authority that issued this warrant.
+    pub warrantor: AgentPubKey,
+    /// The warrantor's signature of the proof.
+    pub signature: Signature,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+/// The proof held by a [Warrant].
+pub enum WarrantProof {
+    /// The agent signed two different headers at
+    /// the same position in their chain.
+    ChainFork {
+        /// The agent whose chain is forked.
+        chain_author: AgentPubKey,
+        /// The conflicting headers.
+        header_pair: (SignedHeader, SignedHeader),
+    },
+}
+
+impl Warrant {
+    /// The agent this warrant is against.
+    pub fn warranted_agent(&self) -> &AgentPubKey {
+        self.proof.warranted_agent()
+    }
+}
+
+impl WarrantProof {
+    /// The agent this proof is against.
+    pub fn warranted_agent(&self) -> &AgentPubKey {
+        match self {
+            WarrantProof::ChainFork { chain_author, .. } => chain_author,
+        }
+    }
+
+    /// The position in the chain this proof is about.
+    pub fn header_seq(&self) -> u32 {
+        match self {
+            WarrantProof::ChainFork { header_pair, .. } => header_pair.0.header().header_seq(),
+        }
+    }
+
+    /// Check the proof is consistent without checking any signatures.
+    /// A chain fork must be two different headers by
+    /// the chain author at the same sequence number.
+    pub fn is_consistent(&self) -> bool {
+        match self {
+            WarrantProof::ChainFork {
+                chain_author,
+                header_pair: (first, second),
+            } => {
+                first.header().author() == chain_author
+                    && second.header().author() == chain_author
+                    && first.header().header_seq() == second.header().header_seq()
+                    && first.header() != second.header()
+            }
+        }
+    }
+}
@@ -89,14 +89,14 @@ wasm_io_types! {
     fn call (zt::call::Call) -> zt::ZomeCallResponse;
 
 
-    // @todo List all the local capability claims.
-    fn capability_claims (()) -> ();
+    // List the live local capability claims, optionally only those with a tag.
+    fn capability_claims (Option<String>) -> Vec<zt::capability::CapClaimInfo>;
 
-    // @todo List all the local capability grants.
-    fn capability_grants (()) -> ();
+    // List the live local capability grants, optionally only those with a tag.
+    fn capability_grants (Option<String>) -> Vec<zt::capability::CapGrantInfo>;
 
-    // @todo Get the capability for the current zome call.
-    fn capability_info (()) -> ();
+    // Get the capability that authorized the current zome call.
+    fn capability_info (()) -> zt::capability::CapabilityInfo;
 
     // Create a link between two entries.
     fn create_link (zt::link::CreateLinkInput) -> holo_hash::HeaderHash;
@@ -120,8 +120,8 @@ wasm_io_types! {
     // Emit a Signal::App to subscribers on the interface
     fn emit_signal (zt::signal::AppSignal) -> ();
 
-    // @todo
-    fn entry_type_properties (()) -> ();
+    // Get the properties declared on one of this zome's entry defs.
+    fn entry_type_properties (zt::entry_def::EntryDefId) -> Option<SerializedBytes>;
 
     fn get_agent_activity (zt::agent_info::GetAgentActivityInput) -> zt::query::AgentActivity;
 
@@ -129,6 +129,9 @@ wasm_io_types! {
 
     fn get_link_details (zt::link::GetLinksInput) -> zt::link::LinkDetails;
 
+    // Count the live links on a base without returning them.
+    fn count_links (zt::link::GetLinksInput) -> u32;
+
     // Get links by entry hash from the cascade.
     fn get_links (zt::link::GetLinksInput) -> zt::link::Links;
 
@@ -138,8 +141,8 @@ wasm_io_types! {
     // Hash an entry on the host.
     fn hash_entry (zt::entry::Entry) -> holo_hash::EntryHash;
 
-    // @todo
-    fn property (()) -> ();
+    // Get the properties of the dna.
+    fn property (()) -> SerializedBytes;
 
     // Query the source chain for data.
     fn query (zt::query::ChainQueryFilter) -> zt::element::ElementVec;
@@ -153,8 +156,8 @@ wasm_io_types! {
     // // @todo
     // fn send (()) -> ();
 
-    // @todo
-    fn schedule (core::time::Duration) -> ();
+    // Schedule a function in the current zome to be called later by the host.
+    fn schedule (zt::schedule::ScheduleInput) -> ();
 
     // @todo
     fn show_env (()) -> ();
@@ -0,0 +1,30 @@
+use kitsune_p2p_types::dependencies::ghost_actor::dependencies::tracing;
+use structopt::StructOpt;
+
+/// Option Parsing
+#[derive(structopt::StructOpt, Debug)]
+#[structopt(name = "kitsune-bootstrap")]
+pub struct Opt {
+    /// To which network interface / port should we bind?
+    #[structopt(short = "b", long, default_value = "127.0.0.1:0")]
+    pub bind_to: std::net::SocketAddr,
+}
+
+#[tokio::main]
+async fn main() {
+    let _ = tracing::subscriber::set_global_default(
+        tracing_subscriber::FmtSubscriber::builder()
+            .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+            .finish(),
+    );
+
+    let opt = Opt::from_args();
+
+    match kitsune_p2p_bootstrap::run(opt.bind_to).await {
+        Ok((driver, addr)) => {
+            println!("http://{}", addr);
+            driver.await;
+        }
+        Err(e) => eprintln!("{:?}", e),
+    }
+}
@@ -0,0 +1,225 @@
+#![deny(missing_docs)]
+//! A bootstrap server for kitsune-p2p.
+//!
+//! Speaks the same msgpack over http protocol as the bootstrap client in
+//! kitsune_p2p. Every request is a POST with the op in the `X-Op` header:
+//! - `put`: store a signed agent info in its space until it expires.
+//! - `now`: the server's time in milliseconds.
+//! - `random`: a random selection of agent infos from a space.
+//!
+//! The server can be run with the `kitsune-bootstrap` binary
+//! or embedded in tests with [run].
+
+use futures::future::BoxFuture;
+use futures::future::FutureExt;
+use hyper::service::make_service_fn;
+use hyper::service::service_fn;
+use hyper::Body;
+use hyper::Method;
+use hyper::Request;
+use hyper::Response;
+use hyper::StatusCode;
+use kitsune_p2p::agent_store::AgentInfo;
+use kitsune_p2p::agent_store::AgentInfoSigned;
+use kitsune_p2p::KitsuneSpace;
+use kitsune_p2p_types::codec::rmp_decode;
+use kitsune_p2p_types::codec::rmp_encode;
+use kitsune_p2p_types::dependencies::ghost_actor::dependencies::tracing;
+use std::convert::Infallible;
+use std::convert::TryFrom;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+mod store;
+use store::Store;
+
+/// The HTTP header name for setting the op on POST requests.
+const OP_HEADER: &str = "X-Op";
+/// The header op to put a signed agent info.
+const OP_PUT: &str = "put";
+/// The header op to return the server's opinion of 'now' in milliseconds.
+const OP_NOW: &str = "now";
+/// The header op to return a random set of agents in a specific space.
+const OP_RANDOM: &str = "random";
+
+/// Agent infos can't ask to be kept for longer than an hour.
+pub const MAX_EXPIRES_AFTER_MS: u64 = 1000 * 60 * 60;
+
+/// How far in the future an agent info can be signed,
+/// to allow for clocks that are a little out.
+pub const MAX_CLOCK_SKEW_MS: u64 = 1000 * 60;
+
+/// The future that drives a running bootstrap server.
+/// The server stops when this is dropped.
+pub type BootstrapDriver = BoxFuture<'static, ()>;
+
+/// Reasons the server rejects a request.
+#[derive(Debug, thiserror::Error)]
+pub enum BootstrapError {
+    /// The op header is missing or not one we know.
+    #[error("Unknown op: {0:?}")]
+    UnknownOp(Option<String>),
+
+    /// The agent info signature does not verify.
+    #[error("Agent info signature is invalid")]
+    InvalidSignature,
+
+    /// The agent info has already expired.
+    #[error("Agent info has expired")]
+    Expired,
+
+    /// The agent info was signed in the future.
+    #[error("Agent info was signed in the future")]
+    SignedInFuture,
+
+    /// The agent info asks to be kept for too long.
+    #[error("Agent info expires after {0}ms which is more than the maximum of {1}ms")]
+    ExpiresTooLate(u64, u64),
+
+    /// The request body could not be decoded.
+    #[error(transparent)]
+    Decode(#[from] std::io::Error),
+
+    /// Error from kitsune while handling the agent info.
+    #[error(transparent)]
+    Kitsune(#[from] kitsune_p2p::KitsuneP2pError),
+}
+
+/// Result type for the bootstrap server.
+pub type BootstrapResult<T> = Result<T, BootstrapError>;
+
+/// Struct decoded for the `random` op.
+/// This must match the `RandomQuery` sent by the client.
+#[derive(serde::Deserialize, serde::Serialize)]
+struct RandomQuery {
+    space: Arc<KitsuneSpace>,
+    limit: RandomLimit,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct RandomLimit(u32);
+
+/// Bind a bootstrap server to the address.
+/// Returns the future that runs the server and the address
+/// that was actually bound, which is useful when binding to port 0.
+pub async fn run(
+    addr: impl Into<SocketAddr>,
+) -> Result<(BootstrapDriver, SocketAddr), hyper::Error> {
+    let store = Store::default();
+    let make_service = make_service_fn(move |_| {
+        let store = store.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req| {
+                let store = store.clone();
+                async move { Ok::<_, Infallible>(handle(store, req).await) }
+            }))
+        }
+    });
+    let server = hyper::Server::try_bind(&addr.into())?.serve(make_service);
+    let addr = server.local_addr();
+    let driver = async move {
+        if let Err(e) = server.await {
+            tracing::error!(msg = "Bootstrap server stopped", ?e);
+        }
+    }
+    .boxed();
+    Ok((driver, addr))
+}
+
+/// Handle a single request, turning any error into a bad request response.
+async fn handle(store: Store, req: Request<Body>) -> Response<Body> {
+    if req.method() != Method::POST {
+        return respond(StatusCode::METHOD_NOT_ALLOWED, "Only POST is supported");
+    }
+    let op = req
+        .headers()
+        .get(OP_HEADER)
+        .and_then(|op| op.to_str().ok())
+        .map(|op| op.to_string());
+    let body = match hyper::body::to_bytes(req.into_body()).await {
+        Ok(body) => body,
+        Err(e) => return respond(StatusCode::BAD_REQUEST, e.to_string()),
+    };
+    let result = match op.as_deref() {
+        Some(OP_PUT) => put(&store, &body).await,
+        Some(OP_NOW) => now(),
+        Some(OP_RANDOM) => random(&store, &body),
+        _ => Err(BootstrapError::UnknownOp(op)),
+    };
+    match result {
+        Ok(data) => Response::new(Body::from(data)),
+        Err(e) => {
+            tracing::debug!(msg = "Rejected bootstrap request", ?e);
+            respond(StatusCode::BAD_REQUEST, e.to_string())
+        }
+    }
+}
+
+fn respond(status: StatusCode, msg: impl Into<Body>) -> Response<Body> {
+    let mut response = Response::new(msg.into());
+    *response.status_mut() = status;
+    response
+}
+
+/// Validate the signed agent info and store it until it expires.
+async fn put(store: &Store, mut body: &[u8]) -> BootstrapResult<Vec<u8>> {
+    let agent_info_signed: AgentInfoSigned = rmp_decode(&mut body)?;
+    if !agent_info_signed.verify_signature().await? {
+        return Err(BootstrapError::InvalidSignature);
+    }
+    let agent_info = AgentInfo::try_from(&agent_info_signed)?;
+
+    let now = now_ms();
+    if agent_info.signed_at_ms() > now + MAX_CLOCK_SKEW_MS {
+        return Err(BootstrapError::SignedInFuture);
+    }
+    if agent_info.expires_after_ms() > MAX_EXPIRES_AFTER_MS {
+        return Err(BootstrapError::ExpiresTooLate(
+            agent_info.expires_after_ms(),
+            MAX_EXPIRES_AFTER_MS,
+        ));
+    }
+    let expires_at_ms = agent_info.signed_at_ms() + agent_info.expires_after_ms();
+    if expires_at_ms <= now {
+        return Err(BootstrapError::Expired);
+    }
+
+    store.put(
+        agent_info.as_space_ref().clone(),
+        agent_info.as_agent_ref().clone(),
+        agent_info_signed,
+        agent_info.signed_at_ms(),
+        expires_at_ms,
+    );
+    encode(())
+}
+
+fn now() -> BootstrapResult<Vec<u8>> {
+    encode(now_ms())
+}
+
+/// Each agent info is encoded separately so the client
+/// can decode them as the exact bytes that were put.
+fn random(store: &Store, mut body: &[u8]) -> BootstrapResult<Vec<u8>> {
+    let query: RandomQuery = rmp_decode(&mut body)?;
+    let agent_infos = store
+        .random(&query.space, query.limit.0 as usize, now_ms())
+        .into_iter()
+        .map(|agent_info_signed| Ok(serde_bytes::ByteBuf::from(encode(agent_info_signed)?)))
+        .collect::<BootstrapResult<Vec<_>>>()?;
+    encode(agent_infos)
+}
+
+fn encode<S: serde::Serialize>(item: S) -> BootstrapResult<Vec<u8>> {
+    let mut data = Vec::new();
+    rmp_encode(&mut data, item)?;
+    Ok(data)
+}
+
+/// The current unix time in milliseconds.
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_millis() as u64
+}
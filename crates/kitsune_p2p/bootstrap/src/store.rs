@@ -0,0 +1,157 @@
+//! In memory store of the agent infos put to the bootstrap server.
+
+use kitsune_p2p::agent_store::AgentInfoSigned;
+use kitsune_p2p::KitsuneAgent;
+use kitsune_p2p::KitsuneSpace;
+use rand::seq::IteratorRandom;
+use rand::seq::SliceRandom;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+/// Agent infos per space, each kept until it expires.
+#[derive(Clone, Default)]
+pub(crate) struct Store(Arc<Mutex<HashMap<KitsuneSpace, HashMap<KitsuneAgent, StoreEntry>>>>);
+
+struct StoreEntry {
+    agent_info_signed: AgentInfoSigned,
+    signed_at_ms: u64,
+    expires_at_ms: u64,
+}
+
+impl Store {
+    /// Store an agent info until it expires.
+    /// An agent info signed before the one already stored
+    /// for the same agent is ignored so old infos can't be replayed.
+    pub(crate) fn put(
+        &self,
+        space: KitsuneSpace,
+        agent: KitsuneAgent,
+        agent_info_signed: AgentInfoSigned,
+        signed_at_ms: u64,
+        expires_at_ms: u64,
+    ) {
+        let mut spaces = self.0.lock().expect("Bootstrap store lock poisoned");
+        let agents = spaces.entry(space).or_insert_with(HashMap::new);
+        if let Some(existing) = agents.get(&agent) {
+            if existing.signed_at_ms > signed_at_ms {
+                return;
+            }
+        }
+        agents.insert(
+            agent,
+            StoreEntry {
+                agent_info_signed,
+                signed_at_ms,
+                expires_at_ms,
+            },
+        );
+    }
+
+    /// Up to `limit` unexpired agent infos from the space in a random order.
+    /// Evicts any expired agent infos first.
+    pub(crate) fn random(
+        &self,
+        space: &KitsuneSpace,
+        limit: usize,
+        now_ms: u64,
+    ) -> Vec<AgentInfoSigned> {
+        let mut spaces = self.0.lock().expect("Bootstrap store lock poisoned");
+        Self::evict_expired(&mut spaces, now_ms);
+        match spaces.get(space) {
+            Some(agents) => {
+                let mut rng = rand::thread_rng();
+                let mut agent_infos = agents
+                    .values()
+                    .map(|entry| entry.agent_info_signed.clone())
+                    .choose_multiple(&mut rng, limit);
+                // The order choose_multiple returns isn't random
+                agent_infos.shuffle(&mut rng);
+                agent_infos
+            }
+            None => Vec::new(),
+        }
+    }
+
+    fn evict_expired(
+        spaces: &mut HashMap<KitsuneSpace, HashMap<KitsuneAgent, StoreEntry>>,
+        now_ms: u64,
+    ) {
+        for agents in spaces.values_mut() {
+            agents.retain(|_, entry| entry.expires_at_ms > now_ms);
+        }
+        spaces.retain(|_, agents| !agents.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use kitsune_p2p::fixt::*;
+
+    #[test]
+    fn expired_agent_infos_are_evicted() {
+        let store = Store::default();
+        let space = fixt!(KitsuneSpace);
+        let alice = fixt!(AgentInfoSigned);
+        let bob = fixt!(AgentInfoSigned);
+        store.put(space.clone(), fixt!(KitsuneAgent), alice.clone(), 0, 100);
+        store.put(space.clone(), fixt!(KitsuneAgent), bob.clone(), 0, 200);
+
+        let mut all = store.random(&space, 10, 50);
+        all.sort();
+        let mut expected = vec![alice, bob.clone()];
+        expected.sort();
+        assert_eq!(all, expected);
+
+        assert_eq!(store.random(&space, 10, 100), vec![bob]);
+        assert!(store.random(&space, 10, 200).is_empty());
+    }
+
+    #[test]
+    fn spaces_are_separate() {
+        let store = Store::default();
+        let space = fixt!(KitsuneSpace, Unpredictable);
+        let other_space = fixt!(KitsuneSpace, Unpredictable);
+        let agent_info = fixt!(AgentInfoSigned);
+        store.put(
+            space.clone(),
+            fixt!(KitsuneAgent),
+            agent_info.clone(),
+            0,
+            100,
+        );
+
+        assert_eq!(store.random(&space, 10, 0), vec![agent_info]);
+        assert!(store.random(&other_space, 10, 0).is_empty());
+    }
+
+    #[test]
+    fn random_is_limited() {
+        let store = Store::default();
+        let space = fixt!(KitsuneSpace);
+        for _ in 0..10 {
+            store.put(
+                space.clone(),
+                fixt!(KitsuneAgent, Unpredictable),
+                fixt!(AgentInfoSigned),
+                0,
+                100,
+            );
+        }
+        assert_eq!(store.random(&space, 3, 0).len(), 3);
+        assert_eq!(store.random(&space, 20, 0).len(), 10);
+    }
+
+    #[test]
+    fn older_agent_infos_are_ignored() {
+        let store = Store::default();
+        let space = fixt!(KitsuneSpace);
+        let agent = fixt!(KitsuneAgent);
+        let newer = fixt!(AgentInfoSigned);
+        store.put(space.clone(), agent.clone(), newer.clone(), 10, 100);
+        store.put(space.clone(), agent.clone(), fixt!(AgentInfoSigned), 5, 100);
+        assert_eq!(store.random(&space, 10, 0), vec![newer]);
+    }
+}
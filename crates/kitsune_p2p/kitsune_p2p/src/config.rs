@@ -17,6 +17,11 @@ pub const BOOTSTRAP_SERVICE_DEV: &str = "https://bootstrap-dev.holohost.workers.
 #[allow(missing_docs)]
 pub struct KitsuneP2pTuningParams {
     pub gossip_loop_iteration_delay_ms: u32,
+    pub gossip_peer_count: u32,
+    pub gossip_region_count: u32,
+    pub arc_target_redundancy: u32,
+    pub arc_min_coverage: f64,
+    pub arc_max_coverage: f64,
     pub default_notify_remote_agent_count: u32,
     pub default_notify_timeout_ms: u32,
     pub default_rpc_single_timeout_ms: u32,
@@ -29,6 +34,11 @@ impl Default for KitsuneP2pTuningParams {
     fn default() -> Self {
         Self {
             gossip_loop_iteration_delay_ms: 10,
+            gossip_peer_count: 8,
+            gossip_region_count: 16,
+            arc_target_redundancy: 5,
+            arc_min_coverage: 0.01,
+            arc_max_coverage: 1.0,
             default_notify_remote_agent_count: 5,
             default_notify_timeout_ms: 1000,
             default_rpc_single_timeout_ms: 2000,
@@ -50,6 +60,17 @@ impl serde::Serialize for KitsuneP2pTuningParams {
             "gossip_loop_iteration_delay_ms",
             &format!("{}", self.gossip_loop_iteration_delay_ms),
         )?;
+        m.serialize_entry("gossip_peer_count", &format!("{}", self.gossip_peer_count))?;
+        m.serialize_entry(
+            "gossip_region_count",
+            &format!("{}", self.gossip_region_count),
+        )?;
+        m.serialize_entry(
+            "arc_target_redundancy",
+            &format!("{}", self.arc_target_redundancy),
+        )?;
+        m.serialize_entry("arc_min_coverage", &format!("{}", self.arc_min_coverage))?;
+        m.serialize_entry("arc_max_coverage", &format!("{}", self.arc_max_coverage))?;
         m.serialize_entry(
             "default_notify_remote_agent_count",
             &format!("{}", self.default_notify_remote_agent_count),
@@ -91,6 +112,26 @@ impl<'de> serde::Deserialize<'de> for KitsuneP2pTuningParams {
                     Ok(v) => out.gossip_loop_iteration_delay_ms = v,
                     Err(e) => tracing::warn!("failed to parse {}: {}", k, e),
                 },
+                "gossip_peer_count" => match v.parse::<u32>() {
+                    Ok(v) => out.gossip_peer_count = v,
+                    Err(e) => tracing::warn!("failed to parse {}: {}", k, e),
+                },
+                "gossip_region_count" => match v.parse::<u32>() {
+                    Ok(v) => out.gossip_region_count = v,
+                    Err(e) => tracing::warn!("failed to parse {}: {}", k, e),
+                },
+                "arc_target_redundancy" => match v.parse::<u32>() {
+                    Ok(v) => out.arc_target_redundancy = v,
+                    Err(e) => tracing::warn!("failed to parse {}: {}", k, e),
+                },
+                "arc_min_coverage" => match v.parse::<f64>() {
+                    Ok(v) => out.arc_min_coverage = v,
+                    Err(e) => tracing::warn!("failed to parse {}: {}", k, e),
+                },
+                "arc_max_coverage" => match v.parse::<f64>() {
+                    Ok(v) => out.arc_max_coverage = v,
+                    Err(e) => tracing::warn!("failed to parse {}: {}", k, e),
+                },
                 "default_notify_remote_agent_count" => match v.parse::<u32>() {
                     Ok(v) => out.default_notify_remote_agent_count = v,
                     Err(e) => tracing::warn!("failed to parse {}: {}", k, e),
@@ -9,6 +9,7 @@ use crate::KitsuneBinType;
 use crate::KitsuneSignature;
 use crate::KitsuneSpace;
 use ::fixt::prelude::*;
+use kitsune_p2p_types::dht_arc::DhtArc;
 use url2::url2;
 
 fixturator!(
@@ -51,9 +52,14 @@ fixturator!(
     from SixtyFourBytes;
 );
 
+fixturator!(
+    DhtArc;
+    constructor fn new(U32, U32);
+);
+
 fixturator!(
     AgentInfo;
-    constructor fn new(KitsuneSpace, KitsuneAgent, Urls, U64, U64);
+    constructor fn new(KitsuneSpace, KitsuneAgent, Urls, DhtArc, U64, U64);
 );
 
 fixturator!(
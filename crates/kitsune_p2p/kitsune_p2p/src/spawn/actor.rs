@@ -119,6 +119,10 @@ impl KitsuneP2pActor {
             t_pool.push_sub_transport(l, e).await?;
         }
 
+        // We never ask a peer for more regions than this
+        // so we don't summarize more for them either
+        let max_regions = config.tuning_params.gossip_region_count as usize;
+
         tokio::task::spawn({
             let evt_sender = evt_sender.clone();
             t_event.for_each_concurrent(/* limit */ 10, move |event| {
@@ -188,6 +192,56 @@ impl KitsuneP2pActor {
                                     KitsuneMetrics::count(KitsuneMetrics::NotifyResp, resp.len());
                                     let _ = write.write_and_close(resp).await;
                                 }
+                                wire::Wire::FetchOpHashSummary(wire::FetchOpHashSummary {
+                                    space,
+                                    from_agent,
+                                    to_agent,
+                                    regions,
+                                }) => {
+                                    if regions.len() > max_regions {
+                                        let reason = format!(
+                                            "Requested {} regions but at most {} are summarized",
+                                            regions.len(),
+                                            max_regions
+                                        );
+                                        let fail =
+                                            wire::Wire::failure(reason).encode_vec().unwrap();
+                                        KitsuneMetrics::count(KitsuneMetrics::Fail, fail.len());
+                                        let _ = write.write_and_close(fail).await;
+                                        return;
+                                    }
+                                    let input =
+                                        ReqOpHashSummaryEvt::new(from_agent, to_agent, regions);
+                                    let (summaries, agent_hashes) =
+                                        match local_req_op_hash_summary(&evt_sender, space, input)
+                                            .await
+                                        {
+                                            Err(err) => {
+                                                let reason = format!("{:?}", err);
+                                                let fail = wire::Wire::failure(reason)
+                                                    .encode_vec()
+                                                    .unwrap();
+                                                KitsuneMetrics::count(
+                                                    KitsuneMetrics::Fail,
+                                                    fail.len(),
+                                                );
+                                                let _ = write.write_and_close(fail).await;
+                                                return;
+                                            }
+                                            Ok(r) => r,
+                                        };
+                                    let resp = wire::Wire::fetch_op_hash_summary_response(
+                                        summaries,
+                                        agent_hashes,
+                                    )
+                                    .encode_vec()
+                                    .expect("This encoding should never fail");
+                                    KitsuneMetrics::count(
+                                        KitsuneMetrics::FetchOpHashSummaryResp,
+                                        resp.len(),
+                                    );
+                                    let _ = write.write_and_close(resp).await;
+                                }
                                 wire::Wire::FetchOpHashes(wire::FetchOpHashes {
                                     space,
                                     from_agent,
@@ -195,7 +249,6 @@ impl KitsuneP2pActor {
                                     dht_arc,
                                     since_utc_epoch_s,
                                     until_utc_epoch_s,
-                                    last_count,
                                 }) => {
                                     let input = ReqOpHashesEvt::new(
                                         from_agent,
@@ -203,9 +256,8 @@ impl KitsuneP2pActor {
                                         dht_arc,
                                         since_utc_epoch_s,
                                         until_utc_epoch_s,
-                                        Default::default(),
                                     );
-                                    let (hashes, agent_hashes) = match local_req_op_hashes(
+                                    let hashes = match local_req_op_hashes(
                                         &evt_sender,
                                         space,
                                         input,
@@ -222,23 +274,9 @@ impl KitsuneP2pActor {
                                         }
                                         Ok(r) => r,
                                     };
-                                    let hashes = match last_count {
-                                        OpCount::Consistent(last_count) => {
-                                            // Requester is consistent,
-                                            // now check if we are consistent.
-                                            if last_count == hashes.len() as u64 {
-                                                OpConsistency::Consistent
-                                            } else {
-                                                OpConsistency::Variance(hashes)
-                                            }
-                                        }
-                                        // Requester has a variance so we must return hashes.
-                                        OpCount::Variance => OpConsistency::Variance(hashes),
-                                    };
-                                    let resp =
-                                        wire::Wire::fetch_op_hashes_response(hashes, agent_hashes)
-                                            .encode_vec()
-                                            .expect("This encoding should never fail");
+                                    let resp = wire::Wire::fetch_op_hashes_response(hashes)
+                                        .encode_vec()
+                                        .expect("This encoding should never fail");
                                     KitsuneMetrics::count(
                                         KitsuneMetrics::FetchOpHashesResp,
                                         resp.len(),
@@ -415,7 +453,19 @@ impl KitsuneP2pEventHandler for KitsuneP2pActor {
         &mut self,
         input: crate::event::PutAgentInfoSignedEvt,
     ) -> KitsuneP2pEventHandlerResult<()> {
-        Ok(self.evt_sender.put_agent_info_signed(input))
+        let evt_sender = self.evt_sender.clone();
+        Ok(async move {
+            // Never store peer info that the agent did not sign
+            if !input.agent_info_signed.verify_signature().await? {
+                return Err(KitsuneP2pError::other(format!(
+                    "invalid agent info signature for agent {:?}",
+                    input.agent_info_signed.as_agent_ref()
+                )));
+            }
+            evt_sender.put_agent_info_signed(input).await
+        }
+        .boxed()
+        .into())
     }
 
     fn handle_get_agent_info_signed(
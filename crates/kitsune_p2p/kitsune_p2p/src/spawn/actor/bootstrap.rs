@@ -210,8 +210,18 @@ mod tests {
     use lair_keystore_api::internal::sign_ed25519::sign_ed25519_keypair_new_from_entropy;
     use std::convert::TryInto;
 
+    /// Run a bootstrap server in this process and get its url.
+    async fn local_bootstrap() -> Option<Url2> {
+        let (driver, addr) = kitsune_p2p_bootstrap::run(([127, 0, 0, 1], 0))
+            .await
+            .unwrap();
+        tokio::task::spawn(driver);
+        Some(url2::url2!("http://{}", addr))
+    }
+
     #[tokio::test(threaded_scheduler)]
     async fn test_bootstrap() {
+        let url = local_bootstrap().await;
         let keypair = sign_ed25519_keypair_new_from_entropy().await.unwrap();
         let space = fixt!(KitsuneSpace);
         let agent = KitsuneAgent::new((*keypair.pub_key.0).clone());
@@ -225,6 +235,7 @@ mod tests {
             space,
             agent.clone(),
             urls,
+            fixt!(DhtArc),
             (millis - 100).try_into().unwrap(),
             1000 * 60 * 20,
         );
@@ -239,24 +250,17 @@ mod tests {
                 .unwrap();
 
         // Simply hitting the endpoint should be OK.
-        super::put(
-            Some(url2::url2!("{}", crate::config::BOOTSTRAP_SERVICE_DEV)),
-            agent_info_signed,
-        )
-        .await
-        .unwrap();
+        super::put(url.clone(), agent_info_signed).await.unwrap();
 
         // We should get back an error if we don't have a good signature.
-        assert!(super::put(
-            Some(url2::url2!("{}", crate::config::BOOTSTRAP_SERVICE_DEV)),
-            fixt!(AgentInfoSigned)
-        )
-        .await
-        .is_err());
+        assert!(super::put(url.clone(), fixt!(AgentInfoSigned))
+            .await
+            .is_err());
     }
 
     #[tokio::test(threaded_scheduler)]
     async fn test_now() {
+        let url = local_bootstrap().await;
         let local_now = std::time::SystemTime::now();
         let local_millis: u64 = local_now
             .duration_since(std::time::UNIX_EPOCH)
@@ -266,39 +270,22 @@ mod tests {
             .unwrap();
 
         // We should be able to get a milliseconds timestamp back.
-        let remote_now: u64 = super::now(Some(url2::url2!(
-            "{}",
-            crate::config::BOOTSTRAP_SERVICE_DEV
-        )))
-        .await
-        .unwrap();
+        let remote_now: u64 = super::now(url.clone()).await.unwrap();
         let threshold = 5000;
 
         assert!((remote_now - local_millis) < threshold);
 
         // Now once should return some number and the remote server offset should be set in the
         // NOW_OFFSET_MILLIS once cell.
-        let _: u64 = super::now_once(Some(url2::url2!(
-            "{}",
-            crate::config::BOOTSTRAP_SERVICE_DEV
-        )))
-        .await
-        .unwrap();
+        let _: u64 = super::now_once(url.clone()).await.unwrap();
         assert!(super::NOW_OFFSET_MILLIS.get().is_some());
     }
 
     #[tokio::test(threaded_scheduler)]
-    #[ignore = "flaky"]
-    // Fixturator seed: 17591570467001263546
-    // thread 'spawn::actor::bootstrap::tests::test_random' panicked at 'dispatch dropped without returning error', /rustc/d3fb005a39e62501b8b0b356166e515ae24e2e54/src/libstd/macros.rs:13:23
     async fn test_random() {
+        let url = local_bootstrap().await;
         let space = fixt!(KitsuneSpace, Unpredictable);
-        let now = super::now(Some(url2::url2!(
-            "{}",
-            crate::config::BOOTSTRAP_SERVICE_DEV
-        )))
-        .await
-        .unwrap();
+        let now = super::now(url.clone()).await.unwrap();
 
         let alice = sign_ed25519_keypair_new_from_entropy().await.unwrap();
         let bob = sign_ed25519_keypair_new_from_entropy().await.unwrap();
@@ -310,6 +297,7 @@ mod tests {
                 space.clone(),
                 kitsune_agent.clone(),
                 fixt!(Urls),
+                fixt!(DhtArc),
                 now,
                 1000 * 60 * 20,
             );
@@ -323,18 +311,15 @@ mod tests {
             )
             .unwrap();
 
-            super::put(
-                Some(url2::url2!("{}", crate::config::BOOTSTRAP_SERVICE_DEV)),
-                agent_info_signed.clone(),
-            )
-            .await
-            .unwrap();
+            super::put(url.clone(), agent_info_signed.clone())
+                .await
+                .unwrap();
 
             expected.push(agent_info_signed);
         }
 
         let mut random = super::random(
-            Some(url2::url2!("{}", crate::config::BOOTSTRAP_SERVICE_DEV)),
+            url.clone(),
             super::RandomQuery {
                 space: Arc::new(space.clone()),
                 ..Default::default()
@@ -350,7 +335,7 @@ mod tests {
         assert!(random == expected);
 
         let random_single = super::random(
-            Some(url2::url2!("{}", crate::config::BOOTSTRAP_SERVICE_DEV)),
+            url.clone(),
             super::RandomQuery {
                 space: Arc::new(space.clone()),
                 limit: 1.into(),
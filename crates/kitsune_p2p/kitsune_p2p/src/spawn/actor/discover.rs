@@ -129,6 +129,10 @@ pub(crate) fn peer_discover(
             }))
             .await?;
 
+            if !req_info.verify_signature().await? {
+                return Err("invalid agent info signature".into());
+            }
+
             // we got a result - let's add it to our store for the future
             let _ = evt_sender
                 .put_agent_info_signed(PutAgentInfoSignedEvt {
@@ -356,7 +360,10 @@ pub(crate) fn get_5_or_less_non_local_agents_near_basis(
         .await
         {
             for item in list {
-                // TODO - someday some validation here
+                // skip any peer info the agent did not sign
+                if !item.verify_signature().await.unwrap_or(false) {
+                    continue;
+                }
                 if let Ok(info) = AgentInfo::try_from(&item) {
                     if let Ok(is_local) = i_s
                         .is_agent_local(Arc::new(info.as_agent_ref().clone()))
@@ -409,7 +416,10 @@ pub(crate) fn add_5_or_less_non_local_agents(
         .await
         {
             for item in list {
-                // TODO - someday some validation here
+                // skip any peer info the agent did not sign
+                if !item.verify_signature().await.unwrap_or(false) {
+                    continue;
+                }
                 if let Ok(info) = AgentInfo::try_from(&item) {
                     if let Ok(is_local) = i_s
                         .is_agent_local(Arc::new(info.as_agent_ref().clone()))
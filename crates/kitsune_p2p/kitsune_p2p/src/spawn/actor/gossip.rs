@@ -1,5 +1,13 @@
-//! This is a temporary quick-hack gossip module for use with the
-//! in-memory / full-sync / non-sharded networking module
+//! Sharded gossip between agents with overlapping arcs.
+//!
+//! Each round one local agent gossips with one peer whose arc overlaps
+//! its own. Only the overlap of the two arcs is synced. The overlap is
+//! split into regions and each side summarizes the op hashes it holds in
+//! each region, so hash lists are only exchanged for the regions that
+//! differ. Regions are split by location rather than by time because the
+//! time an op was integrated is local to each agent.
+//! Every pass each local agent gossips with the `gossip_peer_count`
+//! peers it has gone the longest without gossiping with.
 
 use crate::types::actor::KitsuneP2pResult;
 use crate::types::gossip::*;
@@ -12,6 +20,10 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::iter::FromIterator;
 use std::sync::Arc;
+use std::time::Instant;
+
+mod regions;
+use regions::overlap_regions;
 
 ghost_actor::ghost_chan! {
     /// "Event" requests emitted by the gossip module
@@ -19,10 +31,16 @@ ghost_actor::ghost_chan! {
         /// get a list of agents we know about
         fn list_neighbor_agents() -> ListNeighborAgents;
 
+        /// fetch a summary of the ops held in each region
+        /// and the list of agents from/to
+        fn req_op_hash_summary(
+            input: ReqOpHashSummaryEvt,
+        ) -> OpHashSummaryAgentHashes;
+
         /// fetch op list from/to with constraints
         fn req_op_hashes(
             input: ReqOpHashesEvt,
-        ) -> OpHashesAgentHashes;
+        ) -> OpHashes;
 
         /// fetch op data for op hash list
         fn req_op_data(
@@ -54,7 +72,7 @@ async fn gossip_loop(
     config: Arc<KitsuneP2pConfig>,
     evt_send: futures::channel::mpsc::Sender<GossipEvent>,
 ) -> KitsuneP2pResult<()> {
-    let mut gossip_data = GossipData::new(evt_send);
+    let mut gossip_data = GossipData::new(evt_send, config.clone());
     loop {
         match gossip_data.take_action().await {
             Err(KitsuneP2pError::GhostError(GhostError::Disconnected)) => {
@@ -74,18 +92,31 @@ async fn gossip_loop(
     }
 }
 
+/// One local agent gossiping with one peer
+/// over the regions where their arcs overlap.
+struct GossipRound {
+    from_agent: Arc<KitsuneAgent>,
+    to_agent: Arc<KitsuneAgent>,
+    regions: Vec<DhtArc>,
+}
+
 struct GossipData {
     evt_send: futures::channel::mpsc::Sender<GossipEvent>,
-    pending_gossip_list: Vec<(Arc<KitsuneAgent>, Arc<KitsuneAgent>)>,
-    last_counts: HashMap<Arc<KitsuneAgent>, (u64, u64)>,
+    config: Arc<KitsuneP2pConfig>,
+    pending_gossip_list: Vec<GossipRound>,
+    last_gossip: HashMap<(Arc<KitsuneAgent>, Arc<KitsuneAgent>), Instant>,
 }
 
 impl GossipData {
-    pub fn new(evt_send: futures::channel::mpsc::Sender<GossipEvent>) -> Self {
+    pub fn new(
+        evt_send: futures::channel::mpsc::Sender<GossipEvent>,
+        config: Arc<KitsuneP2pConfig>,
+    ) -> Self {
         Self {
             evt_send,
+            config,
             pending_gossip_list: Vec::new(),
-            last_counts: HashMap::new(),
+            last_gossip: HashMap::new(),
         }
     }
 
@@ -100,99 +131,136 @@ impl GossipData {
 
     async fn fetch_pending_gossip_list(&mut self) -> KitsuneP2pResult<()> {
         let (local_agents, remote_agents) = self.evt_send.list_neighbor_agents().await?;
-        // super naive gossip just processes all combinations
-        // also causes duplication because it runs pairs from both sides
-        for (i, a1) in local_agents.iter().enumerate() {
-            for a2 in local_agents.iter().skip(i) {
-                // at the very least, avoid gossiping with ourselves
-                if a1 != a2 {
-                    self.pending_gossip_list.push((a1.clone(), a2.clone()));
-                }
-            }
-            for a2 in remote_agents.iter() {
-                self.pending_gossip_list.push((a1.clone(), a2.clone()));
-            }
+        self.forget_departed_agents(&local_agents, &remote_agents);
+        let region_count = self.config.tuning_params.gossip_region_count;
+        let peer_count = self.config.tuning_params.gossip_peer_count as usize;
+        for (i, (from_agent, from_arc)) in local_agents.iter().enumerate() {
+            // gossip between two local agents runs in both directions
+            // so only the first of them needs to start it
+            let peers = local_agents.iter().skip(i + 1).chain(remote_agents.iter());
+            let mut rounds = peers
+                .filter_map(|(to_agent, to_arc)| {
+                    let regions = overlap_regions(from_arc, to_arc, region_count);
+                    if regions.is_empty() {
+                        None
+                    } else {
+                        Some(GossipRound {
+                            from_agent: from_agent.clone(),
+                            to_agent: to_agent.clone(),
+                            regions,
+                        })
+                    }
+                })
+                .collect::<Vec<_>>();
+            // peers we have never gossiped with come first
+            rounds.sort_by_key(|round| {
+                self.last_gossip
+                    .get(&(round.from_agent.clone(), round.to_agent.clone()))
+                    .cloned()
+            });
+            self.pending_gossip_list
+                .extend(rounds.into_iter().take(peer_count));
         }
         Ok(())
     }
 
+    /// Drop the last gossip times of agents that are no longer
+    /// neighbors so the map doesn't grow without bound.
+    fn forget_departed_agents(
+        &mut self,
+        local_agents: &[(Arc<KitsuneAgent>, DhtArc)],
+        remote_agents: &[(Arc<KitsuneAgent>, DhtArc)],
+    ) {
+        let current = local_agents
+            .iter()
+            .chain(remote_agents.iter())
+            .map(|(agent, _)| agent.clone())
+            .collect::<HashSet<_>>();
+        self.last_gossip
+            .retain(|(from, to), _| current.contains(from) && current.contains(to));
+    }
+
     #[tracing::instrument(skip(self))]
     async fn process_next_gossip(&mut self) -> KitsuneP2pResult<()> {
         // !is_empty() checked above in take_action
-        let (from_agent, to_agent) = self.pending_gossip_list.remove(0);
+        let GossipRound {
+            from_agent,
+            to_agent,
+            regions,
+        } = self.pending_gossip_list.remove(0);
         let span = tracing::debug_span!("next_gossip", ?from_agent, ?to_agent);
-
-        // Get the last count for this interaction
-        let last_count = self.last_counts.entry(to_agent.clone()).or_insert((0, 0));
+        self.last_gossip
+            .insert((from_agent.clone(), to_agent.clone()), Instant::now());
 
         // required so from_iters below know the build_hasher type
         type S = HashSet<Arc<KitsuneOpHash>>;
         type A = HashSet<(Arc<KitsuneAgent>, u64)>;
 
-        // we'll just fetch all with no constraints for now
-        let (op_hashes_from, agent_info_from) = self
+        let (summaries_from, agent_info_from) = self
             .evt_send
-            .req_op_hashes(ReqOpHashesEvt::new(
+            .req_op_hash_summary(ReqOpHashSummaryEvt::new(
                 from_agent.clone(), // from not to because we're initiating
                 from_agent.clone(),
-                DhtArc::new(0, u32::MAX),
-                i64::MIN,
-                i64::MAX,
-                Default::default(), // This is ignored because requesting from self
+                regions.clone(),
             ))
             .await?;
-        let op_hashes_from = match op_hashes_from {
-            OpConsistency::Variance(h) => h,
-            // Not currently used
-            OpConsistency::Consistent => {
-                unreachable!("We don't track consistency of hashes for local requests")
-            }
-        };
-        let op_count = if last_count.0 == op_hashes_from.len() as u64 {
-            // We have nothing new for them but
-            // they might still have something new
-            // for us.
-            OpCount::Consistent(last_count.1)
-        } else {
-            // We have new gossip for them so
-            // they need to tell us what they have
-            // so we can compute the difference.
-            OpCount::Variance
-        };
-        last_count.0 = op_hashes_from.len() as u64;
-
-        let op_hashes_from: S = HashSet::from_iter(op_hashes_from);
-        let agent_info_from: A = HashSet::from_iter(agent_info_from);
-        span.in_scope(|| {
-            tracing::debug!(from_has_len = ?op_hashes_from.len());
-        });
-
-        // we'll just fetch all with no constraints for now
-        let (op_hashes_to, agent_info_to) = self
+        let (summaries_to, agent_info_to) = self
             .evt_send
-            .req_op_hashes(ReqOpHashesEvt::new(
+            .req_op_hash_summary(ReqOpHashSummaryEvt::new(
                 from_agent.clone(),
                 to_agent.clone(),
-                DhtArc::new(0, u32::MAX),
-                i64::MIN,
-                i64::MAX,
-                op_count,
+                regions.clone(),
             ))
             .await?;
-        let op_hashes_to = match op_hashes_to {
-            OpConsistency::Variance(h) => {
-                last_count.1 = h.len() as u64;
-                h
-            }
-            // There's no new gossip from us or them
-            // so our job is done.
-            OpConsistency::Consistent => {
-                return Ok(());
-            }
-        };
-        let op_hashes_to: S = HashSet::from_iter(op_hashes_to);
+        if summaries_from.len() != regions.len() || summaries_to.len() != regions.len() {
+            return Err(KitsuneP2pError::other(format!(
+                "expected {} region summaries from {:?}",
+                regions.len(),
+                to_agent
+            )));
+        }
+        let agent_info_from: A = HashSet::from_iter(agent_info_from);
         let agent_info_to: A = HashSet::from_iter(agent_info_to);
+
+        // only the regions that differ need their hashes compared
+        let differing = regions
+            .into_iter()
+            .zip(summaries_from.into_iter().zip(summaries_to.into_iter()))
+            .filter(|(_, (from, to))| from != to)
+            .map(|(region, _)| region)
+            .collect::<Vec<_>>();
         span.in_scope(|| {
+            tracing::debug!(differing_regions = ?differing.len());
+        });
+
+        let mut op_hashes_from: S = HashSet::new();
+        let mut op_hashes_to: S = HashSet::new();
+        for region in differing {
+            op_hashes_from.extend(
+                self.evt_send
+                    .req_op_hashes(ReqOpHashesEvt::new(
+                        from_agent.clone(),
+                        from_agent.clone(),
+                        region,
+                        i64::MIN,
+                        i64::MAX,
+                    ))
+                    .await?,
+            );
+            op_hashes_to.extend(
+                self.evt_send
+                    .req_op_hashes(ReqOpHashesEvt::new(
+                        from_agent.clone(),
+                        to_agent.clone(),
+                        region,
+                        i64::MIN,
+                        i64::MAX,
+                    ))
+                    .await?,
+            );
+        }
+        span.in_scope(|| {
+            tracing::debug!(from_has_len = ?op_hashes_from.len());
             tracing::debug!(to_has_len = ?op_hashes_to.len());
         });
 
@@ -291,3 +359,28 @@ impl GossipData {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kitsune_p2p_types::dht_arc::MAX_HALF_LENGTH;
+
+    #[test]
+    fn departed_agents_are_forgotten() {
+        let (evt_send, _evt_recv) = futures::channel::mpsc::channel(1);
+        let mut gossip_data = GossipData::new(evt_send, Arc::new(KitsuneP2pConfig::default()));
+        let agent = |b: u8| Arc::new(KitsuneAgent::new(vec![b; 36]));
+        let arc = DhtArc::new(0, MAX_HALF_LENGTH);
+        let (local, stays, leaves) = (agent(0), agent(1), agent(2));
+        for to_agent in vec![stays.clone(), leaves.clone()] {
+            gossip_data
+                .last_gossip
+                .insert((local.clone(), to_agent), Instant::now());
+        }
+
+        gossip_data.forget_departed_agents(&[(local.clone(), arc)], &[(stays.clone(), arc)]);
+
+        assert_eq!(gossip_data.last_gossip.len(), 1);
+        assert!(gossip_data.last_gossip.contains_key(&(local, stays)));
+    }
+}
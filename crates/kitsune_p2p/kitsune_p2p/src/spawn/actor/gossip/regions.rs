@@ -0,0 +1,134 @@
+//! Split the part of the dht two agents are both holding into regions
+//! that can be summarized and compared independently.
+
+use kitsune_p2p_types::dht_arc::ArcRange;
+use kitsune_p2p_types::dht_arc::DhtArc;
+use std::ops::Bound;
+
+/// An inclusive range of locations that doesn't wrap around the dht.
+type Segment = (u64, u64);
+
+/// The number of locations on the dht.
+const DHT_LEN: u64 = u32::MAX as u64 + 1;
+
+/// The regions of the dht held by both arcs.
+/// Regions never cross the boundaries of a fixed grid of `region_count`
+/// equal slices of the dht, so the same two arcs always produce the
+/// same regions and large overlaps are split into smaller regions.
+pub(crate) fn overlap_regions(a: &DhtArc, b: &DhtArc, region_count: u32) -> Vec<DhtArc> {
+    let region_len = DHT_LEN / region_count.max(1) as u64;
+    let mut regions = Vec::new();
+    for (a_start, a_end) in segments(a) {
+        for (b_start, b_end) in segments(b) {
+            let start = a_start.max(b_start);
+            let end = a_end.min(b_end);
+            let mut region_start = start;
+            while region_start <= end {
+                let grid_end = (region_start / region_len + 1) * region_len - 1;
+                let region_end = grid_end.min(end);
+                regions.push(segment_to_arc((region_start, region_end)));
+                region_start = region_end + 1;
+            }
+        }
+    }
+    regions
+}
+
+/// The locations held by an arc as segments that don't wrap.
+fn segments(arc: &DhtArc) -> Vec<Segment> {
+    match arc.range() {
+        ArcRange {
+            start: Bound::Included(start),
+            end: Bound::Included(end),
+        } => {
+            if start <= end {
+                vec![(start as u64, end as u64)]
+            } else {
+                vec![(start as u64, u32::MAX as u64), (0, end as u64)]
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// The smallest arc that holds every location in the segment.
+/// Arcs always hold an odd number of locations so
+/// an even length segment gets one extra location at the end.
+fn segment_to_arc((start, end): Segment) -> DhtArc {
+    let len = end - start + 1;
+    let center = start + len / 2;
+    let half_length = len / 2 + 1;
+    DhtArc::new(center as u32, half_length as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kitsune_p2p_types::dht_arc::MAX_HALF_LENGTH;
+
+    const QUARTER: u32 = u32::MAX / 4 + 1;
+
+    /// Check a location is held by the overlap
+    fn held(regions: &[DhtArc], loc: u32) -> bool {
+        regions.iter().any(|r| r.contains(loc))
+    }
+
+    #[test]
+    fn full_arcs_overlap_everywhere() {
+        let a = DhtArc::new(0, MAX_HALF_LENGTH);
+        let b = DhtArc::new(12345, MAX_HALF_LENGTH);
+        let regions = overlap_regions(&a, &b, 16);
+        // The slice of the grid where b's arc starts is split in two
+        assert_eq!(regions.len(), 17);
+        for loc in (0..=u32::MAX).step_by(7919) {
+            assert!(held(&regions, loc));
+        }
+        assert!(held(&regions, u32::MAX));
+    }
+
+    #[test]
+    fn disjoint_arcs_have_no_overlap() {
+        let a = DhtArc::new(0, 100);
+        let b = DhtArc::new(u32::MAX / 2, 100);
+        assert!(overlap_regions(&a, &b, 16).is_empty());
+        assert!(overlap_regions(&a, &DhtArc::new(0, 0), 16).is_empty());
+    }
+
+    #[test]
+    fn overlap_is_only_the_shared_locations() {
+        // a holds roughly [0, half) and b holds roughly [quarter, 3 quarters)
+        let a = DhtArc::new(QUARTER, QUARTER);
+        let b = DhtArc::new(QUARTER * 2, QUARTER);
+        let regions = overlap_regions(&a, &b, 16);
+        for loc in (0..=u32::MAX).step_by(7919) {
+            if a.contains(loc) && b.contains(loc) {
+                assert!(held(&regions, loc), "{}", loc);
+            } else if held(&regions, loc) {
+                // Regions can hold one extra location at the end
+                let prev = loc.wrapping_sub(1);
+                assert!(a.contains(prev) && b.contains(prev), "{}", loc);
+            }
+        }
+    }
+
+    #[test]
+    fn overlap_wraps_around_zero() {
+        let a = DhtArc::new(0, 1000);
+        let b = DhtArc::new(u32::MAX - 500, 1000);
+        let regions = overlap_regions(&a, &b, 16);
+        assert!(held(&regions, u32::MAX));
+        assert!(held(&regions, 0));
+        assert!(held(&regions, 498));
+        assert!(!held(&regions, 1000));
+        assert!(!held(&regions, u32::MAX - 1000));
+        // The grid boundary at zero splits the overlap
+        assert_eq!(regions.len(), 2);
+    }
+
+    #[test]
+    fn regions_are_stable() {
+        let a = DhtArc::new(QUARTER, QUARTER);
+        let b = DhtArc::new(QUARTER * 2, QUARTER);
+        assert_eq!(overlap_regions(&a, &b, 16), overlap_regions(&b, &a, 16));
+    }
+}
@@ -4,6 +4,10 @@ use super::*;
 use ghost_actor::dependencies::tracing;
 use ghost_actor::dependencies::tracing_futures::Instrument;
 use kitsune_p2p_types::codec::Codec;
+use kitsune_p2p_types::dht_arc::DhtArc;
+use kitsune_p2p_types::dht_arc::PeerDensity;
+use kitsune_p2p_types::dht_arc::MAX_HALF_LENGTH;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::convert::TryFrom;
 
@@ -19,6 +23,9 @@ ghost_actor::ghost_chan! {
         /// Update / publish our agent info
         fn update_agent_info() -> ();
 
+        /// Store the resized arcs of our local agents
+        fn update_local_agent_arcs(arcs: Vec<(Arc<KitsuneAgent>, DhtArc)>) -> ();
+
         /// see if an agent is locally joined
         fn is_agent_local(agent: Arc<KitsuneAgent>) -> bool;
     }
@@ -64,12 +71,11 @@ impl gossip::GossipEventHandler for Space {
     fn handle_list_neighbor_agents(
         &mut self,
     ) -> gossip::GossipEventHandlerResult<ListNeighborAgents> {
-        // while full-sync this is just a clone of list_by_basis
         let local_agents = self
-            .local_joined_agents
+            .local_agent_arcs
             .iter()
-            .cloned()
-            .collect::<HashSet<_>>();
+            .map(|(agent, arc)| (agent.clone(), *arc))
+            .collect::<Vec<_>>();
         let agent = self.local_joined_agents.iter().next().cloned();
         let fut = match agent {
             Some(agent) => self
@@ -81,30 +87,88 @@ impl gossip::GossipEventHandler for Space {
             None => async { Ok(Vec::new()) }.boxed().into(),
         };
         Ok(async move {
-            let remote_agents = fut
-                .await?
-                .into_iter()
-                .map(|ai| Arc::new(ai.into_agent()))
-                .filter(|a| !local_agents.contains(a))
-                .collect::<Vec<_>>();
-            let local_agents = local_agents.into_iter().collect::<Vec<_>>();
+            let mut remote_agents = Vec::new();
+            for agent_info_signed in fut.await? {
+                let info = match types::agent_store::AgentInfo::try_from(&agent_info_signed) {
+                    Ok(info) => info,
+                    Err(e) => {
+                        tracing::warn!(msg = "skipping bad agent info", ?e);
+                        continue;
+                    }
+                };
+                if local_agents
+                    .iter()
+                    .any(|(a, _)| **a == *info.as_agent_ref())
+                {
+                    continue;
+                }
+                let arc = info.dht_arc();
+                remote_agents.push((Arc::new(info.into()), arc));
+            }
             Ok((local_agents, remote_agents))
         }
         .boxed()
         .into())
     }
 
+    fn handle_req_op_hash_summary(
+        &mut self,
+        input: ReqOpHashSummaryEvt,
+    ) -> gossip::GossipEventHandlerResult<OpHashSummaryAgentHashes> {
+        if self.local_joined_agents.contains(&input.to_agent) {
+            let fut = local_req_op_hash_summary(&self.evt_sender, self.space.clone(), input);
+            Ok(async move { fut.await }.boxed().into())
+        } else {
+            let ReqOpHashSummaryEvt {
+                from_agent,
+                to_agent,
+                regions,
+            } = input;
+            let transport_tx = self.transport.clone();
+            let evt_sender = self.evt_sender.clone();
+            let space = self.space.clone();
+            Ok(async move {
+                // see if we have an entry for this agent in our agent_store
+                let info = match evt_sender
+                    .get_agent_info_signed(GetAgentInfoSignedEvt {
+                        space: space.clone(),
+                        agent: to_agent.clone(),
+                    })
+                    .await?
+                {
+                    None => return Err(KitsuneP2pError::RoutingAgentError(to_agent)),
+                    Some(i) => i,
+                };
+                let data = wire::Wire::fetch_op_hash_summary(space, from_agent, to_agent, regions)
+                    .encode_vec()?;
+                let info = types::agent_store::AgentInfo::try_from(&info)?;
+                let url = info.as_urls_ref().get(0).unwrap().clone();
+                let (_, mut write, read) = transport_tx.create_channel(url).await?;
+                KitsuneMetrics::count(KitsuneMetrics::FetchOpHashSummary, data.len());
+                write.write_and_close(data.to_vec()).await?;
+                let read = read.read_to_end().await;
+                let (_, read) = wire::Wire::decode_ref(&read)?;
+                match read {
+                    wire::Wire::Failure(wire::Failure { reason }) => Err(reason.into()),
+                    wire::Wire::FetchOpHashSummaryResponse(wire::FetchOpHashSummaryResponse {
+                        summaries,
+                        peer_hashes,
+                    }) => Ok((summaries, peer_hashes)),
+                    _ => unreachable!(),
+                }
+            }
+            .boxed()
+            .into())
+        }
+    }
+
     fn handle_req_op_hashes(
         &mut self,
         input: ReqOpHashesEvt,
-    ) -> gossip::GossipEventHandlerResult<OpHashesAgentHashes> {
+    ) -> gossip::GossipEventHandlerResult<OpHashes> {
         if self.local_joined_agents.contains(&input.to_agent) {
             let fut = local_req_op_hashes(&self.evt_sender, self.space.clone(), input);
-            Ok(
-                async move { fut.await.map(|r| (OpConsistency::Variance(r.0), r.1)) }
-                    .boxed()
-                    .into(),
-            )
+            Ok(async move { fut.await }.boxed().into())
         } else {
             let ReqOpHashesEvt {
                 to_agent,
@@ -112,7 +176,6 @@ impl gossip::GossipEventHandler for Space {
                 since_utc_epoch_s,
                 until_utc_epoch_s,
                 from_agent,
-                op_count,
             } = input;
             let transport_tx = self.transport.clone();
             let evt_sender = self.evt_sender.clone();
@@ -136,7 +199,6 @@ impl gossip::GossipEventHandler for Space {
                     dht_arc,
                     since_utc_epoch_s,
                     until_utc_epoch_s,
-                    op_count,
                 )
                 .encode_vec()?;
                 let info = types::agent_store::AgentInfo::try_from(&info)?;
@@ -148,10 +210,9 @@ impl gossip::GossipEventHandler for Space {
                 let (_, read) = wire::Wire::decode_ref(&read)?;
                 match read {
                     wire::Wire::Failure(wire::Failure { reason }) => Err(reason.into()),
-                    wire::Wire::FetchOpHashesResponse(wire::FetchOpHashesResponse {
-                        hashes,
-                        peer_hashes,
-                    }) => Ok((hashes, peer_hashes)),
+                    wire::Wire::FetchOpHashesResponse(wire::FetchOpHashesResponse { hashes }) => {
+                        Ok(hashes)
+                    }
                     _ => unreachable!(),
                 }
             }
@@ -270,25 +331,26 @@ impl gossip::GossipEventHandler for Space {
     }
 }
 
-pub fn local_req_op_hashes(
+pub fn local_req_op_hash_summary(
     evt_sender: &futures::channel::mpsc::Sender<KitsuneP2pEvent>,
     space: Arc<KitsuneSpace>,
-    input: ReqOpHashesEvt,
-) -> impl std::future::Future<Output = Result<LocalOpHashesAgentHashes, KitsuneP2pError>> {
-    let ReqOpHashesEvt {
-        to_agent,
-        dht_arc,
-        since_utc_epoch_s,
-        until_utc_epoch_s,
-        ..
+    input: ReqOpHashSummaryEvt,
+) -> impl std::future::Future<Output = Result<OpHashSummaryAgentHashes, KitsuneP2pError>> {
+    let ReqOpHashSummaryEvt {
+        to_agent, regions, ..
     } = input;
-    let fut = evt_sender.fetch_op_hashes_for_constraints(FetchOpHashesForConstraintsEvt {
-        space: space.clone(),
-        agent: to_agent.clone(),
-        dht_arc,
-        since_utc_epoch_s,
-        until_utc_epoch_s,
-    });
+    let all = regions
+        .into_iter()
+        .map(|dht_arc| {
+            evt_sender.fetch_op_hashes_for_constraints(FetchOpHashesForConstraintsEvt {
+                space: space.clone(),
+                agent: to_agent.clone(),
+                dht_arc,
+                since_utc_epoch_s: i64::MIN,
+                until_utc_epoch_s: i64::MAX,
+            })
+        })
+        .collect::<Vec<_>>();
     let peer_fut = evt_sender.query_agent_info_signed(QueryAgentInfoSignedEvt {
         space,
         agent: to_agent,
@@ -303,10 +365,36 @@ pub fn local_req_op_hashes(
                 Ok((Arc::new(ai.into()), time))
             })
             .collect::<Result<Vec<_>, KitsuneP2pError>>()?;
-        Ok((fut.await?, agent_infos))
+        let mut summaries = Vec::with_capacity(all.len());
+        for hashes in all {
+            summaries.push(OpHashSummary::new(&hashes.await?));
+        }
+        Ok((summaries, agent_infos))
     }
 }
 
+pub fn local_req_op_hashes(
+    evt_sender: &futures::channel::mpsc::Sender<KitsuneP2pEvent>,
+    space: Arc<KitsuneSpace>,
+    input: ReqOpHashesEvt,
+) -> impl std::future::Future<Output = Result<OpHashes, KitsuneP2pError>> {
+    let ReqOpHashesEvt {
+        to_agent,
+        dht_arc,
+        since_utc_epoch_s,
+        until_utc_epoch_s,
+        ..
+    } = input;
+    let fut = evt_sender.fetch_op_hashes_for_constraints(FetchOpHashesForConstraintsEvt {
+        space,
+        agent: to_agent,
+        dht_arc,
+        since_utc_epoch_s,
+        until_utc_epoch_s,
+    });
+    async move { fut.await }
+}
+
 pub fn local_req_op_data(
     evt_sender: &futures::channel::mpsc::Sender<KitsuneP2pEvent>,
     space: Arc<KitsuneSpace>,
@@ -428,22 +516,76 @@ impl SpaceInternalHandler for Space {
 
     fn handle_update_agent_info(&mut self) -> SpaceInternalHandlerResult<()> {
         let space = self.space.clone();
-        let agent_list: Vec<Arc<KitsuneAgent>> = self.local_joined_agents.iter().cloned().collect();
+        let agent_list: Vec<(Arc<KitsuneAgent>, DhtArc)> = self
+            .local_agent_arcs
+            .iter()
+            .map(|(agent, arc)| (agent.clone(), *arc))
+            .collect();
         let bound_url = self.transport.bound_url();
         let evt_sender = self.evt_sender.clone();
+        let i_s = self.i_s.clone();
         let bootstrap_service = self.config.bootstrap_service.clone();
-        let expires_after = self.config.tuning_params.agent_info_expires_after_ms as u64;
+        let tuning_params = self.config.tuning_params.clone();
+        let expires_after = tuning_params.agent_info_expires_after_ms as u64;
+        let peers_fut = match agent_list.first() {
+            Some((agent, _)) => evt_sender.query_agent_info_signed(QueryAgentInfoSignedEvt {
+                space: space.clone(),
+                agent: agent.clone(),
+            }),
+            None => async { Ok(Vec::new()) }.boxed().into(),
+        };
         Ok(async move {
+            // Resize our arcs to the peer density before publishing them
+            let mut peer_arcs = HashMap::new();
+            for agent_info_signed in peers_fut.await? {
+                let info = match types::agent_store::AgentInfo::try_from(&agent_info_signed) {
+                    Ok(info) => info,
+                    Err(e) => {
+                        tracing::warn!(msg = "skipping bad agent info", ?e);
+                        continue;
+                    }
+                };
+                let arc = info.dht_arc();
+                peer_arcs.insert(Arc::new(KitsuneAgent::from(info)), arc);
+            }
+            // Our own stored agent info may have an older arc
+            peer_arcs.extend(agent_list.iter().cloned());
+            let agent_list = agent_list
+                .into_iter()
+                .map(|(agent, mut arc)| {
+                    let peers = peer_arcs
+                        .iter()
+                        .filter(|(a, _)| **a != agent)
+                        .map(|(_, arc)| arc);
+                    let density = PeerDensity::new(&arc, peers);
+                    arc.update_length(
+                        &density,
+                        tuning_params.arc_target_redundancy,
+                        tuning_params.arc_min_coverage,
+                        tuning_params.arc_max_coverage,
+                    );
+                    tracing::debug!(
+                        ?agent,
+                        estimated_redundancy = density.estimated_redundancy(),
+                        coverage = arc.coverage(),
+                        "Resized arc"
+                    );
+                    (agent, arc)
+                })
+                .collect::<Vec<_>>();
+            i_s.update_local_agent_arcs(agent_list.clone()).await?;
+
             let bound_url = bound_url.await?;
             let urls = bound_url
                 .query_pairs()
                 .map(|(_, sub_url)| url2::url2!("{}", sub_url))
                 .collect::<Vec<_>>();
-            for agent in agent_list {
+            for (agent, dht_arc) in agent_list {
                 let agent_info = crate::types::agent_store::AgentInfo::new(
                     (*space).clone(),
                     (*agent).clone(),
                     urls.clone(),
+                    dht_arc,
                     crate::spawn::actor::bootstrap::now_once(None).await?,
                     expires_after,
                 );
@@ -479,6 +621,19 @@ impl SpaceInternalHandler for Space {
         .into())
     }
 
+    fn handle_update_local_agent_arcs(
+        &mut self,
+        arcs: Vec<(Arc<KitsuneAgent>, DhtArc)>,
+    ) -> SpaceInternalHandlerResult<()> {
+        for (agent, arc) in arcs {
+            // Agents may have left while their arc was being resized
+            if let Some(local_arc) = self.local_agent_arcs.get_mut(&agent) {
+                *local_arc = arc;
+            }
+        }
+        Ok(async move { Ok(()) }.boxed().into())
+    }
+
     fn handle_is_agent_local(
         &mut self,
         agent: Arc<KitsuneAgent>,
@@ -505,6 +660,9 @@ impl KitsuneP2pHandler for Space {
         agent: Arc<KitsuneAgent>,
     ) -> KitsuneP2pHandlerResult<()> {
         self.local_joined_agents.insert(agent.clone());
+        // until we know how many peers are around hold the whole dht
+        self.local_agent_arcs
+            .insert(agent.clone(), DhtArc::new(agent.get_loc(), MAX_HALF_LENGTH));
         let fut = self.i_s.update_agent_info();
         let i_s = self.i_s.clone();
         let evt_sender = self.evt_sender.clone();
@@ -544,6 +702,7 @@ impl KitsuneP2pHandler for Space {
         agent: Arc<KitsuneAgent>,
     ) -> KitsuneP2pHandlerResult<()> {
         self.local_joined_agents.remove(&agent);
+        self.local_agent_arcs.remove(&agent);
         Ok(async move { Ok(()) }.boxed().into())
     }
 
@@ -687,6 +846,7 @@ pub(crate) struct Space {
     pub(crate) evt_sender: futures::channel::mpsc::Sender<KitsuneP2pEvent>,
     pub(crate) transport: ghost_actor::GhostSender<TransportListener>,
     pub(crate) local_joined_agents: HashSet<Arc<KitsuneAgent>>,
+    pub(crate) local_agent_arcs: HashMap<Arc<KitsuneAgent>, DhtArc>,
     pub(crate) config: Arc<KitsuneP2pConfig>,
 }
 
@@ -715,6 +875,7 @@ impl Space {
             evt_sender,
             transport,
             local_joined_agents: HashSet::new(),
+            local_agent_arcs: HashMap::new(),
             config,
         }
     }
@@ -450,4 +450,49 @@ mod tests {
         harness.ghost_actor_shutdown().await?;
         Ok(())
     }
+
+    /// Test that ops spread across the whole dht are all
+    /// gossiped when the agents' arcs overlap everywhere.
+    #[tokio::test(threaded_scheduler)]
+    async fn test_gossip_transport_many_regions() -> Result<(), KitsuneP2pError> {
+        observability::test_run().ok();
+        let (harness, _evt) = spawn_test_harness_mem().await?;
+
+        harness.add_space().await?;
+        let (a1, _) = harness.add_direct_agent("one".into()).await?;
+        let (a2, _) = harness.add_direct_agent("two".into()).await?;
+
+        // - Insert data with random locations for both agents
+        let mut expected = std::collections::HashSet::new();
+        for i in 0..50 {
+            expected.insert(
+                harness
+                    .inject_gossip_data(a1.clone(), format!("agent-1-data-{}", i))
+                    .await?,
+            );
+            expected.insert(
+                harness
+                    .inject_gossip_data(a2.clone(), format!("agent-2-data-{}", i))
+                    .await?,
+            );
+        }
+
+        // needed until we have some way of bootstrapping
+        harness.magic_peer_info_exchange().await?;
+
+        tokio::time::delay_for(std::time::Duration::from_millis(500)).await;
+
+        // - Check both agents now have all the data
+        for agent in vec![a1, a2] {
+            let res = harness.dump_local_gossip_data(agent).await?;
+            let held = res
+                .keys()
+                .cloned()
+                .collect::<std::collections::HashSet<_>>();
+            assert_eq!(held, expected);
+        }
+
+        harness.ghost_actor_shutdown().await?;
+        Ok(())
+    }
 }
@@ -213,9 +213,15 @@ impl KitsuneP2pEventHandler for AgentHarness {
 
     fn handle_fetch_op_hashes_for_constraints(
         &mut self,
-        _input: FetchOpHashesForConstraintsEvt,
+        input: FetchOpHashesForConstraintsEvt,
     ) -> KitsuneP2pEventHandlerResult<Vec<Arc<super::KitsuneOpHash>>> {
-        let hashes: Vec<Arc<super::KitsuneOpHash>> = self.gossip_store.keys().cloned().collect();
+        // the harness uses the op hash location as the op's basis
+        let hashes: Vec<Arc<super::KitsuneOpHash>> = self
+            .gossip_store
+            .keys()
+            .filter(|h| input.dht_arc.contains(h.get_loc()))
+            .cloned()
+            .collect();
         let slug_hashes: Vec<Slug> = hashes.iter().map(|h| h.into()).collect();
         tracing::trace!(?slug_hashes, "FETCH_OP_HASHES");
         Ok(async move { Ok(hashes) }.boxed().into())
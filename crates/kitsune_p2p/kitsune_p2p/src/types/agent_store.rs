@@ -1,9 +1,16 @@
 //! Data structures to be stored in the agent/peer database.
 
 use crate::types::KitsuneAgent;
+use crate::types::KitsuneBinType;
 use crate::types::KitsuneP2pError;
 use crate::types::KitsuneSignature;
 use crate::types::KitsuneSpace;
+use kitsune_p2p_types::dht_arc::DhtArc;
+use kitsune_p2p_types::dht_arc::MAX_HALF_LENGTH;
+use lair_keystore_api::internal::sign_ed25519::SignEd25519PubKey;
+use lair_keystore_api::internal::sign_ed25519::SignEd25519Signature;
+use std::convert::TryFrom;
+use std::sync::Arc;
 use url2::Url2;
 
 /// A list of Urls.
@@ -66,6 +73,26 @@ impl AgentInfoSigned {
     pub fn as_agent_info_ref(&self) -> &[u8] {
         self.agent_info.as_ref()
     }
+
+    /// Check that the signature was made by the agent over the agent info,
+    /// and that the agent info is for that same agent.
+    pub async fn verify_signature(&self) -> Result<bool, KitsuneP2pError> {
+        let agent_info = AgentInfo::try_from(self)?;
+        if agent_info.as_agent_ref() != &self.agent {
+            return Ok(false);
+        }
+        // The agent bytes are the 32 byte ed25519 public key
+        // followed by the 4 byte dht location
+        let pub_key: SignEd25519PubKey = match self.agent.get_bytes().get(..32) {
+            Some(key) => key.to_vec().into(),
+            None => return Ok(false),
+        };
+        let signature: SignEd25519Signature = self.signature.0.clone().into();
+        pub_key
+            .verify(Arc::new(self.agent_info.clone()), signature)
+            .await
+            .map_err(KitsuneP2pError::other)
+    }
 }
 
 /// Value that an agent signs to represent themselves on the network.
@@ -79,6 +106,11 @@ pub struct AgentInfo {
     agent: KitsuneAgent,
     // List of urls the agent can be reached at, in the agent's own preference order.
     urls: Urls,
+    // The arc of the dht this agent is holding data for.
+    // Agent infos signed before arcs were included hold the full arc.
+    #[as_ref(ignore)]
+    #[serde(default = "full_arc")]
+    dht_arc: DhtArc,
     // The unix ms timestamp that the agent info was signed at, according to the agent's own clock.
     #[as_ref(ignore)]
     signed_at_ms: u64,
@@ -87,6 +119,10 @@ pub struct AgentInfo {
     expires_after_ms: u64,
 }
 
+fn full_arc() -> DhtArc {
+    DhtArc::new(0, MAX_HALF_LENGTH)
+}
+
 impl std::convert::TryFrom<&AgentInfoSigned> for AgentInfo {
     type Error = KitsuneP2pError;
     fn try_from(agent_info_signed: &AgentInfoSigned) -> Result<Self, Self::Error> {
@@ -102,6 +138,7 @@ impl AgentInfo {
         space: KitsuneSpace,
         agent: KitsuneAgent,
         urls: Urls,
+        dht_arc: DhtArc,
         signed_at_ms: u64,
         expires_after_ms: u64,
     ) -> Self {
@@ -109,6 +146,7 @@ impl AgentInfo {
             space,
             agent,
             urls,
+            dht_arc,
             signed_at_ms,
             expires_after_ms,
         }
@@ -137,6 +175,11 @@ impl AgentInfo {
         self.as_ref()
     }
 
+    /// Accessor for dht_arc.
+    pub fn dht_arc(&self) -> DhtArc {
+        self.dht_arc
+    }
+
     /// Accessor for signed_at_ms.
     pub fn signed_at_ms(&self) -> u64 {
         self.signed_at_ms
@@ -159,3 +202,38 @@ impl From<AgentInfo> for KitsuneAgent {
         ai.agent
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kitsune_p2p_types::codec::rmp_decode;
+    use kitsune_p2p_types::codec::rmp_encode;
+
+    #[test]
+    fn agent_info_without_arc_holds_full_arc() {
+        #[derive(serde::Serialize)]
+        struct AgentInfoWithoutArc {
+            space: KitsuneSpace,
+            agent: KitsuneAgent,
+            urls: Urls,
+            signed_at_ms: u64,
+            expires_after_ms: u64,
+        }
+        let mut data = Vec::new();
+        rmp_encode(
+            &mut data,
+            AgentInfoWithoutArc {
+                space: KitsuneSpace(vec![0; 36]),
+                agent: KitsuneAgent(vec![1; 36]),
+                urls: Vec::new(),
+                signed_at_ms: 1,
+                expires_after_ms: 1000,
+            },
+        )
+        .unwrap();
+        let agent_info: AgentInfo = rmp_decode(&mut &*data).unwrap();
+        assert_eq!(agent_info.dht_arc(), DhtArc::new(0, MAX_HALF_LENGTH));
+        assert!(agent_info.dht_arc().contains(u32::MAX / 3));
+        assert_eq!(agent_info.signed_at_ms(), 1);
+    }
+}
@@ -7,8 +7,20 @@ use crate::agent_store::AgentInfoSigned;
 use super::*;
 
 #[derive(Debug, derive_more::Constructor)]
-/// Request dht op hashes and
-/// agent store information from an agent.
+/// Request a summary of the dht op hashes
+/// an agent holds in each of a list of regions,
+/// along with its agent store information.
+pub struct ReqOpHashSummaryEvt {
+    /// Agent Requesting the summary.
+    pub from_agent: Arc<KitsuneAgent>,
+    /// The agent you are requesting the summary from.
+    pub to_agent: Arc<KitsuneAgent>,
+    /// The regions of the dht to summarize.
+    pub regions: Vec<DhtArc>,
+}
+
+#[derive(Debug, derive_more::Constructor)]
+/// Request dht op hashes from an agent.
 /// This is the lightweight hashes only call.
 pub struct ReqOpHashesEvt {
     /// Agent Requesting the ops.
@@ -21,8 +33,6 @@ pub struct ReqOpHashesEvt {
     pub since_utc_epoch_s: i64,
     /// Get ops till this time.
     pub until_utc_epoch_s: i64,
-    /// Count of the hashes from the requesting agent
-    pub op_count: OpCount,
 }
 
 #[derive(Debug, derive_more::Constructor)]
@@ -51,47 +61,45 @@ pub struct GossipEvt {
     pub agents: Vec<AgentInfoSigned>,
 }
 
-#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
-/// Type for responding to a request for dht op hashes.
-/// If the count hasn't changed then the hashes are consistent
-/// otherwise there is a change in the data and new hashes are sent.
-pub enum OpConsistency {
-    /// There is new hashes since last gossip request.
-    Variance(OpHashes),
-    /// Gossip is consistent since the last call.
-    Consistent,
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+/// A compact summary of a set of dht op hashes.
+/// Two agents holding the same set of ops in a region
+/// will have the same summary for that region so only
+/// regions with different summaries need their hashes sent.
+pub struct OpHashSummary {
+    /// The number of op hashes in the set.
+    pub count: u64,
+    /// The core bytes of all the op hashes xor'd together.
+    pub fingerprint: [u8; 32],
 }
 
-#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
-/// The count of ops last seen from the requester.
-/// If the requester has a variance then the
-/// responder needs to reply with hashes.
-pub enum OpCount {
-    /// Requestor has a variance since they last
-    /// gossiped to this agent.
-    Variance,
-    /// Requestor is consistent since last gossip
-    /// and this is the count they saw from
-    /// this agent.
-    Consistent(u64),
+impl OpHashSummary {
+    /// Summarize a set of op hashes.
+    /// The order of the hashes doesn't matter.
+    pub fn new<'a, I: IntoIterator<Item = &'a Arc<KitsuneOpHash>>>(hashes: I) -> Self {
+        let mut summary = Self::default();
+        for hash in hashes {
+            summary.count += 1;
+            for (f, byte) in summary.fingerprint.iter_mut().zip(hash.get_bytes()) {
+                *f ^= byte;
+            }
+        }
+        summary
+    }
 }
 
 /// Dht Op hashes that an agent holds
 pub type OpHashes = Vec<Arc<KitsuneOpHash>>;
 
-/// Dht op and agent hashes that the agent has information on.
-pub type OpHashesAgentHashes = (OpConsistency, Vec<(Arc<KitsuneAgent>, u64)>);
-
-/// Dht op and agent hashes that the agent has information on.
-/// Same as [OpHashesAgentHashes] but without consistency information.
-pub type LocalOpHashesAgentHashes = (OpHashes, Vec<(Arc<KitsuneAgent>, u64)>);
+/// Summaries of the dht op hashes in each requested region
+/// and the agent hashes that the agent has information on.
+pub type OpHashSummaryAgentHashes = (Vec<OpHashSummary>, Vec<(Arc<KitsuneAgent>, u64)>);
 /// The Dht op data and agent store information
 pub type OpDataAgentInfo = (Vec<(Arc<KitsuneOpHash>, Vec<u8>)>, Vec<AgentInfoSigned>);
-/// Local and remote neighbors.
-pub type ListNeighborAgents = (Vec<Arc<KitsuneAgent>>, Vec<Arc<KitsuneAgent>>);
-
-impl Default for OpCount {
-    fn default() -> Self {
-        OpCount::Variance
-    }
-}
+/// Local and remote neighbors along with the arcs they are holding.
+pub type ListNeighborAgents = (
+    Vec<(Arc<KitsuneAgent>, DhtArc)>,
+    Vec<(Arc<KitsuneAgent>, DhtArc)>,
+);
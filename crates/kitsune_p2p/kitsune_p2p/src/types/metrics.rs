@@ -6,6 +6,8 @@ observability::metrics!(
     CallResp,
     Notify,
     NotifyResp,
+    FetchOpHashSummary,
+    FetchOpHashSummaryResp,
     FetchOpHashes,
     FetchOpHashesResp,
     FetchOpData,
@@ -36,7 +38,8 @@ pub fn print_all_metrics() {
         .expect("Failed to print metrics");
         for (metric, count) in KitsuneMetrics::iter() {
             match metric {
-                Call | Notify | FetchOpHashes | FetchOpData | AgentInfoQuery | Gossip => {
+                Call | Notify | FetchOpHashSummary | FetchOpHashes | FetchOpData
+                | AgentInfoQuery | Gossip => {
                     let percent = if total_writes > 0.0 {
                         count as f64 / total_writes * 100.0
                     } else {
@@ -52,8 +55,14 @@ pub fn print_all_metrics() {
                     )
                     .expect("Failed to print metrics");
                 }
-                CallResp | NotifyResp | FetchOpHashesResp | FetchOpDataResp
-                | AgentInfoQueryResp | GossipResp | Fail => {
+                CallResp
+                | NotifyResp
+                | FetchOpHashSummaryResp
+                | FetchOpHashesResp
+                | FetchOpDataResp
+                | AgentInfoQueryResp
+                | GossipResp
+                | Fail => {
                     let percent = if total_reads > 0.0 {
                         count as f64 / total_reads * 100.0
                     } else {
@@ -1,7 +1,7 @@
 //! KitsuneP2p Wire Protocol Encoding Decoding
 
 use crate::agent_store::AgentInfoSigned;
-use crate::types::gossip::{OpConsistency, OpCount};
+use crate::types::gossip::OpHashSummary;
 use crate::types::*;
 use derive_more::*;
 use kitsune_p2p_types::dht_arc::DhtArc;
@@ -46,7 +46,7 @@ kitsune_p2p_types::write_codec_enum! {
         NotifyResp(0x21) {
         },
 
-        /// Fetch DhtOp and Agent Hashes with Constraints
+        /// Fetch DhtOp Hashes with Constraints
         FetchOpHashes(0x31) {
             space.0: Arc<KitsuneSpace>,
             from_agent.1: Arc<KitsuneAgent>,
@@ -54,13 +54,11 @@ kitsune_p2p_types::write_codec_enum! {
             dht_arc.3: DhtArc,
             since_utc_epoch_s.4: i64,
             until_utc_epoch_s.5: i64,
-            last_count.6: OpCount,
         },
 
         /// List of hashes response to FetchOpHashes
         FetchOpHashesResponse(0x32) {
-            hashes.0: OpConsistency,
-            peer_hashes.1: Vec<(Arc<KitsuneAgent>, u64)>,
+            hashes.0: Vec<Arc<KitsuneOpHash>>,
         },
 
         /// Fetch DhtOp data and AgentInfo for hashes lists
@@ -78,6 +76,20 @@ kitsune_p2p_types::write_codec_enum! {
             agent_infos.1: Vec<AgentInfoSigned>,
         },
 
+        /// Fetch a summary of the DhtOp Hashes in each region and Agent Hashes
+        FetchOpHashSummary(0x35) {
+            space.0: Arc<KitsuneSpace>,
+            from_agent.1: Arc<KitsuneAgent>,
+            to_agent.2: Arc<KitsuneAgent>,
+            regions.3: Vec<DhtArc>,
+        },
+
+        /// Summary per region response to FetchOpHashSummary
+        FetchOpHashSummaryResponse(0x36) {
+            summaries.0: Vec<OpHashSummary>,
+            peer_hashes.1: Vec<(Arc<KitsuneAgent>, u64)>,
+        },
+
         /// Query Agent data from a remote node
         AgentInfoQuery(0x40) {
             space.0: Arc<KitsuneSpace>,
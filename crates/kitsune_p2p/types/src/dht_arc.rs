@@ -8,7 +8,9 @@ use std::ops::RangeBounds;
 #[cfg(test)]
 use std::ops::RangeInclusive;
 
-#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, From, Into)]
+#[derive(
+    Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash, From, Into,
+)]
 /// Type for representing a location that can wrap around
 /// a u32 dht arc
 pub struct DhtLocation(pub Wrapping<u32>);
@@ -20,7 +22,7 @@ pub struct DhtLocation(pub Wrapping<u32>);
 /// 1 more is added to represent the middle point of an odd length array
 pub const MAX_HALF_LENGTH: u32 = (u32::MAX / 2) + 1 + 1;
 
-#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash)]
 /// Represents how much of a dht arc is held
 /// center_loc is where the hash is.
 /// The center_loc is the center of the arc
@@ -101,6 +103,110 @@ impl DhtArc {
             }
         }
     }
+
+    /// The fraction of the dht held by this arc, from 0.0 to 1.0
+    pub fn coverage(&self) -> f64 {
+        if self.half_length == 0 {
+            0.0
+        } else {
+            let len = 2.0 * self.half_length as f64 - 1.0;
+            (len / DHT_LEN).min(1.0)
+        }
+    }
+
+    /// Create an arc at this center location that holds the
+    /// given fraction of the dht
+    pub fn with_coverage<I: Into<DhtLocation>>(center_loc: I, coverage: f64) -> Self {
+        if coverage <= 0.0 {
+            return Self::new(center_loc, 0);
+        }
+        let coverage = coverage.min(1.0);
+        let half_length = ((coverage * DHT_LEN + 1.0) / 2.0).round();
+        Self::new(center_loc, half_length.min(MAX_HALF_LENGTH as f64) as u32)
+    }
+
+    /// Grow or shrink this arc toward the coverage that would give
+    /// each location `target_redundancy` holders given the peer density.
+    /// The arc only moves half way to the target each update so it
+    /// doesn't overshoot while peers are resizing their arcs too.
+    /// The coverage is always kept between `min_coverage` and `max_coverage`.
+    pub fn update_length(
+        &mut self,
+        density: &PeerDensity,
+        target_redundancy: u32,
+        min_coverage: f64,
+        max_coverage: f64,
+    ) {
+        let current = self.coverage();
+        let target = density.target_coverage(target_redundancy);
+        let mut coverage = current + (target - current) / 2.0;
+        // Snap to the target once we are close enough
+        if (target - coverage).abs() < COVERAGE_TOLERANCE {
+            coverage = target;
+        }
+        let coverage = coverage.max(min_coverage).min(max_coverage);
+        *self = Self::with_coverage(self.center_loc, coverage);
+    }
+}
+
+/// The number of locations on the dht
+const DHT_LEN: f64 = u32::MAX as f64 + 1.0;
+
+/// How close an arc's coverage needs to be to its target
+/// before it is considered to have reached it
+const COVERAGE_TOLERANCE: f64 = 0.001;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// An estimate of how many peers are holding the dht, made from
+/// the peers whose arcs are centered in the arc of one of our agents.
+pub struct PeerDensity {
+    /// The coverage of the arc the peers were found in
+    arc_coverage: f64,
+    /// The number of peers centered in the arc, including our agent
+    count: usize,
+    /// The average coverage of those peers' arcs, including our agent
+    average_coverage: f64,
+}
+
+impl PeerDensity {
+    /// Estimate the peer density from our agent's arc and
+    /// the arcs of all the other peers we know about
+    pub fn new<'a, P: IntoIterator<Item = &'a DhtArc>>(arc: &DhtArc, peers: P) -> Self {
+        let mut count = 1;
+        let mut total_coverage = arc.coverage();
+        for peer in peers {
+            if arc.contains(peer.center_loc) {
+                count += 1;
+                total_coverage += peer.coverage();
+            }
+        }
+        Self {
+            arc_coverage: arc.coverage(),
+            count,
+            average_coverage: total_coverage / count as f64,
+        }
+    }
+
+    /// The estimated number of peers in the whole dht.
+    /// An empty arc can't see any peers so we only count ourselves.
+    pub fn estimated_total_peers(&self) -> f64 {
+        if self.arc_coverage > 0.0 {
+            self.count as f64 / self.arc_coverage
+        } else {
+            1.0
+        }
+    }
+
+    /// The estimated number of peers holding each location
+    pub fn estimated_redundancy(&self) -> f64 {
+        self.estimated_total_peers() * self.average_coverage
+    }
+
+    /// The coverage each peer needs for every location
+    /// to be held by `target_redundancy` peers
+    pub fn target_coverage(&self, target_redundancy: u32) -> f64 {
+        (target_redundancy as f64 / self.estimated_total_peers()).min(1.0)
+    }
 }
 
 impl From<u32> for DhtLocation {
@@ -315,4 +421,93 @@ mod tests {
         );
         check_bounds_full(0, MAX_HALF_LENGTH, half, half - 1);
     }
+
+    #[test]
+    fn test_arc_coverage() {
+        assert_eq!(DhtArc::new(0, 0).coverage(), 0.0);
+        assert_eq!(DhtArc::new(0, MAX_HALF_LENGTH).coverage(), 1.0);
+        assert!((DhtArc::new(0, MAX_HALF_LENGTH / 2).coverage() - 0.5).abs() < 0.001);
+
+        assert_eq!(
+            DhtArc::with_coverage(42, 1.0),
+            DhtArc::new(42, MAX_HALF_LENGTH)
+        );
+        assert_eq!(DhtArc::with_coverage(42, 0.0), DhtArc::new(42, 0));
+        assert!((DhtArc::with_coverage(0, 0.25).coverage() - 0.25).abs() < 0.001);
+    }
+
+    /// Arcs for `n` peers spread evenly around the dht
+    fn even_peers(n: u32, half_length: u32) -> Vec<DhtArc> {
+        let step = u32::MAX / (n + 1);
+        (1..=n)
+            .map(|i| DhtArc::new(i * step, half_length))
+            .collect()
+    }
+
+    #[test]
+    fn test_peer_density() {
+        let arc = DhtArc::new(0, MAX_HALF_LENGTH);
+        let peers = even_peers(10, MAX_HALF_LENGTH);
+        let density = PeerDensity::new(&arc, &peers);
+        assert_eq!(density.estimated_total_peers(), 11.0);
+        assert_eq!(density.estimated_redundancy(), 11.0);
+        assert_eq!(density.target_coverage(5), 5.0 / 11.0);
+
+        // Alone on the dht
+        let density = PeerDensity::new(&arc, std::iter::empty());
+        assert_eq!(density.estimated_total_peers(), 1.0);
+        assert_eq!(density.target_coverage(5), 1.0);
+
+        // Only peers centered in the arc are counted
+        let arc = DhtArc::with_coverage(0, 0.5);
+        let density = PeerDensity::new(&arc, &peers);
+        let total = density.estimated_total_peers();
+        assert!(total > 9.0 && total < 13.0, "{}", total);
+    }
+
+    #[test]
+    fn test_arc_resize() {
+        // A lone agent grows to hold everything
+        let mut arc = DhtArc::with_coverage(0, 0.1);
+        for _ in 0..20 {
+            let density = PeerDensity::new(&arc, std::iter::empty());
+            arc.update_length(&density, 5, 0.0, 1.0);
+        }
+        assert_eq!(arc, DhtArc::new(0, MAX_HALF_LENGTH));
+
+        // An agent among many shrinks toward the target redundancy
+        let peers = even_peers(100, MAX_HALF_LENGTH);
+        let mut arc = DhtArc::new(0, MAX_HALF_LENGTH);
+        let density = PeerDensity::new(&arc, &peers);
+        arc.update_length(&density, 5, 0.0, 1.0);
+        // Only moves half way each update
+        assert!((arc.coverage() - 0.525).abs() < 0.01, "{}", arc.coverage());
+        for _ in 0..20 {
+            let density = PeerDensity::new(&arc, &peers);
+            arc.update_length(&density, 5, 0.0, 1.0);
+        }
+        assert!(
+            arc.coverage() > 0.04 && arc.coverage() < 0.07,
+            "{}",
+            arc.coverage()
+        );
+    }
+
+    #[test]
+    fn test_arc_resize_limits() {
+        let peers = even_peers(100, MAX_HALF_LENGTH);
+        let mut arc = DhtArc::new(0, MAX_HALF_LENGTH);
+        for _ in 0..20 {
+            let density = PeerDensity::new(&arc, &peers);
+            arc.update_length(&density, 5, 0.2, 1.0);
+        }
+        assert!((arc.coverage() - 0.2).abs() < 0.001);
+
+        let mut arc = DhtArc::with_coverage(0, 0.1);
+        for _ in 0..20 {
+            let density = PeerDensity::new(&arc, std::iter::empty());
+            arc.update_length(&density, 5, 0.0, 0.5);
+        }
+        assert!((arc.coverage() - 0.5).abs() < 0.001);
+    }
 }
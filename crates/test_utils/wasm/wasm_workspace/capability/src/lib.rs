@@ -51,6 +51,16 @@ pub fn delete_cap_grant(header_hash: HeaderHash) -> ExternResult<HeaderHash> {
     hdk3::prelude::delete_cap_grant(header_hash)
 }
 
+#[hdk_extern]
+fn list_cap_grants(_: ()) -> ExternResult<Vec<CapGrantInfo>> {
+    capability_grants(None)
+}
+
+#[hdk_extern]
+fn list_cap_claims(_: ()) -> ExternResult<Vec<CapClaimInfo>> {
+    capability_claims(None)
+}
+
 #[hdk_extern]
 fn get_entry(header_hash: HeaderHash) -> ExternResult<Option<Element>> {
     get(header_hash, GetOptions::latest())
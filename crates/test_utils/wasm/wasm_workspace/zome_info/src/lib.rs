@@ -1,6 +1,43 @@
 use hdk3::prelude::*;
 
+#[derive(serde::Serialize, serde::Deserialize, SerializedBytes, Debug)]
+struct DnaProperties {
+    p: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, SerializedBytes, Debug)]
+struct PostProperties {
+    max_length: u32,
+}
+
+#[hdk_entry(id = "post")]
+struct Post(String);
+
+#[hdk_entry(id = "comment")]
+struct Comment(String);
+
+entry_defs![
+    Post::entry_def()
+        .with_properties(SerializedBytes::try_from(PostProperties { max_length: 140 }).unwrap()),
+    Comment::entry_def()
+];
+
 #[hdk_extern]
 fn zome_info(_: ()) -> ExternResult<ZomeInfo> {
     hdk3::prelude::zome_info()
 }
+
+#[hdk_extern]
+fn dna_properties(_: ()) -> ExternResult<DnaProperties> {
+    property()
+}
+
+#[hdk_extern]
+fn post_properties(_: ()) -> ExternResult<Option<PostProperties>> {
+    entry_type_properties("post")
+}
+
+#[hdk_extern]
+fn comment_properties(_: ()) -> ExternResult<Option<PostProperties>> {
+    entry_type_properties("comment")
+}